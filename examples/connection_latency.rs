@@ -0,0 +1,262 @@
+//! Measures how the connection settings of a [SquareClient] change the latency of its requests.
+//!
+//! The client talks to a local HTTP/1.1 or HTTP/2 server behind a proxy delaying every byte by the
+//! given one way delay, 20 ms unless told otherwise, and holding back the first request on a new
+//! connection by one more round trip in place of the TCP handshake. The requests are sent over
+//! plain HTTP, so no TLS handshake is included: against Square a new connection costs one round
+//! trip more with TLS 1.3, or two with TLS 1.2, than measured here.
+//!
+//! ```text
+//! cargo run --release --example connection_latency -- 20
+//! ```
+//!
+//! The medians of 50 sequential requests, and of 20 bursts of 20 concurrent requests 50 ms apart,
+//! with a 40 ms round trip were:
+//!
+//! | Setup                                                  | Median                         |
+//! |--------------------------------------------------------|--------------------------------|
+//! | Sequential, on the pooled connection (default)         | 42.8 ms                        |
+//! | Sequential, `pool_max_idle_per_host(0)`                | 83.2 ms                        |
+//! | Sequential, each after idling past `pool_idle_timeout` | 83.4 ms                        |
+//! | Sequential, `http2_prior_knowledge`                    | 42.9 ms                        |
+//! | Bursts, HTTP/1.1 (default)                             | 45.1 ms, 85.4 ms for the first |
+//! | Bursts, HTTP/1.1 with `pool_max_idle_per_host(4)`      | 85.1 ms                        |
+//! | Bursts, `http2_prior_knowledge`                        | 44.4 ms, 85.4 ms for the first |
+//!
+//! A request on a new connection pays for its handshakes, so the pool saves a round trip per
+//! request here, and with TLS two or three. HTTP/2 multiplexes a burst over a single connection,
+//! where HTTP/1.1 needs as many idle connections as there are concurrent requests. TCP and HTTP/2
+//! keep-alive change the latency of no request by themselves and are not measured.
+
+use square_ox::client::SquareClient;
+
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+const BODY: &str = r#"{ "locations": [] }"#;
+const SEQUENTIAL_REQUESTS: usize = 50;
+const BURSTS: usize = 20;
+const BURST_SIZE: usize = 20;
+const BURST_GAP: Duration = Duration::from_millis(50);
+
+// an HTTP/1.1 server keeping its connections alive, answering every request with BODY
+async fn http1_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let mut received = vec![];
+                let mut buffer = [0; 4096];
+                loop {
+                    while !received.windows(4).any(|window| window == b"\r\n\r\n") {
+                        match stream.read(&mut buffer).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(read) => received.extend_from_slice(&buffer[..read]),
+                        }
+                    }
+                    let head_end = received.windows(4)
+                        .position(|window| window == b"\r\n\r\n")
+                        .unwrap() + 4;
+                    received.drain(..head_end);
+
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                        content-length: {}\r\n\r\n{}",
+                        BODY.len(),
+                        BODY,
+                    );
+                    if stream.write_all(response.as_bytes()).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    port
+}
+
+// an HTTP/2 server speaking h2c with prior knowledge, answering every request with BODY
+async fn http2_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let mut connection = match h2::server::handshake(stream).await {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                };
+                while let Some(Ok((_, mut respond))) = connection.accept().await {
+                    let response = http::Response::builder()
+                        .status(200)
+                        .header("content-type", "application/json")
+                        .body(())
+                        .unwrap();
+                    let mut body = respond.send_response(response, false).unwrap();
+                    body.send_data(bytes::Bytes::from_static(BODY.as_bytes()), true).unwrap();
+                }
+            });
+        }
+    });
+
+    port
+}
+
+// forwards the bytes read, delaying each by the one way delay and none before the given instant
+async fn pipe<R, W>(mut from: R, mut to: W, one_way: Duration, not_before: Instant)
+    where R: AsyncRead + Unpin + Send + 'static,
+          W: AsyncWrite + Unpin {
+    let (sender, mut receiver) = mpsc::unbounded_channel::<(Instant, Vec<u8>)>();
+
+    tokio::spawn(async move {
+        let mut buffer = vec![0; 16384];
+        loop {
+            let read = match from.read(&mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(read) => read,
+            };
+            let due = Instant::now().max(not_before) + one_way;
+            if sender.send((due, buffer[..read].to_vec())).is_err() {
+                break;
+            }
+        }
+    });
+
+    while let Some((due, data)) = receiver.recv().await {
+        tokio::time::sleep_until(due.into()).await;
+        if to.write_all(&data).await.is_err() {
+            break;
+        }
+    }
+    let _ = to.shutdown().await;
+}
+
+// a proxy to the given port, holding the requests on a new connection back by a round trip
+async fn proxy(upstream: u16, one_way: Duration) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        loop {
+            let (client, _) = listener.accept().await.unwrap();
+            client.set_nodelay(true).unwrap();
+            let accepted = Instant::now();
+
+            tokio::spawn(async move {
+                let server = TcpStream::connect(("127.0.0.1", upstream)).await.unwrap();
+                server.set_nodelay(true).unwrap();
+
+                let (client_read, client_write) = client.into_split();
+                let (server_read, server_write) = server.into_split();
+                tokio::spawn(pipe(client_read, server_write, one_way, accepted + 2 * one_way));
+                tokio::spawn(pipe(server_read, client_write, one_way, accepted));
+            });
+        }
+    });
+
+    base_url
+}
+
+fn median(mut latencies: Vec<Duration>) -> Duration {
+    latencies.sort();
+
+    latencies[latencies.len() / 2]
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+// the median latency of sequential requests, after one opening the connection
+async fn sequential(client: &SquareClient) -> Duration {
+    client.locations().list().await.unwrap();
+
+    let mut latencies = vec![];
+    for _ in 0..SEQUENTIAL_REQUESTS {
+        let started = Instant::now();
+        client.locations().list().await.unwrap();
+        latencies.push(started.elapsed());
+    }
+
+    median(latencies)
+}
+
+// the latency of the first burst of concurrent requests and the median of those after it
+async fn bursts(client: &SquareClient) -> (Duration, Duration) {
+    let mut latencies = vec![];
+    for _ in 0..=BURSTS {
+        let started = Instant::now();
+        let requests: Vec<_> = (0..BURST_SIZE)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.locations().list().await.unwrap() })
+            })
+            .collect();
+        for request in requests {
+            request.await.unwrap();
+        }
+        latencies.push(started.elapsed());
+
+        tokio::time::sleep(BURST_GAP).await;
+    }
+
+    (latencies[0], median(latencies[1..].to_vec()))
+}
+
+// the median latency of requests sent once the pooled connection idled past its timeout
+async fn after_idling(client: &SquareClient, idle_timeout: Duration) -> Duration {
+    client.locations().list().await.unwrap();
+
+    let mut latencies = vec![];
+    for _ in 0..10 {
+        tokio::time::sleep(idle_timeout * 2).await;
+        let started = Instant::now();
+        client.locations().list().await.unwrap();
+        latencies.push(started.elapsed());
+    }
+
+    median(latencies)
+}
+
+#[tokio::main]
+async fn main() {
+    let one_way = Duration::from_millis(
+        std::env::args().nth(1).map(|delay| delay.parse().unwrap()).unwrap_or(20),
+    );
+    let http1 = proxy(http1_server().await, one_way).await;
+    let http2 = proxy(http2_server().await, one_way).await;
+    let client = |base_url: &str| SquareClient::new("some_token").base_url(base_url);
+
+    println!("one way delay of {:?}", one_way);
+    println!("sequential, pooled: {}", millis(sequential(&client(&http1)).await));
+    println!(
+        "sequential, pool_max_idle_per_host(0): {}",
+        millis(sequential(&client(&http1).pool_max_idle_per_host(0)).await),
+    );
+    let idle_timeout = Duration::from_millis(200);
+    println!(
+        "sequential, after idling past pool_idle_timeout: {}",
+        millis(after_idling(&client(&http1).pool_idle_timeout(idle_timeout), idle_timeout).await),
+    );
+    println!(
+        "sequential, http2_prior_knowledge: {}",
+        millis(sequential(&client(&http2).http2_prior_knowledge()).await),
+    );
+
+    for (setup, client) in [
+        ("HTTP/1.1", client(&http1)),
+        ("HTTP/1.1 with pool_max_idle_per_host(4)", client(&http1).pool_max_idle_per_host(4)),
+        ("http2_prior_knowledge", client(&http2).http2_prior_knowledge()),
+    ] {
+        let (first, rest) = bursts(&client).await;
+        println!("bursts, {}: {}, {} for the first", setup, millis(rest), millis(first));
+    }
+}
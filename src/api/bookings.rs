@@ -2,15 +2,21 @@
 Bookings functionality of the [Square API](https://developer.squareup.com).
  */
 
+pub mod waitlist;
+
 use crate::client::SquareClient;
 use crate::api::{Verb, SquareAPI};
 use crate::errors::{SquareError, SearchQueryBuildError, BookingsPostBuildError, BookingsCancelBuildError, ValidationError};
 use crate::response::SquareResponse;
-use crate::objects::{AppointmentSegment, Booking, FilterValue, enums::BusinessAppointmentSettingsBookingLocationType, StartAtRange, SegmentFilter, AvailabilityQueryFilter};
+use crate::objects::{AppointmentSegment, Availability, Booking, BusinessBookingProfile, FilterValue, LocationBookingProfile, Response, RetrieveTeamMemberBookingProfileResult, TeamMemberBookingProfile, TeamMemberBookingProfilesEnum, enums::BusinessAppointmentSettingsBookingLocationType, StartAtRange, SegmentFilter, AvailabilityQueryFilter};
+
+use chrono::DateTime;
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
 use uuid::Uuid;
-use crate::builder::{Builder, ParentBuilder, Validate};
+use crate::builder::{valid_raw_fields, Builder, ParentBuilder, RawFields, Validate};
 
 impl SquareClient {
     pub fn bookings(&self) -> Bookings {
@@ -41,19 +47,59 @@ impl<'a> Bookings<'a> {
         ).await
     }
 
-    /// Search for availability with the given search query to the Square API
-    /// and get the response back.
+    /// Searches for the slots that can be booked for the services, and team members, of the
+    /// query within its time range.
     ///
     /// # Arguments
-    /// * `search_query` - A search query.
+    /// * `search_query` - A search query built from a
+    ///   [SearchAvailabilityQuery](SearchAvailabilityQuery).
+    ///
+    /// # Example: Find the slots of a service with either of two team members
+    /// ```no_run
+    /// use square_ox::{
+    ///     api::bookings::SearchAvailabilityQuery,
+    ///     builder::Builder,
+    ///     client::SquareClient,
+    /// };
+    ///
+    /// async {
+    ///     let query = Builder::from(SearchAvailabilityQuery::default())
+    ///         .start_at_range(
+    ///             "2022-10-12T00:00:00Z".to_string(),
+    ///             "2022-10-19T00:00:00Z".to_string(),
+    ///         )
+    ///         .location_id("some_loc_id".to_string())
+    ///         .team_member_segment_filter(
+    ///             "some_service_variation_id".to_string(),
+    ///             vec!["some_team_member_id".to_string(), "other_team_member_id".to_string()],
+    ///         )
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     let availabilities = SquareClient::new("some_token")
+    ///         .bookings()
+    ///         .search_availability(query)
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
     pub async fn search_availability(self, search_query: SearchAvailabilityQuery)
-                                     -> Result<SquareResponse, SquareError> {
-        self.client.request(
+                                     -> Result<Vec<Availability>, SquareError> {
+        let res = self.client.request(
             Verb::POST,
             SquareAPI::Bookings("/availability/search".to_string()),
             Some(&search_query),
             None,
-        ).await
+        ).await?;
+
+        Ok(res.into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::Availabilities(availabilities) => Some(availabilities),
+                _ => None,
+            })
+            .unwrap_or_default())
     }
 
     /// Create a booking with the given [BookingsPost](BookingsPost) to the Square API
@@ -72,18 +118,49 @@ impl<'a> Bookings<'a> {
     }
 
     /// Update a booking with the given [BookingsPost](BookingsPost) to the Square API
-    /// and get the response back.
+    /// and get the updated booking back.
+    ///
+    /// If the booking holds a `version`, Square only applies the update to that version of the
+    /// booking and fails if it was changed since, so that changes made in the meantime are not
+    /// overwritten.
     ///
     /// # Arguments
-    /// * `updated_booking` - A [BookingsPost](BookingsPost).
-    pub async fn update(self, updated_booking: BookingsPost, booking_id: String)
-                                -> Result<SquareResponse, SquareError> {
-        self.client.request(
+    /// * `booking_id` - The id of the booking to update.
+    /// * `updated_booking` - A [BookingsPost](BookingsPost) wrapping the updated booking.
+    pub async fn update(self, booking_id: String, updated_booking: BookingsPost)
+                                -> Result<Booking, SquareError> {
+        booking_from(self.client.request(
             Verb::PUT,
             SquareAPI::Bookings(format!("/{}", booking_id)),
             Some(&updated_booking),
             None,
-        ).await
+        ).await?)
+    }
+
+    /// Moves a booking to a new start time, keeping everything else about it. The update is made
+    /// against the version of the booking retrieved, so it fails rather than undoing a change
+    /// made to the booking in between.
+    ///
+    /// # Arguments
+    /// * `booking_id` - The id of the booking to move.
+    /// * `start_at` - The new start time, as an RFC 3339 timestamp.
+    pub async fn reschedule(self, booking_id: String, start_at: String)
+                            -> Result<Booking, SquareError> {
+        let booking = booking_from(self.client.bookings().retrieve(booking_id.clone()).await?)?;
+
+        let mut builder = Builder::from(BookingsPost::default())
+            .customer_id(booking.customer_id.unwrap_or_default())
+            .location_id(booking.location_id.unwrap_or_default())
+            .start_at(start_at);
+        for segment in booking.appointment_segments.unwrap_or_default() {
+            builder = builder.add_appointment_segment(segment);
+        }
+        if let Some(version) = booking.version {
+            builder = builder.version(version);
+        }
+        let updated_booking = builder.build().await.map_err(|_| SquareError::from(None))?;
+
+        self.client.bookings().update(booking_id, updated_booking).await
     }
 
     /// Retrieve an existing booking from the Square API.
@@ -100,65 +177,211 @@ impl<'a> Bookings<'a> {
         ).await
     }
 
-    /// Create a booking with the given [Bookings](Bookings) to the Square API
-    /// and get the response back.
+    /// Cancel a booking at the Square API and get the cancelled booking back.
     ///
     /// # Arguments
-    /// * `booking_to_cancel` - A [BookingsCancel](BookingsCancel) created from the
-    /// [BookingsCancelBuilder](BookingsCancelBuilder)
-    pub async fn cancel(&self, booking_to_cancel: BookingsCancel)
-                                -> Result<SquareResponse, SquareError> {
-        self.client.request(
+    /// * `booking_id` - The id of the booking to cancel.
+    /// * `booking_version` - The version of the booking the cancellation is meant for. If given,
+    ///   Square only cancels the booking if it was not changed since.
+    pub async fn cancel(&self, booking_id: String, booking_version: Option<i32>)
+                                -> Result<Booking, SquareError> {
+        let mut builder = Builder::from(BookingsCancel::default()).booking_id(booking_id);
+        if let Some(booking_version) = booking_version {
+            builder = builder.booking_version(booking_version);
+        }
+        let booking_to_cancel = builder.build().await.map_err(|_| SquareError::from(None))?;
+
+        booking_from(self.client.request(
             Verb::POST,
             SquareAPI::Bookings(format!("/{}/cancel",
-                                        booking_to_cancel.booking_id.unwrap().clone())),
+                                        booking_to_cancel.booking_id.unwrap_or_default())),
             Some(&booking_to_cancel.body),
             None,
-        ).await
+        ).await?)
     }
 
-    /// Retrieves a seller's booking profile at the [Square API](https://developer.squareup.com).
-    pub async fn retrieve_business_profile(self)
-                                                   -> Result<SquareResponse, SquareError> {
-        self.client.request(
+    /// Retrieves a seller's booking profile at the [Square API](https://developer.squareup.com),
+    /// holding whether bookings are enabled, whether customers may cancel them and the policy
+    /// new bookings are accepted by.
+    pub async fn retrieve_business_booking_profile(self)
+                                                   -> Result<BusinessBookingProfile, SquareError> {
+        let res = self.client.request(
             Verb::GET,
             SquareAPI::Bookings("/business-booking-profile".to_string()),
             None::<&BookingsPost>,
             None,
-        ).await
+        ).await?;
+
+        res.into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::BusinessBookingProfile(profile) => Some(profile),
+                _ => None,
+            })
+            .ok_or_else(|| SquareError::from(None))
     }
 
-    /// Lists booking profiles for team members at the [Square API](https://developer.squareup.com).
+    /// Lists the booking profiles of all locations of the seller at the
+    /// [Square API](https://developer.squareup.com), following the cursor through every page.
+    pub async fn list_location_profiles(self)
+                                        -> Result<Vec<LocationBookingProfile>, SquareError> {
+        let mut profiles = vec![];
+        let mut cursor = None;
+
+        loop {
+            let parameters = cursor.map(|cursor| vec![("cursor".to_string(), cursor)]);
+            let res = self.client.request(
+                Verb::GET,
+                SquareAPI::Bookings("/location-booking-profiles".to_string()),
+                None::<&BookingsPost>,
+                parameters,
+            ).await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::LocationBookingProfiles(page) = response {
+                    profiles.extend(page);
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(profiles);
+            }
+        }
+    }
+
+    /// Retrieves the booking profile of a location at the
+    /// [Square API](https://developer.squareup.com).
+    ///
+    /// # Arguments
+    /// * `location_id` - The id of the location to retrieve the booking profile of.
+    pub async fn retrieve_location_profile(self, location_id: String)
+                                           -> Result<LocationBookingProfile, SquareError> {
+        let res = self.client.request(
+            Verb::GET,
+            SquareAPI::Bookings(format!("/location-booking-profiles/{}", location_id)),
+            None::<&BookingsPost>,
+            None,
+        ).await?;
+
+        res.into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::LocationBookingProfile(profile) => Some(profile),
+                _ => None,
+            })
+            .ok_or_else(|| SquareError::from(None))
+    }
+
+    /// Lists booking profiles for team members at the [Square API](https://developer.squareup.com),
+    /// following the cursor through every page.
     ///
     /// # Arguments
     /// * `search_query` - A search query created by the
     /// [ListTeamMemberBookingsProfileBuilder](ListTeamMemberBookingsProfileBuilder).
     pub async fn list_team_member_profiles(self, search_query: Option<Vec<(String, String)>>)
-                                                   -> Result<SquareResponse, SquareError> {
-        self.client.request(
-            Verb::GET,
-            SquareAPI::Bookings("/team-member-booking-profiles".to_string()),
-            None::<&BookingsPost>,
-            search_query,
-        ).await
+                                                   -> Result<Vec<TeamMemberBookingProfile>, SquareError> {
+        let mut profiles = vec![];
+        let mut parameters = search_query.unwrap_or_default();
+
+        loop {
+            let res = self.client.request(
+                Verb::GET,
+                SquareAPI::Bookings("/team-member-booking-profiles".to_string()),
+                None::<&BookingsPost>,
+                Some(parameters.clone()),
+            ).await?;
+            let cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::TeamMemberBookingProfiles(TeamMemberBookingProfilesEnum::List(page)) = response {
+                    profiles.extend(page);
+                }
+            }
+
+            match cursor {
+                Some(cursor) => {
+                    parameters.retain(|(key, _)| key != "cursor");
+                    parameters.push(("cursor".to_string(), cursor));
+                },
+                None => return Ok(profiles),
+            }
+        }
     }
 
-    /// Lists booking profiles for team members at the [Square API](https://developer.squareup.com).
+    /// Retrieves the booking profile of a team member at the
+    /// [Square API](https://developer.squareup.com).
     ///
     /// # Arguments
     /// * `team_member_id` - The id of the team member you would like to retrieve from the
     /// [Square API](https://developer.squareup.com).
     pub async fn retrieve_team_member_profiles(self, team_member_id: String)
-                                                       -> Result<SquareResponse, SquareError> {
-        self.client.request(
+                                                       -> Result<TeamMemberBookingProfile, SquareError> {
+        let res = self.client.request(
             Verb::GET,
             SquareAPI::Bookings(format!("/team-member-booking-profiles/{}", team_member_id)),
             None::<&BookingsPost>,
             None,
-        ).await
+        ).await?;
+
+        res.into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::TeamMemberBookingProfile(profile) => Some(profile),
+                _ => None,
+            })
+            .ok_or_else(|| SquareError::from(None))
+    }
+
+    /// Retrieves the booking profiles of any number of team members at the
+    /// [Square API](https://developer.squareup.com), in requests of up to 100 team members.
+    ///
+    /// Returns the result of every team member by their id, holding either their profile or the
+    /// errors that kept it from being retrieved, such as the team member not existing.
+    ///
+    /// # Arguments
+    /// * `team_member_ids` - The ids of the team members to retrieve the profiles of.
+    pub async fn bulk_retrieve_team_member_profiles(self, team_member_ids: Vec<String>)
+        -> Result<HashMap<String, RetrieveTeamMemberBookingProfileResult>, SquareError> {
+        let mut profiles = HashMap::new();
+
+        for chunk in team_member_ids.chunks(TEAM_MEMBER_PROFILES_PER_BULK_RETRIEVE) {
+            let res = self.client.request(
+                Verb::POST,
+                SquareAPI::Bookings("/team-member-booking-profiles/bulk-retrieve".to_string()),
+                Some(&BulkRetrieveTeamMemberProfiles { team_member_ids: chunk.to_vec() }),
+                None,
+            ).await?;
+
+            for response in res.into_responses() {
+                if let Response::TeamMemberBookingProfiles(TeamMemberBookingProfilesEnum::Bulk(results)) = response {
+                    profiles.extend(results);
+                }
+            }
+        }
+
+        Ok(profiles)
     }
 }
 
+// the maximum number of team members in a single bulk retrieve request
+const TEAM_MEMBER_PROFILES_PER_BULK_RETRIEVE: usize = 100;
+
+#[derive(Serialize)]
+struct BulkRetrieveTeamMemberProfiles {
+    team_member_ids: Vec<String>,
+}
+
+pub(crate) fn booking_from(response: SquareResponse) -> Result<Booking, SquareError> {
+    response.into_responses()
+        .into_iter()
+        .find_map(|response| match response {
+            Response::Booking(booking) => Some(booking),
+            _ => None,
+        })
+        .ok_or_else(|| SquareError::from(None))
+}
+
 // -------------------------------------------------------------------------------------------------
 // ListBookingsQueryBuilder implementation
 // -------------------------------------------------------------------------------------------------
@@ -384,6 +607,8 @@ impl ListTeamMemberBookingsProfileBuilder {
 pub struct BookingsPost {
     idempotency_key: Option<String>,
     booking: Booking,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for BookingsPost {
@@ -391,7 +616,8 @@ impl Validate for BookingsPost {
         if self.booking.customer_id.is_some()
             && self.booking.location_id.is_some()
             && self.booking.appointment_segments.as_ref().unwrap().len() > 0
-            && self.booking.start_at.is_some() {
+            && self.booking.start_at.is_some()
+            && valid_raw_fields(&mut self) {
             self.idempotency_key = Some(Uuid::new_v4().to_string());
 
             Ok(self)
@@ -401,6 +627,12 @@ impl Validate for BookingsPost {
     }
 }
 
+impl RawFields for BookingsPost {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<BookingsPost, T> {
     /// Add a customer_id
     ///
@@ -477,6 +709,13 @@ impl<T: ParentBuilder> Builder<BookingsPost, T> {
 
         self
     }
+
+    /// The version of the booking an update is meant for, as retrieved before changing it.
+    pub fn version(mut self, version: i32) -> Self {
+        self.body.booking.version = Some(version);
+
+        self
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -492,10 +731,10 @@ pub struct BookingsCancel {
 
 impl Validate for BookingsCancel {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.booking_id.is_some() {
-            if let Some(body) = self.body.as_mut() {
-                body.idempotency_key = Some(Uuid::new_v4().to_string())
-            };
+        let body = self.body.get_or_insert_with(Default::default);
+
+        if self.booking_id.is_some() && valid_raw_fields(body) {
+            body.idempotency_key = Some(Uuid::new_v4().to_string());
 
             Ok(self)
         } else {
@@ -504,6 +743,13 @@ impl Validate for BookingsCancel {
     }
 }
 
+// the raw fields are sent in the body, as the booking id is part of the path
+impl RawFields for BookingsCancel {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        self.body.get_or_insert_with(Default::default).raw_fields()
+    }
+}
+
 impl<T: ParentBuilder> Builder<BookingsCancel, T>  {
     pub fn booking_id(mut self, booking_id: String) -> Self {
         self.body.booking_id = Some(booking_id);
@@ -512,25 +758,26 @@ impl<T: ParentBuilder> Builder<BookingsCancel, T>  {
     }
 
     pub fn booking_version(mut self, booking_version: i32) -> Self {
-        if let Some(body) = self.body.body.as_mut() {
-            body.booking_version = Some(booking_version)
-        } else {
-            self.body.body = Some(BookingsCancelBody {
-                idempotency_key: None,
-                booking_version: Some(booking_version)
-            })
-        }
+        self.body.body.get_or_insert_with(Default::default).booking_version = Some(booking_version);
 
         self
     }
 }
 
-#[derive(Serialize, Debug, Deserialize)]
+#[derive(Serialize, Debug, Deserialize, Default)]
 pub struct BookingsCancelBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     idempotency_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     booking_version: Option<i32>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl RawFields for BookingsCancelBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -541,11 +788,27 @@ pub struct BookingsCancelBody {
 #[derive(Serialize, Debug, Deserialize, Default)]
 pub struct SearchAvailabilityQuery {
     query: QueryBody,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for SearchAvailabilityQuery {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.query.filter.start_at_range.is_some() {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        // the range has to end after it starts, compared as times since the offsets may differ
+        let valid_range = self.query.filter.start_at_range.as_ref().is_some_and(|range| {
+            match (
+                DateTime::parse_from_rfc3339(&range.start_at),
+                DateTime::parse_from_rfc3339(&range.end_at),
+            ) {
+                (Ok(start_at), Ok(end_at)) => start_at < end_at,
+                _ => false,
+            }
+        });
+        let valid_segments = self.query.filter.segment_filters.iter()
+            .flatten()
+            .all(|filter| !filter.service_variation_id.is_empty());
+
+        if valid_range && valid_segments && valid_raw_fields(&mut self) {
             Ok(self)
         } else {
             Err(ValidationError)
@@ -553,6 +816,12 @@ impl Validate for SearchAvailabilityQuery {
     }
 }
 
+impl RawFields for SearchAvailabilityQuery {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<SearchAvailabilityQuery, T> {
     pub fn start_at_range(mut self, start: String, end: String) -> Self {
         self.body.query.filter.start_at_range = Some(StartAtRange {
@@ -587,6 +856,35 @@ impl<T: ParentBuilder> Builder<SearchAvailabilityQuery, T> {
 
         self
     }
+
+    /// Adds a segment of the service variation with any of the given team members. Every segment
+    /// added is booked back to back, in the order they were added.
+    pub fn team_member_segment_filter(self, service_variation_id: String,
+                                      team_member_ids: Vec<String>) -> Self {
+        self.add_segment_filter(SegmentFilter {
+            service_variation_id,
+            team_member_id_filter: Some(FilterValue {
+                all: None,
+                any: Some(team_member_ids),
+                none: None,
+            }),
+        })
+    }
+
+    /// Adds a segment filter, with whatever team member filter it holds.
+    pub fn add_segment_filter(mut self, segment_filter: SegmentFilter) -> Self {
+        self.body.query.filter.segment_filters.get_or_insert_with(Vec::new).push(segment_filter);
+
+        self
+    }
+
+    /// Searches for the slots the booking of this ID can be moved to, keeping its own slot
+    /// available.
+    pub fn booking_id(mut self, booking_id: String) -> Self {
+        self.body.query.filter.booking_id = Some(booking_id);
+
+        self
+    }
 }
 
 #[derive(Serialize, Debug, Deserialize, Default)]
@@ -611,7 +909,8 @@ mod test_bookings {
                     location_id: Some("LPNXWH14W6S47".to_string()),
                     segment_filters: None
                 }
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let actual = Builder::from(SearchAvailabilityQuery::default())
@@ -626,6 +925,49 @@ mod test_bookings {
         assert_eq!(format!("{:?}", expected), format!("{:?}", actual))
     }
 
+    #[tokio::test]
+    async fn test_search_query_builder_filters() {
+        let actual = Builder::from(SearchAvailabilityQuery::default())
+            .start_at_range(
+                "2022-10-12T00:00:00Z".to_string(),
+                "2022-10-19T00:00:00Z".to_string())
+            .team_member_segment_filter(
+                "BJHURKYAIAQIDMY267GZNYNW".to_string(),
+                vec!["TMKFnn7X4_Kdo3Wy".to_string()])
+            .booking_id("some_booking_id".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(actual).unwrap(),
+            serde_json::json!({
+                "query": {
+                    "filter": {
+                        "start_at_range": {
+                            "start_at": "2022-10-12T00:00:00Z",
+                            "end_at": "2022-10-19T00:00:00Z",
+                        },
+                        "booking_id": "some_booking_id",
+                        "segment_filters": [{
+                            "service_variation_id": "BJHURKYAIAQIDMY267GZNYNW",
+                            "team_member_id_filter": { "any": ["TMKFnn7X4_Kdo3Wy"] },
+                        }],
+                    },
+                },
+            }),
+        );
+
+        // the range has to end after it starts
+        assert!(Builder::from(SearchAvailabilityQuery::default())
+            .start_at_range(
+                "2022-10-12T02:00:00+02:00".to_string(),
+                "2022-10-12T00:00:00Z".to_string())
+            .build()
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_search_availability() {
         use dotenv::dotenv;
@@ -753,7 +1095,8 @@ mod test_bookings {
                 transition_time_minutes: None,
                 updated_at: None,
                 version: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let res = sut.bookings().create(input).await;
@@ -783,15 +1126,40 @@ mod test_bookings {
             booking_id: Some("9uv6i3p5x5ao1p".to_string()),
             body: Some(BookingsCancelBody {
                 idempotency_key: Some(Uuid::new_v4().to_string()),
-                booking_version: None
+                booking_version: None,
+                raw_fields: Default::default(),
             })
         };
         let actual = Builder::from(BookingsCancel::default())
             .booking_id("9uv6i3p5x5ao1p".to_string()).build().await;
 
         assert!(actual.is_ok());
-        assert_eq!(format!("{:?}", expected.booking_id),
-                   format!("{:?}", actual.unwrap().booking_id));
+        let actual = actual.unwrap();
+        assert_eq!(format!("{:?}", expected.booking_id), format!("{:?}", actual.booking_id));
+        assert!(actual.body.and_then(|body| body.idempotency_key).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_bookings_cancel_raw_field() {
+        let actual = Builder::from(BookingsCancel::default())
+            .booking_id("9uv6i3p5x5ao1p".to_string())
+            .raw_field("brand_new_field", serde_json::json!(true))
+            .build()
+            .await
+            .unwrap();
+
+        // sent in the body, rather than next to the booking id
+        let body = serde_json::to_value(&actual.body).unwrap();
+        assert_eq!(body["brand_new_field"], serde_json::json!(true));
+        assert!(body["idempotency_key"].is_string());
+
+        assert!(Builder::from(BookingsCancel::default())
+            .booking_id("9uv6i3p5x5ao1p".to_string())
+            .booking_version(2)
+            .raw_field("booking_version", serde_json::json!(3))
+            .build()
+            .await
+            .is_err());
     }
 
     #[tokio::test]
@@ -811,15 +1179,7 @@ mod test_bookings {
         let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
         let sut = SquareClient::new(&access_token);
 
-        let input = BookingsCancel {
-            booking_id: Some("pi7kr2va3y4h4f".to_string()),
-            body: Some(BookingsCancelBody {
-                idempotency_key: Some(Uuid::new_v4().to_string()),
-                booking_version: None
-            })
-        };
-
-        let res = sut.bookings().cancel(input).await;
+        let res = sut.bookings().cancel("pi7kr2va3y4h4f".to_string(), None).await;
 
         assert!(res.is_ok())
     }
@@ -860,11 +1220,12 @@ mod test_bookings {
                 transition_time_minutes: None,
                 updated_at: None,
                 version: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let res = sut.bookings()
-            .update(input, "oruft3c9lh0duq".to_string())
+            .update("oruft3c9lh0duq".to_string(), input)
             .await;
 
         assert!(res.is_ok())
@@ -915,7 +1276,7 @@ mod test_bookings {
         let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
         let sut = SquareClient::new(&access_token);
 
-        let res = sut.bookings().retrieve_business_profile().await;
+        let res = sut.bookings().retrieve_business_booking_profile().await;
 
         assert!(res.is_ok())
     }
@@ -962,6 +1323,60 @@ mod test_bookings {
         assert!(res.is_ok())
     }
 
+    #[test]
+    fn test_location_booking_profiles_response() {
+        let res: SquareResponse = serde_json::from_value(serde_json::json!({
+            "location_booking_profiles": [{
+                "location_id": "L1JC53TYHS40Z",
+                "booking_site_url": "https://squareup.com/appointments/book/some_site",
+                "online_booking_enabled": true,
+            }],
+        })).unwrap();
+
+        assert!(matches!(
+            res.into_responses().as_slice(),
+            [Response::LocationBookingProfiles(profiles)]
+                if profiles[0].online_booking_enabled == Some(true)
+        ));
+    }
+
+    #[test]
+    fn test_team_member_booking_profiles_response() {
+        let list: SquareResponse = serde_json::from_value(serde_json::json!({
+            "team_member_booking_profiles": [
+                { "team_member_id": "TMKFnToW8ByXrcm6", "display_name": "Sandbox Seller", "is_bookable": true },
+            ],
+            "cursor": "some_cursor",
+        })).unwrap();
+
+        assert_eq!(list.cursor.as_deref(), Some("some_cursor"));
+        assert!(matches!(
+            list.into_responses().as_slice(),
+            [Response::TeamMemberBookingProfiles(TeamMemberBookingProfilesEnum::List(profiles))]
+                if profiles[0].is_bookable == Some(true)
+        ));
+
+        let bulk: SquareResponse = serde_json::from_value(serde_json::json!({
+            "team_member_booking_profiles": {
+                "TMKFnToW8ByXrcm6": {
+                    "team_member_booking_profile": { "team_member_id": "TMKFnToW8ByXrcm6" },
+                    "errors": [],
+                },
+                "unknown": {
+                    "errors": [{ "category": "INVALID_REQUEST_ERROR", "code": "NOT_FOUND" }],
+                },
+            },
+        })).unwrap();
+
+        match bulk.into_responses().pop() {
+            Some(Response::TeamMemberBookingProfiles(TeamMemberBookingProfilesEnum::Bulk(results))) => {
+                assert!(results["TMKFnToW8ByXrcm6"].team_member_booking_profile.is_some());
+                assert!(results["unknown"].team_member_booking_profile.is_none());
+            },
+            _ => panic!("expected profiles by team member id"),
+        }
+    }
+
     #[tokio::test]
     async fn test_retrieve_team_member_booking_profile() {
         use dotenv::dotenv;
@@ -0,0 +1,417 @@
+/*!
+Waitlist helper built on top of the bookings functionality of the
+[Square API](https://developer.squareup.com).
+
+Customers who could not find a free slot are added to a [Waitlist](Waitlist) as
+[WaitlistEntry](WaitlistEntry)s. The waitlist checks the availability of every entry, either when
+[polled](Waitlist::poll) on an interval of your choosing or when a slot is
+[freed](Waitlist::slot_freed), e.g. after receiving a `booking.updated` webhook for a cancelled
+booking. As soon as a matching slot is available a booking is created for the customer and a
+[WaitlistEvent](WaitlistEvent) is returned, which can be used to notify them.
+
+The entries are kept in a [WaitlistStore](WaitlistStore), so they can be persisted wherever your
+application keeps its data. The [MemoryWaitlistStore](MemoryWaitlistStore) keeps them in memory.
+
+# Example: Add an entry and poll the waitlist
+```no_run
+use square_ox::{
+    api::bookings::waitlist::{MemoryWaitlistStore, Waitlist, WaitlistEntry, WaitlistEvent},
+    builder::Builder,
+    client::SquareClient,
+};
+
+async {
+    let client = SquareClient::new("some_token");
+    let mut waitlist = Waitlist::new(MemoryWaitlistStore::default());
+
+    let entry = Builder::from(WaitlistEntry::default())
+        .customer_id("some_customer_id".to_string())
+        .location_id("some_location_id".to_string())
+        .service_variation_id("some_service_variation_id".to_string())
+        .start_at_range(
+            "2022-10-12T07:20:50.52Z".to_string(),
+            "2022-11-12T07:20:50.52Z".to_string(),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    waitlist.add(entry);
+
+    for event in waitlist.poll(&client).await {
+        if let WaitlistEvent::Booked { entry, booking } = event {
+            println!("booked {:?} for {:?}", booking.id, entry.customer_id);
+        }
+    }
+};
+```
+ */
+
+use crate::api::bookings::{BookingsPost, SearchAvailabilityQuery};
+use crate::builder::{Builder, ParentBuilder, Validate};
+use crate::client::SquareClient;
+use crate::errors::{SquareError, ValidationError};
+use crate::objects::{Availability, Booking, Response};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A customer waiting for a slot of a service at a location within a range of start times.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct WaitlistEntry {
+    pub id: Option<String>,
+    pub customer_id: Option<String>,
+    pub location_id: Option<String>,
+    pub service_variation_id: Option<String>,
+    pub team_member_ids: Option<Vec<String>>,
+    pub start_at_min: Option<String>,
+    pub start_at_max: Option<String>,
+    pub customer_note: Option<String>,
+}
+
+impl WaitlistEntry {
+    // whether a slot freed by the given booking could be taken by this entry
+    fn matches(&self, booking: &Booking) -> bool {
+        let segments = match booking.appointment_segments.as_ref() {
+            Some(segments) => segments,
+            None => return false,
+        };
+
+        self.location_id == booking.location_id
+            && segments.iter().any(|segment| {
+                Some(&segment.service_variation_id) == self.service_variation_id.as_ref()
+                    && self.team_member_ids.as_ref()
+                    .is_none_or(|ids| ids.contains(&segment.team_member_id))
+            })
+    }
+}
+
+impl Validate for WaitlistEntry {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.customer_id.is_some()
+            && self.location_id.is_some()
+            && self.service_variation_id.is_some()
+            && self.start_at_min.is_some()
+            && self.start_at_max.is_some() {
+            if self.id.is_none() {
+                self.id = Some(Uuid::new_v4().to_string());
+            }
+
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<WaitlistEntry, T> {
+    pub fn customer_id(mut self, customer_id: String) -> Self {
+        self.body.customer_id = Some(customer_id);
+
+        self
+    }
+
+    pub fn location_id(mut self, location_id: String) -> Self {
+        self.body.location_id = Some(location_id);
+
+        self
+    }
+
+    pub fn service_variation_id(mut self, service_variation_id: String) -> Self {
+        self.body.service_variation_id = Some(service_variation_id);
+
+        self
+    }
+
+    /// Only accept slots with one of the given team members. If none are added, slots with any
+    /// team member are accepted.
+    pub fn add_team_member_id(mut self, team_member_id: String) -> Self {
+        match self.body.team_member_ids.as_mut() {
+            Some(ids) => ids.push(team_member_id),
+            None => self.body.team_member_ids = Some(vec![team_member_id]),
+        }
+
+        self
+    }
+
+    /// The range of start times, in RFC 3339 format, the customer would accept a slot in.
+    pub fn start_at_range(mut self, start_at_min: String, start_at_max: String) -> Self {
+        self.body.start_at_min = Some(start_at_min);
+        self.body.start_at_max = Some(start_at_max);
+
+        self
+    }
+
+    pub fn customer_note(mut self, customer_note: String) -> Self {
+        self.body.customer_note = Some(customer_note);
+
+        self
+    }
+}
+
+/// The outcome of checking a [WaitlistEntry](WaitlistEntry) against the availability.
+#[derive(Debug)]
+pub enum WaitlistEvent {
+    /// A booking was created for the entry, which was removed from the waitlist.
+    Booked {
+        entry: WaitlistEntry,
+        booking: Box<Booking>,
+    },
+    /// Searching the availability or creating the booking failed. The entry stays on the
+    /// waitlist and is checked again the next time.
+    Failed {
+        entry: WaitlistEntry,
+        error: SquareError,
+    },
+}
+
+/// Where the entries of a [Waitlist](Waitlist) are kept.
+pub trait WaitlistStore {
+    /// Add an entry to the store, replacing any entry with the same id.
+    fn insert(&mut self, entry: WaitlistEntry);
+
+    /// Remove the entry with the given id from the store, returning it if it was present.
+    fn remove(&mut self, id: &str) -> Option<WaitlistEntry>;
+
+    /// All entries in the store, in the order they were added.
+    fn entries(&self) -> Vec<WaitlistEntry>;
+}
+
+/// A [WaitlistStore](WaitlistStore) holding its entries in memory.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryWaitlistStore {
+    entries: Vec<WaitlistEntry>,
+}
+
+impl WaitlistStore for MemoryWaitlistStore {
+    fn insert(&mut self, entry: WaitlistEntry) {
+        match self.entries.iter_mut().find(|existing| existing.id == entry.id) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    fn remove(&mut self, id: &str) -> Option<WaitlistEntry> {
+        let index = self.entries.iter()
+            .position(|entry| entry.id.as_deref() == Some(id))?;
+
+        Some(self.entries.remove(index))
+    }
+
+    fn entries(&self) -> Vec<WaitlistEntry> {
+        self.entries.clone()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Waitlist implementation
+// -------------------------------------------------------------------------------------------------
+/// Books waitlisted customers into slots as they become available.
+pub struct Waitlist<S: WaitlistStore> {
+    store: S,
+}
+
+impl<S: WaitlistStore> Waitlist<S> {
+    pub fn new(store: S) -> Self {
+        Waitlist { store }
+    }
+
+    /// The store holding the entries of this waitlist.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Add an entry built through the [Builder](Builder) to the waitlist.
+    pub fn add(&mut self, entry: WaitlistEntry) {
+        self.store.insert(entry);
+    }
+
+    /// Remove the entry with the given id from the waitlist.
+    pub fn remove(&mut self, id: &str) -> Option<WaitlistEntry> {
+        self.store.remove(id)
+    }
+
+    /// Check the availability of every entry, in the order they were added, booking the first
+    /// available slot for each entry that has one.
+    pub async fn poll(&mut self, client: &SquareClient) -> Vec<WaitlistEvent> {
+        let entries = self.store.entries();
+
+        self.check(client, entries).await
+    }
+
+    /// Check the availability of the entries that could take the slot of the given booking, e.g.
+    /// one received in a `booking.updated` webhook event after it was cancelled.
+    pub async fn slot_freed(&mut self, client: &SquareClient, booking: &Booking)
+                            -> Vec<WaitlistEvent> {
+        let entries = self.store.entries().into_iter()
+            .filter(|entry| entry.matches(booking))
+            .collect();
+
+        self.check(client, entries).await
+    }
+
+    async fn check(&mut self, client: &SquareClient, entries: Vec<WaitlistEntry>)
+                   -> Vec<WaitlistEvent> {
+        let mut events = Vec::new();
+
+        for entry in entries {
+            let booked = match search(client, &entry).await {
+                Ok(Some(availability)) => book(client, &entry, availability).await,
+                Ok(None) => continue,
+                Err(error) => Err(error),
+            };
+
+            match booked {
+                Ok(booking) => {
+                    if let Some(id) = entry.id.as_ref() {
+                        self.store.remove(id);
+                    }
+                    events.push(WaitlistEvent::Booked { entry, booking: Box::new(booking) });
+                },
+                Err(error) => events.push(WaitlistEvent::Failed { entry, error }),
+            }
+        }
+
+        events
+    }
+}
+
+// searches for the first availability matching the entry
+async fn search(client: &SquareClient, entry: &WaitlistEntry)
+                -> Result<Option<Availability>, SquareError> {
+    let mut query = Builder::from(SearchAvailabilityQuery::default())
+        .start_at_range(
+            entry.start_at_min.clone().unwrap_or_default(),
+            entry.start_at_max.clone().unwrap_or_default(),
+        )
+        .location_id(entry.location_id.clone().unwrap_or_default());
+    let service_variation_id = entry.service_variation_id.clone().unwrap_or_default();
+    query = match entry.team_member_ids.clone() {
+        Some(team_member_ids) => query.team_member_segment_filter(service_variation_id, team_member_ids),
+        None => query.segment_filters(service_variation_id),
+    };
+    let query = query.build().await.map_err(|_| SquareError::from(None))?;
+
+    let availability = client.bookings().search_availability(query).await?
+        .into_iter()
+        .next();
+
+    Ok(availability)
+}
+
+// creates a booking for the entry in the given availability
+async fn book(client: &SquareClient, entry: &WaitlistEntry, availability: Availability)
+              -> Result<Booking, SquareError> {
+    let mut builder = Builder::from(BookingsPost::default())
+        .customer_id(entry.customer_id.clone().unwrap_or_default())
+        .location_id(availability.location_id)
+        .start_at(availability.start_at);
+
+    for segment in availability.appointment_segments {
+        builder = builder.add_appointment_segment(segment);
+    }
+    if let Some(note) = entry.customer_note.clone() {
+        builder = builder.customer_note(note);
+    }
+
+    let booking_post = builder.build().await.map_err(|_| SquareError::from(None))?;
+
+    client.bookings().create(booking_post).await?
+        .into_responses()
+        .into_iter()
+        .find_map(|response| match response {
+            Response::Booking(booking) => Some(booking),
+            _ => None,
+        })
+        .ok_or_else(|| SquareError::from(None))
+}
+
+#[cfg(test)]
+mod test_waitlist {
+    use super::*;
+    use crate::objects::AppointmentSegment;
+
+    async fn entry(id: &str) -> WaitlistEntry {
+        let mut entry = Builder::from(WaitlistEntry::default())
+            .customer_id("some_customer_id".to_string())
+            .location_id("some_location_id".to_string())
+            .service_variation_id("some_service_variation_id".to_string())
+            .add_team_member_id("some_team_member_id".to_string())
+            .start_at_range(
+                "2022-10-12T07:20:50.52Z".to_string(),
+                "2022-11-12T07:20:50.52Z".to_string(),
+            )
+            .build()
+            .await
+            .unwrap();
+        entry.id = Some(id.to_string());
+
+        entry
+    }
+
+    #[tokio::test]
+    async fn test_waitlist_entry_builder() {
+        let actual = Builder::from(WaitlistEntry::default())
+            .customer_id("some_customer_id".to_string())
+            .location_id("some_location_id".to_string())
+            .service_variation_id("some_service_variation_id".to_string())
+            .start_at_range(
+                "2022-10-12T07:20:50.52Z".to_string(),
+                "2022-11-12T07:20:50.52Z".to_string(),
+            )
+            .build()
+            .await;
+
+        assert!(actual.unwrap().id.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_waitlist_entry_builder_fail() {
+        let actual = Builder::from(WaitlistEntry::default())
+            .customer_id("some_customer_id".to_string())
+            .location_id("some_location_id".to_string())
+            .build()
+            .await;
+
+        assert!(actual.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_memory_waitlist_store() {
+        let mut waitlist = Waitlist::new(MemoryWaitlistStore::default());
+        waitlist.add(entry("first").await);
+        waitlist.add(entry("second").await);
+        waitlist.add(entry("first").await);
+
+        let ids: Vec<Option<String>> = waitlist.store().entries().into_iter()
+            .map(|entry| entry.id)
+            .collect();
+        assert_eq!(vec![Some("first".to_string()), Some("second".to_string())], ids);
+
+        assert!(waitlist.remove("first").is_some());
+        assert!(waitlist.remove("first").is_none());
+        assert_eq!(1, waitlist.store().entries().len());
+    }
+
+    #[tokio::test]
+    async fn test_waitlist_entry_matches() {
+        let entry = entry("first").await;
+        let mut booking = Booking {
+            location_id: Some("some_location_id".to_string()),
+            appointment_segments: Some(vec![AppointmentSegment {
+                team_member_id: "some_team_member_id".to_string(),
+                service_variation_id: "some_service_variation_id".to_string(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert!(entry.matches(&booking));
+
+        booking.appointment_segments.as_mut().unwrap()[0].team_member_id = "other_id".to_string();
+        assert!(!entry.matches(&booking));
+
+        booking.appointment_segments = None;
+        assert!(!entry.matches(&booking));
+    }
+}
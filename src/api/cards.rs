@@ -1,16 +1,35 @@
 /*!
 Cards functionality of the [Square API](https://developer.squareup.com).
+
+# Example: Find the cards on file that expire within the next two months
+```no_run
+use square_ox::client::SquareClient;
+
+async {
+    let expiring = SquareClient::new("some_token")
+        .cards()
+        .expiring_within(2)
+        .await
+        .unwrap();
+
+    for customer in expiring {
+        println!("ask {} to update {} card(s)", customer.customer_id, customer.cards.len());
+    }
+};
+```
  */
 
 use crate::client::SquareClient;
 use crate::api::{Verb, SquareAPI};
 use crate::errors::{CardBuildError, SquareError, ValidationError};
 use crate::response::SquareResponse;
-use crate::objects::{Address, Card};
+use crate::objects::{Address, Card, Response};
 
+use chrono::{Datelike, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
-use crate::builder::{Builder, ParentBuilder, Validate};
+use crate::builder::{valid_raw_fields, Builder, ParentBuilder, RawFields, Validate};
 use crate::objects::enums::SortOrder;
 
 impl SquareClient {
@@ -33,46 +52,73 @@ impl<'a> Cards<'a> {
     ///
     /// # Example
     /// ```rust
-    ///use square_ox::{
-    ///    response::{SquareResponse, ResponseError},
-    ///    client::SquareClient
-    ///    };
+    ///use square_ox::client::SquareClient;
     ///
     /// async {
-    ///     let locations = SquareClient::new("some_token")
+    ///     let card = SquareClient::new("some_token")
     ///         .cards()
     ///         .retrieve("some_id".to_string())
     ///         .await;
     /// };
-    pub async fn retrieve(self, card_id: String)
-                               -> Result<SquareResponse, SquareError> {
+    /// ```
+    pub async fn retrieve(self, card_id: String) -> Result<Card, SquareError> {
         self.client.request(
             Verb::GET,
             SquareAPI::Cards(format!("/{}", card_id)),
             None::<&Card>,
             None,
-        ).await
+        ).await.and_then(card_from)
     }
 
     /// See which [Card](Card)s are on file by requesting the information from the
-    /// [Square API](https://developer.squareup.com) and receiving them formatted as a
-    /// list of [Card](Card)s.
+    /// [Square API](https://developer.squareup.com), following the cursor through every page.
+    /// # Arguments:
+    /// * `parameters` - The filters to list the cards by, e.g. the customer they belong to. Any
+    ///   cursor set on them is ignored.
+    ///
     /// # Example
     /// ```rust
     ///use square_ox::{
-    ///    response::{SquareResponse, ResponseError},
+    ///    api::cards::ListCardsQueryBuilder,
     ///    client::SquareClient
     ///    };
     ///
     /// async {
-    ///     let locations = SquareClient::new("some_token")
+    ///     let cards = SquareClient::new("some_token")
     ///         .cards()
-    ///         .list(None)
+    ///         .list(ListCardsQueryBuilder::new().customer_id("some_customer_id".to_string()))
     ///         .await;
     /// };
     /// ```
-    pub async fn list(self, search_query: Option<Vec<(String, String)>>)
-                            -> Result<SquareResponse, SquareError> {
+    pub async fn list(self, parameters: ListCardsQueryBuilder) -> Result<Vec<Card>, SquareError> {
+        let mut cards = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut query = parameters.clone();
+            query.cursor = cursor;
+
+            let res = self.client.cards().list_page(Some(query.build().await)).await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::Cards(page) = response {
+                    cards.extend(page);
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(cards);
+            }
+        }
+    }
+
+    /// Lists a single page of [Card](Card)s on file.
+    /// # Arguments:
+    /// * `search_query` - The query parameters, as built by a
+    ///   [ListCardsQueryBuilder](ListCardsQueryBuilder).
+    pub async fn list_page(self, search_query: Option<Vec<(String, String)>>)
+                           -> Result<SquareResponse, SquareError> {
         self.client.request(
             Verb::GET,
             SquareAPI::Cards("".to_string()),
@@ -118,36 +164,99 @@ impl<'a> Cards<'a> {
         ).await
     }
 
-    /// Disable [Card](Card) at the [Square API](https://developer.squareup.com).
+    /// Disable [Card](Card) at the [Square API](https://developer.squareup.com). Disabling a card
+    /// cannot be undone, and a disabled card can no longer be charged.
     /// # Arguments:
     /// * `card_id` - The id of the [Card](Card) you want to disable.
     ///
     /// # Example
     /// ```rust
-    ///use square_ox::{
-    ///    response::{SquareResponse, ResponseError},
-    ///    client::SquareClient
-    ///    };
+    ///use square_ox::client::SquareClient;
     ///
     /// async {
-    ///     let locations = SquareClient::new("some_token")
+    ///     let card = SquareClient::new("some_token")
     ///         .cards()
     ///         .disable("some_id".to_string())
     ///         .await;
     /// };
     /// ```
-    pub async fn disable(self, card_id: String)
-                              -> Result<SquareResponse, SquareError> {
+    pub async fn disable(self, card_id: String) -> Result<Card, SquareError> {
         self.client.request(
             Verb::POST,
             SquareAPI::Cards(format!("/{}/disable", card_id)),
             None::<&Card>,
             None,
-        ).await
+        ).await.and_then(card_from)
+    }
+
+    /// Lists the enabled [Card](Card)s on file that expire within the given number of months,
+    /// grouped by customer. Cards that already expired are included, cards without a customer or
+    /// an expiration date are left out. The customers whose cards expire first come first.
+    /// # Arguments:
+    /// * `months` - How many months past the current one to look ahead. With 0 only the cards
+    ///   expiring at the end of the current month, or earlier, are listed.
+    pub async fn expiring_within(self, months: u32) -> Result<Vec<ExpiringCards>, SquareError> {
+        let cards = self.client.cards()
+            .list(ListCardsQueryBuilder::new().exclude_disabled())
+            .await?;
+
+        Ok(expiring(cards, Utc::now().date_naive(), months))
     }
 }
 
-#[derive(Default)]
+pub(crate) fn card_from(response: SquareResponse) -> Result<Card, SquareError> {
+    response.into_responses()
+        .into_iter()
+        .find_map(|response| match response {
+            Response::Card(card) => Some(card),
+            _ => None,
+        })
+        .ok_or_else(|| SquareError::from(None))
+}
+
+/// The [Card](Card)s of a customer that expire soon, found through
+/// [expiring_within](Cards::expiring_within).
+#[derive(Clone, Debug)]
+pub struct ExpiringCards {
+    pub customer_id: String,
+    /// The expiring cards of the customer, the one expiring first first.
+    pub cards: Vec<Card>,
+}
+
+// the cards expiring at the latest in the month the given number of months after today's,
+// grouped by customer
+fn expiring(cards: Vec<Card>, today: NaiveDate, months: u32) -> Vec<ExpiringCards> {
+    let last_month = month_index(today.year() as i64, today.month() as i64) + months as i64;
+
+    let mut expiring: Vec<(i64, Card)> = cards.into_iter()
+        .filter(|card| card.enabled != Some(false) && card.customer_id.is_some())
+        .filter_map(|card| {
+            let expires = month_index(card.exp_year?, card.exp_month?);
+
+            Some((expires, card)).filter(|(expires, _)| *expires <= last_month)
+        })
+        .collect();
+    expiring.sort_by_key(|(expires, _)| *expires);
+
+    let mut grouped: Vec<ExpiringCards> = vec![];
+    for (_, card) in expiring {
+        let customer_id = card.customer_id.clone().unwrap_or_default();
+
+        match grouped.iter_mut().find(|group| group.customer_id == customer_id) {
+            Some(group) => group.cards.push(card),
+            None => grouped.push(ExpiringCards { customer_id, cards: vec![card] }),
+        }
+    }
+
+    grouped
+}
+
+// the number of months since the start of year 0, so months can be compared and added to
+fn month_index(year: i64, month: i64) -> i64 {
+    year * 12 + month - 1
+}
+
+#[derive(Clone, Default)]
 pub struct ListCardsQueryBuilder {
     cursor: Option<String>,
     customer_id: Option<String>,
@@ -239,12 +348,17 @@ pub struct CardWrapper {
     pub(crate) card: Card,
     pub(crate) idempotency_key: Option<String>,
     pub(crate) source_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) verification_token: Option<String>,
+    #[serde(flatten)]
+    pub(crate) raw_fields: Map<String, Value>,
 }
 
 impl Validate for CardWrapper {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.source_id.is_some() {
+        // a card on file always belongs to a customer
+        if self.source_id.is_some() && self.card.customer_id.is_some()
+            && valid_raw_fields(&mut self) {
             self.idempotency_key = Some(Uuid::new_v4().to_string());
 
             Ok(self)
@@ -254,6 +368,12 @@ impl Validate for CardWrapper {
     }
 }
 
+impl RawFields for CardWrapper {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<CardWrapper, T> {
     pub fn customer_id(mut self, customer_id: String) -> Self {
         self.body.card.customer_id = Some(customer_id);
@@ -261,17 +381,40 @@ impl<T: ParentBuilder> Builder<CardWrapper, T> {
         self
     }
 
+    /// The billing address of the card, which issuers may check the address entered against.
     pub fn billing_address(mut self, address: Address) -> Self {
         self.body.card.billing_address = Some(address);
 
         self
     }
 
+    pub fn cardholder_name(mut self, cardholder_name: String) -> Self {
+        self.body.card.cardholder_name = Some(cardholder_name);
+
+        self
+    }
+
+    /// The token of a buyer verification, e.g. from the Web Payments SDK, which Strong Customer
+    /// Authentication requires when storing cards in the EU and the UK.
+    pub fn verification_token(mut self, verification_token: String) -> Self {
+        self.body.verification_token = Some(verification_token);
+
+        self
+    }
+
     pub fn source_id(mut self, source_id: String) -> Self {
         self.body.source_id = Some(source_id);
 
         self
     }
+
+    /// An optional user-defined reference ID that associates this card with another entity in an
+    /// external system.
+    pub fn reference_id(mut self, reference_id: String) -> Self {
+        self.body.card.reference_id = Some(reference_id);
+
+        self
+    }
 }
 
 #[cfg(test)]
@@ -295,6 +438,41 @@ mod test_cards {
         assert_eq!(expected, actual)
     }
 
+    #[test]
+    fn test_expiring() {
+        let cards: Vec<Card> = serde_json::from_value(serde_json::json!([
+            { "id": "A1", "customer_id": "A", "exp_month": 8, "exp_year": 2022 },
+            { "id": "B1", "customer_id": "B", "exp_month": 6, "exp_year": 2022 },
+            { "id": "A2", "customer_id": "A", "exp_month": 5, "exp_year": 2022 },
+            { "id": "A3", "customer_id": "A", "exp_month": 9, "exp_year": 2022 },
+            { "id": "B2", "customer_id": "B", "exp_month": 7, "exp_year": 2022, "enabled": false },
+            { "id": "C1", "exp_month": 6, "exp_year": 2022 },
+            { "id": "D1", "customer_id": "D", "exp_month": 1, "exp_year": 2023 },
+            { "id": "D2", "customer_id": "D" },
+        ])).unwrap();
+
+        let actual = expiring(cards, NaiveDate::from_ymd_opt(2022, 6, 15).unwrap(), 2);
+
+        let ids: Vec<(String, Vec<String>)> = actual.into_iter()
+            .map(|group| (
+                group.customer_id,
+                group.cards.into_iter().filter_map(|card| card.id).collect(),
+            ))
+            .collect();
+        assert_eq!(ids, vec![
+            ("A".to_string(), vec!["A2".to_string(), "A1".to_string()]),
+            ("B".to_string(), vec!["B1".to_string()]),
+        ]);
+
+        // the year wraps around
+        let card: Card = serde_json::from_value(serde_json::json!(
+            { "id": "D1", "customer_id": "D", "exp_month": 1, "exp_year": 2023 }
+        )).unwrap();
+        let december = NaiveDate::from_ymd_opt(2022, 12, 1).unwrap();
+        assert_eq!(expiring(vec![card.clone()], december, 1).len(), 1);
+        assert!(expiring(vec![card], december, 0).is_empty());
+    }
+
     #[tokio::test]
     async fn test_list_cards() {
         use dotenv::dotenv;
@@ -304,13 +482,12 @@ mod test_cards {
         let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
         let sut = SquareClient::new(&access_token);
 
-        let input = vec![
-            ("include_disabled".to_string(), "false".to_string()),
-            ("sort_order".to_string(), "ASC".to_string()),
-        ];
+        let input = ListCardsQueryBuilder::new()
+            .exclude_disabled()
+            .sort_ascending();
 
         let res = sut.cards()
-            .list(Some(input))
+            .list(input)
             .await;
 
         assert!(res.is_ok())
@@ -358,7 +535,8 @@ mod test_cards {
             },
             idempotency_key: None,
             source_id: Some("cnon:card-nonce-ok".to_string()),
-            verification_token: None
+            verification_token: None,
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(CardWrapper::default())
@@ -375,6 +553,37 @@ mod test_cards {
         assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
     }
 
+    #[tokio::test]
+    async fn test_card_builder_verification() {
+        let actual = Builder::from(CardWrapper::default())
+            .customer_id("EDH2RWZCFCRGZCZ99GMG8ZF59R".to_string())
+            .source_id("cnon:card-nonce-ok".to_string())
+            .verification_token("verf:some-token".to_string())
+            .cardholder_name("Amelia Earhart".to_string())
+            .billing_address(Address {
+                postal_code: Some("94103".to_string()),
+                ..Default::default()
+            })
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(actual.verification_token.as_deref(), Some("verf:some-token"));
+        assert_eq!(actual.card.cardholder_name.as_deref(), Some("Amelia Earhart"));
+        assert_eq!(
+            actual.card.billing_address.and_then(|address| address.postal_code).as_deref(),
+            Some("94103"),
+        );
+
+        let without_customer = Builder::from(CardWrapper::default())
+            .source_id("cnon:card-nonce-ok".to_string())
+            .verification_token("verf:some-token".to_string())
+            .build()
+            .await;
+
+        assert!(without_customer.is_err());
+    }
+
     // #[tokio::test]
     async fn test_create_card() {
         use dotenv::dotenv;
@@ -406,7 +615,8 @@ mod test_cards {
             },
             idempotency_key: Some(Uuid::new_v4().to_string()),
             source_id: Some("cnon:card-nonce-ok".to_string()),
-            verification_token: None
+            verification_token: None,
+            raw_fields: Default::default(),
         };
 
         let res = sut.cards()
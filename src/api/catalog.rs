@@ -1,17 +1,34 @@
 /*!
 Catalog functionality of the [Square API](https://developer.squareup.com).
  */
+
+pub mod attributes;
+pub mod feed;
+pub mod images;
+pub mod pricing;
+pub mod related;
+
 use crate::client::SquareClient;
 use crate::api::{Verb, SquareAPI};
 use crate::errors::{ObjectUpsertRequestBuildError, SquareError, ValidationError};
 use crate::response::SquareResponse;
-use crate::objects::{CatalogItem, CatalogObject, CatalogObjectVariation, CatalogQuery, CustomAttributeFilter, enums::CatalogObjectTypeEnum};
+use crate::objects::{CatalogCustomAttributeDefinition, CatalogDiscount, CatalogInfoResponseLimits, CatalogItem, CatalogObject, CatalogObjectBatch, CatalogObjectVariation, CatalogPricingRule, CatalogProductSet, CatalogQuery, CatalogSubscriptionPlan, CatalogSubscriptionPlanVariation, CatalogTimePeriod, CustomAttributeFilter, Response, StandardUnitDescriptionGroup};
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 use uuid::Uuid;
-use crate::builder::{Builder, Nil, ParentBuilder, Validate};
+use crate::builder::{valid_raw_fields, AddField, Builder, Nil, ParentBuilder, RawFields, Validate};
 use crate::objects::enums::{CatalogItemProductType, CatalogObjectType, SearchCatalogItemsRequestStockLevel, SortOrder};
 
+// the maximum number of objects in a single batch, and in a single batch upsert request
+const OBJECTS_PER_BATCH: usize = 1000;
+const OBJECTS_PER_UPSERT: usize = 10000;
+// the maximum number of objects in a single batch delete and batch retrieve request
+const OBJECTS_PER_DELETE: usize = 200;
+const OBJECTS_PER_RETRIEVE: usize = 1000;
+
 impl SquareClient {
     pub fn catalog(&self) -> Catalog {
         Catalog {
@@ -25,9 +42,44 @@ pub struct Catalog<'a> {
 }
 
 impl<'a> Catalog<'a> {
-    /// Returns a list of all [CatalogObjects](crate::objects::CatalogObject)s of the specified types in the catalog.
-    pub async fn list(self, list_parameters: Option<Vec<(String, String)>>)
-                              -> Result<SquareResponse, SquareError> {
+    /// Lists the [CatalogObject](crate::objects::CatalogObject)s of the types given in the
+    /// parameters, or of all top-level types if none are given, page by page. No request is sent
+    /// until the first page is asked for.
+    /// # Arguments:
+    /// * `parameters` - The types and catalog version to list, and the cursor to start from.
+    ///
+    /// # Example: List all items and categories
+    /// ```no_run
+    /// use square_ox::{
+    ///     api::catalog::CatalogListParameterBuilder,
+    ///     client::SquareClient,
+    ///     objects::enums::CatalogObjectType,
+    /// };
+    ///
+    /// async {
+    ///     let client = SquareClient::new("some_token");
+    ///     let objects = client.catalog()
+    ///         .list(CatalogListParameterBuilder::new()
+    ///             .add_type(CatalogObjectType::Item)
+    ///             .add_type(CatalogObjectType::Category))
+    ///         .all()
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
+    pub fn list(self, parameters: CatalogListParameterBuilder) -> CatalogListPages<'a> {
+        CatalogListPages {
+            client: self.client,
+            cursor: parameters.cursor.clone(),
+            parameters,
+            exhausted: false,
+        }
+    }
+
+    /// Returns a single page of the [CatalogObject](crate::objects::CatalogObject)s of the
+    /// specified types in the catalog.
+    pub async fn list_page(self, list_parameters: Option<Vec<(String, String)>>)
+                           -> Result<SquareResponse, SquareError> {
         self.client.request(
             Verb::GET,
             SquareAPI::Catalog("/list".to_string()),
@@ -47,6 +99,62 @@ impl<'a> Catalog<'a> {
         ).await
     }
 
+    /// Creates or updates up to 10,000 [CatalogObject](crate::objects::CatalogObject)s in batches
+    /// of up to 1,000. Each batch is applied as a whole, so a failing object only fails its own
+    /// batch.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/catalog/batch-upsert-catalog-objects)
+    pub async fn batch_upsert_objects(self, body: BatchUpsertObjects)
+                                      -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Catalog("/batch-upsert".to_string()),
+            Some(&body),
+            None,
+        ).await
+    }
+
+    /// Creates or updates any number of [CatalogObject](crate::objects::CatalogObject)s, such as
+    /// a full menu, split into as many batch upsert requests of up to 10,000 objects in batches of
+    /// up to 1,000 as it takes, or fewer if [info](Catalog::info) reports lower limits.
+    ///
+    /// The objects are sent in the order given. Temporary IDs such as `#coffee` of the objects
+    /// created by an earlier request are replaced by their permanent IDs in the later ones, so an
+    /// object should not come before the objects it references. Fails at the first request that
+    /// fails, after the objects of the requests before it were upserted.
+    /// # Arguments:
+    /// * `objects` - The objects to create or update.
+    pub async fn batch_upsert(self, objects: Vec<CatalogObject>)
+                              -> Result<BatchUpsertResult, SquareError> {
+        let client = self.client;
+        let sizes = client.catalog().batch_sizes().await;
+        let mut result = BatchUpsertResult::default();
+
+        for upsert in objects.chunks(sizes.per_upsert) {
+            let mut body = Builder::from(BatchUpsertObjects::default());
+            for batch in upsert.chunks(sizes.per_batch) {
+                body = body.add_batch(batch.iter()
+                    .map(|object| resolve_ids(object, &result.id_mappings))
+                    .collect::<Result<Vec<CatalogObject>, SquareError>>()?);
+            }
+            let body = body.build().await.map_err(|_| SquareError::from(None))?;
+
+            let res = client.catalog().batch_upsert_objects(body).await?;
+            result.id_mappings.extend(res.id_mappings.iter()
+                .flatten()
+                .filter_map(|mapping| {
+                    Some((mapping.client_object_id.clone()?, mapping.object_id.clone()?))
+                }));
+            result.objects.extend(res.into_responses()
+                .into_iter()
+                .flat_map(|response| match response {
+                    Response::Objects(objects) => objects,
+                    _ => vec![],
+                }));
+        }
+
+        Ok(result)
+    }
+
     /// Deletes a single CatalogObject based on the provided ID and returns the set of successfully
     /// deleted IDs in the response.
     pub async fn delete_object(self, object_id: String)
@@ -75,6 +183,87 @@ impl<'a> Catalog<'a> {
         ).await
     }
 
+    /// Deletes up to 200 [CatalogObject](crate::objects::CatalogObject)s along with their
+    /// children, such as the variations of an item, and returns the IDs of all objects deleted.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/catalog/batch-delete-catalog-objects)
+    pub async fn batch_delete_objects(self, body: BatchDeleteObjects)
+                                      -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Catalog("/batch-delete".to_string()),
+            Some(&body),
+            None,
+        ).await
+    }
+
+    /// Deletes any number of [CatalogObject](crate::objects::CatalogObject)s along with their
+    /// children, split into as many batch delete requests of up to 200 objects as it takes, or
+    /// fewer if [info](Catalog::info) reports a lower limit. Fails at the first request that fails,
+    /// after the objects of the requests before it were deleted.
+    /// # Arguments:
+    /// * `object_ids` - The IDs of the objects to delete.
+    pub async fn batch_delete(self, object_ids: Vec<String>)
+                              -> Result<BatchDeleteResult, SquareError> {
+        let client = self.client;
+        let sizes = client.catalog().batch_sizes().await;
+        let mut result = BatchDeleteResult::default();
+
+        for object_ids in unique(object_ids).chunks(sizes.per_delete) {
+            let body = Builder::from(BatchDeleteObjects::default())
+                .object_ids(object_ids.to_vec())
+                .build()
+                .await
+                .map_err(|_| SquareError::from(None))?;
+
+            let res = client.catalog().batch_delete_objects(body).await?;
+            result.deleted_object_ids.extend(res.deleted_object_ids.into_iter().flatten());
+            result.deleted_at = res.deleted_at.or(result.deleted_at);
+        }
+
+        Ok(result)
+    }
+
+    /// Retrieves any number of [CatalogObject](crate::objects::CatalogObject)s, split into as many
+    /// batch retrieve requests of up to 1,000 objects as it takes, or fewer if
+    /// [info](Catalog::info) reports a lower limit. Objects that do not exist are left out of the
+    /// result.
+    /// # Arguments:
+    /// * `object_ids` - The IDs of the objects to retrieve.
+    /// * `include_related` - Whether to also return the objects the retrieved ones reference,
+    ///   such as the categories and taxes of an item.
+    pub async fn batch_retrieve(self, object_ids: Vec<String>, include_related: bool)
+                                -> Result<BatchRetrieveResult, SquareError> {
+        let client = self.client;
+        let sizes = client.catalog().batch_sizes().await;
+        let mut result = BatchRetrieveResult::default();
+
+        for object_ids in unique(object_ids).chunks(sizes.per_retrieve) {
+            let mut body = Builder::from(BatchRetrieveObjects::default())
+                .object_ids(object_ids.to_vec());
+            if include_related {
+                body = body.include_related_objects();
+            }
+            let body = body.build().await.map_err(|_| SquareError::from(None))?;
+
+            for response in client.catalog().batch_retrieve_object(body).await?.into_responses() {
+                match response {
+                    Response::Objects(objects) => result.objects.extend(objects),
+                    Response::RelatedObjects(objects) => result.related_objects.extend(objects),
+                    _ => {},
+                }
+            }
+        }
+
+        // objects related to those of several requests are returned by each of them
+        let mut related_ids = HashSet::new();
+        result.related_objects.retain(|object| match object.id.as_ref() {
+            Some(id) => related_ids.insert(id.clone()),
+            None => true,
+        });
+
+        Ok(result)
+    }
+
     /// Returns a set of [CatalogObject](CatalogObject)s based on the provided ID.
     /// [Open in API Reference](https://developer.squareup.com/reference/square/catalog/batch-retrieve-catalog-objects)
     pub async fn batch_retrieve_object(
@@ -104,20 +293,54 @@ impl<'a> Catalog<'a> {
     }
 
     /// Retrieves information about the [Square Catalog API](https://developer.squareup.com), such
-    /// as batch size limits that can be used by the `BatchUpsertCatalogObjects` endpoint.
-    pub async fn info(self)
-                              -> Result<SquareResponse, SquareError> {
-        self.client.request(
+    /// as batch size limits that can be used by the `BatchUpsertCatalogObjects` endpoint, and the
+    /// standard units of measurement in the language of the seller.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/catalog-api/catalog-info)
+    pub async fn info(self) -> Result<CatalogInfo, SquareError> {
+        let res = self.client.request(
             Verb::GET,
             SquareAPI::Catalog("/info".to_string()),
             None::<&SearchCatalogObjectsBody>,
             None,
-        ).await
+        ).await?;
+
+        let mut info = CatalogInfo::default();
+        for response in res.into_responses() {
+            match response {
+                Response::Limits(limits) => info.limits = Some(limits),
+                Response::StandardUnitDescriptionGroup(group) => {
+                    info.standard_unit_description_group = Some(group)
+                },
+                _ => {},
+            }
+        }
+
+        Ok(info)
     }
 
-    // TODO implement search_catalog_items
-    /// Retrieves information about the [Square Catalog API](https://developer.squareup.com), such
-    /// as batch size limits that can be used by the `BatchUpsertCatalogObjects` endpoint.
+    // the sizes of the requests of the batch helpers, the documented ones if the limits can not
+    // be retrieved
+    pub(crate) async fn batch_sizes(self) -> BatchSizes {
+        self.info().await
+            .ok()
+            .and_then(|info| info.limits)
+            .map(|limits| BatchSizes::from_limits(&limits))
+            .unwrap_or_default()
+    }
+
+    /// Searches for items and item variations by the filters of the
+    /// [SearchCatalogItemsBody](SearchCatalogItemsBody), such as text, categories, stock levels and
+    /// custom attributes. Returns the matching `items` along with the `matched_variation_ids`.
+    ///
+    /// Unlike [search_objects](Catalog::search_objects), which takes a single
+    /// [CatalogQuery](crate::objects::CatalogQuery) over any type of object, the filters combine
+    /// and only items are searched.
+    /// # Arguments:
+    /// * `search_query` - The filters, built through the builder of
+    ///   [SearchCatalogItemsBody](SearchCatalogItemsBody).
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/catalog-api/search-catalog-items)
     pub async fn search_items(self, search_query: SearchCatalogItemsBody)
                                       -> Result<SquareResponse, SquareError> {
         self.client.request(
@@ -132,10 +355,10 @@ impl<'a> Catalog<'a> {
 // -------------------------------------------------------------------------------------------------
 // CatalogListParameterBuilder implementation
 // -------------------------------------------------------------------------------------------------
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct CatalogListParameterBuilder {
     cursor: Option<String>,
-    types: Option<Vec<CatalogObjectTypeEnum>>,
+    types: Option<Vec<CatalogObjectType>>,
     catalog_version: Option<i64>,
 }
 
@@ -150,7 +373,7 @@ impl CatalogListParameterBuilder {
         self
     }
 
-    pub fn add_type(mut self, type_name: CatalogObjectTypeEnum) -> Self {
+    pub fn add_type(mut self, type_name: CatalogObjectType) -> Self {
         if let Some(ref mut types) = &mut self.types {
                 for existing_type in types.iter() {
                     if *existing_type == type_name {
@@ -166,6 +389,13 @@ impl CatalogListParameterBuilder {
         self
     }
 
+    /// List the objects as of the given version of the catalog instead of the current one.
+    pub fn catalog_version(mut self, catalog_version: i64) -> Self {
+        self.catalog_version = Some(catalog_version);
+
+        self
+    }
+
     pub async fn build(self) -> Vec<(String, String)> {
         let mut res = vec![];
 
@@ -174,15 +404,11 @@ impl CatalogListParameterBuilder {
         }
 
         if let Some(types) = self.types {
-            let mut combined = "".to_string();
-            for type_name in types {
-                combined = format!("{}{}%2C", combined, type_name);
-            }
-            if combined.len() > 3 {
-                for _ in 0..3 {
-                    combined.pop();
-                }
-            }
+            let combined = types.iter()
+                .filter_map(|type_name| serde_json::to_value(type_name).ok())
+                .filter_map(|type_name| type_name.as_str().map(|type_name| type_name.to_string()))
+                .collect::<Vec<String>>()
+                .join(",");
             res.push(("types".to_string(), combined))
         }
 
@@ -194,6 +420,63 @@ impl CatalogListParameterBuilder {
     }
 }
 
+/// The pages of a [list](Catalog::list), following the cursor returned with each page.
+pub struct CatalogListPages<'a> {
+    client: &'a SquareClient,
+    parameters: CatalogListParameterBuilder,
+    cursor: Option<String>,
+    exhausted: bool,
+}
+
+impl<'a> CatalogListPages<'a> {
+    /// The cursor of the next page, `None` before the first page and after the last one. Save
+    /// it, e.g. in a [CheckpointStore](crate::checkpoint::CheckpointStore), to resume the list
+    /// later through [cursor](CatalogListParameterBuilder::cursor).
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// Fetches the next page, `None` once the last page has been returned.
+    pub async fn next_page(&mut self) -> Option<Result<Vec<CatalogObject>, SquareError>> {
+        if self.exhausted {
+            return None
+        }
+
+        let mut parameters = self.parameters.clone();
+        parameters.cursor = self.cursor.take();
+
+        let res = match self.client.catalog().list_page(Some(parameters.build().await)).await {
+            Ok(res) => res,
+            Err(err) => {
+                self.exhausted = true;
+                return Some(Err(err))
+            },
+        };
+        self.cursor = res.cursor.clone();
+        self.exhausted = self.cursor.is_none();
+
+        let mut objects = vec![];
+        for response in res.into_responses() {
+            if let Response::Objects(page) = response {
+                objects.extend(page);
+            }
+        }
+
+        Some(Ok(objects))
+    }
+
+    /// Fetches all remaining pages and returns their objects in order.
+    pub async fn all(mut self) -> Result<Vec<CatalogObject>, SquareError> {
+        let mut objects = vec![];
+
+        while let Some(page) = self.next_page().await {
+            objects.extend(page?);
+        }
+
+        Ok(objects)
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // ObjectUpsertRequest builder implementation
 // -------------------------------------------------------------------------------------------------
@@ -201,12 +484,19 @@ impl CatalogListParameterBuilder {
 pub struct ObjectUpsertRequest {
     idempotency_key: Option<String>,
     object: CatalogObject,
+    // set when a custom attribute value did not match its definition
+    #[serde(skip)]
+    invalid_custom_attributes: bool,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for ObjectUpsertRequest {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
         if self.object.id.is_some() &&
-            self.object.type_name.is_some() {
+            self.object.type_name.is_some() &&
+            !self.invalid_custom_attributes &&
+            valid_raw_fields(&mut self) {
             self.idempotency_key = Some(Uuid::new_v4().to_string());
 
             Ok(self)
@@ -216,6 +506,12 @@ impl Validate for ObjectUpsertRequest {
     }
 }
 
+impl RawFields for ObjectUpsertRequest {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<ObjectUpsertRequest, T> {
     pub fn id(mut self, id: String) -> Self {
         self.body.object.id = Some(id);
@@ -235,6 +531,50 @@ impl<T: ParentBuilder> Builder<ObjectUpsertRequest, T> {
         self
     }
 
+    pub fn custom_attribute_definition_data(mut self,
+                                            definition: CatalogCustomAttributeDefinition) -> Self {
+        self.body.object.custom_attribute_definition_data = Some(definition);
+
+        self
+    }
+
+    pub fn discount_data(mut self, discount: CatalogDiscount) -> Self {
+        self.body.object.discount_data = Some(discount);
+
+        self
+    }
+
+    pub fn pricing_rule_data(mut self, pricing_rule: CatalogPricingRule) -> Self {
+        self.body.object.pricing_rule_data = Some(pricing_rule);
+
+        self
+    }
+
+    pub fn product_set_data(mut self, product_set: CatalogProductSet) -> Self {
+        self.body.object.product_set_data = Some(product_set);
+
+        self
+    }
+
+    pub fn time_period_data(mut self, time_period: CatalogTimePeriod) -> Self {
+        self.body.object.time_period_data = Some(time_period);
+
+        self
+    }
+
+    pub fn subscription_plan_data(mut self, plan: CatalogSubscriptionPlan) -> Self {
+        self.body.object.subscription_plan_data = Some(plan);
+
+        self
+    }
+
+    pub fn subscription_plan_variation_data(mut self,
+                                            variation: CatalogSubscriptionPlanVariation) -> Self {
+        self.body.object.subscription_plan_variation_data = Some(variation);
+
+        self
+    }
+
     pub fn add_variations(mut self, variation: CatalogObjectVariation) -> Self {
         if let Some(mut item_data) = self.body.object.item_data.as_mut() {
             if let Some(variations) = item_data.variations.as_mut() {
@@ -330,11 +670,23 @@ pub struct SearchCatalogObjectsBody {
     limit: Option<i64>,
     object_types: Option<Vec<CatalogObjectType>>,
     query: Option<CatalogQuery>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for SearchCatalogObjectsBody {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        Ok(self)
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if valid_raw_fields(&mut self) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for SearchCatalogObjectsBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
     }
 }
 
@@ -404,6 +756,14 @@ impl<T: ParentBuilder> Builder<SearchCatalogObjectsBody, T> {
     }
 }
 
+// implements the necessary traits to release a CatalogQuery builder from a
+// SearchCatalogObjectsBody builder
+impl AddField<CatalogQuery> for SearchCatalogObjectsBody {
+    fn add_field(&mut self, field: CatalogQuery) {
+        self.query = Some(field);
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // SearchCatalogItemsBody builder implementation
 // -------------------------------------------------------------------------------------------------
@@ -418,15 +778,64 @@ pub struct SearchCatalogItemsBody {
     sort_order: Option<SortOrder>,
     stock_levels: Option<Vec<SearchCatalogItemsRequestStockLevel>>,
     text_filter: Option<String>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
+// the most items a single search returns, and the most custom attribute filters it takes
+const ITEMS_PER_SEARCH: i32 = 100;
+const CUSTOM_ATTRIBUTE_FILTERS_PER_SEARCH: usize = 10;
+
 impl Validate for SearchCatalogItemsBody {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        Ok(self)
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.limit.is_none_or(|limit| (1..=ITEMS_PER_SEARCH).contains(&limit)) &&
+            self.custom_attribute_filters.as_ref()
+                .is_none_or(|filters| filters.len() <= CUSTOM_ATTRIBUTE_FILTERS_PER_SEARCH) &&
+            valid_raw_fields(&mut self) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for SearchCatalogItemsBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
     }
 }
 
 impl<T: ParentBuilder> Builder<SearchCatalogItemsBody, T> {
+    /// Matches the items with the text in their name, description or abbreviation, or in the
+    /// name, SKU or UPC of one of their variations.
+    pub fn text_filter(mut self, text_filter: String) -> Self {
+        self.body.text_filter = Some(text_filter);
+
+        self
+    }
+
+    /// Matches the items of any of the categories added.
+    pub fn add_category_id(mut self, category_id: String) -> Self {
+        self.body.category_ids.get_or_insert_with(Vec::new).push(category_id);
+
+        self
+    }
+
+    /// Matches the items enabled at any of the locations added.
+    pub fn add_enabled_location_id(mut self, location_id: String) -> Self {
+        self.body.enabled_location_ids.get_or_insert_with(Vec::new).push(location_id);
+
+        self
+    }
+
+    /// Matches the items of any of the product types added.
+    pub fn add_product_type(mut self, product_type: CatalogItemProductType) -> Self {
+        self.body.product_types.get_or_insert_with(Vec::new).push(product_type);
+
+        self
+    }
+
+    /// Matches the items with a variation that is low on stock at any of the enabled locations.
     pub fn low_stock_level(mut self) -> Self {
         if let Some(vec) = self.body.stock_levels.as_mut() {
             vec.push(SearchCatalogItemsRequestStockLevel::Low)
@@ -436,6 +845,57 @@ impl<T: ParentBuilder> Builder<SearchCatalogItemsBody, T> {
 
         self
     }
+
+    /// Matches the items with a variation that is out of stock at any of the enabled locations.
+    pub fn out_stock_level(mut self) -> Self {
+        self.body.stock_levels.get_or_insert_with(Vec::new)
+            .push(SearchCatalogItemsRequestStockLevel::Out);
+
+        self
+    }
+
+    /// Matches the items passing the filter, along with all other filters added. Takes up to 10
+    /// filters.
+    pub fn add_custom_attribute_filter(mut self, filter: CustomAttributeFilter) -> Self {
+        self.body.custom_attribute_filters.get_or_insert_with(Vec::new).push(filter);
+
+        self
+    }
+
+    /// Sorts the items by name, from A to Z.
+    pub fn sort_ascending(mut self) -> Self {
+        self.body.sort_order = Some(SortOrder::Asc);
+
+        self
+    }
+
+    /// Sorts the items by name, from Z to A.
+    pub fn sort_descending(mut self) -> Self {
+        self.body.sort_order = Some(SortOrder::Desc);
+
+        self
+    }
+
+    /// The most items to return, up to 100.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.body.limit = Some(limit);
+
+        self
+    }
+
+    pub fn cursor(mut self, cursor: String) -> Self {
+        self.body.cursor = Some(cursor);
+
+        self
+    }
+}
+
+// implements the necessary traits to release a CustomAttributeFilter builder from a
+// SearchCatalogItemsBody builder
+impl AddField<CustomAttributeFilter> for SearchCatalogItemsBody {
+    fn add_field(&mut self, field: CustomAttributeFilter) {
+        self.custom_attribute_filters.get_or_insert_with(Vec::new).push(field);
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -447,11 +907,13 @@ pub struct BatchRetrieveObjects {
     pub catalog_version: Option<i32>,
     pub include_deleted_objects: Option<bool>,
     pub include_related_objects: Option<bool>,
+    #[serde(flatten)]
+    pub(crate) raw_fields: Map<String, Value>,
 }
 
 impl Validate for BatchRetrieveObjects {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.object_ids.len() > 0 {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.object_ids.len() > 0 && valid_raw_fields(&mut self) {
             Ok(self)
         } else {
             Err(ValidationError)
@@ -459,6 +921,12 @@ impl Validate for BatchRetrieveObjects {
     }
 }
 
+impl RawFields for BatchRetrieveObjects {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<BatchRetrieveObjects, T> {
     pub fn object_ids(mut self, ids: Vec<String>) -> Self {
         self.body.object_ids = ids;
@@ -491,19 +959,254 @@ impl<T: ParentBuilder> Builder<BatchRetrieveObjects, T> {
     }
 }
 
+/// The outcome of a [batch_retrieve](Catalog::batch_retrieve), across all of its requests.
+#[derive(Clone, Debug, Default)]
+pub struct BatchRetrieveResult {
+    /// The objects retrieved, in the order of the requests.
+    pub objects: Vec<CatalogObject>,
+    /// The objects referenced by the retrieved ones, each once.
+    pub related_objects: Vec<CatalogObject>,
+}
+
+// -------------------------------------------------------------------------------------------------
+// BatchDeleteObjects builder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct BatchDeleteObjects {
+    object_ids: Vec<String>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for BatchDeleteObjects {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if (1..=OBJECTS_PER_DELETE).contains(&self.object_ids.len()) &&
+            valid_raw_fields(&mut self) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for BatchDeleteObjects {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<BatchDeleteObjects, T> {
+    pub fn object_ids(mut self, ids: Vec<String>) -> Self {
+        self.body.object_ids = ids;
+
+        self
+    }
+
+    pub fn add_object_id(mut self, id: String) -> Self {
+        self.body.object_ids.push(id);
+
+        self
+    }
+}
+
+/// The outcome of a [batch_delete](Catalog::batch_delete), across all of its requests.
+#[derive(Clone, Debug, Default)]
+pub struct BatchDeleteResult {
+    /// The IDs of the objects deleted, including those of their children.
+    pub deleted_object_ids: Vec<String>,
+    /// When the last of the objects were deleted, in RFC 3339 format.
+    pub deleted_at: Option<String>,
+}
+
+// the IDs in their order, without repetitions
+fn unique(ids: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+
+    ids.into_iter().filter(|id| seen.insert(id.clone())).collect()
+}
+
+// -------------------------------------------------------------------------------------------------
+// BatchUpsertObjects builder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct BatchUpsertObjects {
+    idempotency_key: Option<String>,
+    batches: Vec<CatalogObjectBatch>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for BatchUpsertObjects {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if !self.batches.is_empty() &&
+            self.batches.iter().all(|batch| !batch.objects.is_empty()) &&
+            valid_raw_fields(&mut self) {
+            self.idempotency_key = Some(Uuid::new_v4().to_string());
+
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for BatchUpsertObjects {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<BatchUpsertObjects, T> {
+    pub fn add_batch(mut self, objects: Vec<CatalogObject>) -> Self {
+        self.body.batches.push(CatalogObjectBatch { objects });
+
+        self
+    }
+}
+
+/// The information [info](Catalog::info) returns about the Catalog API.
+#[derive(Clone, Debug, Default)]
+pub struct CatalogInfo {
+    /// The limits on the size of requests.
+    pub limits: Option<CatalogInfoResponseLimits>,
+    /// The standard units of measurement, such as for the
+    /// [CatalogMeasurementUnit](crate::objects::CatalogMeasurementUnit) of an item.
+    pub standard_unit_description_group: Option<StandardUnitDescriptionGroup>,
+}
+
+// the number of objects in each request of the batch helpers
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct BatchSizes {
+    pub(crate) per_batch: usize,
+    pub(crate) per_upsert: usize,
+    pub(crate) per_delete: usize,
+    pub(crate) per_retrieve: usize,
+}
+
+impl Default for BatchSizes {
+    fn default() -> Self {
+        BatchSizes {
+            per_batch: OBJECTS_PER_BATCH,
+            per_upsert: OBJECTS_PER_UPSERT,
+            per_delete: OBJECTS_PER_DELETE,
+            per_retrieve: OBJECTS_PER_RETRIEVE,
+        }
+    }
+}
+
+impl BatchSizes {
+    // the limits reported by Square, kept within the documented ones the builders validate
+    // against
+    fn from_limits(limits: &CatalogInfoResponseLimits) -> Self {
+        let size = |limit: Option<i32>, documented: usize| limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .filter(|limit| *limit > 0)
+            .map_or(documented, |limit| limit.min(documented));
+
+        let per_upsert = size(limits.batch_upsert_max_total_objects, OBJECTS_PER_UPSERT);
+
+        BatchSizes {
+            per_batch: size(limits.batch_upsert_max_objects_per_batch, OBJECTS_PER_BATCH)
+                .min(per_upsert),
+            per_upsert,
+            per_delete: size(limits.batch_delete_max_object_ids, OBJECTS_PER_DELETE),
+            per_retrieve: size(limits.batch_retrieve_max_object_ids, OBJECTS_PER_RETRIEVE),
+        }
+    }
+}
+
+/// The outcome of a [batch_upsert](Catalog::batch_upsert), across all of its requests.
+#[derive(Clone, Debug, Default)]
+pub struct BatchUpsertResult {
+    /// The objects as created or updated.
+    pub objects: Vec<CatalogObject>,
+    /// The permanent IDs of the created objects, by their temporary IDs.
+    pub id_mappings: HashMap<String, String>,
+}
+
+// the object with the temporary IDs of objects created before replaced by their permanent IDs,
+// wherever they are referenced
+fn resolve_ids(object: &CatalogObject, id_mappings: &HashMap<String, String>)
+               -> Result<CatalogObject, SquareError> {
+    if id_mappings.is_empty() {
+        return Ok(object.clone());
+    }
+
+    let mut value = serde_json::to_value(object)?;
+    replace_ids(&mut value, id_mappings);
+
+    Ok(serde_json::from_value(value)?)
+}
+
+fn replace_ids(value: &mut Value, id_mappings: &HashMap<String, String>) {
+    match value {
+        Value::String(id) => if let Some(object_id) = id_mappings.get(id.as_str()) {
+            *id = object_id.clone();
+        },
+        Value::Array(values) => values.iter_mut()
+            .for_each(|value| replace_ids(value, id_mappings)),
+        Value::Object(values) => values.values_mut()
+            .for_each(|value| replace_ids(value, id_mappings)),
+        _ => {},
+    }
+}
+
 #[cfg(test)]
 mod test_catalog {
     use crate::objects::{CatalogItem, CatalogItemVariation, CatalogObjectVariation, Money};
     use crate::objects::enums::{CatalogItemProductType, CatalogObjectType, CatalogPricingType, Currency};
     use super::*;
+    use crate::builder::BackIntoBuilder;
+
+    #[tokio::test]
+    async fn test_batch_delete_objects_builder() {
+        let actual = Builder::from(BatchDeleteObjects::default())
+            .add_object_id("W62UWFY35CWMYGVWK6TWJDNI".to_string())
+            .add_object_id("AA27W3M2GGTF3H6AVPNB77CK".to_string())
+            .build()
+            .await;
+        assert!(actual.is_ok());
+
+        assert!(Builder::from(BatchDeleteObjects::default()).build().await.is_err());
+        assert!(Builder::from(BatchDeleteObjects::default())
+            .object_ids((0..201).map(|id| id.to_string()).collect())
+            .build()
+            .await
+            .is_err());
+        assert_eq!(
+            unique(vec!["b".to_string(), "a".to_string(), "b".to_string()]),
+            vec!["b".to_string(), "a".to_string()],
+        );
+    }
+
+    #[test]
+    fn test_resolve_ids() {
+        let variation: CatalogObject = serde_json::from_value(serde_json::json!({
+            "id": "#small_coffee",
+            "type": "ITEM_VARIATION",
+            "item_variation_data": { "item_id": "#coffee", "name": "Small" },
+        })).unwrap();
+        let id_mappings = vec![("#coffee".to_string(), "ITEM_ID".to_string())]
+            .into_iter()
+            .collect::<HashMap<String, String>>();
+
+        let actual = resolve_ids(&variation, &id_mappings).unwrap();
+
+        assert_eq!(actual.id, Some("#small_coffee".to_string()));
+        assert_eq!(actual.item_variation_data.unwrap().item_id, Some("ITEM_ID".to_string()));
+    }
 
     #[tokio::test]
     async fn test_list_parameter_builder() {
-        let expected = vec![("types".to_string(), "ITEM%2CCATEGORY".to_string())];
+        let expected = vec![
+            ("types".to_string(), "ITEM,ITEM_VARIATION".to_string()),
+            ("catalog_version".to_string(), "1655427266071".to_string()),
+        ];
         let actual = CatalogListParameterBuilder::new()
-            .add_type(CatalogObjectTypeEnum::Item)
-            .add_type(CatalogObjectTypeEnum::Category)
-            .add_type(CatalogObjectTypeEnum::Item)
+            .add_type(CatalogObjectType::Item)
+            .add_type(CatalogObjectType::ItemVariation)
+            .add_type(CatalogObjectType::Item)
+            .catalog_version(1655427266071)
             .build().await;
 
         assert_eq!(expected, actual)
@@ -518,10 +1221,13 @@ mod test_catalog {
         let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
         let sut = SquareClient::new(&access_token);
 
-        let input = vec![("types".to_string(), "ITEM,CATEGORY".to_string())];
+        let input = CatalogListParameterBuilder::new()
+            .add_type(CatalogObjectType::Item)
+            .add_type(CatalogObjectType::Category);
 
         let res = sut.catalog()
-            .list(Some(input))
+            .list(input)
+            .all()
             .await;
 
         assert!(res.is_ok())
@@ -531,6 +1237,7 @@ mod test_catalog {
     async fn test_upsert_object_request_builder() {
         let expected = ObjectUpsertRequest {
             idempotency_key: None,
+            invalid_custom_attributes: false,
             object: CatalogObject {
                 id: Some("#91039132".to_string()),
                 type_name: Some(CatalogObjectType::Item),
@@ -625,12 +1332,14 @@ mod test_catalog {
                 product_set_data: None,
                 quick_amount_settings_data: None,
                 subscription_plan_data: None,
+                subscription_plan_variation_data: None,
                 tax_data: None,
                 time_period_data: None,
                 updated_at: None,
                 created_at: None,
                 version: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(ObjectUpsertRequest::default())
@@ -729,6 +1438,7 @@ mod test_catalog {
 
         let input = ObjectUpsertRequest {
             idempotency_key: Some(Uuid::new_v4().to_string()),
+            invalid_custom_attributes: false,
             object: CatalogObject {
                 id: Some("#91039132".to_string()),
                 type_name: Some(CatalogObjectType::Item),
@@ -823,12 +1533,14 @@ mod test_catalog {
                 product_set_data: None,
                 quick_amount_settings_data: None,
                 subscription_plan_data: None,
+                subscription_plan_variation_data: None,
                 tax_data: None,
                 time_period_data: None,
                 updated_at: None,
                 created_at: None,
                 version: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let res = sut.catalog()
@@ -905,7 +1617,8 @@ mod test_catalog {
             include_related_objects: Some(true),
             limit: Some(100),
             object_types: Some(vec![CatalogObjectType::Item, CatalogObjectType::ItemVariation]),
-            query: None
+            query: None,
+            raw_fields: Default::default(),
         };
 
         let actual = Builder::from(SearchCatalogObjectsBody::default())
@@ -922,6 +1635,226 @@ mod test_catalog {
         assert_eq!(format!("{:?}",expected), format!("{:?}",actual));
     }
 
+    #[test]
+    fn test_batch_sizes() {
+        let limits: CatalogInfoResponseLimits = serde_json::from_value(serde_json::json!({
+            "batch_upsert_max_objects_per_batch": 1000,
+            "batch_upsert_max_total_objects": 500,
+            "batch_retrieve_max_object_ids": 5000,
+            "batch_delete_max_object_ids": 0,
+        })).unwrap();
+
+        assert_eq!(BatchSizes::from_limits(&limits), BatchSizes {
+            per_batch: 500,
+            per_upsert: 500,
+            per_delete: OBJECTS_PER_DELETE,
+            per_retrieve: OBJECTS_PER_RETRIEVE,
+        });
+    }
+
+    #[tokio::test]
+    async fn test_catalog_query_builder() {
+        let actual = Builder::from(SearchCatalogObjectsBody::default())
+            .add_object_type(CatalogObjectType::Item)
+            .sub_builder_from(CatalogQuery::default())
+            .items_for_tax(vec!["some_tax_id".to_string()])
+            .into_parent_builder()
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(actual.query).unwrap(),
+            serde_json::json!({ "items_for_tax_query": { "tax_ids": ["some_tax_id"] } }),
+        );
+
+        let range = Builder::from(CatalogQuery::default())
+            .range("price_amount".to_string(), Some(100), None)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(range).unwrap(),
+            serde_json::json!({
+                "range_query": { "attribute_name": "price_amount", "attribute_min_value": 100 },
+            }),
+        );
+
+        // only one kind of query can be set
+        assert!(Builder::from(CatalogQuery::default())
+            .exact("name".to_string(), "Coffee".to_string())
+            .prefix("name".to_string(), "Cof".to_string())
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CatalogQuery::default()).build().await.is_err());
+        assert!(Builder::from(CatalogQuery::default())
+            .range("price_amount".to_string(), Some(200), Some(100))
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CatalogQuery::default())
+            .text(vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()])
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CatalogQuery::default())
+            .sorted_attribute("name".to_string(), None, Some(SortOrder::Desc))
+            .build()
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_subscription_plan_variation_builder() {
+        use crate::objects::SubscriptionPhase;
+        use crate::objects::enums::SubscriptionCadence;
+
+        let actual = Builder::from(CatalogSubscriptionPlanVariation::default())
+            .name("Coffee Club".to_string())
+            .subscription_plan_id("some_plan_id".to_string())
+            .sub_builder_from(SubscriptionPhase::default())
+            .cadence(SubscriptionCadence::Monthly)
+            .periods(1)
+            .static_price(Money { amount: Some(0), currency: Currency::USD })
+            .into_parent_builder()
+            .unwrap()
+            .sub_builder_from(SubscriptionPhase::default())
+            .cadence(SubscriptionCadence::Monthly)
+            .relative_price(vec!["some_discount_id".to_string()])
+            .into_parent_builder()
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(actual).unwrap(),
+            serde_json::json!({
+                "name": "Coffee Club",
+                "subscription_plan_id": "some_plan_id",
+                "phases": [
+                    {
+                        "cadence": "MONTHLY",
+                        "ordinal": 0,
+                        "periods": 1,
+                        "pricing": {
+                            "type": "STATIC",
+                            "price_money": { "amount": 0, "currency": "USD" },
+                        },
+                    },
+                    {
+                        "cadence": "MONTHLY",
+                        "ordinal": 1,
+                        "pricing": { "type": "RELATIVE", "discount_ids": ["some_discount_id"] },
+                    },
+                ],
+            }),
+        );
+
+        // only the last phase may run forever
+        let forever = SubscriptionPhase {
+            cadence: Some(SubscriptionCadence::Monthly),
+            ..Default::default()
+        };
+        assert!(Builder::from(CatalogSubscriptionPlanVariation::default())
+            .name("Coffee Club".to_string())
+            .add_phase(forever.clone())
+            .add_phase(forever)
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CatalogSubscriptionPlanVariation::default())
+            .name("Coffee Club".to_string())
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(SubscriptionPhase::default()).build().await.is_err());
+        assert!(Builder::from(CatalogSubscriptionPlan::default()).build().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_happy_hour_builders() {
+        use chrono::{Duration, NaiveDate, Weekday};
+        use crate::objects::enums::ExcludeStrategy;
+
+        let discount = Builder::from(CatalogDiscount::default())
+            .name("Happy Hour".to_string())
+            .fixed_percentage("50".to_string())
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(discount).unwrap(),
+            serde_json::json!({
+                "name": "Happy Hour",
+                "discount_type": "FIXED_PERCENTAGE",
+                "percentage": "50",
+            }),
+        );
+
+        let time_period = Builder::from(CatalogTimePeriod::default())
+            .weekly(
+                NaiveDate::from_ymd_opt(2022, 6, 6).unwrap().and_hms_opt(17, 0, 0).unwrap(),
+                Duration::hours(2),
+                &[Weekday::Mon, Weekday::Fri],
+            )
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(
+            time_period.event.as_deref(),
+            Some("BEGIN:VEVENT\nDTSTART:20220606T170000\nDURATION:PT120M\nRRULE:FREQ=WEEKLY;BYDAY=MO,FR\nEND:VEVENT"),
+        );
+
+        let rule = Builder::from(CatalogPricingRule::default())
+            .name("Happy Hour".to_string())
+            .discount_id("#happy_hour_discount".to_string())
+            .match_products_id("#drinks".to_string())
+            .add_time_period_id("#happy_hour".to_string())
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(rule.time_period_ids, Some(vec!["#happy_hour".to_string()]));
+
+        assert!(Builder::from(CatalogPricingRule::default())
+            .discount_id("#happy_hour_discount".to_string())
+            .match_products_id("#drinks".to_string())
+            .valid_from_date("2022-06-30".to_string())
+            .valid_until_date("2022-06-01".to_string())
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CatalogPricingRule::default())
+            .discount_id("#happy_hour_discount".to_string())
+            .match_products_id("#drinks".to_string())
+            .exclude_products("#cheapest".to_string(), ExcludeStrategy::LeastExpensive)
+            .build()
+            .await
+            .is_ok());
+        assert!(Builder::from(CatalogDiscount::default())
+            .name("Happy Hour".to_string())
+            .fixed_amount(Money { amount: Some(100), currency: Currency::USD })
+            .maximum_amount_money(Money { amount: Some(100), currency: Currency::USD })
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CatalogProductSet::default())
+            .add_product_id_any("some_category_id".to_string())
+            .quantity_min(2)
+            .build()
+            .await
+            .is_ok());
+        assert!(Builder::from(CatalogProductSet::default())
+            .add_product_id_any("some_category_id".to_string())
+            .all_products(true)
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CatalogTimePeriod::default()).build().await.is_err());
+    }
+
     #[tokio::test]
     async fn test_search_objects() {
         use dotenv::dotenv;
@@ -938,7 +1871,8 @@ mod test_catalog {
             include_related_objects: Some(true),
             limit: Some(100),
             object_types: Some(vec![CatalogObjectType::Item, CatalogObjectType::ItemVariation]),
-            query: None
+            query: None,
+            raw_fields: Default::default(),
         };
 
         let res = sut.catalog()
@@ -975,7 +1909,8 @@ mod test_catalog {
             product_types: None,
             sort_order: None,
             stock_levels: None,
-            text_filter: None
+            text_filter: None,
+            raw_fields: Default::default(),
         };
 
         let actual = Builder::from(SearchCatalogItemsBody::default())
@@ -986,6 +1921,60 @@ mod test_catalog {
         assert_eq!(format!("{:?}",expected), format!("{:?}",actual));
     }
 
+    #[tokio::test]
+    async fn test_search_catalog_items_body_filters() {
+        let actual = Builder::from(SearchCatalogItemsBody::default())
+            .text_filter("iced latte".to_string())
+            .add_category_id("some_category_id".to_string())
+            .add_enabled_location_id("some_location_id".to_string())
+            .low_stock_level()
+            .out_stock_level()
+            .sub_builder_from(CustomAttributeFilter::default())
+            .key("roast".to_string())
+            .selection_uids_filter(vec!["dark".to_string()])
+            .into_parent_builder()
+            .unwrap()
+            .sort_descending()
+            .limit(50)
+            .build()
+            .await
+            .unwrap();
+
+        let json = serde_json::to_value(actual).unwrap();
+        assert_eq!(json["text_filter"], "iced latte");
+        assert_eq!(json["stock_levels"], serde_json::json!(["LOW", "OUT"]));
+        assert_eq!(json["sort_order"], "DESC");
+        assert_eq!(
+            json["custom_attribute_filters"],
+            serde_json::json!([{ "key": "roast", "selection_uids_filter": ["dark"] }]),
+        );
+
+        // a filter needs exactly one attribute and exactly one kind of filter
+        assert!(Builder::from(CustomAttributeFilter::default())
+            .key("roast".to_string())
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CustomAttributeFilter::default())
+            .key("roast".to_string())
+            .custom_attribute_definition_id("some_definition_id".to_string())
+            .bool_filter(true)
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CustomAttributeFilter::default())
+            .custom_attribute_definition_id("some_definition_id".to_string())
+            .number_filter(Some("1.5".to_string()), None)
+            .build()
+            .await
+            .is_ok());
+        assert!(Builder::from(SearchCatalogItemsBody::default())
+            .limit(101)
+            .build()
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_search_items() {
         use dotenv::dotenv;
@@ -1004,7 +1993,8 @@ mod test_catalog {
             product_types: None,
             sort_order: None,
             stock_levels: None,
-            text_filter: None
+            text_filter: None,
+            raw_fields: Default::default(),
         };
 
         let res = sut.catalog()
@@ -1027,7 +2017,8 @@ mod test_catalog {
             object_ids: vec!["6362XBFOY6N6F2J42ZE3JC5R".to_string(), "H4JPRP3IFQZKCW4R3CTKYOTO".to_string()],
             catalog_version: None,
             include_deleted_objects: None,
-            include_related_objects: None
+            include_related_objects: None,
+            raw_fields: Default::default(),
         };
 
         let res = sut.catalog().batch_retrieve_object(input).await;
@@ -0,0 +1,304 @@
+/*!
+Typed custom attribute values for catalog objects of the
+[Square API](https://developer.squareup.com).
+
+A [CatalogCustomAttributeDefinition](CatalogCustomAttributeDefinition) describes a string,
+boolean, number or selection attribute that can be set on catalog objects. Square only checks a
+value against its definition once the object is upserted. Setting values through
+[custom_attribute_value](Builder::custom_attribute_value) checks them while the object is being
+built instead, so a value of the wrong type, a number with too many decimal places or an unknown
+selection makes the build fail.
+
+Selections are given by name and translated to the uids Square assigned to them, so the definition
+has to be the one retrieved from Square rather than the one that was built to create it.
+
+# Example: Set a number attribute on an item
+```
+use square_ox::{
+    api::catalog::{ObjectUpsertRequest, attributes::CatalogAttributeValue},
+    builder::Builder,
+    objects::CatalogCustomAttributeDefinition,
+    objects::enums::CatalogObjectType,
+};
+
+async {
+    let definition = Builder::from(CatalogCustomAttributeDefinition::default())
+        .key("weight".to_string())
+        .name("Weight in kg".to_string())
+        .number(2)
+        .allowed_object_type(CatalogObjectType::Item)
+        .build()
+        .await
+        .unwrap();
+
+    let upsert = Builder::from(ObjectUpsertRequest::default())
+        .id("#some_item".to_string())
+        .object_type(CatalogObjectType::Item)
+        .custom_attribute_value(&definition, CatalogAttributeValue::Number("1.25".to_string()))
+        .build()
+        .await;
+
+    assert!(upsert.is_ok());
+
+    let upsert = Builder::from(ObjectUpsertRequest::default())
+        .id("#some_item".to_string())
+        .object_type(CatalogObjectType::Item)
+        .custom_attribute_value(&definition, CatalogAttributeValue::Boolean(true))
+        .build()
+        .await;
+
+    assert!(upsert.is_err());
+};
+```
+ */
+
+use crate::api::catalog::ObjectUpsertRequest;
+use crate::builder::{Builder, ParentBuilder};
+use crate::objects::{CatalogCustomAttributeDefinition, CatalogCustomAttributeValue};
+use crate::objects::enums::{CatalogCustomAttributeDefinitionType, CatalogObjectType};
+
+// the number of decimal places and selections Square allows when a definition does not say
+const DEFAULT_PRECISION: i32 = 5;
+const DEFAULT_MAX_ALLOWED_SELECTIONS: i32 = 1;
+
+/// A value of a catalog custom attribute, which has to match the type of its definition.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CatalogAttributeValue {
+    String(String),
+    Boolean(bool),
+    /// A decimal number such as `-12.5`, with at most as many decimal places as the precision of
+    /// the definition.
+    Number(String),
+    /// The names of the selected values.
+    Selection(Vec<String>),
+}
+
+impl<T: ParentBuilder> Builder<ObjectUpsertRequest, T> {
+    /// Sets the value of a custom attribute on the object. The build fails if the value does not
+    /// match the definition, if the definition has no key, or if the object type is already set
+    /// and the definition does not allow it.
+    /// # Arguments:
+    /// * `definition` - The definition of the attribute, as retrieved from Square.
+    /// * `value` - The value of the attribute.
+    pub fn custom_attribute_value(mut self,
+                                  definition: &CatalogCustomAttributeDefinition,
+                                  value: CatalogAttributeValue) -> Self {
+        match attribute_value(definition, value, self.body.object.type_name.as_ref()) {
+            Some((key, value)) => {
+                self.body.object.custom_attributes_values
+                    .get_or_insert_with(Default::default)
+                    .insert(key, value);
+            },
+            None => self.body.invalid_custom_attributes = true,
+        }
+
+        self
+    }
+}
+
+// the key and value of the attribute if the value matches the definition
+fn attribute_value(definition: &CatalogCustomAttributeDefinition,
+                   value: CatalogAttributeValue,
+                   object_type: Option<&CatalogObjectType>)
+                   -> Option<(String, CatalogCustomAttributeValue)> {
+    let key = definition.key.clone().filter(|key| !key.is_empty())?;
+
+    let allowed_object_type = match (&definition.allowed_object_types, object_type) {
+        (Some(allowed), Some(object_type)) => allowed.contains(object_type),
+        _ => true,
+    };
+    if !allowed_object_type {
+        return None;
+    }
+
+    let mut attribute = CatalogCustomAttributeValue {
+        key: Some(key.clone()),
+        type_name: definition.type_name.clone(),
+        ..Default::default()
+    };
+
+    match (definition.type_name.as_ref()?, value) {
+        (CatalogCustomAttributeDefinitionType::String, CatalogAttributeValue::String(value)) => {
+            attribute.string_value = Some(value);
+        },
+        (CatalogCustomAttributeDefinitionType::Boolean, CatalogAttributeValue::Boolean(value)) => {
+            attribute.boolean_value = Some(value);
+        },
+        (CatalogCustomAttributeDefinitionType::Number, CatalogAttributeValue::Number(value)) => {
+            let precision = definition.number_config.as_ref()
+                .and_then(|config| config.precision)
+                .unwrap_or(DEFAULT_PRECISION);
+
+            if !valid_number(&value, precision) {
+                return None;
+            }
+            attribute.number_value = Some(value);
+        },
+        (CatalogCustomAttributeDefinitionType::Selection, CatalogAttributeValue::Selection(names)) => {
+            attribute.selection_uid_values = Some(selection_uids(definition, &names)?);
+        },
+        _ => return None,
+    }
+
+    Some((key, attribute))
+}
+
+// whether the value is a decimal number with at most the given number of decimal places
+fn valid_number(value: &str, precision: i32) -> bool {
+    let unsigned = value.strip_prefix('-').unwrap_or(value);
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+        Some(_) => return false,
+        None => (unsigned, ""),
+    };
+
+    !integer.is_empty() &&
+        integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) &&
+        fraction.len() as i32 <= precision
+}
+
+// the uids of the selections with the given names, as long as every name is allowed, none is
+// repeated and there are no more than the definition allows
+fn selection_uids(definition: &CatalogCustomAttributeDefinition, names: &[String])
+                  -> Option<Vec<String>> {
+    let config = definition.selection_config.as_ref()?;
+    let max_allowed_selections = config.max_allowed_selections
+        .unwrap_or(DEFAULT_MAX_ALLOWED_SELECTIONS);
+
+    if names.len() as i32 > max_allowed_selections {
+        return None;
+    }
+
+    let mut uids: Vec<String> = vec![];
+    for name in names {
+        let uid = config.allowed_selections.iter()
+            .flatten()
+            .find(|selection| selection.name.as_ref() == Some(name))?
+            .uid
+            .clone()?;
+
+        if uids.contains(&uid) {
+            return None;
+        }
+        uids.push(uid);
+    }
+
+    Some(uids)
+}
+
+#[cfg(test)]
+mod test_attributes {
+    use super::*;
+
+    fn definition(value: serde_json::Value) -> CatalogCustomAttributeDefinition {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_custom_attribute_definition_builder() {
+        let actual = Builder::from(CatalogCustomAttributeDefinition::default())
+            .key("color".to_string())
+            .name("Color".to_string())
+            .selection(vec!["Red".to_string(), "Blue".to_string()], 2)
+            .allowed_object_type(CatalogObjectType::Item)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&actual).unwrap(),
+            serde_json::json!({
+                "allowed_object_types": ["ITEM"],
+                "key": "color",
+                "name": "Color",
+                "type": "SELECTION",
+                "selection_config": {
+                    "allowed_selections": [{ "name": "Red" }, { "name": "Blue" }],
+                    "max_allowed_selections": 2,
+                },
+            }),
+        );
+
+        let invalid = vec![
+            Builder::from(CatalogCustomAttributeDefinition::default())
+                .name("Weight".to_string())
+                .number(6),
+            Builder::from(CatalogCustomAttributeDefinition::default())
+                .name("Color".to_string())
+                .selection(vec!["Red".to_string(), "Red".to_string()], 1),
+            Builder::from(CatalogCustomAttributeDefinition::default())
+                .name("Color".to_string())
+                .selection(vec!["Red".to_string()], 2),
+            Builder::from(CatalogCustomAttributeDefinition::default())
+                .name("Gift wrap".to_string())
+                .number(2)
+                .boolean(),
+            Builder::from(CatalogCustomAttributeDefinition::default())
+                .name("Untyped".to_string()),
+        ];
+
+        for builder in invalid {
+            assert!(builder.build().await.is_err());
+        }
+    }
+
+    #[test]
+    fn test_attribute_value() {
+        let color = definition(serde_json::json!({
+            "key": "color",
+            "type": "SELECTION",
+            "allowed_object_types": ["ITEM"],
+            "selection_config": {
+                "allowed_selections": [
+                    { "name": "Red", "uid": "R" },
+                    { "name": "Blue", "uid": "B" },
+                    { "name": "Green", "uid": "G" },
+                ],
+                "max_allowed_selections": 2,
+            },
+        }));
+
+        let (key, actual) = attribute_value(
+            &color,
+            CatalogAttributeValue::Selection(vec!["Blue".to_string(), "Red".to_string()]),
+            Some(&CatalogObjectType::Item),
+        ).unwrap();
+
+        assert_eq!(key, "color");
+        assert_eq!(actual.selection_uid_values, Some(vec!["B".to_string(), "R".to_string()]));
+        assert_eq!(actual.type_name, Some(CatalogCustomAttributeDefinitionType::Selection));
+
+        let too_many = CatalogAttributeValue::Selection(
+            vec!["Red".to_string(), "Blue".to_string(), "Green".to_string()]
+        );
+        assert!(attribute_value(&color, too_many, None).is_none());
+        let unknown = CatalogAttributeValue::Selection(vec!["Pink".to_string()]);
+        assert!(attribute_value(&color, unknown, None).is_none());
+        let red = CatalogAttributeValue::Selection(vec!["Red".to_string()]);
+        assert!(attribute_value(&color, red, Some(&CatalogObjectType::Category)).is_none());
+
+        let weight = definition(serde_json::json!({
+            "key": "weight",
+            "type": "NUMBER",
+            "number_config": { "precision": 2 },
+        }));
+
+        assert!(attribute_value(&weight, CatalogAttributeValue::Number("-1.25".to_string()), None)
+            .is_some());
+        assert!(attribute_value(&weight, CatalogAttributeValue::Number("1.255".to_string()), None)
+            .is_none());
+        assert!(attribute_value(&weight, CatalogAttributeValue::String("1.25".to_string()), None)
+            .is_none());
+    }
+
+    #[test]
+    fn test_valid_number() {
+        assert!(valid_number("12", 0));
+        assert!(valid_number("-0.12345", 5));
+        assert!(!valid_number("1.5", 0));
+        assert!(!valid_number("1.", 2));
+        assert!(!valid_number(".5", 2));
+        assert!(!valid_number("1e3", 2));
+        assert!(!valid_number("", 2));
+    }
+}
@@ -0,0 +1,501 @@
+/*!
+Product feed exporter built on top of the catalog functionality of the
+[Square API](https://developer.squareup.com).
+
+The [CatalogFeedExporter](CatalogFeedExporter) walks the items of the catalog, along with their
+variations, images, categories, prices and stock, and turns every variation into a
+[ProductFeedRecord](ProductFeedRecord). The records serialize to a generic JSON feed through
+serde, and with the `google-merchant` feature enabled into a
+[Google Merchant Center](https://support.google.com/merchants/answer/7052112) feed through
+[google_merchant_tsv](google_merchant_tsv).
+
+# Example: Export the catalog as a JSON feed
+```no_run
+use square_ox::{
+    api::catalog::feed::CatalogFeedExporter,
+    client::SquareClient,
+};
+
+async {
+    let client = SquareClient::new("some_token");
+
+    let records = CatalogFeedExporter::new()
+        .location_id("some_location_id".to_string())
+        .link_template("https://shop.example.com/products/{item_id}".to_string())
+        .export(&client)
+        .await
+        .unwrap();
+
+    let feed = serde_json::to_string(&records).unwrap();
+};
+```
+ */
+
+use crate::api::catalog::SearchCatalogObjectsBody;
+use crate::builder::Builder;
+use crate::client::SquareClient;
+use crate::errors::SquareError;
+use crate::objects::{CatalogItemVariation, CatalogObject, InventoryCount, Money, Response};
+use crate::objects::enums::{CatalogObjectType, InventoryState};
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of catalog object ids accepted by a single batch retrieval of inventory
+/// counts.
+const INVENTORY_COUNTS_BATCH_SIZE: usize = 1000;
+
+/// Whether a product can currently be bought.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedAvailability {
+    InStock,
+    OutOfStock,
+}
+
+/// A single product of the feed, corresponding to one variation of a catalog item.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProductFeedRecord {
+    /// The id of the item variation.
+    pub id: String,
+    /// The id of the item, shared by all of its variations.
+    pub item_group_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// The name of the category of the item.
+    pub category: Option<String>,
+    pub link: Option<String>,
+    /// The images of the variation, or of the item if the variation has none.
+    pub image_urls: Vec<String>,
+    pub price: Option<Money>,
+    pub sku: Option<String>,
+    /// The UPC of the variation.
+    pub gtin: Option<String>,
+    /// The quantity in stock at the location of the exporter, if the variation tracks inventory.
+    pub quantity: Option<f64>,
+    /// `None` if the variation tracks inventory but no location was set to read the stock of.
+    pub availability: Option<FeedAvailability>,
+}
+
+/// Exports the items of the catalog as [ProductFeedRecord](ProductFeedRecord)s.
+#[derive(Clone, Debug, Default)]
+pub struct CatalogFeedExporter {
+    location_id: Option<String>,
+    link_template: Option<String>,
+}
+
+impl CatalogFeedExporter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Only export the items sold at the given location, and read their stock at it.
+    pub fn location_id(mut self, location_id: String) -> Self {
+        self.location_id = Some(location_id);
+
+        self
+    }
+
+    /// The link to the product page of each record, with `{item_id}` and `{variation_id}`
+    /// replaced by the respective ids.
+    pub fn link_template(mut self, link_template: String) -> Self {
+        self.link_template = Some(link_template);
+
+        self
+    }
+
+    /// Walk the catalog, and the inventory if a location is set, and build the feed records.
+    pub async fn export(&self, client: &SquareClient) -> Result<Vec<ProductFeedRecord>, SquareError> {
+        let objects = catalog_objects(client).await?;
+
+        let counts = match self.location_id.as_ref() {
+            Some(location_id) => {
+                let variation_ids = objects.iter()
+                    .filter(|object| self.is_exported(object))
+                    .flat_map(|object| variations(object))
+                    .filter(|(_, variation)| variation.track_inventory == Some(true))
+                    .map(|(id, _)| id)
+                    .collect();
+
+                inventory_counts(client, location_id, variation_ids).await?
+            },
+            None => vec![],
+        };
+
+        Ok(self.records(&objects, &counts))
+    }
+
+    /// Build the feed records from the given item, image and category objects and the inventory
+    /// counts of their variations.
+    pub fn records(&self, objects: &[CatalogObject], counts: &[InventoryCount])
+                   -> Vec<ProductFeedRecord> {
+        let image_url = |id: &String| objects.iter()
+            .find(|object| object.id.as_ref() == Some(id))
+            .and_then(|object| object.image_data.as_ref())
+            .and_then(|image| image.url.clone());
+
+        let category_name = |id: &String| objects.iter()
+            .find(|object| object.id.as_ref() == Some(id))
+            .and_then(|object| object.category_data.as_ref())
+            .and_then(|category| category.name.clone());
+
+        let mut records = vec![];
+
+        for object in objects.iter().filter(|object| self.is_exported(object)) {
+            let item = match object.item_data.as_ref() {
+                Some(item) => item,
+                None => continue,
+            };
+            let item_id = object.id.clone().unwrap_or_default();
+            let item_name = item.name.clone().unwrap_or_default();
+            let item_variations = variations(object);
+            let single_variation = item_variations.len() == 1;
+
+            for (variation_id, variation) in item_variations {
+                if variation.sellable == Some(false) {
+                    continue;
+                }
+
+                let title = match variation.name.as_ref() {
+                    Some(name) if !single_variation => format!("{} ({})", item_name, name),
+                    _ => item_name.clone(),
+                };
+
+                let image_ids = variation.image_ids.as_ref()
+                    .filter(|ids| !ids.is_empty())
+                    .or(item.image_ids.as_ref());
+
+                let (quantity, availability) = self.stock(&variation_id, variation, counts);
+
+                records.push(ProductFeedRecord {
+                    link: self.link_template.as_ref().map(|template| template
+                        .replace("{item_id}", &item_id)
+                        .replace("{variation_id}", &variation_id)),
+                    id: variation_id,
+                    item_group_id: item_id.clone(),
+                    title,
+                    description: item.description.clone(),
+                    category: item.category_id.as_ref().and_then(category_name),
+                    image_urls: image_ids
+                        .map(|ids| ids.iter().filter_map(image_url).collect())
+                        .unwrap_or_default(),
+                    price: variation.price_money.clone(),
+                    sku: variation.sku.clone(),
+                    gtin: variation.upc.clone(),
+                    quantity,
+                    availability,
+                })
+            }
+        }
+
+        records
+    }
+
+    // whether the object is a non-deleted item sold at the location of the exporter
+    fn is_exported(&self, object: &CatalogObject) -> bool {
+        if object.item_data.is_none() || object.is_deleted == Some(true) {
+            return false;
+        }
+
+        let location_id = match self.location_id.as_ref() {
+            Some(location_id) => location_id,
+            None => return true,
+        };
+
+        let absent = object.absent_at_location_ids.as_ref()
+            .is_some_and(|ids| ids.contains(location_id));
+        let present = object.present_at_all_locations.unwrap_or(true)
+            || object.present_at_location_ids.as_ref()
+            .is_some_and(|ids| ids.contains(location_id));
+
+        present && !absent
+    }
+
+    // the quantity in stock and the availability of the variation
+    fn stock(&self, variation_id: &str, variation: &CatalogItemVariation, counts: &[InventoryCount])
+             -> (Option<f64>, Option<FeedAvailability>) {
+        if variation.track_inventory != Some(true) {
+            return (None, Some(FeedAvailability::InStock));
+        }
+        if self.location_id.is_none() {
+            return (None, None);
+        }
+
+        let quantity = counts.iter()
+            .filter(|count| count.catalog_object_id.as_deref() == Some(variation_id))
+            .filter(|count| matches!(count.state, Some(InventoryState::InStock)))
+            .filter_map(|count| count.quantity.as_ref()?.parse::<f64>().ok())
+            .sum::<f64>();
+
+        let availability = if quantity > 0.0 {
+            FeedAvailability::InStock
+        } else {
+            FeedAvailability::OutOfStock
+        };
+
+        (Some(quantity), Some(availability))
+    }
+}
+
+// the ids and data of the variations of an item
+fn variations(object: &CatalogObject) -> Vec<(String, &CatalogItemVariation)> {
+    object.item_data.as_ref()
+        .and_then(|item| item.variations.as_ref())
+        .map(|variations| variations.iter()
+            .filter(|variation| variation.is_deleted != Some(true))
+            .filter_map(|variation| Some((
+                variation.id.clone()?,
+                variation.item_variation_data.as_ref()?,
+            )))
+            .collect())
+        .unwrap_or_default()
+}
+
+// all items, images and categories of the catalog, following the cursor through every page
+async fn catalog_objects(client: &SquareClient) -> Result<Vec<CatalogObject>, SquareError> {
+    let mut objects = vec![];
+    let mut cursor = None;
+
+    loop {
+        let mut search_body = Builder::from(SearchCatalogObjectsBody::default())
+            .set_object_types(vec![
+                CatalogObjectType::Item,
+                CatalogObjectType::Image,
+                CatalogObjectType::Category,
+            ]);
+        if let Some(cursor) = cursor {
+            search_body = search_body.cursor(cursor);
+        }
+        let search_body = search_body.build().await.map_err(|_| SquareError::from(None))?;
+
+        let res = client.catalog().search_objects(search_body).await?;
+        cursor = res.cursor.clone();
+
+        for response in res.into_responses() {
+            if let Response::Objects(page) = response {
+                objects.extend(page);
+            }
+        }
+
+        if cursor.is_none() {
+            return Ok(objects);
+        }
+    }
+}
+
+// the inventory counts of the given variations at the location, following the cursor through
+// every page
+async fn inventory_counts(client: &SquareClient, location_id: &str, variation_ids: Vec<String>)
+                          -> Result<Vec<InventoryCount>, SquareError> {
+    let mut counts = vec![];
+
+    for chunk in variation_ids.chunks(INVENTORY_COUNTS_BATCH_SIZE) {
+        counts.extend(client.inventory()
+            .batch_retrieve_counts(chunk.to_vec(), vec![location_id.to_string()], None)
+            .await?);
+    }
+
+    Ok(counts)
+}
+
+/// Serializes the records into a tab separated
+/// [Google Merchant Center](https://support.google.com/merchants/answer/7052112) product feed.
+///
+/// Records whose availability is unknown are listed as in stock.
+#[cfg(feature = "google-merchant")]
+pub fn google_merchant_tsv(records: &[ProductFeedRecord]) -> String {
+    const COLUMNS: [&str; 12] = [
+        "id", "item_group_id", "title", "description", "link", "image_link",
+        "additional_image_link", "availability", "price", "gtin", "mpn", "product_type",
+    ];
+
+    // tabs and line breaks would break up the row
+    let field = |value: Option<&String>| value
+        .map(|value| value.replace(['\t', '\n', '\r'], " "))
+        .unwrap_or_default();
+
+    let mut feed = COLUMNS.join("\t");
+
+    for record in records {
+        let availability = match record.availability {
+            Some(FeedAvailability::OutOfStock) => "out_of_stock",
+            _ => "in_stock",
+        };
+        let price = record.price.as_ref()
+            .and_then(|price| Some(google_merchant_price(price.amount?, &price.currency)));
+
+        let row = [
+            field(Some(&record.id)),
+            field(Some(&record.item_group_id)),
+            field(Some(&record.title)),
+            field(record.description.as_ref()),
+            field(record.link.as_ref()),
+            field(record.image_urls.first()),
+            field(Some(&record.image_urls.iter().skip(1).cloned().collect::<Vec<_>>().join(","))),
+            availability.to_string(),
+            field(price.as_ref()),
+            field(record.gtin.as_ref()),
+            field(record.sku.as_ref()),
+            field(record.category.as_ref()),
+        ];
+
+        feed.push('\n');
+        feed.push_str(&row.join("\t"));
+    }
+
+    feed
+}
+
+// formats an amount in the lowest denomination of the currency as e.g. `15.00 USD`
+#[cfg(feature = "google-merchant")]
+fn google_merchant_price(amount: i64, currency: &crate::objects::enums::Currency) -> String {
+    let code = serde_json::to_value(currency).ok()
+        .and_then(|code| code.as_str().map(|code| code.to_string()))
+        .unwrap_or_default();
+    let decimal_places = currency.decimal_places() as usize;
+
+    if decimal_places == 0 {
+        return format!("{} {}", amount, code);
+    }
+
+    let unit = currency.unit();
+    format!("{}.{:0width$} {}", amount / unit, amount % unit, code, width = decimal_places)
+}
+
+#[cfg(test)]
+mod test_feed {
+    use super::*;
+
+    fn objects() -> Vec<CatalogObject> {
+        serde_json::from_value(serde_json::json!([
+            {
+                "id": "SHIRT",
+                "type": "ITEM",
+                "present_at_all_locations": true,
+                "item_data": {
+                    "name": "Shirt",
+                    "description": "A plain shirt.",
+                    "category_id": "CLOTHING",
+                    "image_ids": ["SHIRT_IMAGE"],
+                    "variations": [
+                        {
+                            "id": "SHIRT_S",
+                            "type": "ITEM_VARIATION",
+                            "item_variation_data": {
+                                "name": "Small",
+                                "price_money": { "amount": 1500, "currency": "USD" },
+                                "sku": "SH-S",
+                                "track_inventory": true
+                            }
+                        },
+                        {
+                            "id": "SHIRT_L",
+                            "type": "ITEM_VARIATION",
+                            "item_variation_data": {
+                                "name": "Large",
+                                "price_money": { "amount": 1700, "currency": "USD" },
+                                "track_inventory": true
+                            }
+                        }
+                    ]
+                }
+            },
+            {
+                "id": "MUG",
+                "type": "ITEM",
+                "present_at_all_locations": false,
+                "present_at_location_ids": ["OTHER_LOCATION"],
+                "item_data": {
+                    "name": "Mug",
+                    "variations": [
+                        {
+                            "id": "MUG_REGULAR",
+                            "type": "ITEM_VARIATION",
+                            "item_variation_data": { "name": "Regular" }
+                        }
+                    ]
+                }
+            },
+            {
+                "id": "SHIRT_IMAGE",
+                "type": "IMAGE",
+                "image_data": { "url": "https://example.com/shirt.png" }
+            },
+            {
+                "id": "CLOTHING",
+                "type": "CATEGORY",
+                "category_data": { "name": "Clothing" }
+            }
+        ])).unwrap()
+    }
+
+    fn counts() -> Vec<InventoryCount> {
+        serde_json::from_value(serde_json::json!([
+            {
+                "catalog_object_id": "SHIRT_S",
+                "location_id": "LOCATION",
+                "quantity": "3",
+                "state": "IN_STOCK"
+            },
+            {
+                "catalog_object_id": "SHIRT_L",
+                "location_id": "LOCATION",
+                "quantity": "2",
+                "state": "SOLD"
+            }
+        ])).unwrap()
+    }
+
+    #[test]
+    fn test_records() {
+        let actual = CatalogFeedExporter::new()
+            .location_id("LOCATION".to_string())
+            .link_template("https://shop.example.com/{item_id}?variation={variation_id}".to_string())
+            .records(&objects(), &counts());
+
+        assert_eq!(2, actual.len());
+
+        assert_eq!("SHIRT_S", actual[0].id);
+        assert_eq!("SHIRT", actual[0].item_group_id);
+        assert_eq!("Shirt (Small)", actual[0].title);
+        assert_eq!(Some("Clothing".to_string()), actual[0].category);
+        assert_eq!(vec!["https://example.com/shirt.png".to_string()], actual[0].image_urls);
+        assert_eq!(
+            Some("https://shop.example.com/SHIRT?variation=SHIRT_S".to_string()),
+            actual[0].link,
+        );
+        assert_eq!(Some(3.0), actual[0].quantity);
+        assert_eq!(Some(FeedAvailability::InStock), actual[0].availability);
+
+        assert_eq!(Some(0.0), actual[1].quantity);
+        assert_eq!(Some(FeedAvailability::OutOfStock), actual[1].availability);
+    }
+
+    #[test]
+    fn test_records_without_location() {
+        let actual = CatalogFeedExporter::new().records(&objects(), &[]);
+
+        assert_eq!(3, actual.len());
+        assert_eq!(None, actual[0].availability);
+        assert_eq!("Mug", actual[2].title);
+        assert_eq!(Some(FeedAvailability::InStock), actual[2].availability);
+    }
+
+    #[cfg(feature = "google-merchant")]
+    #[test]
+    fn test_google_merchant_tsv() {
+        let records = CatalogFeedExporter::new()
+            .location_id("LOCATION".to_string())
+            .records(&objects(), &counts());
+
+        let actual = google_merchant_tsv(&records);
+        let rows: Vec<&str> = actual.lines().collect();
+
+        assert_eq!(3, rows.len());
+        assert_eq!(
+            "SHIRT_S\tSHIRT\tShirt (Small)\tA plain shirt.\t\thttps://example.com/shirt.png\t\t\
+            in_stock\t15.00 USD\t\tSH-S\tClothing",
+            rows[1],
+        );
+        assert_eq!("1250 JPY", google_merchant_price(1250, &crate::objects::enums::Currency::JPY));
+    }
+}
@@ -0,0 +1,418 @@
+/*!
+Loading of the [CatalogImage](crate::objects::CatalogImage)s of the catalog of the
+[Square API](https://developer.squareup.com).
+
+The `url` of an image points to the image as uploaded. [sized_url](CatalogImage::sized_url) and
+[srcset](CatalogImage::srcset) add `width` and `height` size hints to it, so a CDN in front of the
+images that resizes on request serves a variant fitting the layout. A host that does not take size
+hints ignores them and serves the image as uploaded, so the URLs work either way, only without the
+savings.
+
+[image_available](Catalog::image_available) checks that an image can be loaded without fetching it,
+and [prefetch_images](Catalog::prefetch_images) fetches images ahead of time, e.g. to warm a cache
+before a menu is shown.
+
+[create_image](Catalog::create_image) uploads a new image, attached to a catalog object right away
+if one is given, and [update_image](Catalog::update_image) replaces the file of an existing one.
+
+# Example: Prefetch the images of a menu
+```no_run
+use square_ox::{client::SquareClient, objects::CatalogImage};
+
+async {
+    let client = SquareClient::new("some_token");
+    let image = CatalogImage {
+        caption: None,
+        name: Some("Burger".to_string()),
+        photo_studio_order_id: None,
+        url: Some("https://items-images.example.com/files/burger.jpeg".to_string()),
+    };
+
+    assert_eq!(
+        image.srcset(&[320, 640]).unwrap(),
+        "https://items-images.example.com/files/burger.jpeg?width=320 320w, \
+         https://items-images.example.com/files/burger.jpeg?width=640 640w",
+    );
+
+    if client.catalog().image_available(&image).await.unwrap() {
+        let images = client.catalog().prefetch_images(&[image], Some(640)).await;
+    }
+};
+```
+ */
+
+use crate::api::catalog::Catalog;
+use crate::api::{SquareAPI, Verb};
+use crate::builder::{valid_raw_fields, Builder, ParentBuilder, RawFields, Validate};
+use crate::errors::{SquareError, ValidationError};
+use crate::objects::{CatalogImage, CatalogObject, enums::CatalogObjectType};
+use crate::response::{BinaryResponse, SquareResponse};
+
+use reqwest::Url;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::io;
+use std::path::Path;
+use uuid::Uuid;
+
+// the name of the part of an upload holding the image file
+const IMAGE_FILE_PART: &str = "image_file";
+
+impl CatalogImage {
+    /// The URL of the image with size hints for a variant of the given width and, optionally,
+    /// height, replacing any size hints the URL already has. `None` if the image has no valid URL.
+    /// # Arguments:
+    /// * `width` - The width of the variant in pixels.
+    /// * `height` - The height of the variant in pixels, leave it out to keep the aspect ratio.
+    pub fn sized_url(&self, width: u32, height: Option<u32>) -> Option<String> {
+        let mut url = Url::parse(self.url.as_deref()?).ok()?;
+
+        let query = url.query_pairs()
+            .filter(|(key, _)| key != "width" && key != "height")
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect::<Vec<(String, String)>>();
+
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.clear().extend_pairs(query);
+            pairs.append_pair("width", &width.to_string());
+            if let Some(height) = height {
+                pairs.append_pair("height", &height.to_string());
+            }
+        }
+
+        Some(url.to_string())
+    }
+
+    /// The `srcset` of an HTML `img` element offering a variant of the image for each width.
+    /// `None` if the image has no valid URL or no widths are given.
+    /// # Arguments:
+    /// * `widths` - The widths of the variants in pixels.
+    pub fn srcset(&self, widths: &[u32]) -> Option<String> {
+        let variants = widths.iter()
+            .map(|width| Some(format!("{} {}w", self.sized_url(*width, None)?, width)))
+            .collect::<Option<Vec<String>>>()?;
+
+        if variants.is_empty() {
+            return None;
+        }
+
+        Some(variants.join(", "))
+    }
+}
+
+impl<'a> Catalog<'a> {
+    /// Whether the image can be loaded, checked without fetching it. An image without a URL is not
+    /// available. Fails only if the host of the image can not be reached.
+    /// # Arguments:
+    /// * `image` - The image to check.
+    pub async fn image_available(self, image: &CatalogImage) -> Result<bool, SquareError> {
+        match image.url.as_deref().filter(|url| !url.is_empty()) {
+            Some(url) => self.client.is_available(url).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Fetches the images one after the other, returning the result for each in the order they
+    /// were given. Fetching an image without a URL fails without sending a request.
+    /// # Arguments:
+    /// * `images` - The images to fetch.
+    /// * `width` - The width of the variants to fetch, see [sized_url](CatalogImage::sized_url),
+    ///   leave it out to fetch the images as uploaded.
+    pub async fn prefetch_images(self, images: &[CatalogImage], width: Option<u32>)
+                                 -> Vec<Result<BinaryResponse, SquareError>> {
+        let client = self.client;
+        let mut fetched = Vec::with_capacity(images.len());
+
+        for image in images {
+            let url = match width {
+                Some(width) => image.sized_url(width, None),
+                None => image.url.clone().filter(|url| !url.is_empty()),
+            };
+
+            fetched.push(match url {
+                Some(url) => client.download(&url).await,
+                None => Err(SquareError::invalid_request(
+                    "MISSING_REQUIRED_PARAMETER",
+                    "url",
+                    "the image has no valid URL".to_string(),
+                )),
+            });
+        }
+
+        fetched
+    }
+
+    /// Uploads an image file and creates a [CatalogImage](crate::objects::CatalogImage) for it,
+    /// attached to the catalog object given in the body if there is one.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/catalog-api/create-catalog-image)
+    /// # Arguments:
+    /// * `body` - The image object to create, built through a
+    ///   [Builder](crate::builder::Builder) of [CreateCatalogImage](CreateCatalogImage).
+    /// * `file` - The JPEG, PNG or GIF file of the image.
+    ///
+    /// # Example: Add a photo to an item
+    /// ```no_run
+    /// use square_ox::{
+    ///     api::catalog::images::{CreateCatalogImage, ImageFile},
+    ///     builder::Builder,
+    ///     client::SquareClient,
+    /// };
+    ///
+    /// async {
+    ///     let body = Builder::from(CreateCatalogImage::default())
+    ///         .name("Burger".to_string())
+    ///         .object_id("some_item_id".to_string())
+    ///         .primary()
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     let res = SquareClient::new("some_token")
+    ///         .catalog()
+    ///         .create_image(body, &ImageFile::from_path("burger.jpeg").unwrap())
+    ///         .await;
+    /// };
+    /// ```
+    pub async fn create_image(self, body: CreateCatalogImage, file: &ImageFile)
+                              -> Result<SquareResponse, SquareError> {
+        self.client.request_multipart(
+            Verb::POST,
+            SquareAPI::Catalog("/images".to_string()),
+            &body,
+            Some((IMAGE_FILE_PART, file)),
+        ).await
+    }
+
+    /// Replaces the file of an existing [CatalogImage](crate::objects::CatalogImage), keeping its
+    /// ID and the objects it is attached to.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/catalog-api/update-catalog-image)
+    /// # Arguments:
+    /// * `image_id` - The ID of the image object to update.
+    /// * `file` - The new JPEG, PNG or GIF file of the image.
+    pub async fn update_image(self, image_id: String, file: &ImageFile)
+                              -> Result<SquareResponse, SquareError> {
+        self.client.request_multipart(
+            Verb::PUT,
+            SquareAPI::Catalog(format!("/images/{}", image_id)),
+            &UpdateCatalogImage { idempotency_key: Uuid::new_v4().to_string() },
+            Some((IMAGE_FILE_PART, file)),
+        ).await
+    }
+}
+
+/// An image file to upload to the catalog. Square takes JPEG, PNG and GIF files of up to 15MB.
+#[derive(Clone, Debug)]
+pub struct ImageFile {
+    pub file_name: String,
+    /// The media type of the file, such as `image/jpeg`.
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+impl ImageFile {
+    pub fn new(file_name: String, content_type: String, bytes: Vec<u8>) -> Self {
+        ImageFile {
+            file_name,
+            content_type,
+            bytes,
+        }
+    }
+
+    /// Reads the image file at the path, taking its media type from its extension. Fails if the
+    /// file can not be read or its extension is not that of a JPEG, PNG or GIF file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let content_type = path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(content_type)
+            .ok_or_else(|| io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the image file is not a JPEG, PNG or GIF file",
+            ))?;
+        let file_name = path.file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or_default()
+            .to_string();
+
+        Ok(ImageFile::new(file_name, content_type.to_string(), fs::read(path)?))
+    }
+}
+
+// the media type of an image file with the given extension, if Square takes it
+fn content_type(extension: &str) -> Option<&'static str> {
+    match extension.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" | "pjpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "gif" => Some("image/gif"),
+        _ => None,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CreateCatalogImage builder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateCatalogImage {
+    idempotency_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_id: Option<String>,
+    image: CatalogObject,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_primary: Option<bool>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Default for CreateCatalogImage {
+    fn default() -> Self {
+        CreateCatalogImage {
+            idempotency_key: None,
+            object_id: None,
+            image: CatalogObject {
+                id: Some("#image".to_string()),
+                type_name: Some(CatalogObjectType::Image),
+                image_data: Some(CatalogImage {
+                    caption: None,
+                    name: None,
+                    photo_studio_order_id: None,
+                    url: None,
+                }),
+                ..Default::default()
+            },
+            is_primary: None,
+            raw_fields: Map::new(),
+        }
+    }
+}
+
+impl Validate for CreateCatalogImage {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if (self.is_primary.is_some() && self.object_id.is_none()) ||
+            !valid_raw_fields(&mut self) {
+            return Err(ValidationError);
+        }
+
+        self.idempotency_key = Some(Uuid::new_v4().to_string());
+
+        Ok(self)
+    }
+}
+
+impl RawFields for CreateCatalogImage {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<CreateCatalogImage, T> {
+    /// The name of the image, shown as its alt text.
+    pub fn name(mut self, name: String) -> Self {
+        if let Some(image) = self.body.image.image_data.as_mut() {
+            image.name = Some(name);
+        }
+
+        self
+    }
+
+    /// The caption of the image.
+    pub fn caption(mut self, caption: String) -> Self {
+        if let Some(image) = self.body.image.image_data.as_mut() {
+            image.caption = Some(caption);
+        }
+
+        self
+    }
+
+    /// The ID of the catalog object to attach the image to, such as an item or a category.
+    pub fn object_id(mut self, object_id: String) -> Self {
+        self.body.object_id = Some(object_id);
+
+        self
+    }
+
+    /// Make the image the first image of the object it is attached to. Building fails if no
+    /// object is given.
+    pub fn primary(mut self) -> Self {
+        self.body.is_primary = Some(true);
+
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct UpdateCatalogImage {
+    idempotency_key: String,
+}
+
+#[cfg(test)]
+mod test_images {
+    use super::*;
+
+    fn image(url: Option<&str>) -> CatalogImage {
+        CatalogImage {
+            caption: None,
+            name: None,
+            photo_studio_order_id: None,
+            url: url.map(|url| url.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_sized_url() {
+        let sut = image(Some("https://images.example.com/burger.jpeg?v=2&width=1200"));
+
+        assert_eq!(
+            sut.sized_url(320, None),
+            Some("https://images.example.com/burger.jpeg?v=2&width=320".to_string()),
+        );
+        assert_eq!(
+            sut.sized_url(320, Some(240)),
+            Some("https://images.example.com/burger.jpeg?v=2&width=320&height=240".to_string()),
+        );
+        assert_eq!(
+            image(Some("https://images.example.com/a.png")).srcset(&[320, 640]),
+            Some("https://images.example.com/a.png?width=320 320w, \
+                  https://images.example.com/a.png?width=640 640w".to_string()),
+        );
+        assert_eq!(sut.srcset(&[]), None);
+        assert_eq!(image(None).sized_url(320, None), None);
+        assert_eq!(image(Some("not a url")).sized_url(320, None), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_catalog_image_builder() {
+        let actual = Builder::from(CreateCatalogImage::default())
+            .name("Burger".to_string())
+            .caption("Our classic burger".to_string())
+            .object_id("some_item_id".to_string())
+            .primary()
+            .build()
+            .await
+            .unwrap();
+
+        let mut json = serde_json::to_value(&actual).unwrap();
+        assert!(json["idempotency_key"].is_string());
+        json.as_object_mut().unwrap().remove("idempotency_key");
+
+        assert_eq!(json, serde_json::json!({
+            "object_id": "some_item_id",
+            "image": {
+                "id": "#image",
+                "type": "IMAGE",
+                "image_data": { "name": "Burger", "caption": "Our classic burger" },
+            },
+            "is_primary": true,
+        }));
+        assert!(Builder::from(CreateCatalogImage::default()).primary().build().await.is_err());
+    }
+
+    #[test]
+    fn test_content_type() {
+        assert_eq!(content_type("JPG"), Some("image/jpeg"));
+        assert_eq!(content_type("png"), Some("image/png"));
+        assert_eq!(content_type("webp"), None);
+        assert!(ImageFile::from_path("burger.webp").is_err());
+    }
+}
@@ -0,0 +1,267 @@
+/*!
+Bulk price changes built on top of the catalog functionality of the
+[Square API](https://developer.squareup.com).
+
+[bulk_reprice](Catalog::bulk_reprice) pages through the item variations matching a query, applies
+a [RepriceRule](RepriceRule) to the fixed price of each one and upserts the changed variations in
+batches. Prices are kept in the lowest denomination of their currency, so a percentage change is
+rounded to the nearest cent, or yen, with halves rounded away from zero. An absolute change only
+applies to variations priced in its currency. [preview_reprice](Catalog::preview_reprice) does the
+same without upserting anything, listing the changes for review.
+
+The variations are upserted as retrieved apart from their price, along with their version so a
+variation changed in the meantime fails its batch instead of being overwritten.
+
+# Example: Preview a seasonal price increase of 5%
+```no_run
+use square_ox::{api::catalog::pricing::RepriceRule, client::SquareClient};
+
+async {
+    let changes = SquareClient::new("some_token")
+        .catalog()
+        .preview_reprice(None, RepriceRule::PercentIncrease(5.0))
+        .await
+        .unwrap();
+
+    for change in changes {
+        println!("{}: {:?} -> {:?}", change.variation_id, change.old_price.amount,
+                 change.new_price.amount);
+    }
+};
+```
+ */
+
+use crate::api::catalog::{BatchUpsertObjects, Catalog, SearchCatalogObjectsBody};
+use crate::builder::Builder;
+use crate::errors::SquareError;
+use crate::objects::{CatalogObject, CatalogQuery, Money, Response};
+use crate::objects::enums::{CatalogObjectType, CatalogPricingType};
+use crate::utils::tips::round_half_up;
+
+/// How to change the price of an item variation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepriceRule {
+    /// Raise the price by the given percentage, e.g. `5.0` for 5%.
+    PercentIncrease(f64),
+    /// Lower the price by the given percentage, down to no less than 0.
+    PercentDecrease(f64),
+    /// Raise the price by the given amount.
+    AbsoluteIncrease(Money),
+    /// Lower the price by the given amount, down to no less than 0.
+    AbsoluteDecrease(Money),
+}
+
+impl RepriceRule {
+    /// The price after applying the rule, or `None` if the rule does not apply to it because it
+    /// is in another currency or has no amount.
+    pub fn apply(&self, price: &Money) -> Option<Money> {
+        let amount = price.amount?;
+
+        let new_amount = match self {
+            RepriceRule::PercentIncrease(percent) => scale(amount, *percent),
+            RepriceRule::PercentDecrease(percent) => scale(amount, -percent),
+            RepriceRule::AbsoluteIncrease(change) if change.currency == price.currency => {
+                amount + change.amount?
+            },
+            RepriceRule::AbsoluteDecrease(change) if change.currency == price.currency => {
+                amount - change.amount?
+            },
+            _ => return None,
+        };
+
+        Some(Money { amount: Some(new_amount.max(0)), currency: price.currency.clone() })
+    }
+}
+
+/// The change of the price of a single item variation.
+#[derive(Clone, Debug)]
+pub struct PriceChange {
+    pub variation_id: String,
+    pub item_id: Option<String>,
+    pub name: Option<String>,
+    pub old_price: Money,
+    pub new_price: Money,
+    // the variation as retrieved, with the new price applied
+    object: CatalogObject,
+}
+
+impl<'a> Catalog<'a> {
+    /// Lists how the rule would change the prices of the item variations matching the query,
+    /// without changing anything. Variations without a fixed price, and those whose price would
+    /// stay the same, are left out.
+    /// # Arguments:
+    /// * `filter` - The query the variations have to match, all variations if `None`.
+    /// * `rule` - How to change the prices.
+    pub async fn preview_reprice(self, filter: Option<CatalogQuery>, rule: RepriceRule)
+                                 -> Result<Vec<PriceChange>, SquareError> {
+        let mut changes = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut search_body = Builder::from(SearchCatalogObjectsBody::default())
+                .add_object_type(CatalogObjectType::ItemVariation)
+                .exclude_deleted_objects();
+            if let Some(query) = filter.clone() {
+                search_body = search_body.query(query);
+            }
+            if let Some(cursor) = cursor {
+                search_body = search_body.cursor(cursor);
+            }
+            let search_body = search_body.build().await.map_err(|_| SquareError::from(None))?;
+
+            let res = self.client.catalog().search_objects(search_body).await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::Objects(page) = response {
+                    changes.extend(page.into_iter()
+                        .filter_map(|object| price_change(object, &rule)));
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(changes);
+            }
+        }
+    }
+
+    /// Changes the prices of the item variations matching the query according to the rule and
+    /// returns the changes made. Variations without a fixed price, and those whose price would
+    /// stay the same, are left alone.
+    /// # Arguments:
+    /// * `filter` - The query the variations have to match, all variations if `None`.
+    /// * `rule` - How to change the prices.
+    pub async fn bulk_reprice(self, filter: Option<CatalogQuery>, rule: RepriceRule)
+                              -> Result<Vec<PriceChange>, SquareError> {
+        let client = self.client;
+        let changes = client.catalog().preview_reprice(filter, rule).await?;
+        let sizes = client.catalog().batch_sizes().await;
+
+        for upsert in changes.chunks(sizes.per_upsert) {
+            let mut body = Builder::from(BatchUpsertObjects::default());
+            for batch in upsert.chunks(sizes.per_batch) {
+                body = body.add_batch(batch.iter().map(|change| change.object.clone()).collect());
+            }
+            let body = body.build().await.map_err(|_| SquareError::from(None))?;
+
+            client.catalog().batch_upsert_objects(body).await?;
+        }
+
+        Ok(changes)
+    }
+}
+
+// the change of the fixed price of an item variation, if the rule changes it
+fn price_change(mut object: CatalogObject, rule: &RepriceRule) -> Option<PriceChange> {
+    let variation = object.item_variation_data.as_mut()?;
+    if variation.pricing_type == Some(CatalogPricingType::VariablePricing) {
+        return None;
+    }
+
+    let old_price = variation.price_money.clone()?;
+    let new_price = rule.apply(&old_price).filter(|price| price.amount != old_price.amount)?;
+    variation.price_money = Some(new_price.clone());
+
+    Some(PriceChange {
+        variation_id: object.id.clone()?,
+        item_id: variation.item_id.clone(),
+        name: variation.name.clone(),
+        old_price,
+        new_price,
+        object,
+    })
+}
+
+// the parts a percentage is counted in, so the percentage is exact to four decimal places
+const PERCENT_SCALE: i64 = 1_000_000;
+
+// changes the amount by the percentage, rounding halves away from zero. The product is taken in
+// whole numbers, as in floating point e.g. 250 * 0.93 lands just below 232.5 and rounds down.
+fn scale(amount: i64, percent: f64) -> i64 {
+    let change = (percent * (PERCENT_SCALE / 100) as f64).round() as i64;
+
+    round_half_up(amount * (PERCENT_SCALE + change), PERCENT_SCALE)
+}
+
+#[cfg(test)]
+mod test_pricing {
+    use super::*;
+    use crate::objects::enums::Currency;
+
+    fn usd(amount: i64) -> Money {
+        Money { amount: Some(amount), currency: Currency::USD }
+    }
+
+    fn variation(id: &str, value: serde_json::Value) -> CatalogObject {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": "ITEM_VARIATION",
+            "version": 7,
+            "item_variation_data": value,
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_apply() {
+        let amount = |rule: RepriceRule, price: i64| rule.apply(&usd(price)).unwrap().amount;
+
+        assert_eq!(amount(RepriceRule::PercentIncrease(5.0), 1999), Some(2099));
+        assert_eq!(amount(RepriceRule::PercentIncrease(2.5), 10), Some(10));
+        assert_eq!(amount(RepriceRule::PercentDecrease(50.0), 25), Some(13));
+        assert_eq!(amount(RepriceRule::PercentDecrease(150.0), 25), Some(0));
+        assert_eq!(amount(RepriceRule::PercentIncrease(0.125), 2000), Some(2003));
+        assert_eq!(amount(RepriceRule::AbsoluteDecrease(usd(500)), 300), Some(0));
+        assert_eq!(
+            RepriceRule::PercentIncrease(10.0)
+                .apply(&Money { amount: Some(1250), currency: Currency::JPY })
+                .unwrap(),
+            Money { amount: Some(1375), currency: Currency::JPY },
+        );
+        assert!(RepriceRule::AbsoluteIncrease(usd(100))
+            .apply(&Money { amount: Some(300), currency: Currency::EUR })
+            .is_none());
+    }
+
+    #[test]
+    fn test_apply_exact_half() {
+        let amount = |rule: RepriceRule, price: i64| rule.apply(&usd(price)).unwrap().amount;
+
+        for (price, expected) in [(250, 233), (550, 512), (950, 884), (1050, 977), (1850, 1721)] {
+            assert_eq!(amount(RepriceRule::PercentDecrease(7.0), price), Some(expected));
+        }
+        assert_eq!(amount(RepriceRule::PercentIncrease(7.0), 50), Some(54));
+    }
+
+    #[test]
+    fn test_price_change() {
+        let rule = RepriceRule::AbsoluteIncrease(usd(50));
+
+        let actual = price_change(variation("V1", serde_json::json!({
+            "item_id": "I1",
+            "name": "Large",
+            "pricing_type": "FIXED_PRICING",
+            "price_money": { "amount": 450, "currency": "USD" },
+            "sku": "LARGE-1",
+        })), &rule).unwrap();
+
+        assert_eq!(actual.variation_id, "V1");
+        assert_eq!(actual.old_price.amount, Some(450));
+        assert_eq!(actual.new_price.amount, Some(500));
+        assert_eq!(actual.object.version, Some(7));
+        let data = actual.object.item_variation_data.unwrap();
+        assert_eq!(data.price_money.unwrap().amount, Some(500));
+        assert_eq!(data.sku, Some("LARGE-1".to_string()));
+
+        let variable = variation("V2", serde_json::json!({
+            "pricing_type": "VARIABLE_PRICING",
+            "price_money": { "amount": 450, "currency": "USD" },
+        }));
+        assert!(price_change(variable, &rule).is_none());
+        let unpriced = variation("V3", serde_json::json!({ "pricing_type": "FIXED_PRICING" }));
+        assert!(price_change(unpriced, &rule).is_none());
+        let unchanged = variation("V4", serde_json::json!({
+            "price_money": { "amount": 450, "currency": "USD" },
+        }));
+        assert!(price_change(unchanged, &RepriceRule::PercentIncrease(0.0)).is_none());
+    }
+}
@@ -0,0 +1,243 @@
+/*!
+Resolution of the related objects of catalog objects of the
+[Square API](https://developer.squareup.com).
+
+Retrieving catalog objects with `include_related_objects` returns the objects they reference, such
+as the category, taxes and images of an item or the item of a variation, as a second flat list.
+A [RelatedObjects](RelatedObjects) looks those references up and stitches them back onto their
+items, returning a [ResolvedItem](ResolvedItem) for every item retrieved and for the item of every
+variation retrieved. References to objects that were not returned, such as deleted ones, are left
+out.
+
+# Example: Show items with their category and taxes
+```no_run
+use square_ox::client::SquareClient;
+
+async {
+    let items = SquareClient::new("some_token")
+        .catalog()
+        .retrieve_items(vec!["some_item_id".to_string(), "some_variation_id".to_string()])
+        .await
+        .unwrap();
+
+    for item in items {
+        println!(
+            "{:?} in {:?} with {} variations and {} taxes",
+            item.name(),
+            item.category.as_ref()
+                .and_then(|category| category.category_data.as_ref())
+                .and_then(|data| data.name.as_deref()),
+            item.variations.len(),
+            item.taxes.len(),
+        );
+    }
+};
+```
+ */
+
+use crate::api::catalog::{BatchRetrieveResult, Catalog};
+use crate::errors::SquareError;
+use crate::objects::{CatalogObject, CatalogObjectVariation, enums::CatalogObjectType};
+
+use std::collections::{HashMap, HashSet};
+
+/// An item along with the objects it references.
+#[derive(Clone, Debug)]
+pub struct ResolvedItem {
+    pub item: CatalogObject,
+    /// The variations of the item, those it holds followed by those retrieved on their own.
+    pub variations: Vec<CatalogObjectVariation>,
+    pub category: Option<CatalogObject>,
+    pub taxes: Vec<CatalogObject>,
+    pub images: Vec<CatalogObject>,
+    pub modifier_lists: Vec<CatalogObject>,
+}
+
+impl ResolvedItem {
+    /// The name of the item.
+    pub fn name(&self) -> Option<&str> {
+        self.item.item_data.as_ref()?.name.as_deref()
+    }
+}
+
+/// The retrieved and related objects by ID, to resolve the references of items against.
+#[derive(Clone, Debug, Default)]
+pub struct RelatedObjects {
+    objects: HashMap<String, CatalogObject>,
+}
+
+impl RelatedObjects {
+    /// # Arguments:
+    /// * `objects` - The objects retrieved and the objects related to them.
+    pub fn new<I: IntoIterator<Item = CatalogObject>>(objects: I) -> Self {
+        RelatedObjects {
+            objects: objects.into_iter()
+                .filter_map(|object| Some((object.id.clone()?, object)))
+                .collect(),
+        }
+    }
+
+    /// The object of the given ID, if it was retrieved or is related to one that was.
+    pub fn get(&self, id: &str) -> Option<&CatalogObject> {
+        self.objects.get(id)
+    }
+
+    /// Resolves the items among the objects, and the items of the variations among them, in the
+    /// order they come in. Every item is resolved once, and objects that are neither items nor
+    /// variations are skipped.
+    pub fn resolve(&self, objects: &[CatalogObject]) -> Vec<ResolvedItem> {
+        let mut seen = HashSet::new();
+
+        objects.iter()
+            .filter_map(|object| match object.type_name {
+                Some(CatalogObjectType::Item) => object.id.clone(),
+                Some(CatalogObjectType::ItemVariation) => object.item_variation_data.as_ref()?
+                    .item_id.clone(),
+                _ => None,
+            })
+            .filter(|item_id| seen.insert(item_id.clone()))
+            .filter_map(|item_id| self.resolve_item(&item_id))
+            .collect()
+    }
+
+    /// Resolves the item of the given ID, `None` if it is not an item or was not retrieved.
+    pub fn resolve_item(&self, item_id: &str) -> Option<ResolvedItem> {
+        let item = self.get(item_id)?;
+        let data = item.item_data.as_ref()?;
+
+        let mut variations = data.variations.clone().unwrap_or_default();
+        let mut variation_ids: HashSet<String> = variations.iter()
+            .filter_map(|variation| variation.id.clone())
+            .collect();
+        let mut separate: Vec<&CatalogObject> = self.objects.values()
+            .filter(|object| object.item_variation_data.as_ref()
+                .and_then(|variation| variation.item_id.as_deref()) == Some(item_id))
+            .filter(|object| object.id.as_ref()
+                .is_some_and(|id| variation_ids.insert(id.clone())))
+            .collect();
+        // the objects are kept by ID, so order the separate variations the way Square does
+        separate.sort_by_key(|object| (
+            object.item_variation_data.as_ref().and_then(|variation| variation.ordinal),
+            object.id.clone(),
+        ));
+        variations.extend(separate.into_iter().filter_map(variation_from));
+
+        Some(ResolvedItem {
+            item: item.clone(),
+            variations,
+            category: data.category_id.as_deref().and_then(|id| self.get(id)).cloned(),
+            taxes: self.all(data.tax_ids.iter().flatten()),
+            images: self.all(data.image_ids.iter().flatten()),
+            modifier_lists: self.all(data.modifier_list_info.iter()
+                .flatten()
+                .filter_map(|info| info.modifier_list_id.as_ref())),
+        })
+    }
+
+    // the objects of the IDs that were returned, in the order of the IDs
+    fn all<'a, I: Iterator<Item = &'a String>>(&self, ids: I) -> Vec<CatalogObject> {
+        ids.filter_map(|id| self.get(id)).cloned().collect()
+    }
+}
+
+// a variation retrieved as an object of its own, in the shape items hold their variations in
+fn variation_from(object: &CatalogObject) -> Option<CatalogObjectVariation> {
+    serde_json::to_value(object)
+        .and_then(serde_json::from_value)
+        .ok()
+}
+
+impl BatchRetrieveResult {
+    /// Resolves the items among the retrieved objects, and the items of the variations among
+    /// them, against the retrieved and related objects.
+    pub fn resolve_items(&self) -> Vec<ResolvedItem> {
+        RelatedObjects::new(self.objects.iter().chain(self.related_objects.iter()).cloned())
+            .resolve(&self.objects)
+    }
+}
+
+impl<'a> Catalog<'a> {
+    /// Retrieves any number of items and variations along with the objects they reference, and
+    /// returns each item, or item of a variation, with its references resolved.
+    /// # Arguments:
+    /// * `object_ids` - The IDs of the items and variations to retrieve.
+    pub async fn retrieve_items(self, object_ids: Vec<String>)
+                                -> Result<Vec<ResolvedItem>, SquareError> {
+        Ok(self.client.catalog().batch_retrieve(object_ids, true).await?.resolve_items())
+    }
+}
+
+#[cfg(test)]
+mod test_related {
+    use super::*;
+
+    fn object(value: serde_json::Value) -> CatalogObject {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn ids(objects: &[CatalogObject]) -> Vec<&str> {
+        objects.iter().filter_map(|object| object.id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_resolve_items() {
+        let result = BatchRetrieveResult {
+            objects: vec![
+                object(serde_json::json!({
+                    "id": "coffee",
+                    "type": "ITEM",
+                    "item_data": {
+                        "name": "Coffee",
+                        "category_id": "drinks",
+                        "tax_ids": ["vat", "deleted_tax"],
+                        "image_ids": ["coffee_image"],
+                        "variations": [{
+                            "id": "small",
+                            "type": "ITEM_VARIATION",
+                            "item_variation_data": { "item_id": "coffee", "name": "Small" },
+                        }],
+                    },
+                })),
+                object(serde_json::json!({
+                    "id": "large_tea",
+                    "type": "ITEM_VARIATION",
+                    "item_variation_data": { "item_id": "tea", "name": "Large", "ordinal": 2 },
+                })),
+                object(serde_json::json!({
+                    "id": "small",
+                    "type": "ITEM_VARIATION",
+                    "item_variation_data": { "item_id": "coffee", "name": "Small" },
+                })),
+            ],
+            related_objects: vec![
+                object(serde_json::json!({
+                    "id": "drinks",
+                    "type": "CATEGORY",
+                    "category_data": { "name": "Drinks" },
+                })),
+                object(serde_json::json!({ "id": "vat", "type": "TAX", "tax_data": { "name": "VAT" } })),
+                object(serde_json::json!({ "id": "coffee_image", "type": "IMAGE", "image_data": {} })),
+                object(serde_json::json!({
+                    "id": "tea",
+                    "type": "ITEM",
+                    "item_data": { "name": "Tea", "tax_ids": ["vat"] },
+                })),
+            ],
+        };
+
+        let actual = result.resolve_items();
+
+        assert_eq!(actual.iter().map(|item| item.name()).collect::<Vec<_>>(), vec![Some("Coffee"), Some("Tea")]);
+
+        let coffee = &actual[0];
+        assert_eq!(coffee.variations.len(), 1);
+        assert_eq!(coffee.category.as_ref().and_then(|category| category.id.as_deref()), Some("drinks"));
+        assert_eq!(ids(&coffee.taxes), vec!["vat"]);
+        assert_eq!(ids(&coffee.images), vec!["coffee_image"]);
+
+        let tea = &actual[1];
+        assert_eq!(tea.variations.iter().map(|variation| variation.id.as_deref()).collect::<Vec<_>>(), vec![Some("large_tea")]);
+        assert!(tea.category.is_none());
+        assert_eq!(ids(&tea.taxes), vec!["vat"]);
+    }
+}
@@ -8,11 +8,15 @@ use crate::errors::{SquareError, ValidationError};
 use crate::response::SquareResponse;
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
-use crate::builder::{AddField, Builder, ParentBuilder, Validate};
-use crate::objects::{self, Address, ChargeRequestAdditionalRecipient, CheckoutOptions,
-                     CreateOrderRequest, Order, PaymentLink, PrePopulatedData,
-                     QuickPay};
+use crate::builder::{valid_raw_fields, AddField, Builder, ParentBuilder, RawFields, Validate};
+use crate::api::orders::{order_from, OrderCalculateBody, OrderUpdateBody};
+use crate::objects::{self, Address, ChargeRequestAdditionalRecipient, CheckoutLocationSettings,
+                     CheckoutOptions, CreateOrderRequest, Money, Order, OrderLineItem,
+                     OrderLineItemDiscount, OrderLineItemTax, PaymentLink,
+                     PrePopulatedData, QuickPay, ShippingFee};
+use crate::objects::enums::AcceptedPaymentMethod;
 
 impl SquareClient {
     pub fn checkout(&self) -> Checkout {
@@ -83,6 +87,54 @@ impl<'a> Checkout<'a> {
         ).await
     }
 
+    /// Creates a Square-hosted checkout page for a one-off sale of a single, freely named item and
+    /// returns the URL of the page, which can be shared with the buyer.
+    ///
+    /// # Arguments:
+    /// * `name` - The name of the item being sold, shown on the checkout page.
+    /// * `amount` - The price of the item.
+    /// * `location_id` - The id of the location the payment is taken at.
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::{
+    ///     client::SquareClient,
+    ///     objects::{enums::Currency, Money},
+    /// };
+    ///
+    /// async {
+    ///     let url = SquareClient::new("some_token")
+    ///         .checkout()
+    ///         .quick_pay(
+    ///             "Auto Detailing".to_string(),
+    ///             Money { amount: Some(12500), currency: Currency::USD },
+    ///             "some_location_id".to_string(),
+    ///         )
+    ///         .await;
+    /// };
+    /// ```
+    pub async fn quick_pay(self, name: String, amount: Money, location_id: String)
+                           -> Result<String, SquareError> {
+        let payment_link = Builder::from(CreatePaymentLinkWrapper::default())
+            .quick_pay(QuickPay {
+                location_id,
+                name,
+                price_money: amount,
+            })
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+
+        self.client.checkout().create(payment_link).await?
+            .into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                objects::Response::PaymentLink(link) => link.url,
+                _ => None,
+            })
+            .ok_or_else(|| SquareError::from(None))
+    }
+
     /// Deletes a payment link.
     ///
     /// # Arguments:
@@ -117,9 +169,42 @@ impl<'a> Checkout<'a> {
 
     /// Updates a payment link at the [Square API](https://developer.squareup.com).
     ///
+    /// Only the `description`, `checkout_options`, `pre_populated_data` and `payment_note` of a
+    /// payment link can be updated. Its order is updated through the
+    /// [Orders](crate::api::orders::Orders) API, see [refresh_link](Checkout::refresh_link).
+    ///
     /// # Arguments:
     /// * `link_id` - The id of the payment link to update.
-    /// * `payment_link` - The updated [PaymentLink](PaymentLink).
+    /// * `payment_link` - The updated [PaymentLink](PaymentLink), built through the
+    ///   [Builder](Builder) of an [UpdatePaymentLinkWrapper](UpdatePaymentLinkWrapper).
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::{
+    ///     api::checkout::UpdatePaymentLinkWrapper,
+    ///     builder::Builder,
+    ///     client::SquareClient,
+    ///     objects::PrePopulatedData,
+    /// };
+    ///
+    /// async {
+    ///     let payment_link = Builder::from(UpdatePaymentLinkWrapper::default())
+    ///         .version(1)
+    ///         .pre_populated_data(PrePopulatedData {
+    ///             buyer_address: None,
+    ///             buyer_email: Some("buyer@example.com".to_string()),
+    ///             buyer_phone_number: None,
+    ///         })
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     let res = SquareClient::new("some_token")
+    ///         .checkout()
+    ///         .update("some_link_id".to_string(), payment_link)
+    ///         .await;
+    /// };
+    /// ```
     pub async fn update(
         self, link_id: String, payment_link: UpdatePaymentLinkWrapper
     )
@@ -131,6 +216,193 @@ impl<'a> Checkout<'a> {
             None,
         ).await
     }
+
+    /// Retrieves the settings of the checkout pages of a location, such as its branding and the
+    /// tips offered.
+    ///
+    /// # Arguments:
+    /// * `location_id` - The id of the location whose settings to retrieve.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/checkout-api/retrieve-location-settings)
+    pub async fn retrieve_location_settings(self, location_id: String)
+                                            -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Checkout(format!("/location-settings/{}", location_id)),
+            None::<&()>,
+            None,
+        ).await
+    }
+
+    /// Updates the settings of the checkout pages of a location. Only the settings that are set
+    /// are changed.
+    ///
+    /// # Arguments:
+    /// * `location_id` - The id of the location whose settings to update.
+    /// * `location_settings` - The settings to change.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/checkout-api/update-location-settings)
+    pub async fn update_location_settings(
+        self, location_id: String, location_settings: CheckoutLocationSettings
+    )
+        -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::PUT,
+            SquareAPI::Checkout(format!("/location-settings/{}", location_id)),
+            Some(&LocationSettingsBody { location_settings }),
+            None,
+        ).await
+    }
+
+    /// Re-prices the order of a payment link with the current prices of its catalog items.
+    ///
+    /// Useful for long-lived payment links whose prices may have gone stale. The order of the link
+    /// is recalculated through the [Square API](https://developer.squareup.com) and, if any of its
+    /// line items changed price, updated with the new prices, which the link then presents.
+    ///
+    /// # Arguments:
+    /// * `link_id` - The id of the payment link to refresh.
+    pub async fn refresh_link(self, link_id: String) -> Result<PaymentLinkRefresh, SquareError> {
+        let client = self.client;
+
+        let payment_link = payment_link_from(client.checkout().retrieve(link_id.clone()).await?)?;
+        let order_id = payment_link.order_id.clone().ok_or_else(|| SquareError::from(None))?;
+
+        let order = order_from(client.orders().retrieve(order_id.clone()).await?)?;
+        let previous_total_money = order.total_money.clone();
+
+        // omitting the prices of catalog line items makes the Square API use the current ones,
+        // while the fields computed by Square, such as the totals, are left to it
+        let calculate_body = Builder::from(OrderCalculateBody::default())
+            .order(Order {
+                location_id: order.location_id.clone(),
+                line_items: order.line_items.clone().map(|items| items.into_iter()
+                    .map(|item| without_computed_money(item, true))
+                    .collect()),
+                taxes: order.taxes.clone().map(|taxes| taxes.into_iter()
+                    .map(|tax| OrderLineItemTax { applied_money: None, auto_applied: None, ..tax })
+                    .collect()),
+                discounts: order.discounts.clone().map(|discounts| discounts.into_iter()
+                    .map(|discount| OrderLineItemDiscount { applied_money: None, ..discount })
+                    .collect()),
+                ..Default::default()
+            })
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+        let calculated = order_from(client.orders().calculate(calculate_body).await?)?;
+
+        let changes = line_item_price_changes(&order, &calculated);
+        if changes.is_empty() {
+            return Ok(PaymentLinkRefresh { payment_link, order, changes, previous_total_money });
+        }
+
+        let changed_line_items = calculated.line_items.unwrap_or_default().into_iter()
+            .filter(|item| changes.iter().any(|change| change.uid == item.uid))
+            .map(|item| without_computed_money(item, false))
+            .collect();
+
+        let update_body = Builder::from(OrderUpdateBody::default())
+            .order(Order {
+                location_id: order.location_id,
+                line_items: Some(changed_line_items),
+                version: order.version,
+                ..Default::default()
+            })
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+        let order = order_from(client.orders().update(order_id, update_body).await?)?;
+        let payment_link = payment_link_from(client.checkout().retrieve(link_id).await?)?;
+
+        Ok(PaymentLinkRefresh { payment_link, order, changes, previous_total_money })
+    }
+}
+
+// the payment link a response holds
+fn payment_link_from(response: SquareResponse) -> Result<PaymentLink, SquareError> {
+    response.into_responses()
+        .into_iter()
+        .find_map(|response| match response {
+            objects::Response::PaymentLink(link) => Some(link),
+            _ => None,
+        })
+        .ok_or_else(|| SquareError::from(None))
+}
+
+/// The outcome of [refreshing](Checkout::refresh_link) a payment link.
+#[derive(Clone, Debug)]
+pub struct PaymentLinkRefresh {
+    /// The payment link as retrieved after its order was refreshed.
+    pub payment_link: PaymentLink,
+    /// The order of the payment link, holding the current prices.
+    pub order: Order,
+    /// The line items whose price changed. If empty, the order was left untouched.
+    pub changes: Vec<LineItemPriceChange>,
+    /// The `total_money` of the order before it was refreshed.
+    pub previous_total_money: Option<Money>,
+}
+
+/// A line item of an order whose base price differs from the current price of its catalog item.
+#[derive(Clone, Debug)]
+pub struct LineItemPriceChange {
+    pub uid: Option<String>,
+    pub catalog_object_id: Option<String>,
+    pub name: Option<String>,
+    pub previous_base_price_money: Option<Money>,
+    pub base_price_money: Option<Money>,
+}
+
+// clears the money amounts calculated by the Square API, and for catalog line items optionally
+// the price, so the line item can be sent back
+fn without_computed_money(line_item: OrderLineItem, clear_catalog_price: bool) -> OrderLineItem {
+    let is_catalog_item = line_item.catalog_object_id.is_some();
+
+    OrderLineItem {
+        base_price_money: if clear_catalog_price && is_catalog_item {
+            None
+        } else {
+            line_item.base_price_money
+        },
+        catalog_version: if clear_catalog_price { None } else { line_item.catalog_version },
+        gross_sales_money: None,
+        total_discount_money: None,
+        total_money: None,
+        total_tax_money: None,
+        variation_total_price_money: None,
+        ..line_item
+    }
+}
+
+// pairs the line items of both orders by uid and collects those whose base price differs
+fn line_item_price_changes(order: &Order, calculated: &Order) -> Vec<LineItemPriceChange> {
+    let calculated_items = calculated.line_items.as_deref().unwrap_or_default();
+
+    order.line_items.as_deref().unwrap_or_default().iter()
+        .filter(|item| item.catalog_object_id.is_some())
+        .filter_map(|item| {
+            let current = calculated_items.iter().find(|current| current.uid == item.uid)?;
+            let amount = |money: &Option<Money>| money.as_ref().and_then(|money| money.amount);
+
+            if amount(&item.base_price_money) == amount(&current.base_price_money) {
+                return None;
+            }
+
+            Some(LineItemPriceChange {
+                uid: item.uid.clone(),
+                catalog_object_id: item.catalog_object_id.clone(),
+                name: item.name.clone(),
+                previous_base_price_money: item.base_price_money.clone(),
+                base_price_money: current.base_price_money.clone(),
+            })
+        })
+        .collect()
+}
+
+// the body of a location settings update
+#[derive(Serialize)]
+struct LocationSettingsBody {
+    location_settings: CheckoutLocationSettings,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
@@ -139,11 +411,13 @@ pub struct CreateOrderRequestWrapper {
     order: CreateOrderRequest,
     ask_for_shipping_address: Option<bool>,
     merchant_support_email: Option<String>,
-    pre_populate_buyer_email: Option<bool>,
+    pre_populate_buyer_email: Option<String>,
     pre_populate_shipping_address: Option<Address>,
     redirect_url: Option<String>,
     additional_recipients: Option<Vec<ChargeRequestAdditionalRecipient>>,
     note: Option<String>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Default for CreateOrderRequestWrapper {
@@ -157,14 +431,21 @@ impl Default for CreateOrderRequestWrapper {
             pre_populate_shipping_address: None,
             redirect_url: None,
             additional_recipients: None,
-            note: None
+            note: None,
+            raw_fields: Map::new(),
         }
     }
 }
 
 impl Validate for CreateOrderRequestWrapper {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.order.order.location_id.is_some() {
+        if self.order.order.location_id.is_some() &&
+            valid_raw_fields(&mut self) &&
+            valid_buyer(
+                self.pre_populate_buyer_email.as_ref(),
+                None,
+                self.pre_populate_shipping_address.as_ref(),
+            ) {
             self.idempotency_key = Some(Uuid::new_v4().to_string());
 
             Ok(self)
@@ -174,6 +455,12 @@ impl Validate for CreateOrderRequestWrapper {
     }
 }
 
+impl RawFields for CreateOrderRequestWrapper {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<CreateOrderRequestWrapper, T> {
     pub fn order(mut self, order: Order) -> Self {
         self.body.order.order = order;
@@ -199,8 +486,16 @@ impl<T: ParentBuilder> Builder<CreateOrderRequestWrapper, T> {
         self
     }
 
-    pub fn pre_populate_buyer_email(mut self) -> Self {
-        self.body.pre_populate_buyer_email = Some(true);
+    /// The email of the buyer, filled in on the checkout page.
+    pub fn pre_populate_buyer_email(mut self, buyer_email: String) -> Self {
+        self.body.pre_populate_buyer_email = Some(buyer_email);
+
+        self
+    }
+
+    /// The shipping address of the buyer, filled in on the checkout page.
+    pub fn pre_populate_shipping_address(mut self, address: Address) -> Self {
+        self.body.pre_populate_shipping_address = Some(address);
 
         self
     }
@@ -272,11 +567,15 @@ pub struct CreatePaymentLinkWrapper {
     source: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     payment_note: Option<String>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for CreatePaymentLinkWrapper {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.order.is_some() || self.quick_pay.is_some() {
+        if (self.order.is_some() || self.quick_pay.is_some()) &&
+            valid_raw_fields(&mut self) &&
+            valid_checkout(self.checkout_options.as_ref(), self.pre_populated_data.as_ref()) {
             self.idempotency_key = Uuid::new_v4().to_string();
 
             Ok(self)
@@ -286,6 +585,12 @@ impl Validate for CreatePaymentLinkWrapper {
     }
 }
 
+impl RawFields for CreatePaymentLinkWrapper {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<CreatePaymentLinkWrapper, T> {
     pub fn checkout_options(mut self, checkout_options: CheckoutOptions) -> Self {
         self.body.checkout_options = Some(checkout_options);
@@ -293,6 +598,16 @@ impl<T: ParentBuilder> Builder<CreatePaymentLinkWrapper, T> {
         self
     }
 
+    /// Offers a digital wallet or buy now pay later service at checkout, besides cards.
+    pub fn accept_payment_method(mut self, method: AcceptedPaymentMethod) -> Self {
+        accept_payment_method(
+            self.body.checkout_options.get_or_insert_with(Default::default),
+            method,
+        );
+
+        self
+    }
+
     pub fn description(mut self, description: String) -> Self {
         self.body.description = Some(description);
 
@@ -328,6 +643,54 @@ impl<T: ParentBuilder> Builder<CreatePaymentLinkWrapper, T> {
 
         self
     }
+
+    /// Asks the buyer for a shipping address on the checkout page.
+    pub fn ask_for_shipping_address(mut self) -> Self {
+        let checkout_options = self.body.checkout_options.get_or_insert_with(Default::default);
+        checkout_options.ask_for_shipping_address = Some(true);
+
+        self
+    }
+
+    /// Charges a shipping fee on top of the order, for a payment link that asks for a shipping
+    /// address.
+    /// # Arguments:
+    /// * `name` - The name of the fee, such as "Standard shipping", shown on the checkout page.
+    /// * `charge` - The amount charged for shipping.
+    pub fn shipping_fee(mut self, name: String, charge: Money) -> Self {
+        let checkout_options = self.body.checkout_options.get_or_insert_with(Default::default);
+        checkout_options.shipping_fee = Some(ShippingFee {
+            name: Some(name),
+            charge,
+        });
+
+        self
+    }
+
+    /// The email of the buyer, filled in on the checkout page.
+    pub fn pre_populate_buyer_email(mut self, buyer_email: String) -> Self {
+        let pre_populated_data = self.body.pre_populated_data.get_or_insert_with(Default::default);
+        pre_populated_data.buyer_email = Some(buyer_email);
+
+        self
+    }
+
+    /// The phone number of the buyer in E.164 format, such as "+15551234567", filled in on the
+    /// checkout page.
+    pub fn pre_populate_buyer_phone_number(mut self, buyer_phone_number: String) -> Self {
+        let pre_populated_data = self.body.pre_populated_data.get_or_insert_with(Default::default);
+        pre_populated_data.buyer_phone_number = Some(buyer_phone_number);
+
+        self
+    }
+
+    /// The address of the buyer, filled in on the checkout page.
+    pub fn pre_populate_buyer_address(mut self, buyer_address: Address) -> Self {
+        let pre_populated_data = self.body.pre_populated_data.get_or_insert_with(Default::default);
+        pre_populated_data.buyer_address = Some(buyer_address);
+
+        self
+    }
 }
 
 impl AddField<Order> for CreatePaymentLinkWrapper {
@@ -339,6 +702,8 @@ impl AddField<Order> for CreatePaymentLinkWrapper {
 #[derive(Clone, Serialize, Debug)]
 pub struct UpdatePaymentLinkWrapper {
     payment_link: PaymentLink,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Default for UpdatePaymentLinkWrapper {
@@ -355,14 +720,20 @@ impl Default for UpdatePaymentLinkWrapper {
                 pre_populated_data: None,
                 updated_at: None,
                 url: None
-            }
+            },
+            raw_fields: Map::new(),
         }
     }
 }
 
 impl Validate for UpdatePaymentLinkWrapper {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.payment_link.version > 1 {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.payment_link.version >= 1 &&
+            valid_raw_fields(&mut self) &&
+            valid_checkout(
+                self.payment_link.checkout_options.as_ref(),
+                self.payment_link.pre_populated_data.as_ref(),
+            ) {
             Ok(self)
         } else {
             Err(ValidationError)
@@ -371,6 +742,12 @@ impl Validate for UpdatePaymentLinkWrapper {
     }
 }
 
+impl RawFields for UpdatePaymentLinkWrapper {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<UpdatePaymentLinkWrapper, T> {
     pub fn set_updated_payment_link(mut self, payment_link: PaymentLink) -> Self {
         self.body.payment_link = payment_link;
@@ -378,11 +755,149 @@ impl<T: ParentBuilder> Builder<UpdatePaymentLinkWrapper, T> {
         self
     }
 
+    /// The current version of the payment link, as returned when it was created or last updated.
     pub fn version(mut self, version: i32) -> Self {
         self.body.payment_link.version = version;
 
         self
     }
+
+    pub fn checkout_options(mut self, checkout_options: CheckoutOptions) -> Self {
+        self.body.payment_link.checkout_options = Some(checkout_options);
+
+        self
+    }
+
+    /// Offers a digital wallet or buy now pay later service at checkout, besides cards.
+    pub fn accept_payment_method(mut self, method: AcceptedPaymentMethod) -> Self {
+        accept_payment_method(
+            self.body.payment_link.checkout_options.get_or_insert_with(Default::default),
+            method,
+        );
+
+        self
+    }
+
+    pub fn description(mut self, description: String) -> Self {
+        self.body.payment_link.description = Some(description);
+
+        self
+    }
+
+    pub fn payment_note(mut self, payment_note: String) -> Self {
+        self.body.payment_link.payment_note = Some(payment_note);
+
+        self
+    }
+
+    pub fn pre_populated_data(mut self, pre_populated_data: PrePopulatedData) -> Self {
+        self.body.payment_link.pre_populated_data = Some(pre_populated_data);
+
+        self
+    }
+
+    /// Asks the buyer for a shipping address on the checkout page.
+    pub fn ask_for_shipping_address(mut self) -> Self {
+        let checkout_options = self.body.payment_link.checkout_options
+            .get_or_insert_with(Default::default);
+        checkout_options.ask_for_shipping_address = Some(true);
+
+        self
+    }
+
+    /// Charges a shipping fee on top of the order, for a payment link that asks for a shipping
+    /// address.
+    /// # Arguments:
+    /// * `name` - The name of the fee, such as "Standard shipping", shown on the checkout page.
+    /// * `charge` - The amount charged for shipping.
+    pub fn shipping_fee(mut self, name: String, charge: Money) -> Self {
+        let checkout_options = self.body.payment_link.checkout_options
+            .get_or_insert_with(Default::default);
+        checkout_options.shipping_fee = Some(ShippingFee {
+            name: Some(name),
+            charge,
+        });
+
+        self
+    }
+
+    /// The email of the buyer, filled in on the checkout page.
+    pub fn pre_populate_buyer_email(mut self, buyer_email: String) -> Self {
+        let pre_populated_data = self.body.payment_link.pre_populated_data
+            .get_or_insert_with(Default::default);
+        pre_populated_data.buyer_email = Some(buyer_email);
+
+        self
+    }
+
+    /// The phone number of the buyer in E.164 format, such as "+15551234567", filled in on the
+    /// checkout page.
+    pub fn pre_populate_buyer_phone_number(mut self, buyer_phone_number: String) -> Self {
+        let pre_populated_data = self.body.payment_link.pre_populated_data
+            .get_or_insert_with(Default::default);
+        pre_populated_data.buyer_phone_number = Some(buyer_phone_number);
+
+        self
+    }
+
+    /// The address of the buyer, filled in on the checkout page.
+    pub fn pre_populate_buyer_address(mut self, buyer_address: Address) -> Self {
+        let pre_populated_data = self.body.payment_link.pre_populated_data
+            .get_or_insert_with(Default::default);
+        pre_populated_data.buyer_address = Some(buyer_address);
+
+        self
+    }
+}
+
+fn accept_payment_method(checkout_options: &mut CheckoutOptions, method: AcceptedPaymentMethod) {
+    let methods = checkout_options.accepted_payment_methods.get_or_insert_with(Default::default);
+
+    match method {
+        AcceptedPaymentMethod::ApplePay => methods.apple_pay = Some(true),
+        AcceptedPaymentMethod::GooglePay => methods.google_pay = Some(true),
+        AcceptedPaymentMethod::CashAppPay => methods.cash_app_pay = Some(true),
+        AcceptedPaymentMethod::AfterpayClearpay => methods.afterpay_clearpay = Some(true),
+    }
+}
+
+// a shipping fee is only charged when the buyer is asked for a shipping address
+fn valid_checkout(checkout_options: Option<&CheckoutOptions>,
+                  pre_populated_data: Option<&PrePopulatedData>) -> bool {
+    let valid_shipping_fee = checkout_options
+        .and_then(|options| options.shipping_fee.as_ref().map(|fee| (options, fee)))
+        .is_none_or(|(options, fee)| {
+            options.ask_for_shipping_address == Some(true) &&
+                fee.charge.amount.is_some_and(|amount| amount >= 0)
+        });
+
+    valid_shipping_fee && pre_populated_data.is_none_or(|data| valid_buyer(
+        data.buyer_email.as_ref(),
+        data.buyer_phone_number.as_ref(),
+        data.buyer_address.as_ref(),
+    ))
+}
+
+// an email needs a local part and a domain, a phone number has to be in E.164 format and an
+// address needs at least its first line
+fn valid_buyer(email: Option<&String>, phone_number: Option<&String>, address: Option<&Address>)
+    -> bool {
+    let valid_email = email.is_none_or(|email| {
+        email.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty() && domain.contains('.') && !domain.starts_with('.') &&
+                !domain.ends_with('.')
+        })
+    });
+    let valid_phone_number = phone_number.is_none_or(|phone_number| {
+        phone_number.strip_prefix('+').is_some_and(|digits| {
+            (2..=15).contains(&digits.len()) && digits.bytes().all(|digit| digit.is_ascii_digit())
+        })
+    });
+    let valid_address = address.is_none_or(|address| {
+        address.address_line_1.as_ref().is_some_and(|line| !line.trim().is_empty())
+    });
+
+    valid_email && valid_phone_number && valid_address
 }
 
 #[cfg(test)]
@@ -485,7 +1000,8 @@ mod test_checkout {
             pre_populate_shipping_address: None,
             redirect_url: None,
             additional_recipients: None,
-            note: None
+            note: None,
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(CreateOrderRequestWrapper::default())
@@ -655,7 +1171,8 @@ mod test_checkout {
             pre_populate_shipping_address: None,
             redirect_url: None,
             additional_recipients: None,
-            note: None
+            note: None,
+            raw_fields: Default::default(),
         };
 
         let res = sut.checkout()
@@ -713,7 +1230,8 @@ mod test_checkout {
             checkout_options: None,
             pre_populated_data: None,
             source: None,
-            payment_note: None
+            payment_note: None,
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(CreatePaymentLinkWrapper::default())
@@ -732,6 +1250,28 @@ mod test_checkout {
         assert_eq!(format!("{:?}", expected), format!("{:?}", actual))
     }
 
+    #[tokio::test]
+    async fn test_accept_payment_method() {
+        let actual = Builder::from(CreatePaymentLinkWrapper::default())
+            .quick_pay(QuickPay {
+                location_id: "L1JC53TYHS40Z".to_string(),
+                name: "Another Thing".to_string(),
+                price_money: Money { amount: Some(10), currency: Currency::USD }
+            })
+            .accept_payment_method(AcceptedPaymentMethod::CashAppPay)
+            .accept_payment_method(AcceptedPaymentMethod::AfterpayClearpay)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&actual).unwrap()["checkout_options"],
+            serde_json::json!({
+                "accepted_payment_methods": { "afterpay_clearpay": true, "cash_app_pay": true },
+            }),
+        );
+    }
+
     #[tokio::test]
     async fn test_create_payment_link() {
         use dotenv::dotenv;
@@ -753,7 +1293,8 @@ mod test_checkout {
             checkout_options: None,
             pre_populated_data: None,
             source: None,
-            payment_note: None
+            payment_note: None,
+            raw_fields: Default::default(),
         };
 
         let res = sut.checkout()
@@ -763,6 +1304,26 @@ mod test_checkout {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_quick_pay() {
+        use dotenv::dotenv;
+        use std::env;
+
+        dotenv().ok();
+        let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
+        let sut = SquareClient::new(&access_token);
+
+        let res = sut.checkout()
+            .quick_pay(
+                "Another Thing".to_string(),
+                Money { amount: Some(10), currency: Currency::USD },
+                "L1JC53TYHS40Z".to_string(),
+            )
+            .await;
+
+        assert!(res.is_ok());
+    }
+
     #[tokio::test]
     async fn test_delete_payment_link() {
         use dotenv::dotenv;
@@ -799,6 +1360,134 @@ mod test_checkout {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_update_payment_link_builder() {
+        let expected = UpdatePaymentLinkWrapper {
+            payment_link: objects::PaymentLink {
+                id: None,
+                version: 1,
+                checkout_options: None,
+                created_at: None,
+                description: Some("a description".to_string()),
+                order_id: None,
+                payment_note: Some("a payment note".to_string()),
+                pre_populated_data: Some(PrePopulatedData {
+                    buyer_address: None,
+                    buyer_email: Some("buyer@example.com".to_string()),
+                    buyer_phone_number: None,
+                }),
+                updated_at: None,
+                url: None
+            },
+            raw_fields: Default::default(),
+        };
+
+        let actual = Builder::from(UpdatePaymentLinkWrapper::default())
+            .version(1)
+            .description("a description".to_string())
+            .payment_note("a payment note".to_string())
+            .pre_populated_data(PrePopulatedData {
+                buyer_address: None,
+                buyer_email: Some("buyer@example.com".to_string()),
+                buyer_phone_number: None,
+            })
+            .build()
+            .await;
+
+        assert_eq!(format!("{:?}", expected), format!("{:?}", actual.unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_raw_field() {
+        let quick_pay = QuickPay {
+            location_id: "L1JC53TYHS40Z".to_string(),
+            name: "Ceramic Mug".to_string(),
+            price_money: Money { amount: Some(2400), currency: Currency::USD }
+        };
+
+        let actual = Builder::from(CreatePaymentLinkWrapper::default())
+            .quick_pay(quick_pay.clone())
+            .raw_field("brand_new_field", serde_json::json!({ "enabled": true }))
+            .build()
+            .await
+            .unwrap();
+        let actual = serde_json::to_value(actual).unwrap();
+
+        assert_eq!(actual["brand_new_field"], serde_json::json!({ "enabled": true }));
+        assert_eq!(actual["quick_pay"]["name"], "Ceramic Mug");
+
+        // a raw field shadowing a typed one that is set
+        assert!(Builder::from(CreatePaymentLinkWrapper::default())
+            .quick_pay(quick_pay.clone())
+            .raw_field("quick_pay", serde_json::json!({}))
+            .build()
+            .await
+            .is_err());
+        // but not one that is unset
+        assert!(Builder::from(CreatePaymentLinkWrapper::default())
+            .quick_pay(quick_pay)
+            .raw_field("description", serde_json::json!("A mug"))
+            .build()
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_payment_link_shipping_builder() {
+        let quick_pay = QuickPay {
+            location_id: "L1JC53TYHS40Z".to_string(),
+            name: "Ceramic Mug".to_string(),
+            price_money: Money { amount: Some(2400), currency: Currency::USD }
+        };
+
+        let actual = Builder::from(CreatePaymentLinkWrapper::default())
+            .quick_pay(quick_pay.clone())
+            .ask_for_shipping_address()
+            .shipping_fee(
+                "Standard shipping".to_string(),
+                Money { amount: Some(500), currency: Currency::USD },
+            )
+            .pre_populate_buyer_email("buyer@example.com".to_string())
+            .pre_populate_buyer_phone_number("+15551234567".to_string())
+            .pre_populate_buyer_address(Address {
+                address_line_1: Some("500 Electric Ave".to_string()),
+                country: Some("US".to_string()),
+                ..Default::default()
+            })
+            .build()
+            .await
+            .unwrap();
+
+        let checkout_options = actual.checkout_options.unwrap();
+        assert_eq!(checkout_options.ask_for_shipping_address, Some(true));
+        assert_eq!(checkout_options.shipping_fee.unwrap().charge.amount, Some(500));
+        let pre_populated_data = actual.pre_populated_data.unwrap();
+        assert_eq!(pre_populated_data.buyer_email, Some("buyer@example.com".to_string()));
+        assert_eq!(pre_populated_data.buyer_phone_number, Some("+15551234567".to_string()));
+
+        // a shipping fee without a shipping address
+        assert!(Builder::from(CreatePaymentLinkWrapper::default())
+            .quick_pay(quick_pay.clone())
+            .shipping_fee(
+                "Standard shipping".to_string(),
+                Money { amount: Some(500), currency: Currency::USD },
+            )
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(UpdatePaymentLinkWrapper::default())
+            .pre_populate_buyer_email("buyer.example.com".to_string())
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(CreatePaymentLinkWrapper::default())
+            .quick_pay(quick_pay)
+            .pre_populate_buyer_phone_number("555 123 4567".to_string())
+            .build()
+            .await
+            .is_err());
+    }
+
     // #[tokio::test]
     async fn test_update_payment_link() {
         use dotenv::dotenv;
@@ -822,7 +1511,8 @@ mod test_checkout {
                     pre_populated_data: None,
                     updated_at: None,
                     url: None
-                }
+                },
+                raw_fields: Default::default(),
             });
 
         let res = sut.checkout()
@@ -831,4 +1521,68 @@ mod test_checkout {
 
         assert!(res.is_ok());
     }
+
+    fn line_item(uid: &str, catalog_object_id: Option<&str>, amount: i64) -> OrderLineItem {
+        OrderLineItem {
+            quantity: "1".to_string(),
+            applied_discounts: None,
+            applied_taxes: None,
+            base_price_money: Some(Money { amount: Some(amount), currency: Currency::USD }),
+            catalog_object_id: catalog_object_id.map(|id| id.to_string()),
+            catalog_version: Some(1655427266071),
+            gross_sales_money: None,
+            item_type: Some(OrderLineItemItemType::Item),
+            metadata: None,
+            modifiers: None,
+            name: Some(uid.to_string()),
+            note: None,
+            pricing_blocklists: None,
+            quantity_unit: None,
+            total_discount_money: None,
+            total_money: Some(Money { amount: Some(amount), currency: Currency::USD }),
+            total_tax_money: None,
+            uid: Some(uid.to_string()),
+            variation_name: None,
+            variation_total_price_money: None,
+            api_reference_ids: None,
+        }
+    }
+
+    #[test]
+    fn test_line_item_price_changes() {
+        let order = Order {
+            line_items: Some(vec![
+                line_item("coffee", Some("COFFEE_ID"), 300),
+                line_item("tea", Some("TEA_ID"), 250),
+                line_item("custom", None, 100),
+            ]),
+            ..Default::default()
+        };
+        let calculated = Order {
+            line_items: Some(vec![
+                line_item("coffee", Some("COFFEE_ID"), 350),
+                line_item("tea", Some("TEA_ID"), 250),
+                line_item("custom", None, 120),
+            ]),
+            ..Default::default()
+        };
+
+        let actual = line_item_price_changes(&order, &calculated);
+
+        assert_eq!(1, actual.len());
+        assert_eq!(Some("coffee".to_string()), actual[0].uid);
+        assert_eq!(Some(300), actual[0].previous_base_price_money.as_ref().unwrap().amount);
+        assert_eq!(Some(350), actual[0].base_price_money.as_ref().unwrap().amount);
+    }
+
+    #[test]
+    fn test_without_computed_money() {
+        let catalog_item = without_computed_money(line_item("coffee", Some("COFFEE_ID"), 300), true);
+        let custom_item = without_computed_money(line_item("custom", None, 100), true);
+
+        assert!(catalog_item.base_price_money.is_none());
+        assert!(catalog_item.catalog_version.is_none());
+        assert!(catalog_item.total_money.is_none());
+        assert_eq!(Some(100), custom_item.base_price_money.unwrap().amount);
+    }
 }
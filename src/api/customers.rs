@@ -2,16 +2,25 @@
 Customers functionality of the [Square API](https://developer.squareup.com).
  */
 
+pub mod addresses;
+pub mod duplicates;
+pub mod history;
+pub mod merge;
+
 use crate::client::SquareClient;
 use crate::api::{Verb, SquareAPI};
 use crate::errors::{SquareError, ListParametersBuilderError, ValidationError};
 use crate::response::SquareResponse;
-use crate::objects::{Address, Customer, enums::CustomerCreationSource, SearchQueryAttribute,
-                     TimeRange, CustomerFilter, CustomerTextFilter, CreationSource};
+use crate::objects::{Address, Customer, enums::CustomerCreationSource, CustomerQuery,
+                     TimeRange, CustomerFilter, CustomerTextFilter, CreationSource,
+                     CustomAttribute, CustomAttributeDefinition,
+                     enums::{CustomAttributeVisibility, CustomerSortField}, Response};
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
-use crate::builder::{Builder, ParentBuilder, Validate};
+use crate::builder::{valid_raw_fields, AddField, BackIntoBuilder, Builder, Nil, ParentBuilder,
+                     RawFields, Validate};
 
 impl SquareClient {
     pub fn customers(&self) -> Customers {
@@ -73,6 +82,169 @@ impl<'a> Customers<'a> {
             customer_to_delete.version,
         ).await
     }
+
+    /// Returns details for a single customer.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/customers/retrieve-customer)
+    pub async fn retrieve(self, customer_id: String)
+                          -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Customers(format!("/{}", customer_id)),
+            None::<&Customer>,
+            None,
+        ).await
+    }
+
+    /// Updates a customer profile. Only the fields that are set on the given
+    /// [Customer](Customer) are changed, and setting its `version` enables optimistic concurrency.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/customers/update-customer)
+    pub async fn update(self, customer_id: String, customer: Customer)
+                        -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::PUT,
+            SquareAPI::Customers(format!("/{}", customer_id)),
+            Some(&customer),
+            None,
+        ).await
+    }
+
+    /// Adds a group membership to a customer.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/customers-api/add-group-to-customer)
+    pub async fn add_group(self, customer_id: String, group_id: String)
+                           -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::PUT,
+            SquareAPI::Customers(format!("/{}/groups/{}", customer_id, group_id)),
+            None::<&Customer>,
+            None,
+        ).await
+    }
+
+    /// Removes a group membership from a customer.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/customers-api/remove-group-from-customer)
+    pub async fn remove_group(self, customer_id: String, group_id: String)
+                              -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::DELETE,
+            SquareAPI::Customers(format!("/{}/groups/{}", customer_id, group_id)),
+            None::<&Customer>,
+            None,
+        ).await
+    }
+
+    /// Returns the customer profile with the given email address, and only creates one when there
+    /// is none yet, so the same person does not end up with a new profile on every visit. When
+    /// several profiles share the email address, the oldest one is returned.
+    /// # Arguments
+    /// * `email_address` - The email address to look the customer up by, which is also set on
+    ///   the customer created.
+    /// * `customer` - The [Builder](Builder) of the customer to create if none is found.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use square_ox::{builder::Builder, client::SquareClient, objects::Customer};
+    ///
+    /// async {
+    ///     let customer = SquareClient::new("some_token")
+    ///         .customers()
+    ///         .get_or_create(
+    ///             "amelia@example.com".to_string(),
+    ///             Builder::from(Customer::default()).given_name("Amelia".to_string()),
+    ///         )
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
+    pub async fn get_or_create(self, email_address: String, customer: Builder<Customer, Nil>)
+                               -> Result<Customer, SquareError> {
+        let customer = customer.email_address(email_address.clone())
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+        let query = Builder::from(CustomerSearchQuery::default())
+            .limit(1)
+            .sub_builder_from(CustomerQuery::default())
+            .exact_email_address(email_address)
+            .sort_field(CustomerSortField::CreatedAt)
+            .sort_ascending()
+            .into_parent_builder()
+            .map_err(|_| SquareError::from(None))?
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+
+        let existing = self.client.customers()
+            .search(query)
+            .await?
+            .into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::Customers(customers) => customers.into_iter().next(),
+                _ => None,
+            });
+
+        match existing {
+            Some(customer) => Ok(customer),
+            None => self.client.customers().create(customer).await.and_then(customer_from),
+        }
+    }
+
+    /// Creates a customer-related custom attribute definition for a Square seller account.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/customer-custom-attributes-api/create-customer-custom-attribute-definition)
+    pub async fn create_custom_attribute_definition(self, body: CustomAttributeDefinitionBody)
+                                                    -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Customers("/custom-attribute-definitions".to_string()),
+            Some(&body),
+            None,
+        ).await
+    }
+
+    /// Retrieves a custom attribute associated with a customer profile.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/customer-custom-attributes-api/retrieve-customer-custom-attribute)
+    ///
+    /// # Arguments
+    /// * `customer_id` - The ID of the target customer profile.
+    /// * `key` - The key of the custom attribute to retrieve.
+    pub async fn retrieve_custom_attribute(self, customer_id: String, key: String)
+                                           -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Customers(format!("/{}/custom-attributes/{}", customer_id, key)),
+            None::<&Customer>,
+            None,
+        ).await
+    }
+
+    /// Creates or updates a custom attribute for a customer profile.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/customer-custom-attributes-api/upsert-customer-custom-attribute)
+    ///
+    /// # Arguments
+    /// * `customer_id` - The ID of the target customer profile.
+    /// * `key` - The key of the custom attribute to create or update.
+    /// * `body` - The new value of the custom attribute, built through the [Builder](Builder) of a
+    ///   [UpsertCustomAttributeBody](UpsertCustomAttributeBody).
+    pub async fn upsert_custom_attribute(self, customer_id: String, key: String,
+                                         body: UpsertCustomAttributeBody)
+                                         -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Customers(format!("/{}/custom-attributes/{}", customer_id, key)),
+            Some(&body),
+            None,
+        ).await
+    }
+}
+
+pub(crate) fn customer_from(response: SquareResponse) -> Result<Customer, SquareError> {
+    response.into_responses()
+        .into_iter()
+        .find_map(|response| match response {
+            Response::Customer(customer) => Some(customer),
+            _ => None,
+        })
+        .ok_or_else(|| SquareError::from(None))
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -211,7 +383,15 @@ impl<T: ParentBuilder> Builder<Customer, T> {
     }
 
     pub fn note(mut self, note: String) -> Self {
-        self.body.birthday = Some(note);
+        self.body.note = Some(note);
+
+        self
+    }
+
+    /// An optional second ID used to associate the customer profile with an entity in another
+    /// system.
+    pub fn reference_id(mut self, reference_id: String) -> Self {
+        self.body.reference_id = Some(reference_id);
 
         self
     }
@@ -260,12 +440,24 @@ pub struct CustomerSearchQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub query: Option<SearchQueryAttribute>
+    pub query: Option<CustomerQuery>,
+    #[serde(flatten)]
+    pub(crate) raw_fields: Map<String, Value>,
 }
 
 impl Validate for CustomerSearchQuery {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        Ok(self)
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if valid_raw_fields(&mut self) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for CustomerSearchQuery {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
     }
 }
 
@@ -276,6 +468,14 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
         self
     }
 
+    /// Replaces the query, e.g. with one built through a
+    /// [CustomerQuery](CustomerQuery) sub-builder.
+    pub fn query(mut self, query: CustomerQuery) -> Self {
+        self.body.query = Some(query);
+
+        self
+    }
+
     pub fn limit(mut self, limit: i64) -> Self {
         if limit < 1 || limit > 100 { return self };
         self.body.limit = Some(limit);
@@ -297,7 +497,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: None,
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -329,7 +529,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: None,
             updated_at: Some(time_range.clone())
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -361,7 +561,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: None,
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -397,7 +597,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: None,
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -433,7 +633,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: None,
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -469,7 +669,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: None,
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -505,7 +705,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: Some(reference_id_group.clone()),
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -541,7 +741,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: Some(reference_id_group.clone()),
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -577,7 +777,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: None,
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -613,7 +813,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: None,
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -650,7 +850,7 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
             reference_id: None,
             updated_at: None
         };
-        let query = SearchQueryAttribute {
+        let query = CustomerQuery {
             filter: Some(filter.clone()),
             sort: None
         };
@@ -680,6 +880,139 @@ impl<T: ParentBuilder> Builder<CustomerSearchQuery, T> {
     }
 }
 
+// implements the necessary traits to release a CustomerQuery builder from a CustomerSearchQuery
+// builder
+impl AddField<CustomerQuery> for CustomerSearchQuery {
+    fn add_field(&mut self, field: CustomerQuery) {
+        self.query = Some(field);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CustomAttributeDefinitionBody builder implementation
+// -------------------------------------------------------------------------------------------------
+/// The schema reference of custom attributes holding a `String`.
+pub const STRING_SCHEMA: &str =
+    "https://developer-production-s.squarecdn.com/schemas/v1/common.json#squareup.common.String";
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CustomAttributeDefinitionBody {
+    custom_attribute_definition: CustomAttributeDefinition,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idempotency_key: Option<String>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for CustomAttributeDefinitionBody {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        let definition = &self.custom_attribute_definition;
+        if definition.key.is_some() &&
+            definition.name.is_some() &&
+            definition.schema.is_some() &&
+            definition.visibility.is_some() &&
+            valid_raw_fields(&mut self) {
+            self.idempotency_key = Some(Uuid::new_v4().to_string());
+
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for CustomAttributeDefinitionBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<CustomAttributeDefinitionBody, T> {
+    /// The identifier of the custom attribute definition, unique within the seller account.
+    pub fn key(mut self, key: String) -> Self {
+        self.body.custom_attribute_definition.key = Some(key);
+
+        self
+    }
+
+    pub fn name(mut self, name: String) -> Self {
+        self.body.custom_attribute_definition.name = Some(name);
+
+        self
+    }
+
+    pub fn description(mut self, description: String) -> Self {
+        self.body.custom_attribute_definition.description = Some(description);
+
+        self
+    }
+
+    /// The JSON schema of the custom attribute, which references one of the
+    /// [supported data types](https://developer.squareup.com/docs/devtools/customattributes/overview#supported-data-types).
+    pub fn schema(mut self, schema: serde_json::Value) -> Self {
+        self.body.custom_attribute_definition.schema = Some(schema);
+
+        self
+    }
+
+    /// Shorthand for a [schema](Self::schema) of a `String` custom attribute.
+    pub fn string_schema(self) -> Self {
+        self.schema(serde_json::json!({ "$ref": STRING_SCHEMA }))
+    }
+
+    pub fn visibility(mut self, visibility: CustomAttributeVisibility) -> Self {
+        self.body.custom_attribute_definition.visibility = Some(visibility);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// UpsertCustomAttributeBody builder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct UpsertCustomAttributeBody {
+    custom_attribute: CustomAttribute,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idempotency_key: Option<String>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for UpsertCustomAttributeBody {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.custom_attribute.value.is_some() && valid_raw_fields(&mut self) {
+            self.idempotency_key = Some(Uuid::new_v4().to_string());
+
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for UpsertCustomAttributeBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<UpsertCustomAttributeBody, T> {
+    /// The value of the custom attribute, which must conform to the schema of its definition.
+    pub fn value(mut self, value: serde_json::Value) -> Self {
+        self.body.custom_attribute.value = Some(value);
+
+        self
+    }
+
+    /// The current version of the custom attribute, which enables optimistic concurrency.
+    pub fn version(mut self, version: i32) -> Self {
+        self.body.custom_attribute.version = Some(version);
+
+        self
+    }
+}
+
 #[cfg(test)]
 mod test_customers {
     use super::*;
@@ -729,7 +1062,8 @@ mod test_customers {
                 sublocality: None,
                 administrative_district_level: Some("MA".to_string()),
                 postal_code: Some("12345".to_string()),
-                country: Some("United States".to_string())
+                country: Some("United States".to_string()),
+                ..Default::default()
             }),
             company_name: None,
             created_at: None,
@@ -759,7 +1093,8 @@ mod test_customers {
             sublocality: None,
             administrative_district_level: Some("MA".to_string()),
             postal_code: Some("12345".to_string()),
-            country: Some("United States".to_string())
+            country: Some("United States".to_string()),
+            ..Default::default()
         };
 
         let mut actual = Builder::from(Customer::default())
@@ -839,7 +1174,7 @@ mod test_customers {
         let expected = CustomerSearchQuery {
             cursor: None,
             limit: Some(5),
-            query: Some(SearchQueryAttribute {
+            query: Some(CustomerQuery {
                 filter: Some(CustomerFilter {
                     created_at: Some(TimeRange {
                         end_at: Some("2022-01-23T20:21:54.859Z".to_string()),
@@ -866,7 +1201,8 @@ mod test_customers {
                     updated_at: None
                 }),
                 sort: None
-            })
+            }),
+            raw_fields: Default::default(),
         };
 
         let actual = Builder::from(CustomerSearchQuery::default())
@@ -889,6 +1225,94 @@ mod test_customers {
         assert_eq!(format!("{:?}", expected), format!("{:?}", actual.unwrap()));
     }
 
+    #[tokio::test]
+    async fn test_customer_query_builder() {
+        use crate::objects::enums::SortOrder;
+
+        let actual = Builder::from(CustomerSearchQuery::default())
+            .limit(10)
+            .sub_builder_from(CustomerQuery::default())
+            .fuzzy_email_address("example.com".to_string())
+            .exact_email_address("someone@example.com".to_string())
+            .in_any_group(vec!["vip".to_string(), "regulars".to_string()])
+            .in_no_group(vec!["blocked".to_string()])
+            .exclude_creation_sources(vec![CustomerCreationSource::ThirdParty])
+            .created_at(TimeRange {
+                start_at: Some("2022-01-01T00:00:00Z".to_string()),
+                end_at: None,
+            })
+            .sort_field(CustomerSortField::CreatedAt)
+            .sort_descending()
+            .into_parent_builder()
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let query = actual.query.unwrap();
+        let filter = query.filter.unwrap();
+        let email_address = filter.email_address.unwrap();
+        assert_eq!(email_address.exact.as_deref(), Some("someone@example.com"));
+        assert!(email_address.fuzzy.is_none());
+        let group_ids = filter.group_ids.unwrap();
+        assert_eq!(group_ids.any.unwrap().len(), 2);
+        assert_eq!(group_ids.none.unwrap(), vec!["blocked".to_string()]);
+        assert!(group_ids.all.is_none());
+        assert_eq!(filter.creation_source.unwrap().rule.as_deref(), Some("EXCLUDE"));
+        let sort = query.sort.unwrap();
+        assert_eq!(sort.field, Some(CustomerSortField::CreatedAt));
+        assert!(matches!(sort.order, Some(SortOrder::Desc)));
+
+        let both = CustomerQuery {
+            filter: Some(CustomerFilter {
+                phone_number: Some(CustomerTextFilter {
+                    exact: Some("571-694-6282".to_string()),
+                    fuzzy: Some("694".to_string()),
+                }),
+                ..Default::default()
+            }),
+            sort: None,
+        };
+        assert!(Builder::from(both).build().await.is_err());
+
+        let backwards = Builder::from(CustomerQuery::default())
+            .updated_at(TimeRange {
+                start_at: Some("2022-02-01T00:00:00Z".to_string()),
+                end_at: Some("2022-01-01T00:00:00Z".to_string()),
+            })
+            .build()
+            .await;
+        assert!(backwards.is_err());
+    }
+
+    // #[tokio::test]
+    async fn test_get_or_create_customer() {
+        use dotenv::dotenv;
+        use std::env;
+
+        dotenv().ok();
+        let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
+        let sut = SquareClient::new(&access_token);
+
+        let created = sut.customers()
+            .get_or_create(
+                "get.or.create@example.com".to_string(),
+                Builder::from(Customer::default()).given_name("Amelia".to_string()),
+            )
+            .await
+            .unwrap();
+        let found = sut.customers()
+            .get_or_create(
+                "get.or.create@example.com".to_string(),
+                Builder::from(Customer::default()).given_name("Someone else".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(created.id, found.id);
+        assert_eq!(found.given_name.as_deref(), Some("Amelia"));
+    }
+
     #[tokio::test]
     async fn test_search_customers() {
         use dotenv::dotenv;
@@ -901,7 +1325,7 @@ mod test_customers {
         let input = CustomerSearchQuery {
             cursor: None,
             limit: None,
-            query: Some(SearchQueryAttribute {
+            query: Some(CustomerQuery {
                 filter: Some(CustomerFilter {
                     created_at: Some(TimeRange {
                         end_at: None,
@@ -928,7 +1352,8 @@ mod test_customers {
                     updated_at: None
                 }),
                 sort: None
-            })
+            }),
+            raw_fields: Default::default(),
         };
 
         let result = sut.customers().search(input).await;
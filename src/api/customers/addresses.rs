@@ -0,0 +1,301 @@
+/*!
+Address book helpers built on top of the customers functionality of the
+[Square API](https://developer.squareup.com).
+
+A customer profile only holds a single address. Merchants delivering to their customers often need
+more than one, so the additional addresses of a customer are kept in a `String` custom attribute
+under the [ADDRESS_BOOK_KEY](ADDRESS_BOOK_KEY), as a JSON encoded list of
+[LabeledAddress](LabeledAddress)es. The [AddressBook](AddressBook) combines them with the address
+of the profile itself, which stays the primary address.
+
+The definition of the custom attribute has to be created once per seller account, through
+[create_address_book_definition](Customers::create_address_book_definition).
+
+# Example: Add a delivery address to a customer
+```no_run
+use square_ox::{
+    client::SquareClient,
+    objects::Address,
+};
+
+async {
+    let client = SquareClient::new("some_token");
+
+    let mut address_book = client.customers()
+        .address_book("some_customer_id".to_string())
+        .await
+        .unwrap();
+
+    address_book.insert("office".to_string(), Address {
+        address_line_1: Some("500 Electric Ave".to_string()),
+        locality: Some("New York".to_string()),
+        administrative_district_level: Some("NY".to_string()),
+        postal_code: Some("10003".to_string()),
+        country: Some("US".to_string()),
+        ..Default::default()
+    });
+
+    client.customers()
+        .save_address_book("some_customer_id".to_string(), &mut address_book)
+        .await
+        .unwrap();
+};
+```
+ */
+
+use crate::api::customers::{
+    Customers, CustomAttributeDefinitionBody, UpsertCustomAttributeBody,
+};
+use crate::builder::Builder;
+use crate::errors::SquareError;
+use crate::objects::{Address, Customer, Response};
+use crate::objects::enums::CustomAttributeVisibility;
+use crate::response::SquareResponse;
+
+use serde::{Deserialize, Serialize};
+
+/// The key of the custom attribute holding the additional addresses of a customer.
+pub const ADDRESS_BOOK_KEY: &str = "address_book";
+
+/// An additional address of a customer, identified by a label such as `home` or `office`.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct LabeledAddress {
+    pub label: String,
+    pub address: Address,
+}
+
+/// All of the addresses of a customer.
+#[derive(Clone, Debug, Default)]
+pub struct AddressBook {
+    /// The address stored on the customer profile itself.
+    pub primary: Option<Address>,
+    /// The addresses stored in the [ADDRESS_BOOK_KEY](ADDRESS_BOOK_KEY) custom attribute.
+    pub additional: Vec<LabeledAddress>,
+    // the version of the custom attribute the additional addresses were read from
+    version: Option<i32>,
+}
+
+impl AddressBook {
+    /// The additional address with the given label.
+    pub fn get(&self, label: &str) -> Option<&Address> {
+        self.additional.iter()
+            .find(|labeled| labeled.label == label)
+            .map(|labeled| &labeled.address)
+    }
+
+    /// Adds an additional address, replacing any address with the same label.
+    pub fn insert(&mut self, label: String, address: Address) {
+        match self.additional.iter_mut().find(|labeled| labeled.label == label) {
+            Some(labeled) => labeled.address = address,
+            None => self.additional.push(LabeledAddress { label, address }),
+        }
+    }
+
+    /// Removes the additional address with the given label, returning it if it existed.
+    pub fn remove(&mut self, label: &str) -> Option<Address> {
+        let index = self.additional.iter().position(|labeled| labeled.label == label)?;
+
+        Some(self.additional.remove(index).address)
+    }
+
+    /// All of the addresses, starting with the primary one.
+    pub fn addresses(&self) -> Vec<&Address> {
+        self.primary.iter()
+            .chain(self.additional.iter().map(|labeled| &labeled.address))
+            .collect()
+    }
+}
+
+impl<'a> Customers<'a> {
+    /// Creates the definition of the [ADDRESS_BOOK_KEY](ADDRESS_BOOK_KEY) custom attribute. This
+    /// only needs to be done once per seller account, before any address book is saved.
+    pub async fn create_address_book_definition(self) -> Result<SquareResponse, SquareError> {
+        let body = Builder::from(CustomAttributeDefinitionBody::default())
+            .key(ADDRESS_BOOK_KEY.to_string())
+            .name("Address book".to_string())
+            .description("The additional addresses of the customer.".to_string())
+            .string_schema()
+            .visibility(CustomAttributeVisibility::VisibilityReadOnly)
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+
+        self.create_custom_attribute_definition(body).await
+    }
+
+    /// Retrieves the [AddressBook](AddressBook) of a customer. A customer without any additional
+    /// addresses yields an address book with only the primary address.
+    /// # Arguments
+    /// * `customer_id` - The ID of the customer profile.
+    pub async fn address_book(self, customer_id: String) -> Result<AddressBook, SquareError> {
+        let customer = self.client.customers()
+            .retrieve(customer_id.clone())
+            .await?
+            .into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::Customer(customer) => Some(customer),
+                _ => None,
+            })
+            .ok_or_else(|| SquareError::from(None))?;
+
+        let attribute = match self.client.customers()
+            .retrieve_custom_attribute(customer_id, ADDRESS_BOOK_KEY.to_string())
+            .await {
+            Ok(res) => res.into_responses()
+                .into_iter()
+                .find_map(|response| match response {
+                    Response::CustomAttribute(attribute) => Some(attribute),
+                    _ => None,
+                }),
+            Err(err) => match err.get() {
+                Some(errors) if errors.iter().all(|error| error.code == "NOT_FOUND") => None,
+                errors => return Err(SquareError::from(errors)),
+            },
+        };
+
+        let (additional, version) = match attribute {
+            Some(attribute) => (
+                decode_addresses(attribute.value.as_ref())?,
+                attribute.version,
+            ),
+            None => (vec![], None),
+        };
+
+        Ok(AddressBook {
+            primary: customer.address,
+            additional,
+            version,
+        })
+    }
+
+    /// Saves the [AddressBook](AddressBook) of a customer, updating the address of the profile to
+    /// the primary address and the custom attribute to the additional addresses.
+    /// # Arguments
+    /// * `customer_id` - The ID of the customer profile.
+    /// * `address_book` - The address book to save, which is updated to the saved version so it
+    ///   can be saved again.
+    pub async fn save_address_book(self, customer_id: String, address_book: &mut AddressBook)
+                                   -> Result<(), SquareError> {
+        if let Some(primary) = address_book.primary.as_ref() {
+            self.client.customers()
+                .update(customer_id.clone(), Customer {
+                    address: Some(primary.clone()),
+                    ..Default::default()
+                })
+                .await?;
+        }
+
+        let mut body = Builder::from(UpsertCustomAttributeBody::default())
+            .value(encode_addresses(&address_book.additional)?);
+        if let Some(version) = address_book.version {
+            body = body.version(version);
+        }
+        let body = body.build().await.map_err(|_| SquareError::from(None))?;
+
+        let attribute = self.client.customers()
+            .upsert_custom_attribute(customer_id, ADDRESS_BOOK_KEY.to_string(), body)
+            .await?
+            .into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::CustomAttribute(attribute) => Some(attribute),
+                _ => None,
+            });
+
+        if let Some(attribute) = attribute {
+            address_book.version = attribute.version;
+        }
+
+        Ok(())
+    }
+}
+
+// the additional addresses as the value of a `String` custom attribute
+fn encode_addresses(addresses: &[LabeledAddress]) -> Result<serde_json::Value, SquareError> {
+    Ok(serde_json::Value::String(serde_json::to_string(addresses)?))
+}
+
+// the additional addresses held by the value of a `String` custom attribute
+fn decode_addresses(value: Option<&serde_json::Value>) -> Result<Vec<LabeledAddress>, SquareError> {
+    match value.and_then(|value| value.as_str()) {
+        Some(addresses) if !addresses.is_empty() => Ok(serde_json::from_str(addresses)?),
+        _ => Ok(vec![]),
+    }
+}
+
+#[cfg(test)]
+mod test_addresses {
+    use super::*;
+
+    fn address(address_line_1: &str) -> Address {
+        Address {
+            address_line_1: Some(address_line_1.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_address_book() {
+        let mut address_book = AddressBook {
+            primary: Some(address("1234 Hillborrow Rd.")),
+            ..Default::default()
+        };
+
+        address_book.insert("office".to_string(), address("500 Electric Ave"));
+        address_book.insert("cabin".to_string(), address("1 Lakeshore Dr."));
+        address_book.insert("office".to_string(), address("600 Electric Ave"));
+
+        assert_eq!(address_book.additional.len(), 2);
+        assert_eq!(
+            address_book.get("office").unwrap().address_line_1,
+            Some("600 Electric Ave".to_string()),
+        );
+
+        let removed = address_book.remove("cabin");
+
+        assert_eq!(removed.unwrap().address_line_1, Some("1 Lakeshore Dr.".to_string()));
+        assert!(address_book.remove("cabin").is_none());
+        assert_eq!(
+            format!("{:?}", address_book.addresses()),
+            format!("{:?}", vec![&address("1234 Hillborrow Rd."), &address("600 Electric Ave")]),
+        );
+    }
+
+    #[test]
+    fn test_encode_decode_addresses() {
+        let addresses = vec![
+            LabeledAddress { label: "home".to_string(), address: address("1234 Hillborrow Rd.") },
+            LabeledAddress { label: "office".to_string(), address: address("500 Electric Ave") },
+        ];
+
+        let value = encode_addresses(&addresses).unwrap();
+
+        assert!(value.is_string());
+
+        let actual = decode_addresses(Some(&value)).unwrap();
+
+        assert_eq!(format!("{:?}", addresses), format!("{:?}", actual));
+        assert!(decode_addresses(None).unwrap().is_empty());
+        assert!(decode_addresses(Some(&serde_json::json!(""))).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_address_serialization() {
+        let address: Address = serde_json::from_value(serde_json::json!({
+            "address_line_1": "500 Electric Ave",
+            "administrative_district_level_1": "NY",
+            "first_name": "Pierre",
+        })).unwrap();
+
+        assert_eq!(address.administrative_district_level, Some("NY".to_string()));
+        assert_eq!(
+            serde_json::to_value(&address).unwrap(),
+            serde_json::json!({
+                "address_line_1": "500 Electric Ave",
+                "administrative_district_level_1": "NY",
+                "first_name": "Pierre",
+            }),
+        );
+    }
+}
@@ -0,0 +1,381 @@
+/*!
+Duplicate detection built on top of the customers functionality of the
+[Square API](https://developer.squareup.com).
+
+Customer profiles are easily created more than once, for instance once at the register and once
+through an online checkout. [find_duplicates](Customers::find_duplicates) pages through every
+customer profile of the seller and groups the ones that probably belong to the same person into
+[DuplicateSet](DuplicateSet)s.
+
+Whether two profiles match is decided by the [DuplicateMatcher](DuplicateMatcher)s of a
+[DuplicateStrategy](DuplicateStrategy). Each matcher derives normalized keys from a profile, and
+profiles sharing any key end up in the same set. Besides the [EmailMatcher](EmailMatcher),
+[PhoneMatcher](PhoneMatcher) and [NameMatcher](NameMatcher), any type implementing the trait can
+be plugged into a strategy.
+
+# Example: Find customers sharing an email address or phone number
+```no_run
+use square_ox::{
+    client::SquareClient,
+    api::customers::duplicates::{DuplicateStrategy, EmailMatcher, PhoneMatcher},
+};
+
+async {
+    let duplicates = SquareClient::new("some_token")
+        .customers()
+        .find_duplicates(DuplicateStrategy::new()
+            .matcher(EmailMatcher)
+            .matcher(PhoneMatcher))
+        .await
+        .unwrap();
+
+    for set in duplicates {
+        println!("{:?} match on {:?}", set.customer_ids(), set.matched_on);
+    }
+};
+```
+ */
+
+use crate::api::customers::{Customers, CustomerListParametersBuilder};
+use crate::errors::SquareError;
+use crate::objects::{Customer, Response};
+
+use std::collections::{BTreeSet, HashMap};
+
+/// Derives the keys under which a customer profile is compared to the others. Two profiles
+/// sharing any key are considered probable duplicates.
+pub trait DuplicateMatcher: Send + Sync {
+    /// The normalized keys of the customer. A customer without the data the matcher relies on
+    /// yields no keys and is never matched by it.
+    fn keys(&self, customer: &Customer) -> Vec<String>;
+}
+
+/// Matches customers on their email address, ignoring case, surrounding whitespace and any
+/// `+tag` in the local part.
+#[derive(Clone, Debug, Default)]
+pub struct EmailMatcher;
+
+impl DuplicateMatcher for EmailMatcher {
+    fn keys(&self, customer: &Customer) -> Vec<String> {
+        customer.email_address.as_deref()
+            .and_then(normalize_email)
+            .map(|email| vec![format!("email:{}", email)])
+            .unwrap_or_default()
+    }
+}
+
+/// Matches customers on the last ten digits of their phone number, so numbers with and without
+/// a country code or formatting are considered equal.
+#[derive(Clone, Debug, Default)]
+pub struct PhoneMatcher;
+
+impl DuplicateMatcher for PhoneMatcher {
+    fn keys(&self, customer: &Customer) -> Vec<String> {
+        customer.phone_number.as_deref()
+            .and_then(normalize_phone)
+            .map(|phone| vec![format!("phone:{}", phone)])
+            .unwrap_or_default()
+    }
+}
+
+/// Matches customers on their given and family name, ignoring case, punctuation and repeated
+/// whitespace. Only customers with both names are matched.
+#[derive(Clone, Debug, Default)]
+pub struct NameMatcher;
+
+impl DuplicateMatcher for NameMatcher {
+    fn keys(&self, customer: &Customer) -> Vec<String> {
+        let given_name = customer.given_name.as_deref().and_then(normalize_name);
+        let family_name = customer.family_name.as_deref().and_then(normalize_name);
+
+        match (given_name, family_name) {
+            (Some(given_name), Some(family_name)) => {
+                vec![format!("name:{} {}", given_name, family_name)]
+            },
+            _ => vec![],
+        }
+    }
+}
+
+/// The set of [DuplicateMatcher](DuplicateMatcher)s used to find duplicate customers.
+/// The default strategy matches on email address, phone number and name.
+pub struct DuplicateStrategy {
+    matchers: Vec<Box<dyn DuplicateMatcher>>,
+}
+
+impl DuplicateStrategy {
+    /// A strategy without any matchers.
+    pub fn new() -> Self {
+        DuplicateStrategy {
+            matchers: vec![],
+        }
+    }
+
+    /// Adds a matcher to the strategy.
+    pub fn matcher<M: DuplicateMatcher + 'static>(mut self, matcher: M) -> Self {
+        self.matchers.push(Box::new(matcher));
+
+        self
+    }
+
+    // the deduplicated keys of all matchers for the customer
+    fn keys(&self, customer: &Customer) -> BTreeSet<String> {
+        self.matchers.iter()
+            .flat_map(|matcher| matcher.keys(customer))
+            .collect()
+    }
+}
+
+impl Default for DuplicateStrategy {
+    fn default() -> Self {
+        DuplicateStrategy::new()
+            .matcher(EmailMatcher)
+            .matcher(PhoneMatcher)
+            .matcher(NameMatcher)
+    }
+}
+
+/// A group of customer profiles that probably belong to the same person.
+#[derive(Clone, Debug)]
+pub struct DuplicateSet {
+    /// The matching profiles, oldest first.
+    pub customers: Vec<Customer>,
+    /// The keys shared by at least two of the profiles, such as `email:john@example.com`.
+    pub matched_on: Vec<String>,
+}
+
+impl DuplicateSet {
+    /// The IDs of the matching profiles, oldest first.
+    pub fn customer_ids(&self) -> Vec<&str> {
+        self.customers.iter()
+            .filter_map(|customer| customer.id.as_deref())
+            .collect()
+    }
+}
+
+impl<'a> Customers<'a> {
+    /// Pages through all customer profiles and groups the probable duplicates according to the
+    /// given [DuplicateStrategy](DuplicateStrategy).
+    /// # Arguments:
+    /// * `strategy` - The matchers deciding which profiles are duplicates of each other.
+    pub async fn find_duplicates(self, strategy: DuplicateStrategy)
+                                 -> Result<Vec<DuplicateSet>, SquareError> {
+        let mut customers = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut parameters = CustomerListParametersBuilder::new()
+                .limit(100)
+                .sort_field_created_at()
+                .sort_order_asc();
+            if let Some(cursor) = cursor {
+                parameters = parameters.cursor(cursor);
+            }
+            let parameters = parameters.build().await.map_err(|_| SquareError::from(None))?;
+
+            let res = self.client.customers().list(parameters).await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::Customers(page) = response {
+                    customers.extend(page);
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(group_duplicates(customers, &strategy));
+            }
+        }
+    }
+}
+
+// groups the customers that share any key, directly or through other customers of the group
+fn group_duplicates(customers: Vec<Customer>, strategy: &DuplicateStrategy) -> Vec<DuplicateSet> {
+    let keys: Vec<BTreeSet<String>> = customers.iter()
+        .map(|customer| strategy.keys(customer))
+        .collect();
+
+    let mut parents: Vec<usize> = (0..customers.len()).collect();
+    let mut owners: HashMap<&str, usize> = HashMap::new();
+    let mut shared: BTreeSet<&str> = BTreeSet::new();
+
+    for (index, customer_keys) in keys.iter().enumerate() {
+        for key in customer_keys {
+            match owners.get(key.as_str()) {
+                Some(&owner) => {
+                    shared.insert(key);
+                    let (a, b) = (root(&mut parents, owner), root(&mut parents, index));
+                    parents[a.max(b)] = a.min(b);
+                },
+                None => {
+                    owners.insert(key, index);
+                },
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = vec![vec![]; customers.len()];
+    for index in 0..customers.len() {
+        let root = root(&mut parents, index);
+        groups[root].push(index);
+    }
+
+    let mut customers: Vec<Option<Customer>> = customers.into_iter().map(Some).collect();
+
+    groups.into_iter()
+        .filter(|group| group.len() > 1)
+        .map(|group| {
+            let matched_on = group.iter()
+                .flat_map(|&index| keys[index].iter())
+                .filter(|key| shared.contains(key.as_str()))
+                .cloned()
+                .collect::<BTreeSet<String>>()
+                .into_iter()
+                .collect();
+
+            DuplicateSet {
+                customers: group.into_iter()
+                    .filter_map(|index| customers[index].take())
+                    .collect(),
+                matched_on,
+            }
+        })
+        .collect()
+}
+
+// the representative of the group the customer at the index belongs to
+fn root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+
+    index
+}
+
+fn normalize_email(email: &str) -> Option<String> {
+    let email = email.trim().to_lowercase();
+    let (local, domain) = email.split_once('@')?;
+    let local = local.split('+').next().unwrap_or_default();
+
+    if local.is_empty() || domain.is_empty() {
+        return None;
+    }
+
+    Some(format!("{}@{}", local, domain))
+}
+
+fn normalize_phone(phone: &str) -> Option<String> {
+    let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    if digits.len() < 7 {
+        return None;
+    }
+
+    Some(digits[digits.len().saturating_sub(10)..].to_string())
+}
+
+fn normalize_name(name: &str) -> Option<String> {
+    let name = name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(name)
+}
+
+#[cfg(test)]
+mod test_duplicates {
+    use super::*;
+
+    fn customer(id: &str, value: serde_json::Value) -> Customer {
+        let mut customer: Customer = serde_json::from_value(value).unwrap();
+        customer.id = Some(id.to_string());
+
+        customer
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize_email(" John.Doe+shop@Example.com "), Some("john.doe@example.com".to_string()));
+        assert_eq!(normalize_email("not an email"), None);
+        assert_eq!(normalize_phone("+1 (206) 222-3456"), Some("2062223456".to_string()));
+        assert_eq!(normalize_phone("206-2223456"), Some("2062223456".to_string()));
+        assert_eq!(normalize_phone("123"), None);
+        assert_eq!(normalize_name("  Jean-Luc "), Some("jean luc".to_string()));
+        assert_eq!(normalize_name(" - "), None);
+    }
+
+    #[test]
+    fn test_group_duplicates() {
+        let customers = vec![
+            customer("a", serde_json::json!({
+                "given_name": "John",
+                "family_name": "Doe",
+                "email_address": "john.doe@example.com",
+            })),
+            customer("b", serde_json::json!({
+                "email_address": "JOHN.DOE+web@example.com",
+                "phone_number": "+1 206-222-3456",
+            })),
+            customer("c", serde_json::json!({
+                "given_name": "Johnny",
+                "phone_number": "(206) 222-3456",
+            })),
+            customer("d", serde_json::json!({
+                "given_name": "Jane",
+                "family_name": "Doe",
+                "email_address": "jane@example.com",
+            })),
+            customer("e", serde_json::json!({
+                "given_name": "jane",
+                "family_name": "DOE",
+            })),
+            customer("f", serde_json::json!({
+                "given_name": "Amelia",
+                "family_name": "Earhart",
+            })),
+        ];
+
+        let actual = group_duplicates(customers, &DuplicateStrategy::default());
+
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[0].customer_ids(), vec!["a", "b", "c"]);
+        assert_eq!(
+            actual[0].matched_on,
+            vec!["email:john.doe@example.com".to_string(), "phone:2062223456".to_string()],
+        );
+        assert_eq!(actual[1].customer_ids(), vec!["d", "e"]);
+        assert_eq!(actual[1].matched_on, vec!["name:jane doe".to_string()]);
+    }
+
+    #[test]
+    fn test_custom_matcher() {
+        struct CompanyMatcher;
+
+        impl DuplicateMatcher for CompanyMatcher {
+            fn keys(&self, customer: &Customer) -> Vec<String> {
+                customer.company_name.iter().map(|name| format!("company:{}", name)).collect()
+            }
+        }
+
+        let customers = vec![
+            customer("a", serde_json::json!({ "company_name": "Square", "given_name": "Ann" })),
+            customer("b", serde_json::json!({ "company_name": "Square", "given_name": "Bob" })),
+            customer("c", serde_json::json!({ "given_name": "Ann" })),
+        ];
+
+        let actual = group_duplicates(customers, &DuplicateStrategy::new().matcher(CompanyMatcher));
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].customer_ids(), vec!["a", "b"]);
+        assert!(group_duplicates(vec![], &DuplicateStrategy::default()).is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+/*!
+Purchase history built on top of the customers and orders functionality of the
+[Square API](https://developer.squareup.com).
+
+Orders can only be searched across ten locations at a time. [orders](Customers::orders) fans the
+search for the orders of a customer out over every location of the seller, merges the results
+newest first and, when asked to, attaches the [Payment](Payment)s and
+[PaymentRefund](PaymentRefund)s belonging to each order.
+
+# Example: List the purchase history of a customer
+```no_run
+use square_ox::client::SquareClient;
+
+async {
+    let history = SquareClient::new("some_token")
+        .customers()
+        .orders("some_customer_id".to_string(), true)
+        .await
+        .unwrap();
+
+    for purchase in history {
+        println!("{:?}: {} payments, {} refunds",
+                 purchase.order.id, purchase.payments.len(), purchase.refunds.len());
+    }
+};
+```
+ */
+
+use crate::api::customers::Customers;
+use crate::api::orders::SearchOrderBody;
+use crate::errors::SquareError;
+use crate::objects::{
+    Order, Payment, PaymentRefund, Response, SearchOrdersCustomerFilter, SearchOrdersFilter,
+    SearchOrdersQuery, SearchOrdersSort,
+    enums::{SearchOrdersSortField, SortOrder},
+};
+
+use std::cmp::Reverse;
+
+// the maximum number of locations a single order search may span
+const LOCATIONS_PER_SEARCH: usize = 10;
+
+/// An order of a customer, along with the payments and refunds made against it.
+#[derive(Clone, Debug)]
+pub struct CustomerOrder {
+    pub order: Order,
+    /// The payments tendered for the order. Only filled in when enrichment was requested.
+    pub payments: Vec<Payment>,
+    /// The refunds issued against the payments of the order. Only filled in when enrichment was
+    /// requested.
+    pub refunds: Vec<PaymentRefund>,
+}
+
+impl<'a> Customers<'a> {
+    /// Searches the orders of a customer across all locations of the seller, newest first.
+    /// # Arguments:
+    /// * `customer_id` - The ID of the customer whose orders to search.
+    /// * `with_payments` - Whether to retrieve the [Payment](Payment)s and
+    ///   [PaymentRefund](PaymentRefund)s of every order as well.
+    pub async fn orders(self, customer_id: String, with_payments: bool)
+                        -> Result<Vec<CustomerOrder>, SquareError> {
+        let location_ids: Vec<String> = self.client.locations()
+            .list()
+            .await?
+            .into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::Locations(locations) => Some(locations),
+                _ => None,
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|location| location.id)
+            .collect();
+
+        let mut orders = vec![];
+
+        for location_ids in location_ids.chunks(LOCATIONS_PER_SEARCH) {
+            let mut cursor = None;
+
+            loop {
+                let res = self.client.orders()
+                    .search(search_body(location_ids.to_vec(), &customer_id, cursor))
+                    .await?;
+                cursor = res.cursor.clone();
+
+                for response in res.into_responses() {
+                    if let Response::Orders(page) = response {
+                        orders.extend(page);
+                    }
+                }
+
+                if cursor.is_none() {
+                    break;
+                }
+            }
+        }
+
+        let mut history = vec![];
+
+        for order in newest_first(orders) {
+            let mut purchase = CustomerOrder {
+                order,
+                payments: vec![],
+                refunds: vec![],
+            };
+
+            if with_payments {
+                for payment_id in payment_ids(&purchase.order) {
+                    let payment = self.client.payments()
+                        .get(payment_id)
+                        .await?
+                        .into_responses()
+                        .into_iter()
+                        .find_map(|response| match response {
+                            Response::Payment(payment) => Some(payment),
+                            _ => None,
+                        })
+                        .ok_or_else(|| SquareError::from(None))?;
+
+                    for refund_id in payment.refund_ids.clone().unwrap_or_default() {
+                        let refund = self.client.refunds()
+                            .get(refund_id)
+                            .await?
+                            .into_responses()
+                            .into_iter()
+                            .find_map(|response| match response {
+                                Response::Refund(refund) => Some(refund),
+                                _ => None,
+                            })
+                            .ok_or_else(|| SquareError::from(None))?;
+
+                        purchase.refunds.push(refund);
+                    }
+
+                    purchase.payments.push(payment);
+                }
+            }
+
+            history.push(purchase);
+        }
+
+        Ok(history)
+    }
+}
+
+// a search for the full orders of the customer at the given locations
+fn search_body(location_ids: Vec<String>, customer_id: &str, cursor: Option<String>)
+               -> SearchOrderBody {
+    SearchOrderBody {
+        cursor,
+        limit: None,
+        location_ids: Some(location_ids),
+        query: Some(SearchOrdersQuery {
+            filter: Some(SearchOrdersFilter {
+                customer_filter: Some(SearchOrdersCustomerFilter {
+                    customer_ids: Some(vec![customer_id.to_string()]),
+                }),
+                ..Default::default()
+            }),
+            sort: Some(SearchOrdersSort {
+                sort_field: Some(SearchOrdersSortField::CreatedAt),
+                sort_order: Some(SortOrder::Desc),
+            }),
+        }),
+        return_entries: Some(false),
+        raw_fields: Default::default(),
+    }
+}
+
+// the orders merged across locations, newest first; RFC 3339 timestamps sort chronologically
+fn newest_first(mut orders: Vec<Order>) -> Vec<Order> {
+    orders.sort_by(|a, b| {
+        Reverse(a.created_at.as_deref()).cmp(&Reverse(b.created_at.as_deref()))
+    });
+
+    orders
+}
+
+// the IDs of the payments tendered for the order
+fn payment_ids(order: &Order) -> Vec<String> {
+    order.tenders.iter()
+        .flatten()
+        .filter_map(|tender| tender.payment_id.clone().or_else(|| tender.id.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test_history {
+    use super::*;
+
+    #[test]
+    fn test_search_body() {
+        let actual = serde_json::to_value(search_body(
+            vec!["L1".to_string(), "L2".to_string()],
+            "some_customer_id",
+            Some("some_cursor".to_string()),
+        )).unwrap();
+
+        assert_eq!(actual["cursor"], serde_json::json!("some_cursor"));
+        assert_eq!(actual["location_ids"], serde_json::json!(["L1", "L2"]));
+        assert_eq!(actual["return_entries"], serde_json::json!(false));
+        assert_eq!(
+            actual["query"]["filter"],
+            serde_json::json!({ "customer_filter": { "customer_ids": ["some_customer_id"] } }),
+        );
+        assert_eq!(
+            actual["query"]["sort"],
+            serde_json::json!({ "sort_field": "CREATED_AT", "sort_order": "DESC" }),
+        );
+    }
+
+    #[test]
+    fn test_newest_first_and_payment_ids() {
+        let orders: Vec<Order> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "older",
+                "location_id": "L1",
+                "created_at": "2022-03-01T10:00:00Z",
+                "tenders": [
+                    { "id": "T1", "type": "CARD", "payment_id": "P1" },
+                    { "id": "T2", "type": "CASH" },
+                ],
+            },
+            { "id": "undated", "location_id": "L2" },
+            { "id": "newer", "location_id": "L2", "created_at": "2022-04-01T10:00:00Z" },
+        ])).unwrap();
+
+        let actual = newest_first(orders);
+
+        assert_eq!(
+            actual.iter().map(|order| order.id.clone().unwrap()).collect::<Vec<String>>(),
+            vec!["newer", "older", "undated"],
+        );
+        assert_eq!(payment_ids(&actual[1]), vec!["P1".to_string(), "T2".to_string()]);
+        assert!(payment_ids(&actual[0]).is_empty());
+    }
+}
@@ -0,0 +1,385 @@
+/*!
+Merging of duplicate customer profiles, built on top of the customers and cards functionality of
+the [Square API](https://developer.squareup.com).
+
+[merge_duplicates](Customers::merge_duplicates) finds the probable duplicates through
+[find_duplicates](Customers::find_duplicates), picks the profile to keep in every
+[DuplicateSet](DuplicateSet) according to a [SurvivorRule](SurvivorRule), adds the survivor to the
+groups of the other profiles and deletes them.
+
+Square cannot move a card on file from one customer to another, and deleting a customer removes
+its cards. A duplicate holding enabled cards is therefore kept, unless
+[delete_with_cards](MergeOptions::delete_with_cards) is set, and its cards are reported on the
+[MergePlan](MergePlan) so the buyer can be asked to store them for the survivor again.
+
+With [dry_run](MergeOptions::dry_run) nothing is changed, and the outcomes only report the planned
+merges. A merge stops at the first request that fails; the merges applied until then stay applied,
+so running it again picks up where it left off.
+
+# Example: Report the planned merges
+```no_run
+use square_ox::{
+    api::customers::{
+        duplicates::{DuplicateStrategy, EmailMatcher, PhoneMatcher},
+        merge::{MergeOptions, SurvivorRule},
+    },
+    client::SquareClient,
+};
+
+async {
+    let outcomes = SquareClient::new("some_token")
+        .customers()
+        .merge_duplicates(
+            DuplicateStrategy::new().matcher(EmailMatcher).matcher(PhoneMatcher),
+            &MergeOptions::new().survivor(SurvivorRule::MostComplete).dry_run(),
+        )
+        .await
+        .unwrap();
+
+    for outcome in outcomes {
+        println!(
+            "{:?} absorbs {} profile(s), keeping {:?} for their cards",
+            outcome.plan.survivor.id,
+            outcome.plan.duplicates.len(),
+            outcome.plan.duplicates_with_cards(),
+        );
+    }
+};
+```
+ */
+
+use crate::api::cards::ListCardsQueryBuilder;
+use crate::api::customers::{CustomerDelete, Customers};
+use crate::api::customers::duplicates::{DuplicateSet, DuplicateStrategy};
+use crate::builder::Builder;
+use crate::errors::SquareError;
+use crate::objects::{Card, Customer};
+
+use chrono::DateTime;
+
+/// Which profile of a [DuplicateSet](DuplicateSet) is kept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SurvivorRule {
+    /// The profile created first.
+    Oldest,
+    /// The profile updated last.
+    MostRecentlyUpdated,
+    /// The profile with the most details filled in, the oldest one among equals.
+    MostComplete,
+}
+
+/// How duplicate profiles are merged.
+#[derive(Clone, Debug)]
+pub struct MergeOptions {
+    survivor: SurvivorRule,
+    dry_run: bool,
+    delete_with_cards: bool,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        MergeOptions {
+            survivor: SurvivorRule::Oldest,
+            dry_run: false,
+            delete_with_cards: false,
+        }
+    }
+}
+
+impl MergeOptions {
+    /// Options keeping the oldest profile of every set, and the duplicates holding cards on file.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Which profile of every set is kept.
+    pub fn survivor(mut self, survivor: SurvivorRule) -> Self {
+        self.survivor = survivor;
+
+        self
+    }
+
+    /// Only plans the merges, without changing anything.
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+
+        self
+    }
+
+    /// Deletes duplicates holding enabled cards on file as well, which removes their cards.
+    pub fn delete_with_cards(mut self) -> Self {
+        self.delete_with_cards = true;
+
+        self
+    }
+}
+
+/// The merge of a set of duplicate profiles into the one kept.
+#[derive(Clone, Debug)]
+pub struct MergePlan {
+    pub survivor: Customer,
+    /// The profiles merged into the survivor, oldest first.
+    pub duplicates: Vec<Customer>,
+    /// The keys the profiles matched on, as reported by the [DuplicateSet](DuplicateSet).
+    pub matched_on: Vec<String>,
+    /// The groups of the duplicates the survivor does not belong to yet.
+    pub group_ids: Vec<String>,
+    /// The enabled cards on file of the duplicates.
+    pub cards: Vec<Card>,
+}
+
+impl MergePlan {
+    /// The IDs of the duplicates holding any of the cards on file.
+    pub fn duplicates_with_cards(&self) -> Vec<&str> {
+        self.duplicates.iter()
+            .filter_map(|customer| customer.id.as_deref())
+            .filter(|id| self.cards.iter().any(|card| card.customer_id.as_deref() == Some(id)))
+            .collect()
+    }
+}
+
+/// A planned merge along with what was done to apply it.
+#[derive(Clone, Debug)]
+pub struct MergeOutcome {
+    pub plan: MergePlan,
+    /// The groups the survivor was added to.
+    pub added_group_ids: Vec<String>,
+    pub deleted_customer_ids: Vec<String>,
+    /// The duplicates left in place because they hold cards on file.
+    pub kept_customer_ids: Vec<String>,
+}
+
+impl<'a> Customers<'a> {
+    /// Finds the probable duplicates according to the given [DuplicateStrategy](DuplicateStrategy)
+    /// and plans their merges, looking up the enabled cards on file of every duplicate.
+    /// # Arguments:
+    /// * `strategy` - The matchers deciding which profiles are duplicates of each other.
+    /// * `survivor` - Which profile of every set is kept.
+    pub async fn plan_merges(self, strategy: DuplicateStrategy, survivor: SurvivorRule)
+                             -> Result<Vec<MergePlan>, SquareError> {
+        let mut plans = vec![];
+
+        for set in self.client.customers().find_duplicates(strategy).await? {
+            let mut plan = plan(set, survivor);
+
+            for customer_id in plan.duplicates.iter().filter_map(|customer| customer.id.clone()) {
+                plan.cards.extend(self.client.cards()
+                    .list(ListCardsQueryBuilder::new().customer_id(customer_id).exclude_disabled())
+                    .await?);
+            }
+
+            plans.push(plan);
+        }
+
+        Ok(plans)
+    }
+
+    /// Plans the merges of the probable duplicates and, unless it is a dry run, applies them.
+    /// # Arguments:
+    /// * `strategy` - The matchers deciding which profiles are duplicates of each other.
+    /// * `options` - Which profiles to keep and whether to change anything.
+    pub async fn merge_duplicates(self, strategy: DuplicateStrategy, options: &MergeOptions)
+                                  -> Result<Vec<MergeOutcome>, SquareError> {
+        let mut outcomes = vec![];
+
+        for plan in self.client.customers().plan_merges(strategy, options.survivor).await? {
+            let mut outcome = MergeOutcome {
+                plan,
+                added_group_ids: vec![],
+                deleted_customer_ids: vec![],
+                kept_customer_ids: vec![],
+            };
+
+            let survivor_id = match outcome.plan.survivor.id.clone() {
+                Some(survivor_id) if !options.dry_run => survivor_id,
+                _ => {
+                    outcomes.push(outcome);
+                    continue;
+                },
+            };
+
+            for group_id in outcome.plan.group_ids.iter() {
+                self.client.customers().add_group(survivor_id.clone(), group_id.clone()).await?;
+                outcome.added_group_ids.push(group_id.clone());
+            }
+
+            let with_cards = outcome.plan.duplicates_with_cards();
+            for duplicate in outcome.plan.duplicates.iter() {
+                let customer_id = match duplicate.id.clone() {
+                    Some(customer_id) => customer_id,
+                    None => continue,
+                };
+
+                if with_cards.contains(&customer_id.as_str()) && !options.delete_with_cards {
+                    outcome.kept_customer_ids.push(customer_id);
+                    continue;
+                }
+
+                let mut delete = Builder::from(CustomerDelete::default())
+                    .customer_id(customer_id.clone());
+                if let Some(version) = duplicate.version {
+                    delete = delete.version(version);
+                }
+                let delete = delete.build().await.map_err(|_| SquareError::from(None))?;
+
+                self.client.customers().delete(delete).await?;
+                outcome.deleted_customer_ids.push(customer_id);
+            }
+
+            outcomes.push(outcome);
+        }
+
+        Ok(outcomes)
+    }
+}
+
+// the merge of the set into the profile picked by the rule, without the cards of the duplicates
+fn plan(set: DuplicateSet, rule: SurvivorRule) -> MergePlan {
+    let DuplicateSet { mut customers, matched_on } = set;
+
+    let index = match rule {
+        SurvivorRule::Oldest => 0,
+        // parsed, as timestamps in other offsets or with fractional seconds do not compare
+        // chronologically as text
+        SurvivorRule::MostRecentlyUpdated => first_max_by_key(&customers, |customer| {
+            customer.updated_at.as_deref()
+                .and_then(|updated_at| DateTime::parse_from_rfc3339(updated_at).ok())
+        }),
+        SurvivorRule::MostComplete => first_max_by_key(&customers, completeness),
+    };
+    let survivor = customers.remove(index);
+
+    let mut group_ids: Vec<String> = vec![];
+    for group_id in customers.iter().flat_map(|customer| customer.group_ids.iter().flatten()) {
+        let joined = survivor.group_ids.iter().flatten().any(|id| id == group_id);
+        if !joined && !group_ids.contains(group_id) {
+            group_ids.push(group_id.clone());
+        }
+    }
+
+    MergePlan {
+        survivor,
+        duplicates: customers,
+        matched_on,
+        group_ids,
+        cards: vec![],
+    }
+}
+
+// the index of the first customer with the greatest key
+fn first_max_by_key<K: Ord, F: Fn(&Customer) -> K>(customers: &[Customer], key: F) -> usize {
+    let mut best = 0;
+    for index in 1..customers.len() {
+        if key(&customers[index]) > key(&customers[best]) {
+            best = index;
+        }
+    }
+
+    best
+}
+
+// the number of details filled in on the profile
+fn completeness(customer: &Customer) -> usize {
+    [
+        customer.given_name.is_some(),
+        customer.family_name.is_some(),
+        customer.company_name.is_some(),
+        customer.nickname.is_some(),
+        customer.email_address.is_some(),
+        customer.phone_number.is_some(),
+        customer.address.is_some(),
+        customer.birthday.is_some(),
+        customer.reference_id.is_some(),
+        customer.note.is_some(),
+    ].iter().filter(|set| **set).count()
+}
+
+#[cfg(test)]
+mod test_merge {
+    use super::*;
+
+    fn set() -> DuplicateSet {
+        let customers: Vec<Customer> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "a",
+                "email_address": "john.doe@example.com",
+                "updated_at": "2022-01-01T00:00:00Z",
+                "group_ids": ["regulars"],
+            },
+            {
+                "id": "b",
+                "given_name": "John",
+                "family_name": "Doe",
+                "email_address": "john.doe@example.com",
+                "updated_at": "2022-03-01T00:00:00Z",
+                "group_ids": ["vip", "regulars"],
+            },
+            {
+                "id": "c",
+                "given_name": "John",
+                "email_address": "JOHN.DOE@example.com",
+                "updated_at": "2022-03-01T00:00:00Z",
+                "group_ids": ["vip", "newsletter"],
+            },
+        ])).unwrap();
+
+        DuplicateSet {
+            customers,
+            matched_on: vec!["email:john.doe@example.com".to_string()],
+        }
+    }
+
+    fn ids(customers: &[Customer]) -> Vec<&str> {
+        customers.iter().filter_map(|customer| customer.id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_plan() {
+        let oldest = plan(set(), SurvivorRule::Oldest);
+        assert_eq!(oldest.survivor.id.as_deref(), Some("a"));
+        assert_eq!(ids(&oldest.duplicates), vec!["b", "c"]);
+        assert_eq!(oldest.group_ids, vec!["vip".to_string(), "newsletter".to_string()]);
+        assert_eq!(oldest.matched_on, vec!["email:john.doe@example.com".to_string()]);
+
+        let updated = plan(set(), SurvivorRule::MostRecentlyUpdated);
+        assert_eq!(updated.survivor.id.as_deref(), Some("b"));
+        assert_eq!(ids(&updated.duplicates), vec!["a", "c"]);
+        assert_eq!(updated.group_ids, vec!["newsletter".to_string()]);
+
+        let complete = plan(set(), SurvivorRule::MostComplete);
+        assert_eq!(complete.survivor.id.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn test_plan_most_recently_updated() {
+        let updated_at = |timestamps: [&str; 3]| {
+            let mut set = set();
+            for (customer, timestamp) in set.customers.iter_mut().zip(timestamps) {
+                customer.updated_at = Some(timestamp.to_string());
+            }
+
+            plan(set, SurvivorRule::MostRecentlyUpdated).survivor.id
+        };
+
+        // later as text, but earlier in time
+        let offset = ["2022-03-01T00:00:00Z", "2022-03-01T01:00:00+02:00", "2022-01-01T00:00:00Z"];
+        assert_eq!(updated_at(offset), Some("a".to_string()));
+        assert_eq!(
+            updated_at(["2022-03-01T00:00:00Z", "2022-03-01T00:00:00.500Z", "not a timestamp"]),
+            Some("b".to_string()),
+        );
+    }
+
+    #[test]
+    fn test_duplicates_with_cards() {
+        let mut sut = plan(set(), SurvivorRule::Oldest);
+        assert!(sut.duplicates_with_cards().is_empty());
+
+        sut.cards = serde_json::from_value(serde_json::json!([
+            { "id": "ccof:1", "customer_id": "c" },
+            { "id": "ccof:2", "customer_id": "c" },
+        ])).unwrap();
+
+        assert_eq!(sut.duplicates_with_cards(), vec!["c"]);
+    }
+}
@@ -0,0 +1,127 @@
+/*!
+Devices functionality of the [Square API](https://developer.squareup.com).
+ */
+
+use crate::client::SquareClient;
+use crate::api::{Verb, SquareAPI};
+use crate::builder::{valid_raw_fields, Builder, ParentBuilder, RawFields, Validate};
+use crate::errors::{SquareError, ValidationError};
+use crate::objects::DeviceCode;
+use crate::response::SquareResponse;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+impl SquareClient {
+    pub fn devices(&self) -> Devices<'_> {
+        Devices {
+            client: self,
+        }
+    }
+}
+
+pub struct Devices<'a> {
+    client: &'a SquareClient,
+}
+
+impl<'a> Devices<'a> {
+    /// Creates a [DeviceCode](crate::objects::DeviceCode) a Square Terminal can be signed in to a
+    /// location with.
+    /// # Arguments:
+    /// * `body` - The code to create, built through the builder of
+    ///   [CreateDeviceCodeBody](CreateDeviceCodeBody).
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/devices-api/create-device-code)
+    pub async fn create_code(self, body: CreateDeviceCodeBody)
+                             -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Devices("/codes".to_string()),
+            Some(&body),
+            None,
+        ).await
+    }
+
+    /// Retrieves a [DeviceCode](crate::objects::DeviceCode), e.g. to see whether a device was
+    /// signed in with it.
+    /// # Arguments:
+    /// * `id` - The ID of the device code.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/devices-api/get-device-code)
+    pub async fn get_code(self, id: String) -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Devices(format!("/codes/{}", id)),
+            None::<&()>,
+            None,
+        ).await
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CreateDeviceCodeBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idempotency_key: Option<String>,
+    device_code: DeviceCode,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for CreateDeviceCodeBody {
+    fn validate(mut self) -> Result<Self, ValidationError> {
+        if self.device_code.location_id.as_ref().is_some_and(|id| !id.is_empty()) &&
+            valid_raw_fields(&mut self) {
+            self.device_code.product_type.get_or_insert_with(Default::default);
+            self.idempotency_key = Some(Uuid::new_v4().to_string());
+
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for CreateDeviceCodeBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<CreateDeviceCodeBody, T> {
+    /// The ID of the location the device will be signed in to.
+    pub fn location_id(mut self, location_id: String) -> Self {
+        self.body.device_code.location_id = Some(location_id);
+
+        self
+    }
+
+    /// The name of the device once it is signed in.
+    pub fn name(mut self, name: String) -> Self {
+        self.body.device_code.name = Some(name);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_devices {
+    use super::*;
+    use crate::objects::enums::DeviceCodeProductType;
+
+    #[tokio::test]
+    async fn test_create_device_code_body_builder() {
+        let actual = Builder::from(CreateDeviceCodeBody::default())
+            .location_id("location_id".to_string())
+            .name("Counter 1".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        assert!(actual.idempotency_key.is_some());
+        assert_eq!(actual.device_code.product_type, Some(DeviceCodeProductType::TerminalApi));
+        assert_eq!(actual.device_code.name, Some("Counter 1".to_string()));
+
+        assert!(Builder::from(CreateDeviceCodeBody::default()).build().await.is_err());
+    }
+}
@@ -0,0 +1,334 @@
+/*!
+Events functionality of the [Square API](https://developer.squareup.com).
+
+The events are the same ones delivered to webhook subscriptions, so searching them allows missed
+webhook notifications to be backfilled.
+ */
+
+use crate::client::SquareClient;
+use crate::api::{Verb, SquareAPI};
+use crate::errors::{SquareError, ValidationError};
+use crate::response::SquareResponse;
+use crate::objects::{SearchEventsFilter, SearchEventsQuery, SearchEventsSort, TimeRange};
+use crate::objects::enums::{SearchEventsSortField, SortOrder};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::builder::{valid_raw_fields, Builder, ParentBuilder, RawFields, Validate};
+
+impl SquareClient {
+    pub fn events(&self) -> Events<'_> {
+        Events {
+            client: self,
+        }
+    }
+}
+
+pub struct Events<'a> {
+    client: &'a SquareClient,
+}
+
+impl<'a> Events<'a> {
+    /// Search for [Event](crate::objects::Event)s within the past 28 days, matching the given
+    /// filters.
+    /// # Arguments:
+    /// * `search_query` - The filters and sorting of the search, built through the
+    ///   [Builder](Builder) of a [SearchEventsBody](SearchEventsBody).
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::{
+    ///     api::events::SearchEventsBody,
+    ///     builder::Builder,
+    ///     client::SquareClient,
+    /// };
+    ///
+    /// async {
+    ///     let search_query = Builder::from(SearchEventsBody::default())
+    ///         .add_event_type("payment.updated".to_string())
+    ///         .created_at_range(
+    ///             "2022-10-12T07:20:50.52Z".to_string(),
+    ///             "2022-10-13T07:20:50.52Z".to_string(),
+    ///         )
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     let events = SquareClient::new("some_token")
+    ///         .events()
+    ///         .search_events(search_query)
+    ///         .await;
+    /// };
+    /// ```
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/events-api/search-events)
+    pub async fn search_events(self, search_query: SearchEventsBody)
+                               -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Events("".to_string()),
+            Some(&search_query),
+            None,
+        ).await
+    }
+
+    /// Disables events to prevent them from being searchable. All events are disabled by default,
+    /// and must be enabled to be searchable. Disabling events for a specific time period prevents
+    /// them from being searchable, even if they are re-enabled later.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/events-api/disable-events)
+    pub async fn disable_events(self) -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::PUT,
+            SquareAPI::Events("/disable".to_string()),
+            None::<&SearchEventsBody>,
+            None,
+        ).await
+    }
+
+    /// Enables events to make them searchable. Only events that occur while in the enabled state
+    /// are searchable.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/events-api/enable-events)
+    pub async fn enable_events(self) -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::PUT,
+            SquareAPI::Events("/enable".to_string()),
+            None::<&SearchEventsBody>,
+            None,
+        ).await
+    }
+
+    /// Lists all event types that can be subscribed to, along with their metadata.
+    /// # Arguments:
+    /// * `api_version` - The API version for which to list the event types. Defaults to the
+    ///   version of the application if `None`.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/events-api/list-event-types)
+    pub async fn list_event_types(self, api_version: Option<String>)
+                                  -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Events("/types".to_string()),
+            None::<&SearchEventsBody>,
+            api_version.map(|api_version| vec![("api_version".to_string(), api_version)]),
+        ).await
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// SearchEventsBody builder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct SearchEventsBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<SearchEventsQuery>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for SearchEventsBody {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if !valid_raw_fields(&mut self) {
+            return Err(ValidationError);
+        }
+
+        match self.limit {
+            Some(limit) if !(1..=100).contains(&limit) => Err(ValidationError),
+            _ => Ok(self),
+        }
+    }
+}
+
+impl RawFields for SearchEventsBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<SearchEventsBody, T> {
+    pub fn cursor(mut self, cursor: String) -> Self {
+        self.body.cursor = Some(cursor);
+
+        self
+    }
+
+    /// The maximum number of events to return in a single page, from 1 to 100 (inclusive).
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.body.limit = Some(limit);
+
+        self
+    }
+
+    /// Only return events of the given type, e.g. `payment.updated`. Can be called repeatedly to
+    /// search for multiple event types.
+    pub fn add_event_type(mut self, event_type: String) -> Self {
+        match self.filter().event_types.as_mut() {
+            Some(event_types) => event_types.push(event_type),
+            None => self.filter().event_types = Some(vec![event_type]),
+        }
+
+        self
+    }
+
+    pub fn event_types(mut self, event_types: Vec<String>) -> Self {
+        self.filter().event_types = Some(event_types);
+
+        self
+    }
+
+    pub fn merchant_ids(mut self, merchant_ids: Vec<String>) -> Self {
+        self.filter().merchant_ids = Some(merchant_ids);
+
+        self
+    }
+
+    pub fn location_ids(mut self, location_ids: Vec<String>) -> Self {
+        self.filter().location_ids = Some(location_ids);
+
+        self
+    }
+
+    /// Only return events created within the given range, with both ends in RFC 3339 format.
+    pub fn created_at_range(mut self, start_at: String, end_at: String) -> Self {
+        self.filter().created_at = Some(TimeRange {
+            end_at: Some(end_at),
+            start_at: Some(start_at),
+        });
+
+        self
+    }
+
+    /// Only return events created within the given range, with its ends in RFC 3339 format.
+    pub fn created_at(mut self, range: TimeRange) -> Self {
+        self.filter().created_at = Some(range);
+
+        self
+    }
+
+    pub fn sort_ascending(mut self) -> Self {
+        self.query().sort = Some(SearchEventsSort {
+            field: Some(SearchEventsSortField::Default),
+            order: Some(SortOrder::Asc),
+        });
+
+        self
+    }
+
+    pub fn sort_descending(mut self) -> Self {
+        self.query().sort = Some(SearchEventsSort {
+            field: Some(SearchEventsSortField::Default),
+            order: Some(SortOrder::Desc),
+        });
+
+        self
+    }
+
+    fn query(&mut self) -> &mut SearchEventsQuery {
+        self.body.query.get_or_insert_with(Default::default)
+    }
+
+    fn filter(&mut self) -> &mut SearchEventsFilter {
+        self.query().filter.get_or_insert_with(Default::default)
+    }
+}
+
+#[cfg(test)]
+mod test_events {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_search_events_body_builder() {
+        let expected = SearchEventsBody {
+            cursor: None,
+            limit: Some(10),
+            query: Some(SearchEventsQuery {
+                filter: Some(SearchEventsFilter {
+                    event_types: Some(vec![
+                        "payment.created".to_string(),
+                        "payment.updated".to_string(),
+                    ]),
+                    merchant_ids: None,
+                    location_ids: Some(vec!["L1JC53TYHS40Z".to_string()]),
+                    created_at: Some(TimeRange {
+                        end_at: Some("2022-10-13T07:20:50.52Z".to_string()),
+                        start_at: Some("2022-10-12T07:20:50.52Z".to_string()),
+                    }),
+                }),
+                sort: Some(SearchEventsSort {
+                    field: Some(SearchEventsSortField::Default),
+                    order: Some(SortOrder::Asc),
+                }),
+            }),
+            raw_fields: Default::default(),
+        };
+
+        let actual = Builder::from(SearchEventsBody::default())
+            .limit(10)
+            .add_event_type("payment.created".to_string())
+            .add_event_type("payment.updated".to_string())
+            .location_ids(vec!["L1JC53TYHS40Z".to_string()])
+            .created_at_range(
+                "2022-10-12T07:20:50.52Z".to_string(),
+                "2022-10-13T07:20:50.52Z".to_string(),
+            )
+            .sort_ascending()
+            .build()
+            .await;
+
+        assert_eq!(format!("{:?}", expected), format!("{:?}", actual.unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_search_events_body_builder_fail() {
+        let res = Builder::from(SearchEventsBody::default())
+            .limit(101)
+            .build()
+            .await;
+
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_events() {
+        use dotenv::dotenv;
+        use std::env;
+
+        dotenv().ok();
+        let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
+        let sut = SquareClient::new(&access_token);
+
+        let input = Builder::from(SearchEventsBody::default())
+            .limit(10)
+            .sort_descending()
+            .build()
+            .await
+            .unwrap();
+
+        let res = sut.events()
+            .search_events(input)
+            .await;
+
+        assert!(res.is_ok())
+    }
+
+    #[tokio::test]
+    async fn test_list_event_types() {
+        use dotenv::dotenv;
+        use std::env;
+
+        dotenv().ok();
+        let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
+        let sut = SquareClient::new(&access_token);
+
+        let res = sut.events()
+            .list_event_types(None)
+            .await;
+
+        assert!(res.is_ok())
+    }
+}
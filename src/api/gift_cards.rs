@@ -0,0 +1,478 @@
+/*!
+Gift cards functionality of the [Square API](https://developer.squareup.com).
+
+Square reports the balance of a [GiftCard](crate::objects::GiftCard) after each of its
+[GiftCardActivity](crate::objects::GiftCardActivity)s, but not what each activity did to it.
+[ledger](GiftCards::ledger) pages through every activity of a gift card, oldest first, and
+replays them into a [GiftCardLedger](GiftCardLedger) with the change and the running balance of
+every entry. Entries whose running balance differs from the one reported by Square point at the
+activities to look into when a customer disputes the balance of their gift card.
+
+# Example: Reconstruct the balance history of a gift card
+```no_run
+use square_ox::client::SquareClient;
+
+async {
+    let ledger = SquareClient::new("some_token")
+        .gift_cards()
+        .ledger("some_gift_card_id".to_string())
+        .await
+        .unwrap();
+
+    for entry in &ledger.entries {
+        println!("{:?} {:?}: {:+} -> {}",
+                 entry.activity.created_at, entry.category, entry.amount, entry.balance);
+    }
+    println!("{} entries disagree with Square", ledger.discrepancies().count());
+};
+```
+ */
+
+use crate::client::SquareClient;
+use crate::api::{Verb, SquareAPI};
+use crate::errors::SquareError;
+use crate::response::SquareResponse;
+use crate::objects::{GiftCardActivity, Response};
+use crate::objects::enums::{Currency, GiftCardActivityType, GiftCardRedeemStatus, SortOrder};
+
+impl SquareClient {
+    pub fn gift_cards(&self) -> GiftCards<'_> {
+        GiftCards {
+            client: self,
+        }
+    }
+}
+
+pub struct GiftCards<'a> {
+    client: &'a SquareClient,
+}
+
+impl<'a> GiftCards<'a> {
+    /// Retrieves a [GiftCard](crate::objects::GiftCard) using its ID.
+    /// # Arguments:
+    /// * `gift_card_id` - The ID of the gift card to retrieve.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/gift-cards-api/retrieve-gift-card)
+    pub async fn retrieve(self, gift_card_id: String) -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::GiftCards(format!("/{}", gift_card_id)),
+            None::<&()>,
+            None,
+        ).await
+    }
+
+    /// Lists [GiftCardActivity](crate::objects::GiftCardActivity)s. By default all activities
+    /// of the seller from the last year are listed, newest first.
+    /// # Arguments:
+    /// * `parameters` - A vector of parameters created through the
+    ///   [ListGiftCardActivitiesParametersBuilder](ListGiftCardActivitiesParametersBuilder).
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/gift-card-activities-api/list-gift-card-activities)
+    pub async fn list_activities(self, parameters: Option<Vec<(String, String)>>)
+                                 -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::GiftCards("/activities".to_string()),
+            None::<&()>,
+            parameters,
+        ).await
+    }
+
+    /// Pages through all activities of a gift card and replays them into a
+    /// [GiftCardLedger](GiftCardLedger).
+    /// # Arguments:
+    /// * `gift_card_id` - The ID of the gift card whose activities to replay.
+    pub async fn ledger(self, gift_card_id: String) -> Result<GiftCardLedger, SquareError> {
+        let mut activities = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut parameters = ListGiftCardActivitiesParametersBuilder::new()
+                .gift_card_id(gift_card_id.clone())
+                .sort_ascending()
+                .limit(100);
+            if let Some(cursor) = cursor {
+                parameters = parameters.cursor(cursor);
+            }
+
+            let res = self.client.gift_cards()
+                .list_activities(Some(parameters.build().await))
+                .await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::GiftCardActivities(page) = response {
+                    activities.extend(page);
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(GiftCardLedger::replay(gift_card_id, activities));
+            }
+        }
+    }
+}
+
+/// What a [GiftCardActivity](crate::objects::GiftCardActivity) did to the gift card.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GiftCardLedgerCategory {
+    /// Money was added by activating, loading, importing or transferring to the gift card.
+    Load,
+    /// Money was spent by redeeming or transferring from the gift card.
+    Redemption,
+    /// A payment made with the gift card was refunded to it.
+    Refund,
+    /// The balance was corrected by the seller or Square.
+    Adjustment,
+    /// The gift card was blocked, unblocked or deactivated without changing its balance.
+    StatusChange,
+}
+
+impl From<&GiftCardActivityType> for GiftCardLedgerCategory {
+    fn from(activity_type: &GiftCardActivityType) -> Self {
+        match activity_type {
+            GiftCardActivityType::Activate |
+            GiftCardActivityType::Load |
+            GiftCardActivityType::Import |
+            GiftCardActivityType::TransferBalanceTo => GiftCardLedgerCategory::Load,
+            GiftCardActivityType::Redeem |
+            GiftCardActivityType::TransferBalanceFrom => GiftCardLedgerCategory::Redemption,
+            GiftCardActivityType::Refund |
+            GiftCardActivityType::UnlinkedActivityRefund => GiftCardLedgerCategory::Refund,
+            GiftCardActivityType::AdjustIncrement |
+            GiftCardActivityType::AdjustDecrement |
+            GiftCardActivityType::ClearBalance |
+            GiftCardActivityType::ImportReversal => GiftCardLedgerCategory::Adjustment,
+            GiftCardActivityType::Block |
+            GiftCardActivityType::Unblock |
+            GiftCardActivityType::Deactivate => GiftCardLedgerCategory::StatusChange,
+        }
+    }
+}
+
+/// A [GiftCardActivity](crate::objects::GiftCardActivity) along with its effect on the balance of
+/// the gift card.
+#[derive(Clone, Debug)]
+pub struct GiftCardLedgerEntry {
+    pub activity: GiftCardActivity,
+    pub category: GiftCardLedgerCategory,
+    /// The change to the balance, negative when money left the gift card.
+    pub amount: i64,
+    /// The balance after this and all earlier activities.
+    pub balance: i64,
+    /// The balance after the activity as reported by Square, if it was.
+    pub reported_balance: Option<i64>,
+}
+
+/// The balance history of a gift card, reconstructed from its activities.
+#[derive(Clone, Debug)]
+pub struct GiftCardLedger {
+    pub gift_card_id: String,
+    /// The currency of the gift card, if any activity moved money.
+    pub currency: Option<Currency>,
+    /// The activities of the gift card, oldest first.
+    pub entries: Vec<GiftCardLedgerEntry>,
+}
+
+impl GiftCardLedger {
+    /// The balance after the last activity.
+    pub fn balance(&self) -> i64 {
+        self.entries.last().map(|entry| entry.balance).unwrap_or_default()
+    }
+
+    /// The entries whose running balance differs from the balance reported by Square.
+    pub fn discrepancies(&self) -> impl Iterator<Item = &GiftCardLedgerEntry> {
+        self.entries.iter()
+            .filter(|entry| entry.reported_balance.is_some_and(|reported| reported != entry.balance))
+    }
+
+    // replays the activities in chronological order; RFC 3339 timestamps sort chronologically
+    fn replay(gift_card_id: String, mut activities: Vec<GiftCardActivity>) -> Self {
+        activities.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+        let currency = activities.iter()
+            .filter_map(|activity| activity.gift_card_balance_money.as_ref()
+                .or_else(|| activity.details().and_then(|details| details.amount_money.as_ref())))
+            .map(|money| money.currency.clone())
+            .next();
+
+        let mut balance = 0;
+        let entries = activities.into_iter()
+            .filter_map(|activity| {
+                let category = GiftCardLedgerCategory::from(activity.type_name.as_ref()?);
+                let amount = change(&activity, balance);
+                balance += amount;
+
+                Some(GiftCardLedgerEntry {
+                    reported_balance: activity.gift_card_balance_money.as_ref()
+                        .and_then(|money| money.amount),
+                    activity,
+                    category,
+                    amount,
+                    balance,
+                })
+            })
+            .collect();
+
+        GiftCardLedger {
+            gift_card_id,
+            currency,
+            entries,
+        }
+    }
+}
+
+// the change an activity made to the given balance
+fn change(activity: &GiftCardActivity, balance: i64) -> i64 {
+    let details = activity.details();
+    let amount = details
+        .and_then(|details| details.amount_money.as_ref())
+        .and_then(|money| money.amount)
+        .unwrap_or_default();
+
+    match activity.type_name {
+        Some(GiftCardActivityType::Activate) |
+        Some(GiftCardActivityType::Load) |
+        Some(GiftCardActivityType::Import) |
+        Some(GiftCardActivityType::TransferBalanceTo) |
+        Some(GiftCardActivityType::Refund) |
+        Some(GiftCardActivityType::UnlinkedActivityRefund) |
+        Some(GiftCardActivityType::AdjustIncrement) => amount,
+        // a canceled redemption gave its money back to the gift card
+        Some(GiftCardActivityType::Redeem)
+            if details.and_then(|details| details.status.as_ref())
+                == Some(&GiftCardRedeemStatus::Canceled) => 0,
+        Some(GiftCardActivityType::Redeem) |
+        Some(GiftCardActivityType::TransferBalanceFrom) |
+        Some(GiftCardActivityType::AdjustDecrement) |
+        Some(GiftCardActivityType::ImportReversal) => -amount,
+        Some(GiftCardActivityType::ClearBalance) => -balance,
+        Some(GiftCardActivityType::Block) |
+        Some(GiftCardActivityType::Unblock) |
+        Some(GiftCardActivityType::Deactivate) |
+        None => 0,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// ListGiftCardActivitiesParametersBuilder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Default)]
+pub struct ListGiftCardActivitiesParametersBuilder {
+    gift_card_id: Option<String>,
+    location_id: Option<String>,
+    begin_time: Option<String>,
+    end_time: Option<String>,
+    sort_order: Option<SortOrder>,
+    cursor: Option<String>,
+    limit: Option<i32>,
+}
+
+impl ListGiftCardActivitiesParametersBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Only list the activities of this gift card.
+    pub fn gift_card_id(mut self, gift_card_id: String) -> Self {
+        self.gift_card_id = Some(gift_card_id);
+
+        self
+    }
+
+    /// Only list the activities that took place at this location.
+    pub fn location_id(mut self, location_id: String) -> Self {
+        self.location_id = Some(location_id);
+
+        self
+    }
+
+    /// The inclusive start of the time range to list activities of, as an RFC 3339 timestamp.
+    pub fn begin_time(mut self, begin_time: String) -> Self {
+        self.begin_time = Some(begin_time);
+
+        self
+    }
+
+    /// The inclusive end of the time range to list activities of, as an RFC 3339 timestamp.
+    pub fn end_time(mut self, end_time: String) -> Self {
+        self.end_time = Some(end_time);
+
+        self
+    }
+
+    /// The order in which the activities are listed, by the time they were created.
+    pub fn sort_ascending(mut self) -> Self {
+        self.sort_order = Some(SortOrder::Asc);
+
+        self
+    }
+
+    /// The order in which the activities are listed, by the time they were created.
+    pub fn sort_descending(mut self) -> Self {
+        self.sort_order = Some(SortOrder::Desc);
+
+        self
+    }
+
+    /// A pagination cursor returned by a previous call to this endpoint.
+    /// Provide this cursor to retrieve the next set of results for the original query.
+    pub fn cursor(mut self, cursor: String) -> Self {
+        self.cursor = Some(cursor);
+
+        self
+    }
+
+    /// The maximum number of results to be returned in a single page, up to 100.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+
+        self
+    }
+
+    pub async fn build(self) -> Vec<(String, String)> {
+        let ListGiftCardActivitiesParametersBuilder {
+            gift_card_id,
+            location_id,
+            begin_time,
+            end_time,
+            sort_order,
+            cursor,
+            limit,
+        } = self;
+
+        let mut res = vec![];
+
+        if let Some(gift_card_id) = gift_card_id {
+            res.push(("gift_card_id".to_string(), gift_card_id))
+        }
+        if let Some(location_id) = location_id {
+            res.push(("location_id".to_string(), location_id))
+        }
+        if let Some(begin_time) = begin_time {
+            res.push(("begin_time".to_string(), begin_time))
+        }
+        if let Some(end_time) = end_time {
+            res.push(("end_time".to_string(), end_time))
+        }
+        if let Some(sort_order) = sort_order {
+            res.push(("sort_order".to_string(), sort_order.to_string()))
+        }
+        if let Some(cursor) = cursor {
+            res.push(("cursor".to_string(), cursor))
+        }
+        if let Some(limit) = limit {
+            res.push(("limit".to_string(), limit.to_string()))
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod test_gift_cards {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_gift_card_activities_parameters_builder() {
+        let expected = vec![
+            ("gift_card_id".to_string(), "some_gift_card_id".to_string()),
+            ("sort_order".to_string(), "ASC".to_string()),
+            ("limit".to_string(), "100".to_string()),
+        ];
+
+        let actual = ListGiftCardActivitiesParametersBuilder::new()
+            .limit(100)
+            .sort_ascending()
+            .gift_card_id("some_gift_card_id".to_string())
+            .build()
+            .await;
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn test_replay_ledger() {
+        let activities: Vec<GiftCardActivity> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "redeem",
+                "type": "REDEEM",
+                "created_at": "2022-03-02T10:00:00Z",
+                "gift_card_balance_money": { "amount": 3500, "currency": "USD" },
+                "redeem_activity_details": {
+                    "amount_money": { "amount": 1500, "currency": "USD" },
+                    "status": "COMPLETED",
+                },
+            },
+            {
+                "id": "activate",
+                "type": "ACTIVATE",
+                "created_at": "2022-03-01T10:00:00Z",
+                "gift_card_balance_money": { "amount": 5000, "currency": "USD" },
+                "activate_activity_details": {
+                    "amount_money": { "amount": 5000, "currency": "USD" },
+                    "order_id": "some_order_id",
+                },
+            },
+            {
+                "id": "canceled_redeem",
+                "type": "REDEEM",
+                "created_at": "2022-03-03T10:00:00Z",
+                "gift_card_balance_money": { "amount": 3500, "currency": "USD" },
+                "redeem_activity_details": {
+                    "amount_money": { "amount": 1000, "currency": "USD" },
+                    "status": "CANCELED",
+                },
+            },
+            {
+                "id": "refund",
+                "type": "REFUND",
+                "created_at": "2022-03-04T10:00:00Z",
+                "gift_card_balance_money": { "amount": 4500, "currency": "USD" },
+                "refund_activity_details": {
+                    "amount_money": { "amount": 500, "currency": "USD" },
+                    "redeem_activity_id": "redeem",
+                },
+            },
+            {
+                "id": "block",
+                "type": "BLOCK",
+                "created_at": "2022-03-05T10:00:00Z",
+                "gift_card_balance_money": { "amount": 4000, "currency": "USD" },
+                "block_activity_details": { "reason": "CHARGEBACK_BLOCK" },
+            },
+            {
+                "id": "clear",
+                "type": "CLEAR_BALANCE",
+                "created_at": "2022-03-06T10:00:00Z",
+                "gift_card_balance_money": { "amount": 0, "currency": "USD" },
+                "clear_balance_activity_details": { "reason": "SUSPICIOUS_ACTIVITY" },
+            },
+        ])).unwrap();
+
+        let actual = GiftCardLedger::replay("some_gift_card_id".to_string(), activities);
+
+        assert_eq!(
+            actual.entries.iter()
+                .map(|entry| (entry.activity.id.clone().unwrap(), entry.category, entry.amount, entry.balance))
+                .collect::<Vec<(String, GiftCardLedgerCategory, i64, i64)>>(),
+            vec![
+                ("activate".to_string(), GiftCardLedgerCategory::Load, 5000, 5000),
+                ("redeem".to_string(), GiftCardLedgerCategory::Redemption, -1500, 3500),
+                ("canceled_redeem".to_string(), GiftCardLedgerCategory::Redemption, 0, 3500),
+                ("refund".to_string(), GiftCardLedgerCategory::Refund, 500, 4000),
+                ("block".to_string(), GiftCardLedgerCategory::StatusChange, 0, 4000),
+                ("clear".to_string(), GiftCardLedgerCategory::Adjustment, -4000, 0),
+            ],
+        );
+        assert_eq!(actual.balance(), 0);
+        assert_eq!(actual.currency, Some(Currency::USD));
+        assert_eq!(
+            actual.discrepancies()
+                .map(|entry| entry.activity.id.as_deref().unwrap())
+                .collect::<Vec<&str>>(),
+            vec!["refund"],
+        );
+    }
+}
@@ -6,13 +6,16 @@ use crate::client::SquareClient;
 use crate::api::{Verb, SquareAPI};
 use crate::errors::{InventoryChangeBodyBuildError, SquareError, ValidationError};
 use crate::response::SquareResponse;
-use crate::objects::{CatalogObject, InventoryChange, InventoryPhysicalCount,
-                     InventoryTransfer};
+use crate::objects::{CatalogObject, InventoryAdjustment, InventoryChange, InventoryCount,
+                     InventoryPhysicalCount, InventoryTransfer, Response};
 use crate::objects::enums::{InventoryChangeType, InventoryState};
 
+use chrono::{SecondsFormat, Utc};
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
-use crate::builder::{AddField, Builder, ParentBuilder, Validate};
+use crate::builder::{valid_raw_fields, AddField, Builder, ParentBuilder, RawFields, Validate};
 
 
 impl SquareClient {
@@ -51,8 +54,52 @@ pub struct Inventory<'a> {
 
 impl<'a> Inventory<'a> {
 
-    /// Applies adjustments and counts to the provided item quantities.
+    /// Applies up to 100 physical counts, adjustments and transfers to the provided item
+    /// quantities in one call. The changes are validated when the body is built: each needs an
+    /// RFC 3339 `occurred_at` time, and adjustments have to move between states Square allows.
     /// [Open in API Reference](https://developer.squareup.com/reference/square/inventory/batch-change-inventory)
+    ///
+    /// # Example: Count the stock of a variation and write off what broke
+    /// ```no_run
+    /// use square_ox::{
+    ///     api::inventory::InventoryChangeBody,
+    ///     builder::Builder,
+    ///     client::SquareClient,
+    ///     objects::{InventoryAdjustment, InventoryPhysicalCount},
+    ///     objects::enums::InventoryState,
+    /// };
+    ///
+    /// async {
+    ///     let body = Builder::from(InventoryChangeBody::default())
+    ///         .physical_count(InventoryPhysicalCount {
+    ///             id: None,
+    ///             catalog_object_id: "some_variation_id".to_string(),
+    ///             catalog_object_type: None,
+    ///             created_at: None,
+    ///             location_id: "some_loc_id".to_string(),
+    ///             occurred_at: "2022-07-09T12:25:34Z".to_string(),
+    ///             quantity: "30".to_string(),
+    ///             reference_id: None,
+    ///             source: None,
+    ///             state: InventoryState::InStock,
+    ///             team_member_id: None,
+    ///         })
+    ///         .adjustment(InventoryAdjustment {
+    ///             catalog_object_id: "some_variation_id".to_string(),
+    ///             location_id: "some_loc_id".to_string(),
+    ///             from_state: Some(InventoryState::InStock),
+    ///             to_state: Some(InventoryState::Waste),
+    ///             occurred_at: Some("2022-07-09T13:00:00Z".to_string()),
+    ///             quantity: Some("2".to_string()),
+    ///             ..Default::default()
+    ///         })
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     let res = SquareClient::new("some_token").inventory().batch_change(body).await;
+    /// };
+    /// ```
     pub async fn batch_change(self, body: InventoryChangeBody)
                                 -> Result<SquareResponse, SquareError>{
         self.client.request(
@@ -84,44 +131,204 @@ impl<'a> Inventory<'a> {
     /// Returns the [InventoryAdjustment](InventoryAdjustment) object with the provided adjustment_id.
     /// [Open in API Reference](https://developer.squareup.com/reference/square/inventory/retrieve-inventory-adjustment)
     pub async fn retrieve_adjustment(self, adjustment_id: String)
-                                -> Result<SquareResponse, SquareError>{
-        self.client.request(
+                                -> Result<InventoryAdjustment, SquareError>{
+        let res = self.client.request(
             Verb::GET,
             SquareAPI::Inventory(format!("/adjustments/{}", adjustment_id)),
             None::<&CatalogObject>,
             None,
-        ).await
+        ).await?;
+
+        res.into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::Adjustment(adjustment) => Some(adjustment),
+                _ => None,
+            })
+            .ok_or_else(|| SquareError::from(None))
     }
 
     /// Returns the [InventoryTransfer](InventoryTransfer) object with the provided `transfer_id`.
     /// [Open in API Reference](https://developer.squareup.com/reference/square/inventory/retrieve-inventory-transfer)
     pub async fn retrieve_transfer(self, transfer_id: String)
-                                -> Result<SquareResponse, SquareError>{
-        self.client.request(
+                                -> Result<InventoryTransfer, SquareError>{
+        let res = self.client.request(
             Verb::GET,
-            SquareAPI::Inventory(format!("/transfer/{}", transfer_id)),
+            SquareAPI::Inventory(format!("/transfers/{}", transfer_id)),
             None::<&CatalogObject>,
             None,
-        ).await
+        ).await?;
+
+        res.into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::Transfer(transfer) => Some(transfer),
+                _ => None,
+            })
+            .ok_or_else(|| SquareError::from(None))
     }
 
     /// Returns the [InventoryPhysicalCount](InventoryPhysicalCount) object with the provided `physical_count_id`.
     /// [Open in API Reference](https://developer.squareup.com/reference/square/inventory/retrieve-inventory-physical-count)
     pub async fn retrieve_physical_count(self, physical_count_id: String)
-                                -> Result<SquareResponse, SquareError>{
-        self.client.request(
+                                -> Result<InventoryPhysicalCount, SquareError>{
+        let res = self.client.request(
             Verb::GET,
             SquareAPI::Inventory(format!("/physical-counts/{}", physical_count_id)),
             None::<&CatalogObject>,
             None,
-        ).await
+        ).await?;
+
+        res.into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::Count(physical_count) => Some(physical_count),
+                _ => None,
+            })
+            .ok_or_else(|| SquareError::from(None))
+    }
+
+    /// Returns the change of the given type and ID as an [InventoryChange](InventoryChange), such
+    /// as one referenced by a webhook, to look into how a count came about.
+    /// # Arguments:
+    /// * `change_type` - Whether the ID is that of a physical count, adjustment or transfer.
+    /// * `change_id` - The ID of the change.
+    pub async fn retrieve_change(self, change_type: InventoryChangeType, change_id: String)
+                                 -> Result<InventoryChange, SquareError> {
+        let inventory = self.client.inventory();
+        let mut change = InventoryChange::default();
+
+        match change_type {
+            InventoryChangeType::PhysicalCount => {
+                change.physical_count = Some(inventory.retrieve_physical_count(change_id).await?);
+            },
+            InventoryChangeType::Adjustment => {
+                change.adjustment = Some(inventory.retrieve_adjustment(change_id).await?);
+            },
+            InventoryChangeType::Transfer => {
+                change.transfer = Some(inventory.retrieve_transfer(change_id).await?);
+            },
+        }
+        change.inventory_change_type = change_type;
+
+        Ok(change)
+    }
+
+    /// Transfers stock of an item variation that is `IN_STOCK` at one location to another,
+    /// after checking that the source location holds enough of it.
+    /// # Arguments:
+    /// * `variation_id` - The ID of the [CatalogItemVariation](crate::objects::CatalogItemVariation)
+    ///   to transfer.
+    /// * `quantity` - The positive, decimal quantity to transfer, such as `"3"` or `"1.5"`.
+    /// * `from_location_id` - The ID of the location the stock is taken from.
+    /// * `to_location_id` - The ID of the location the stock is moved to.
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::client::SquareClient;
+    ///
+    /// async {
+    ///     let transfer = SquareClient::new("some_token")
+    ///         .inventory()
+    ///         .transfer(
+    ///             "some_variation_id".to_string(),
+    ///             "3".to_string(),
+    ///             "some_loc_id".to_string(),
+    ///             "some_other_loc_id".to_string(),
+    ///         )
+    ///         .await;
+    /// };
+    /// ```
+    pub async fn transfer(self, variation_id: String, quantity: String, from_location_id: String,
+                          to_location_id: String) -> Result<InventoryTransferResult, SquareError> {
+        let requested = transfer_quantity(&quantity, &from_location_id, &to_location_id)?;
+
+        let counts = counts_of(
+            self.client.inventory()
+                .retrieve_count(variation_id.clone(), Some(from_location_id.clone()))
+                .await?
+        );
+        let available = in_stock(&counts, &from_location_id);
+
+        if available < requested {
+            return Err(SquareError::invalid_request(
+                "INSUFFICIENT_INVENTORY",
+                "quantity",
+                format!("only {} of {} are in stock at {}", available, variation_id, from_location_id),
+            ));
+        }
+
+        let transfer = InventoryTransfer {
+            id: None,
+            catalog_object_id: variation_id,
+            catalog_object_type: None,
+            created_at: None,
+            employee_id: None,
+            from_location_id,
+            occurred_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            quantity,
+            reference_id: None,
+            source: None,
+            state: InventoryState::InStock,
+            team_member_id: None,
+            to_location_id,
+        };
+
+        let body = Builder::from(InventoryChangeBody::default())
+            .change(InventoryChange {
+                transfer: Some(transfer.clone()),
+                inventory_change_type: InventoryChangeType::Transfer,
+                ..Default::default()
+            })
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+
+        let counts = counts_of(self.client.inventory().batch_change(body).await?);
+
+        Ok(InventoryTransferResult { transfer, counts })
     }
 
     /// Returns current counts for the provided [CatalogObject](CatalogObject)s at the requested
-    /// [Location](Location)s.
-    /// [Open in API Reference](https://developer.squareup.com/reference/square/inventory/retrieve-inventory-physical-count)
-    pub async fn batch_retrieve_counts(self, body: BatchRetrieveCounts)
-                                -> Result<SquareResponse, SquareError>{
+    /// [Location](crate::objects::Location)s, following the cursor through every page.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/inventory/batch-retrieve-inventory-counts)
+    /// # Arguments:
+    /// * `catalog_object_ids` - The IDs of the item variations to count.
+    /// * `location_ids` - The IDs of the locations to count them at.
+    /// * `updated_after` - Only returns the counts that changed after this RFC 3339 time, if
+    ///   given.
+    pub async fn batch_retrieve_counts(self, catalog_object_ids: Vec<String>,
+                                       location_ids: Vec<String>, updated_after: Option<String>)
+                                       -> Result<Vec<InventoryCount>, SquareError> {
+        let mut counts = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut body = Builder::from(BatchRetrieveCounts::default())
+                .object_ids(catalog_object_ids.clone())
+                .location_ids(location_ids.clone());
+            if let Some(updated_after) = updated_after.clone() {
+                body = body.updated_after(updated_after);
+            }
+            if let Some(cursor) = cursor {
+                body = body.cursor(cursor);
+            }
+            let body = body.build().await.map_err(|_| SquareError::from(None))?;
+
+            let res = self.client.inventory().batch_retrieve_counts_page(body).await?;
+            cursor = res.cursor.clone();
+            counts.extend(counts_of(res));
+
+            if cursor.is_none() {
+                return Ok(counts);
+            }
+        }
+    }
+
+    /// Returns a single page of the current counts matching the body.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/inventory/batch-retrieve-inventory-counts)
+    pub async fn batch_retrieve_counts_page(self, body: BatchRetrieveCounts)
+                                            -> Result<SquareResponse, SquareError> {
         self.client.request(
             Verb::POST,
             SquareAPI::Inventory("/counts/batch-retrieve".to_string()),
@@ -129,6 +336,117 @@ impl<'a> Inventory<'a> {
             None,
         ).await
     }
+
+    /// Returns the history of physical counts and adjustments matching the body, oldest first,
+    /// following the cursor through every page.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/inventory/batch-retrieve-inventory-changes)
+    ///
+    /// # Example: Find what was written off last week
+    /// ```no_run
+    /// use square_ox::{
+    ///     api::inventory::BatchRetrieveChanges,
+    ///     builder::Builder,
+    ///     client::SquareClient,
+    ///     objects::enums::{InventoryChangeType, InventoryState},
+    /// };
+    ///
+    /// async {
+    ///     let body = Builder::from(BatchRetrieveChanges::default())
+    ///         .add_location_id("some_loc_id".to_string())
+    ///         .add_type(InventoryChangeType::Adjustment)
+    ///         .add_state(InventoryState::Waste)
+    ///         .updated_after("2022-07-01T00:00:00Z".to_string())
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     let changes = SquareClient::new("some_token")
+    ///         .inventory()
+    ///         .batch_retrieve_changes(body)
+    ///         .await
+    ///         .unwrap();
+    /// };
+    /// ```
+    pub async fn batch_retrieve_changes(self, mut body: BatchRetrieveChanges)
+                                        -> Result<Vec<InventoryChange>, SquareError> {
+        let mut changes = vec![];
+
+        loop {
+            let res = self.client.inventory().batch_retrieve_changes_page(body.clone()).await?;
+            body.cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::Changes(page) = response {
+                    changes.extend(page);
+                }
+            }
+
+            if body.cursor.is_none() {
+                return Ok(changes);
+            }
+        }
+    }
+
+    /// Returns a single page of the physical counts and adjustments matching the body.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/inventory/batch-retrieve-inventory-changes)
+    pub async fn batch_retrieve_changes_page(self, body: BatchRetrieveChanges)
+                                             -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Inventory("/changes/batch-retrieve".to_string()),
+            Some(&body),
+            None,
+        ).await
+    }
+}
+
+/// The outcome of an [Inventory::transfer](Inventory::transfer).
+#[derive(Clone, Debug)]
+pub struct InventoryTransferResult {
+    /// The transfer as it was submitted.
+    pub transfer: InventoryTransfer,
+    /// The counts of the variation that changed through the transfer.
+    pub counts: Vec<InventoryCount>,
+}
+
+// the quantity to transfer, if it is positive and moves stock between two different locations
+fn transfer_quantity(quantity: &str, from_location_id: &str, to_location_id: &str)
+                     -> Result<f64, SquareError> {
+    if from_location_id == to_location_id {
+        return Err(SquareError::invalid_request(
+            "INVALID_VALUE",
+            "to_location_id",
+            "stock has to be transferred to a different location".to_string(),
+        ));
+    }
+
+    match quantity.parse::<f64>() {
+        Ok(quantity) if quantity > 0.0 => Ok(quantity),
+        _ => Err(SquareError::invalid_request(
+            "INVALID_VALUE",
+            "quantity",
+            format!("{} is not a positive quantity", quantity),
+        )),
+    }
+}
+
+// the quantity in stock at the location, according to the counts
+fn in_stock(counts: &[InventoryCount], location_id: &str) -> f64 {
+    counts.iter()
+        .filter(|count| count.location_id.as_deref() == Some(location_id))
+        .filter(|count| matches!(count.state, Some(InventoryState::InStock)))
+        .filter_map(|count| count.quantity.as_ref()?.parse::<f64>().ok())
+        .sum()
+}
+
+fn counts_of(response: SquareResponse) -> Vec<InventoryCount> {
+    response.into_responses()
+        .into_iter()
+        .find_map(|response| match response {
+            Response::Counts(counts) => Some(counts),
+            _ => None,
+        })
+        .unwrap_or_default()
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -139,17 +457,34 @@ pub struct InventoryChangeBody {
     idempotency_key: Option<String>,
     changes: Vec<InventoryChange>,
     ignore_unchanged_counts: Option<bool>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
+// the maximum number of changes in a single batch change request
+const CHANGES_PER_BATCH: usize = 100;
+
 impl Validate for InventoryChangeBody {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.changes.len() > 0 {
-            self.idempotency_key = Some(Uuid::new_v4().to_string());
-
-            Ok(self)
-        } else {
-            Err(ValidationError)
+        if self.changes.is_empty() ||
+            self.changes.len() > CHANGES_PER_BATCH ||
+            !valid_raw_fields(&mut self) {
+            return Err(ValidationError);
         }
+
+        // changes added directly have not been validated yet
+        self.changes = self.changes.into_iter()
+            .map(Validate::validate)
+            .collect::<Result<Vec<InventoryChange>, ValidationError>>()?;
+        self.idempotency_key = Some(Uuid::new_v4().to_string());
+
+        Ok(self)
+    }
+}
+
+impl RawFields for InventoryChangeBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
     }
 }
 
@@ -159,6 +494,41 @@ impl<T: ParentBuilder> Builder<InventoryChangeBody, T> {
 
         self
     }
+
+    /// Adds a change setting the quantity of an item variation in a state at a location.
+    pub fn physical_count(self, physical_count: InventoryPhysicalCount) -> Self {
+        self.change(InventoryChange {
+            physical_count: Some(physical_count),
+            inventory_change_type: InventoryChangeType::PhysicalCount,
+            ..Default::default()
+        })
+    }
+
+    /// Adds a change moving a quantity of an item variation from one state to another, such as
+    /// from `IN_STOCK` to `WASTE`.
+    pub fn adjustment(self, adjustment: InventoryAdjustment) -> Self {
+        self.change(InventoryChange {
+            adjustment: Some(adjustment),
+            inventory_change_type: InventoryChangeType::Adjustment,
+            ..Default::default()
+        })
+    }
+
+    /// Adds a change moving a quantity of an item variation from one location to another.
+    pub fn transfer(self, transfer: InventoryTransfer) -> Self {
+        self.change(InventoryChange {
+            transfer: Some(transfer),
+            inventory_change_type: InventoryChangeType::Transfer,
+            ..Default::default()
+        })
+    }
+
+    /// Whether physical counts that match the current count are left out of the history.
+    pub fn ignore_unchanged_counts(mut self, ignore_unchanged_counts: bool) -> Self {
+        self.body.ignore_unchanged_counts = Some(ignore_unchanged_counts);
+
+        self
+    }
 }
 
 impl AddField<InventoryChange> for InventoryChangeBody {
@@ -179,11 +549,15 @@ pub struct BatchRetrieveCounts {
     location_ids: Vec<String>,
     states: Option<Vec<InventoryState>>,
     updated_after: Option<String>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for BatchRetrieveCounts {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.location_ids.len() > 0 && self.catalog_object_ids.len() > 0 {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.location_ids.len() > 0 &&
+            self.catalog_object_ids.len() > 0 &&
+            valid_raw_fields(&mut self) {
             Ok(self)
         } else {
             Err(ValidationError)
@@ -191,6 +565,12 @@ impl Validate for BatchRetrieveCounts {
     }
 }
 
+impl RawFields for BatchRetrieveCounts {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<BatchRetrieveCounts, T> {
     pub fn object_ids(mut self, ids: Vec<String>) -> Self {
         self.body.catalog_object_ids = ids;
@@ -209,6 +589,141 @@ impl<T: ParentBuilder> Builder<BatchRetrieveCounts, T> {
 
         self
     }
+
+    pub fn cursor(mut self, cursor: String) -> Self {
+        self.body.cursor = Some(cursor);
+
+        self
+    }
+
+    /// Only counts the quantities in this state, all states if none are added.
+    pub fn add_state(mut self, state: InventoryState) -> Self {
+        self.body.states.get_or_insert_with(Vec::new).push(state);
+
+        self
+    }
+
+    /// Only returns the counts that changed after this RFC 3339 time.
+    pub fn updated_after(mut self, updated_after: String) -> Self {
+        self.body.updated_after = Some(updated_after);
+
+        self
+    }
+
+    /// The number of counts per page, from 1 up to 1000.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.body.limit = Some(limit);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// BatchRetrieveChanges builder implementation
+// -------------------------------------------------------------------------------------------------
+// the most changes or counts Square returns per page
+const MAX_RETRIEVE_LIMIT: i32 = 1000;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct BatchRetrieveChanges {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    catalog_object_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    types: Option<Vec<InventoryChangeType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    states: Option<Vec<InventoryState>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<i32>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for BatchRetrieveChanges {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        // transfers are only returned as the adjustments they consist of
+        let valid_types = self.types.iter()
+            .flatten()
+            .all(|change_type| !matches!(change_type, InventoryChangeType::Transfer));
+
+        if valid_types &&
+            self.limit.is_none_or(|limit| (1..=MAX_RETRIEVE_LIMIT).contains(&limit)) &&
+            valid_raw_fields(&mut self) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for BatchRetrieveChanges {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<BatchRetrieveChanges, T> {
+    /// Only returns the changes of the item variation of this ID.
+    pub fn add_catalog_object_id(mut self, catalog_object_id: String) -> Self {
+        self.body.catalog_object_ids.get_or_insert_with(Vec::new).push(catalog_object_id);
+
+        self
+    }
+
+    /// Only returns the changes at the location of this ID.
+    pub fn add_location_id(mut self, location_id: String) -> Self {
+        self.body.location_ids.get_or_insert_with(Vec::new).push(location_id);
+
+        self
+    }
+
+    /// Only returns changes of this type, either physical counts or adjustments.
+    pub fn add_type(mut self, change_type: InventoryChangeType) -> Self {
+        self.body.types.get_or_insert_with(Vec::new).push(change_type);
+
+        self
+    }
+
+    /// Only returns the changes that set or moved quantities into this state.
+    pub fn add_state(mut self, state: InventoryState) -> Self {
+        self.body.states.get_or_insert_with(Vec::new).push(state);
+
+        self
+    }
+
+    /// Only returns the changes that occurred after this RFC 3339 time.
+    pub fn updated_after(mut self, updated_after: String) -> Self {
+        self.body.updated_after = Some(updated_after);
+
+        self
+    }
+
+    /// Only returns the changes that occurred before this RFC 3339 time.
+    pub fn updated_before(mut self, updated_before: String) -> Self {
+        self.body.updated_before = Some(updated_before);
+
+        self
+    }
+
+    pub fn cursor(mut self, cursor: String) -> Self {
+        self.body.cursor = Some(cursor);
+
+        self
+    }
+
+    /// The number of changes per page, from 1 up to 1000.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.body.limit = Some(limit);
+
+        self
+    }
 }
 
 #[cfg(test)]
@@ -238,6 +753,30 @@ mod test_inventory {
         assert!(res.is_ok())
     }
 
+    #[test]
+    fn test_transfer_quantity() {
+        assert_eq!(transfer_quantity("1.5", "L1", "L2").unwrap(), 1.5);
+        assert!(transfer_quantity("0", "L1", "L2").is_err());
+        assert!(transfer_quantity("three", "L1", "L2").is_err());
+
+        let errors = transfer_quantity("3", "L1", "L1").unwrap_err().get().unwrap();
+
+        assert_eq!(errors[0].code, "INVALID_VALUE".to_string());
+        assert_eq!(errors[0].field, Some("to_location_id".to_string()));
+    }
+
+    #[test]
+    fn test_in_stock() {
+        let counts: Vec<InventoryCount> = serde_json::from_value(serde_json::json!([
+            { "catalog_object_id": "V1", "location_id": "L1", "state": "IN_STOCK", "quantity": "7.5" },
+            { "catalog_object_id": "V1", "location_id": "L1", "state": "WASTE", "quantity": "2" },
+            { "catalog_object_id": "V1", "location_id": "L2", "state": "IN_STOCK", "quantity": "4" },
+        ])).unwrap();
+
+        assert_eq!(in_stock(&counts, "L1"), 7.5);
+        assert_eq!(in_stock(&counts, "L3"), 0.0);
+    }
+
     #[tokio::test]
     async fn test_change_body_builder() {
         let expected = InventoryChangeBody {
@@ -264,7 +803,8 @@ mod test_inventory {
                     inventory_change_type: InventoryChangeType::PhysicalCount
                 }
             ],
-            ignore_unchanged_counts: None
+            ignore_unchanged_counts: None,
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(InventoryChangeBody::default())
@@ -296,6 +836,134 @@ mod test_inventory {
         assert_eq!(format!("{:?}",expected), format!("{:?}",actual));
     }
 
+    #[tokio::test]
+    async fn test_change_body_validation() {
+        let adjustment = |from_state, to_state, occurred_at: &str| InventoryAdjustment {
+            catalog_object_id: "DUHTRFG3OEYAXG5I22XLFN23".to_string(),
+            location_id: "L1JC53TYHS40Z".to_string(),
+            from_state: Some(from_state),
+            to_state: Some(to_state),
+            occurred_at: Some(occurred_at.to_string()),
+            quantity: Some("2".to_string()),
+            ..Default::default()
+        };
+        let transfer = InventoryTransfer {
+            id: None,
+            catalog_object_id: "DUHTRFG3OEYAXG5I22XLFN23".to_string(),
+            catalog_object_type: None,
+            created_at: None,
+            employee_id: None,
+            from_location_id: "L1JC53TYHS40Z".to_string(),
+            occurred_at: "2022-07-09T12:25:34Z".to_string(),
+            quantity: "1".to_string(),
+            reference_id: None,
+            source: None,
+            state: InventoryState::InStock,
+            team_member_id: None,
+            to_location_id: "L2".to_string(),
+        };
+
+        let actual = Builder::from(InventoryChangeBody::default())
+            .adjustment(adjustment(InventoryState::InStock, InventoryState::Waste, "2022-07-09T12:25:34Z"))
+            .transfer(transfer.clone())
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(actual.changes.len(), 2);
+
+        // sold stock comes back as a return, not straight into stock
+        assert!(Builder::from(InventoryChangeBody::default())
+            .adjustment(adjustment(InventoryState::Sold, InventoryState::InStock, "2022-07-09T12:25:34Z"))
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(InventoryChangeBody::default())
+            .adjustment(adjustment(InventoryState::InStock, InventoryState::Waste, "yesterday"))
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(InventoryChangeBody::default())
+            .transfer(InventoryTransfer { to_location_id: "L1JC53TYHS40Z".to_string(), ..transfer })
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(InventoryChangeBody::default())
+            .change(InventoryChange {
+                inventory_change_type: InventoryChangeType::Adjustment,
+                ..Default::default()
+            })
+            .build()
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_retrieve_changes_builder() {
+        let actual = Builder::from(BatchRetrieveChanges::default())
+            .add_location_id("L1JC53TYHS40Z".to_string())
+            .add_type(InventoryChangeType::Adjustment)
+            .add_state(InventoryState::Waste)
+            .updated_after("2022-07-01T00:00:00Z".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(actual).unwrap(),
+            serde_json::json!({
+                "location_ids": ["L1JC53TYHS40Z"],
+                "types": ["ADJUSTMENT"],
+                "states": ["WASTE"],
+                "updated_after": "2022-07-01T00:00:00Z",
+            }),
+        );
+        assert!(Builder::from(BatchRetrieveChanges::default())
+            .add_type(InventoryChangeType::Transfer)
+            .build()
+            .await
+            .is_err());
+        assert!(Builder::from(BatchRetrieveChanges::default()).limit(0).build().await.is_err());
+    }
+
+    #[test]
+    fn test_changes_response() {
+        let response: Response = serde_json::from_value(serde_json::json!({
+            "changes": [{
+                "type": "ADJUSTMENT",
+                "adjustment": {
+                    "id": "some_adjustment_id",
+                    "catalog_object_id": "DUHTRFG3OEYAXG5I22XLFN23",
+                    "location_id": "L1JC53TYHS40Z",
+                    "from_state": "IN_STOCK",
+                    "to_state": "WASTE",
+                    "quantity": "2",
+                    "occurred_at": "2022-07-09T12:25:34Z",
+                },
+            }],
+        })).unwrap();
+
+        match response {
+            Response::Changes(changes) => assert_eq!(
+                changes[0].adjustment.as_ref().and_then(|adjustment| adjustment.to_state.clone()),
+                Some(InventoryState::Waste),
+            ),
+            _ => panic!("expected changes"),
+        }
+
+        let response: Response = serde_json::from_value(serde_json::json!({
+            "count": {
+                "id": "some_physical_count_id",
+                "catalog_object_id": "DUHTRFG3OEYAXG5I22XLFN23",
+                "location_id": "L1JC53TYHS40Z",
+                "state": "IN_STOCK",
+                "quantity": "30",
+                "occurred_at": "2022-07-09T12:25:34Z",
+            },
+        })).unwrap();
+
+        assert!(matches!(response, Response::Count(count) if count.quantity == "30"));
+    }
+
     // #[tokio::test]
     async fn test_batch_change() {
         use dotenv::dotenv;
@@ -329,7 +997,8 @@ mod test_inventory {
                     inventory_change_type: InventoryChangeType::PhysicalCount
                 }
             ],
-            ignore_unchanged_counts: None
+            ignore_unchanged_counts: None,
+            raw_fields: Default::default(),
         };
 
         let res = sut.inventory()
@@ -0,0 +1,194 @@
+/*!
+Invoices functionality of the [Square API](https://developer.squareup.com).
+
+Once an [Invoice](crate::objects::Invoice) is published Square hosts a page for it at its
+`public_url`, where the buyer can view the invoice, download its attachments and pay it.
+[download_public](Invoices::download_public) fetches that page, e.g. to check that it is live and
+take its title before linking it in an email sent by the seller instead of Square.
+
+The API offers no way to download the attachments of an invoice or a PDF of it directly, both are
+only reachable through the hosted page.
+
+# Example: Fetch the hosted page of an invoice
+```no_run
+use square_ox::{client::SquareClient, objects::Response};
+
+async {
+    let client = SquareClient::new("some_token");
+
+    let invoice = client.invoices()
+        .get("some_invoice_id".to_string())
+        .await
+        .unwrap()
+        .into_responses()
+        .into_iter()
+        .find_map(|response| match response {
+            Response::Invoice(invoice) => Some(invoice),
+            _ => None,
+        })
+        .unwrap();
+
+    let page = client.invoices().download_public(&invoice).await.unwrap();
+
+    println!("{}: {:?}", page.url, page.title);
+};
+```
+ */
+
+pub mod dunning;
+
+use crate::client::SquareClient;
+use crate::api::{Verb, SquareAPI};
+use crate::errors::SquareError;
+use crate::response::{BinaryResponse, SquareResponse};
+use crate::objects::{Invoice, InvoiceQuery};
+
+use serde::Serialize;
+
+impl SquareClient {
+    pub fn invoices(&self) -> Invoices<'_> {
+        Invoices {
+            client: self,
+        }
+    }
+}
+
+pub struct Invoices<'a> {
+    client: &'a SquareClient,
+}
+
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct SearchInvoicesBody {
+    pub query: InvoiceQuery,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// The page Square hosts for a published [Invoice](crate::objects::Invoice).
+#[derive(Clone, Debug)]
+pub struct HostedInvoicePage {
+    pub invoice_id: Option<String>,
+    /// The URL of the page, after following any redirects.
+    pub url: String,
+    /// The title of the page, if it is an HTML page that has one.
+    pub title: Option<String>,
+    pub content: BinaryResponse,
+}
+
+impl<'a> Invoices<'a> {
+    /// Retrieves an [Invoice](crate::objects::Invoice) using its ID.
+    /// # Arguments:
+    /// * `invoice_id` - The ID of the invoice to retrieve.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/invoices-api/get-invoice)
+    pub async fn get(self, invoice_id: String) -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Invoices(format!("/{}", invoice_id)),
+            None::<&()>,
+            None,
+        ).await
+    }
+
+    /// Searches for [Invoice](crate::objects::Invoice)s of a location, optionally limited to some
+    /// customers.
+    /// # Arguments:
+    /// * `body` - The query, which has to filter on exactly one location.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/invoices-api/search-invoices)
+    pub async fn search(self, body: SearchInvoicesBody) -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Invoices("/search".to_string()),
+            Some(&body),
+            None,
+        ).await
+    }
+
+    /// Fetches the page Square hosts for the invoice at its `public_url`. Fails without sending a
+    /// request if the invoice has no `public_url`, which is the case until it is published.
+    /// # Arguments:
+    /// * `invoice` - The invoice whose page to fetch.
+    pub async fn download_public(self, invoice: &Invoice)
+                                 -> Result<HostedInvoicePage, SquareError> {
+        let public_url = invoice.public_url.as_deref()
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| SquareError::invalid_request(
+                "MISSING_REQUIRED_PARAMETER",
+                "public_url",
+                "the invoice has no public URL, it has to be published first".to_string(),
+            ))?;
+
+        let content = self.client.download(public_url).await?;
+
+        Ok(HostedInvoicePage {
+            invoice_id: invoice.id.clone(),
+            url: content.url.clone(),
+            title: page_title(&content),
+            content,
+        })
+    }
+}
+
+// the text of the title element of an HTML page
+fn page_title(content: &BinaryResponse) -> Option<String> {
+    if !content.is_content_type("text/html") {
+        return None;
+    }
+
+    let html = String::from_utf8_lossy(&content.bytes);
+    // ASCII lowercasing keeps byte offsets intact, so they can be used on the original
+    let lowercase = html.to_ascii_lowercase();
+
+    let element = lowercase.find("<title")?;
+    let start = element + lowercase[element..].find('>')? + 1;
+    let end = start + lowercase[start..].find("</title")?;
+    let title = html[start..end].split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    Some(title).filter(|title| !title.is_empty())
+}
+
+#[cfg(test)]
+mod test_invoices {
+    use super::*;
+
+    fn page(content_type: Option<&str>, body: &str) -> BinaryResponse {
+        BinaryResponse {
+            url: "https://squareup.com/pay-invoice/some_invoice_id".to_string(),
+            content_type: content_type.map(|content_type| content_type.to_string()),
+            bytes: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_page_title() {
+        let actual = page_title(&page(
+            Some("text/html; charset=utf-8"),
+            "<html><head><TITLE lang=\"en\">\n  Invoice #0001 from\n  Café Rust </TITLE></head></html>",
+        ));
+
+        assert_eq!(actual, Some("Invoice #0001 from Café Rust".to_string()));
+        assert!(page_title(&page(Some("application/pdf"), "<title>PDF</title>")).is_none());
+        assert!(page_title(&page(None, "<title>Unknown</title>")).is_none());
+        assert!(page_title(&page(Some("text/html"), "<title></title>")).is_none());
+        assert!(page_title(&page(Some("text/html"), "<title>Unclosed")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_download_public_unpublished() {
+        let invoice = Invoice {
+            id: Some("some_invoice_id".to_string()),
+            ..Default::default()
+        };
+
+        let actual = SquareClient::new("some_token")
+            .invoices()
+            .download_public(&invoice)
+            .await;
+
+        let errors = actual.unwrap_err().get().unwrap();
+        assert_eq!(errors[0].field, Some("public_url".to_string()));
+    }
+}
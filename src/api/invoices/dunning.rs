@@ -0,0 +1,343 @@
+/*!
+Dunning of failed subscription invoices, built on top of the invoices and payments functionality
+of the [Square API](https://developer.squareup.com).
+
+When the automatic payment of a subscription [Invoice](Invoice) fails, Square marks the invoice as
+`FAILED` and does not try again. [dunning](Invoices::dunning) finds those invoices at the given
+locations, asks a [DunningPolicy](DunningPolicy) what to do about each one and charges the card on
+file of the invoices that are due for another attempt through [recollect](Invoices::recollect).
+
+Square keeps no record of earlier attempts, so the caller keeps count of them per invoice and
+passes the counts in, raising the count of every invoice that was attempted.
+
+# Example: Run a dunning pass
+```no_run
+use square_ox::{api::invoices::dunning::DunningPolicy, client::SquareClient};
+use std::collections::HashMap;
+
+async {
+    let mut attempts: HashMap<String, usize> = HashMap::new();
+
+    let outcomes = SquareClient::new("some_token")
+        .invoices()
+        .dunning(vec!["some_location_id".to_string()], &DunningPolicy::new(), &attempts)
+        .await
+        .unwrap();
+
+    for outcome in outcomes {
+        if let (Some(id), Some(_)) = (outcome.invoice.id.clone(), &outcome.payment) {
+            *attempts.entry(id).or_insert(0) += 1;
+        }
+    }
+};
+```
+ */
+
+use crate::api::invoices::{Invoices, SearchInvoicesBody};
+use crate::api::payment::{payment_from, PaymentRequest};
+use crate::builder::Builder;
+use crate::errors::SquareError;
+use crate::objects::{
+    Invoice, InvoiceFilter, InvoiceQuery, Money, Payment, Response,
+    enums::InvoiceStatus,
+};
+
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use std::collections::HashMap;
+
+// the days after the failure at which a payment is attempted again unless told otherwise
+const DEFAULT_RETRY_AFTER_DAYS: [i64; 3] = [1, 3, 7];
+
+/// What to do about a failed invoice.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DunningSuggestion {
+    /// Charge the card on file now.
+    RetryNow,
+    /// Charge the card on file once the given time has come.
+    RetryAt(DateTime<FixedOffset>),
+    /// Every scheduled attempt has been made, so stop charging the card on file and cancel the
+    /// subscription or follow up with the customer.
+    GiveUp,
+    /// There is no card on file to charge or nothing left to pay, so ask the customer to pay
+    /// through the `public_url` of the invoice instead.
+    ContactCustomer,
+}
+
+/// When to attempt the payment of a failed invoice again, counted from the time the invoice was
+/// last updated, which for a failed invoice is the time its payment failed.
+#[derive(Clone, Debug)]
+pub struct DunningPolicy {
+    retry_after: Vec<Duration>,
+}
+
+impl Default for DunningPolicy {
+    fn default() -> Self {
+        DunningPolicy {
+            retry_after: DEFAULT_RETRY_AFTER_DAYS.iter()
+                .map(|days| Duration::days(*days))
+                .collect(),
+        }
+    }
+}
+
+impl DunningPolicy {
+    /// A policy attempting the payment again 1, 3 and 7 days after it failed.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The days after the failure at which to attempt the payment again.
+    pub fn retry_after_days(mut self, days: Vec<u32>) -> Self {
+        let mut retry_after: Vec<Duration> = days.into_iter()
+            .map(|days| Duration::days(days as i64))
+            .collect();
+        retry_after.sort();
+        self.retry_after = retry_after;
+
+        self
+    }
+
+    /// The times at which to attempt the payment of the invoice again. Empty if the invoice has no
+    /// valid `updated_at` time.
+    pub fn schedule(&self, invoice: &Invoice) -> Vec<DateTime<FixedOffset>> {
+        let failed_at = match invoice.updated_at.as_deref()
+            .and_then(|updated_at| DateTime::parse_from_rfc3339(updated_at).ok()) {
+            Some(failed_at) => failed_at,
+            None => return vec![],
+        };
+
+        self.retry_after.iter().map(|after| failed_at + *after).collect()
+    }
+
+    /// What to do about the failed invoice at the given time.
+    /// # Arguments:
+    /// * `invoice` - The failed invoice.
+    /// * `attempts` - The number of times the payment has been attempted again so far.
+    /// * `now` - The current time.
+    pub fn suggest(&self, invoice: &Invoice, attempts: usize, now: DateTime<Utc>)
+                   -> DunningSuggestion {
+        if amount_due(invoice).is_none() {
+            return DunningSuggestion::ContactCustomer;
+        }
+
+        match self.schedule(invoice).get(attempts) {
+            Some(retry_at) if *retry_at <= now => DunningSuggestion::RetryNow,
+            Some(retry_at) => DunningSuggestion::RetryAt(*retry_at),
+            None => DunningSuggestion::GiveUp,
+        }
+    }
+}
+
+/// A failed invoice along with what was suggested and done about it in a dunning pass.
+#[derive(Debug)]
+pub struct DunningOutcome {
+    pub invoice: Invoice,
+    pub suggestion: DunningSuggestion,
+    /// The result of charging the card on file, if it was charged.
+    pub payment: Option<Result<Payment, SquareError>>,
+}
+
+impl<'a> Invoices<'a> {
+    /// Lists the subscription invoices at the given locations whose automatic payment failed.
+    /// # Arguments:
+    /// * `location_ids` - The IDs of the locations to search.
+    pub async fn failed_subscription_invoices(self, location_ids: Vec<String>)
+                                              -> Result<Vec<Invoice>, SquareError> {
+        let mut invoices = vec![];
+
+        // a search may only span a single location
+        for location_id in location_ids {
+            let mut cursor = None;
+
+            loop {
+                let body = SearchInvoicesBody {
+                    query: InvoiceQuery {
+                        filter: InvoiceFilter {
+                            location_ids: vec![location_id.clone()],
+                            customer_ids: None,
+                        },
+                        sort: None,
+                    },
+                    limit: None,
+                    cursor,
+                };
+
+                let res = self.client.invoices().search(body).await?;
+                cursor = res.cursor.clone();
+
+                for response in res.into_responses() {
+                    if let Response::Invoices(page) = response {
+                        invoices.extend(page.into_iter().filter(failed_subscription_invoice));
+                    }
+                }
+
+                if cursor.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(invoices)
+    }
+
+    /// Charges the card on file of a failed invoice for the amount still due on it, attaching the
+    /// payment to the order of the invoice. Fails without sending a request if the invoice has no
+    /// card on file or nothing is due.
+    /// # Arguments:
+    /// * `invoice` - The failed invoice to collect the payment for.
+    pub async fn recollect(self, invoice: &Invoice) -> Result<Payment, SquareError> {
+        let (card_id, due) = amount_due(invoice)
+            .ok_or_else(|| SquareError::invalid_request(
+                "INVALID_VALUE",
+                "payment_requests",
+                "the invoice has no amount due to be charged to a card on file".to_string(),
+            ))?;
+
+        let mut request = Builder::from(PaymentRequest::default())
+            .source_id(card_id)
+            .amount(due.amount.unwrap_or(0), due.currency);
+        if let Some(order_id) = invoice.order_id.clone() {
+            request = request.order_id(order_id);
+        }
+        if let Some(location_id) = invoice.location_id.clone() {
+            request = request.location_id(location_id);
+        }
+        if let Some(customer_id) = invoice.primary_recipient.as_ref()
+            .and_then(|recipient| recipient.customer_id.clone()) {
+            request = request.customer_id(customer_id);
+        }
+        let request = request.build().await.map_err(|_| SquareError::from(None))?;
+
+        self.client.payments().create(request).await.and_then(payment_from)
+    }
+
+    /// Runs a dunning pass over the failed subscription invoices at the given locations, charging
+    /// the card on file of every invoice the policy suggests to retry now.
+    /// # Arguments:
+    /// * `location_ids` - The IDs of the locations to search.
+    /// * `policy` - When to attempt the payments again.
+    /// * `attempts` - The number of attempts made so far, by invoice ID. Invoices missing from it
+    ///   have not been attempted again yet.
+    pub async fn dunning(self, location_ids: Vec<String>, policy: &DunningPolicy,
+                         attempts: &HashMap<String, usize>)
+                         -> Result<Vec<DunningOutcome>, SquareError> {
+        let now = Utc::now();
+        let mut outcomes = vec![];
+
+        for invoice in self.client.invoices().failed_subscription_invoices(location_ids).await? {
+            let made = invoice.id.as_ref()
+                .and_then(|id| attempts.get(id))
+                .copied()
+                .unwrap_or(0);
+            let suggestion = policy.suggest(&invoice, made, now);
+
+            let payment = match suggestion {
+                DunningSuggestion::RetryNow => {
+                    Some(self.client.invoices().recollect(&invoice).await)
+                },
+                _ => None,
+            };
+
+            outcomes.push(DunningOutcome { invoice, suggestion, payment });
+        }
+
+        Ok(outcomes)
+    }
+}
+
+fn failed_subscription_invoice(invoice: &Invoice) -> bool {
+    invoice.status == Some(InvoiceStatus::Failed) && invoice.subscription_id.is_some()
+}
+
+// the card on file to charge and the amount still due on the first payment request that has both
+fn amount_due(invoice: &Invoice) -> Option<(String, Money)> {
+    invoice.payment_requests.iter()
+        .flatten()
+        .find_map(|request| {
+            let card_id = request.card_id.clone()?;
+            let computed = request.computed_amount_money.as_ref()?;
+            let completed = match request.total_completed_amount_money.as_ref() {
+                Some(completed) if completed.currency != computed.currency => return None,
+                Some(completed) => completed.amount.unwrap_or(0),
+                None => 0,
+            };
+
+            let due = computed.amount.unwrap_or(0) - completed;
+
+            Some((card_id, Money { amount: Some(due), currency: computed.currency.clone() }))
+                .filter(|_| due > 0)
+        })
+}
+
+#[cfg(test)]
+mod test_dunning {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn invoice(value: serde_json::Value) -> Invoice {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn failed() -> Invoice {
+        invoice(serde_json::json!({
+            "id": "some_invoice_id",
+            "status": "FAILED",
+            "subscription_id": "some_subscription_id",
+            "updated_at": "2022-06-01T12:00:00Z",
+            "payment_requests": [
+                {
+                    "uid": "deposit",
+                    "computed_amount_money": { "amount": 500, "currency": "USD" },
+                    "total_completed_amount_money": { "amount": 500, "currency": "USD" },
+                    "card_id": "ccof:some_card",
+                },
+                {
+                    "uid": "balance",
+                    "computed_amount_money": { "amount": 2000, "currency": "USD" },
+                    "total_completed_amount_money": { "amount": 500, "currency": "USD" },
+                    "card_id": "ccof:some_card",
+                },
+            ],
+        }))
+    }
+
+    #[test]
+    fn test_amount_due() {
+        let (card_id, due) = amount_due(&failed()).unwrap();
+
+        assert_eq!(card_id, "ccof:some_card");
+        assert_eq!(due.amount, Some(1500));
+
+        let without_card = invoice(serde_json::json!({
+            "payment_requests": [{ "computed_amount_money": { "amount": 500, "currency": "USD" } }],
+        }));
+        assert!(amount_due(&without_card).is_none());
+        assert!(failed_subscription_invoice(&failed()));
+        assert!(!failed_subscription_invoice(&without_card));
+    }
+
+    #[test]
+    fn test_suggest() {
+        let sut = DunningPolicy::new().retry_after_days(vec![3, 1]);
+        let at = |day, hour| Utc.with_ymd_and_hms(2022, 6, day, hour, 0, 0).unwrap();
+
+        assert_eq!(
+            sut.suggest(&failed(), 0, at(1, 13)),
+            DunningSuggestion::RetryAt(at(2, 12).fixed_offset()),
+        );
+        assert_eq!(sut.suggest(&failed(), 0, at(2, 12)), DunningSuggestion::RetryNow);
+        assert_eq!(
+            sut.suggest(&failed(), 1, at(3, 12)),
+            DunningSuggestion::RetryAt(at(4, 12).fixed_offset()),
+        );
+        assert_eq!(sut.suggest(&failed(), 2, at(9, 12)), DunningSuggestion::GiveUp);
+
+        let mut paid = failed();
+        paid.payment_requests = None;
+        assert_eq!(sut.suggest(&paid, 0, at(9, 12)), DunningSuggestion::ContactCustomer);
+
+        let mut undated = failed();
+        undated.updated_at = None;
+        assert!(sut.schedule(&undated).is_empty());
+    }
+}
@@ -2,6 +2,8 @@
 Customers functionality of the [Square API](https://developer.squareup.com).
  */
 
+pub mod onboarding;
+
 use crate::client::SquareClient;
 use crate::api::{Verb, SquareAPI};
 use crate::errors::{SquareError, LocationBuildError, ValidationError};
@@ -14,7 +16,8 @@ use crate::objects::{
 };
 
 use serde::{Deserialize, Serialize};
-use crate::builder::{Builder, ParentBuilder, Validate};
+use serde_json::{Map, Value};
+use crate::builder::{valid_raw_fields, Builder, ParentBuilder, RawFields, Validate};
 
 impl SquareClient {
     pub fn locations(&self) -> Locations {
@@ -182,12 +185,14 @@ impl<'a> Locations<'a> {
 /// ```
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct LocationCreationWrapper {
-    location: Location
+    location: Location,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for LocationCreationWrapper {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.location.name.is_some() {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.location.name.is_some() && valid_raw_fields(&mut self) {
             Ok(self)
         } else {
             Err(ValidationError)
@@ -196,6 +201,12 @@ impl Validate for LocationCreationWrapper {
     }
 }
 
+impl RawFields for LocationCreationWrapper {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<LocationCreationWrapper, T> {
     pub fn name(mut self, name: String) -> Self {
         self.body.location.name = Some(name);
@@ -492,7 +503,8 @@ mod test_locations {
                 business_hours: None,
                 website_url: None,
                 business_email: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let res = sut.locations()
@@ -541,7 +553,8 @@ mod test_locations {
                 business_hours: None,
                 business_name: None,
                 website_url: Some("example-website.com".to_string())
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let res = sut.locations()
@@ -0,0 +1,325 @@
+/*!
+Store onboarding built on top of the locations functionality of the
+[Square API](https://developer.squareup.com).
+
+Opening a store takes a handful of calls: the location is created, its business hours are set,
+its checkout pages are configured and a Square Terminal is signed in to it.
+[onboard](Locations::onboard) makes all of them from a single
+[LocationOnboardingPlan](LocationOnboardingPlan) and reports how each step went in an
+[OnboardingReport](OnboardingReport). Only a failure to create the location stops the onboarding,
+the steps after it do not depend on each other, so each runs even if another failed and can be
+retried on its own.
+
+The logo of the location is not part of the plan, as the Square API takes none: the `logo_url` of
+a [Location](crate::objects::Location) is read only and set in the Seller Dashboard, and an image
+uploaded through [create_image](crate::api::catalog::Catalog::create_image) belongs to the catalog
+rather than the location.
+
+# Example: Open a store
+```no_run
+use square_ox::{
+    api::locations::{LocationCreationWrapper, onboarding::LocationOnboardingPlan},
+    builder::Builder,
+    client::SquareClient,
+    objects::{BusinessHours, BusinessHoursPeriod, CheckoutLocationSettings},
+};
+
+async {
+    let location = Builder::from(LocationCreationWrapper::default())
+        .name("The Foo Bar Downtown".to_string())
+        .build()
+        .await
+        .unwrap();
+
+    let plan = Builder::from(LocationOnboardingPlan::default())
+        .location(location)
+        .business_hours(BusinessHours {
+            periods: vec![BusinessHoursPeriod {
+                day_of_week: "MON".to_string(),
+                start_local_time: "09:00:00".to_string(),
+                end_local_time: "17:00:00".to_string(),
+            }],
+        })
+        .checkout_settings(CheckoutLocationSettings {
+            customer_notes_enabled: Some(true),
+            ..Default::default()
+        })
+        .device_code("Counter 1".to_string())
+        .build()
+        .await
+        .unwrap();
+
+    let report = SquareClient::new("some_token").locations().onboard(plan).await.unwrap();
+    if let Some(device_code) = report.device_code.done() {
+        println!("sign in the terminal with {:?}", device_code.code);
+    }
+};
+```
+ */
+
+use crate::api::devices::CreateDeviceCodeBody;
+use crate::api::locations::{LocationCreationWrapper, Locations};
+use crate::builder::{Builder, ParentBuilder, Validate};
+use crate::errors::{SquareError, ValidationError};
+use crate::objects::{
+    BusinessHours, CheckoutLocationSettings, DeviceCode, Location, Response,
+};
+use crate::response::SquareResponse;
+
+// -------------------------------------------------------------------------------------------------
+// LocationOnboardingPlan builder implementation
+// -------------------------------------------------------------------------------------------------
+/// The store to [onboard](Locations::onboard), built through the [Builder](Builder) of a
+/// [LocationOnboardingPlan](LocationOnboardingPlan). Only the location is required, the steps
+/// that are not set are skipped.
+#[derive(Clone, Debug, Default)]
+pub struct LocationOnboardingPlan {
+    location: Option<LocationCreationWrapper>,
+    business_hours: Option<BusinessHours>,
+    checkout_settings: Option<CheckoutLocationSettings>,
+    device_name: Option<String>,
+}
+
+impl Validate for LocationOnboardingPlan {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.location.is_some() &&
+            self.business_hours.as_ref().is_none_or(|hours| !hours.periods.is_empty()) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<LocationOnboardingPlan, T> {
+    /// The location to create.
+    pub fn location(mut self, location: LocationCreationWrapper) -> Self {
+        self.body.location = Some(location);
+
+        self
+    }
+
+    /// The business hours to set once the location is created.
+    pub fn business_hours(mut self, business_hours: BusinessHours) -> Self {
+        self.body.business_hours = Some(business_hours);
+
+        self
+    }
+
+    /// The settings of the checkout pages of the location. Only the settings that are set are
+    /// changed from the defaults Square gives a new location.
+    pub fn checkout_settings(mut self, checkout_settings: CheckoutLocationSettings) -> Self {
+        self.body.checkout_settings = Some(checkout_settings);
+
+        self
+    }
+
+    /// Creates a code to sign a Square Terminal in to the location with, under the given name.
+    pub fn device_code(mut self, device_name: String) -> Self {
+        self.body.device_name = Some(device_name);
+
+        self
+    }
+}
+
+/// How a step of [onboarding](Locations::onboard) a store went.
+#[derive(Debug)]
+pub enum OnboardingStep<T> {
+    /// The step succeeded, with the object as returned by the Square API.
+    Done(T),
+    /// The step was not part of the plan.
+    Skipped,
+    Failed(SquareError),
+}
+
+impl<T> OnboardingStep<T> {
+    /// The object the step returned, if it succeeded.
+    pub fn done(&self) -> Option<&T> {
+        match self {
+            OnboardingStep::Done(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, OnboardingStep::Failed(_))
+    }
+}
+
+/// The outcome of [onboarding](Locations::onboard) a store.
+#[derive(Debug)]
+pub struct OnboardingReport {
+    /// The location as created.
+    pub location: Location,
+    /// The business hours as set.
+    pub business_hours: OnboardingStep<BusinessHours>,
+    pub checkout_settings: OnboardingStep<CheckoutLocationSettings>,
+    pub device_code: OnboardingStep<DeviceCode>,
+}
+
+impl OnboardingReport {
+    /// Whether none of the steps failed.
+    pub fn is_complete(&self) -> bool {
+        !self.business_hours.is_failed() &&
+            !self.checkout_settings.is_failed() &&
+            !self.device_code.is_failed()
+    }
+}
+
+impl<'a> Locations<'a> {
+    /// Creates the location of the [LocationOnboardingPlan](LocationOnboardingPlan) and then
+    /// sets it up as planned, reporting how each step went. Fails only if the location could not
+    /// be created.
+    /// # Arguments:
+    /// * `plan` - The store to onboard.
+    pub async fn onboard(self, plan: LocationOnboardingPlan)
+                         -> Result<OnboardingReport, SquareError> {
+        let client = self.client;
+
+        let location = plan.location.ok_or_else(|| SquareError::from(None))?;
+        let location = step(client.locations().create(location).await, |response| {
+            match response {
+                Response::Location(location) => Some(location),
+                _ => None,
+            }
+        });
+        let location = match location {
+            OnboardingStep::Done(location) => location,
+            OnboardingStep::Failed(e) => return Err(e),
+            OnboardingStep::Skipped => return Err(SquareError::from(None)),
+        };
+        let location_id = location.id.clone().ok_or_else(|| SquareError::from(None))?;
+
+        let business_hours = match plan.business_hours {
+            Some(business_hours) => {
+                let update = LocationCreationWrapper {
+                    location: Location {
+                        business_hours: Some(business_hours),
+                        ..Default::default()
+                    },
+                    raw_fields: Default::default(),
+                };
+
+                step(client.locations().update(update, location_id.clone()).await, |response| {
+                    match response {
+                        Response::Location(location) => location.business_hours,
+                        _ => None,
+                    }
+                })
+            },
+            None => OnboardingStep::Skipped,
+        };
+
+        let checkout_settings = match plan.checkout_settings {
+            Some(checkout_settings) => step(
+                client.checkout()
+                    .update_location_settings(location_id.clone(), checkout_settings)
+                    .await,
+                |response| match response {
+                    Response::LocationSettings(settings) => Some(settings),
+                    _ => None,
+                },
+            ),
+            None => OnboardingStep::Skipped,
+        };
+
+        let device_code = match plan.device_name {
+            Some(device_name) => {
+                let body = Builder::from(CreateDeviceCodeBody::default())
+                    .location_id(location_id)
+                    .name(device_name)
+                    .build()
+                    .await
+                    .map_err(|_| SquareError::from(None));
+
+                match body {
+                    Ok(body) => step(client.devices().create_code(body).await, |response| {
+                        match response {
+                            Response::DeviceCode(device_code) => Some(device_code),
+                            _ => None,
+                        }
+                    }),
+                    Err(e) => OnboardingStep::Failed(e),
+                }
+            },
+            None => OnboardingStep::Skipped,
+        };
+
+        Ok(OnboardingReport { location, business_hours, checkout_settings, device_code })
+    }
+}
+
+// the object a step returned, a step whose response lacks it failed
+fn step<T>(res: Result<SquareResponse, SquareError>, extract: fn(Response) -> Option<T>)
+           -> OnboardingStep<T> {
+    let object = res.and_then(|res| res.into_responses()
+        .into_iter()
+        .find_map(extract)
+        .ok_or_else(|| SquareError::from(None)));
+
+    match object {
+        Ok(object) => OnboardingStep::Done(object),
+        Err(e) => OnboardingStep::Failed(e),
+    }
+}
+
+#[cfg(test)]
+mod test_onboarding {
+    use super::*;
+    use crate::objects::BusinessHoursPeriod;
+
+    #[tokio::test]
+    async fn test_location_onboarding_plan_builder() {
+        let location = Builder::from(LocationCreationWrapper::default())
+            .name("The Foo Bar Downtown".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        let actual = Builder::from(LocationOnboardingPlan::default())
+            .location(location.clone())
+            .business_hours(BusinessHours {
+                periods: vec![BusinessHoursPeriod {
+                    day_of_week: "MON".to_string(),
+                    start_local_time: "09:00:00".to_string(),
+                    end_local_time: "17:00:00".to_string(),
+                }],
+            })
+            .device_code("Counter 1".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(actual.device_name, Some("Counter 1".to_string()));
+        assert!(actual.checkout_settings.is_none());
+
+        assert!(Builder::from(LocationOnboardingPlan::default()).build().await.is_err());
+        assert!(Builder::from(LocationOnboardingPlan::default())
+            .location(location)
+            .business_hours(BusinessHours { periods: vec![] })
+            .build()
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_step() {
+        let res: SquareResponse = serde_json::from_value(serde_json::json!({
+            "device_code": { "id": "device_code_id", "code": "EBCARJ" },
+        })).unwrap();
+        let extract: fn(Response) -> Option<DeviceCode> = |response| match response {
+            Response::DeviceCode(device_code) => Some(device_code),
+            _ => None,
+        };
+
+        let actual = step(Ok(res.clone()), extract);
+        assert_eq!(actual.done().and_then(|code| code.code.clone()), Some("EBCARJ".to_string()));
+
+        // a response without the object counts as a failure
+        let other: SquareResponse = serde_json::from_value(serde_json::json!({
+            "location": { "id": "location_id" },
+        })).unwrap();
+        assert!(step(Ok(other), extract).is_failed());
+        assert!(step(Err(SquareError::from(None)), extract).is_failed());
+    }
+}
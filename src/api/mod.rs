@@ -10,12 +10,21 @@ pub mod bookings;
 pub mod locations;
 pub mod catalog;
 pub mod customers;
+pub mod devices;
 pub mod cards;
 pub mod checkout;
 pub mod inventory;
 pub mod sites;
 pub mod terminal;
 pub mod orders;
+pub mod events;
+pub mod gift_cards;
+pub mod invoices;
+pub mod oauth;
+pub mod payouts;
+pub mod refunds;
+pub mod reports;
+pub mod sandbox;
 
 use crate::client::ClientMode;
 use crate::client::SquareClient;
@@ -36,10 +45,18 @@ pub enum SquareAPI {
     Sites(String),
     Terminals(String),
     Orders(String),
+    Events(String),
+    OAuth(String),
+    Payouts(String),
+    Refunds(String),
+    GiftCards(String),
+    Invoices(String),
+    Devices(String),
 }
 
 /// All of the HTTP verbs that have been implemented and are accepted by the different
 /// [Square API](https://developer.squareup.com) endpoints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Verb {
     GET,
     POST,
@@ -48,36 +65,318 @@ pub enum Verb {
     DELETE,
 }
 
+impl SquareAPI {
+    /// The name of the API the endpoint belongs to, which is the first segment of its path, e.g.
+    /// `payments` for `payments/some_id`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SquareAPI::Payments(_) => "payments",
+            SquareAPI::Bookings(_) => "bookings",
+            SquareAPI::Locations(_) => "locations",
+            SquareAPI::Catalog(_) => "catalog",
+            SquareAPI::Customers(_) => "customers",
+            SquareAPI::Cards(_) => "cards",
+            SquareAPI::Checkout(_) => "online-checkout",
+            SquareAPI::Inventory(_) => "inventory",
+            SquareAPI::Sites(_) => "sites",
+            SquareAPI::Terminals(_) => "terminals",
+            SquareAPI::Orders(_) => "orders",
+            SquareAPI::Events(_) => "events",
+            SquareAPI::OAuth(_) => "oauth2",
+            SquareAPI::Payouts(_) => "payouts",
+            SquareAPI::Refunds(_) => "refunds",
+            SquareAPI::GiftCards(_) => "gift-cards",
+            SquareAPI::Invoices(_) => "invoices",
+            SquareAPI::Devices(_) => "devices",
+        }
+    }
+}
+
 /// Implement the Display trait for all of the endpoints we need this allows
 /// for them to be changed in the future without effecting the existing code
 /// base.
 impl fmt::Display for SquareAPI {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            SquareAPI::Payments(path) => write!(f, "payments{}", path),
-            SquareAPI::Bookings(path) => write!(f, "bookings{}", path),
-            SquareAPI::Locations(path) => write!(f, "locations{}", path),
-            SquareAPI::Catalog(path) => write!(f, "catalog{}", path),
-            SquareAPI::Customers(path) => write!(f, "customers{}", path),
-            SquareAPI::Cards(path) => write!(f, "cards{}", path),
-            SquareAPI::Checkout(path) => write!(f, "online-checkout{}", path),
-            SquareAPI::Inventory(path) => write!(f, "inventory{}", path),
-            SquareAPI::Sites(path) => write!(f, "sites{}", path),
-            SquareAPI::Terminals(path) => write!(f, "terminals{}", path),
-            SquareAPI::Orders(path) => write!(f, "orders{}", path),
-        }
+        let path = match self {
+            SquareAPI::Payments(path)
+            | SquareAPI::Bookings(path)
+            | SquareAPI::Locations(path)
+            | SquareAPI::Catalog(path)
+            | SquareAPI::Customers(path)
+            | SquareAPI::Cards(path)
+            | SquareAPI::Checkout(path)
+            | SquareAPI::Inventory(path)
+            | SquareAPI::Sites(path)
+            | SquareAPI::Terminals(path)
+            | SquareAPI::Orders(path)
+            | SquareAPI::Events(path)
+            | SquareAPI::OAuth(path)
+            | SquareAPI::Payouts(path)
+            | SquareAPI::Refunds(path)
+            | SquareAPI::GiftCards(path)
+            | SquareAPI::Invoices(path)
+            | SquareAPI::Devices(path) => path,
+        };
+
+        write!(f, "{}{}", self.name(), path)
     }
 }
 
 impl SquareClient {
     pub fn endpoint(&self, end_point: SquareAPI) -> String {
         /// The main base URL for the Square API
-        const SQUARE_PRODUCTION_BASE: &str = "https://connect.squareup.com/v2/";
-        const SQUARE_SANDBOX_BASE: &str = "https://connect.squareupsandbox.com/v2/";
+        const SQUARE_PRODUCTION_BASE: &str = "https://connect.squareup.com/";
+        const SQUARE_SANDBOX_BASE: &str = "https://connect.squareupsandbox.com/";
+
+        let base = match &self.client_mode {
+            ClientMode::Production => SQUARE_PRODUCTION_BASE.to_string(),
+            ClientMode::Sandboxed => SQUARE_SANDBOX_BASE.to_string(),
+            ClientMode::Custom(base) => format!("{}/", base.trim_end_matches('/')),
+        };
+
+        // the OAuth endpoints are not versioned through the path
+        match end_point {
+            SquareAPI::OAuth(_) => format!("{}{}", base, end_point),
+            _ => format!("{}v2/{}", base, end_point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_api {
+    use super::*;
+
+    #[test]
+    fn test_endpoint() {
+        let sut = SquareClient::new("some_token");
+        assert_eq!(
+            sut.endpoint(SquareAPI::Payments("/some_id".to_string())),
+            "https://connect.squareupsandbox.com/v2/payments/some_id",
+        );
+
+        let sut = sut.base_url("http://localhost:8080/");
+        assert_eq!(
+            sut.endpoint(SquareAPI::Payments("/some_id".to_string())),
+            "http://localhost:8080/v2/payments/some_id",
+        );
+        assert_eq!(
+            sut.endpoint(SquareAPI::OAuth("/token".to_string())),
+            "http://localhost:8080/oauth2/token",
+        );
+    }
+
+    // a server answering a single request with an empty JSON object, sending the head of the
+    // request it received back through the channel
+    fn serve_once() -> (String, std::sync::mpsc::Receiver<String>) {
+        serve_once_with(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n{}".to_vec())
+    }
+
+    // a server answering a single request with the given raw response
+    fn serve_once_with(response: Vec<u8>) -> (String, std::sync::mpsc::Receiver<String>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = vec![];
+            let mut buffer = [0; 1024];
+            while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                if read == 0 {
+                    break;
+                }
+                head.extend_from_slice(&buffer[..read]);
+            }
+            stream.write_all(&response).unwrap();
+            sender.send(String::from_utf8_lossy(&head).to_lowercase()).unwrap();
+        });
+
+        (base_url, receiver)
+    }
+
+    #[tokio::test]
+    async fn test_square_version() {
+        let (base_url, received) = serve_once();
+        let sut = SquareClient::new("some_token").base_url(&base_url);
+
+        sut.locations().list().await.unwrap();
+        let head = received.recv().unwrap();
+        assert!(head.contains(&format!("square-version: {}", crate::client::SQUARE_VERSION)));
+
+        let (base_url, received) = serve_once();
+        let sut = sut.base_url(&base_url).square_version("2022-09-21");
+
+        sut.locations().list().await.unwrap();
+        assert!(received.recv().unwrap().contains("square-version: 2022-09-21"));
+    }
+
+    #[tokio::test]
+    async fn test_builder() {
+        let (base_url, received) = serve_once();
+        let sut = SquareClient::builder()
+            .access_token("some_token")
+            .production()
+            .base_url(&base_url)
+            .square_version("2022-09-21")
+            .user_agent("some-app/1.0")
+            .build()
+            .unwrap();
+
+        sut.locations().list().await.unwrap();
+        let head = received.recv().unwrap();
+        assert!(head.contains("user-agent: some-app/1.0"));
+        assert!(head.contains("square-version: 2022-09-21"));
+        assert!(head.contains("authorization: bearer some_token"));
 
-        match self.client_mode {
-            ClientMode::Production => format!("{}{}", SQUARE_PRODUCTION_BASE, end_point),
-            ClientMode::Sandboxed => format!("{}{}", SQUARE_SANDBOX_BASE, end_point),
+        let err = SquareClient::builder().build().err().unwrap().get().unwrap();
+        assert_eq!(err[0].field.as_deref(), Some("access_token"));
+        let err = SquareClient::builder()
+            .access_token("some_token")
+            .user_agent("some\napp")
+            .build()
+            .err()
+            .unwrap()
+            .get()
+            .unwrap();
+        assert_eq!(err[0].code, "INVALID_VALUE");
+    }
+
+    #[tokio::test]
+    async fn test_response_meta() {
+        use crate::client::HeaderValue;
+        use crate::interceptor::{InterceptedRequest, InterceptedResponse, RequestInterceptor};
+        use reqwest::StatusCode;
+
+        // answers with the given status and body along with a request id
+        struct Canned(StatusCode, &'static str);
+
+        impl RequestInterceptor for Canned {
+            fn before_request(&self, _: &mut InterceptedRequest) -> Option<InterceptedResponse> {
+                let mut response = InterceptedResponse::new(self.0, self.1.to_string());
+                response.headers.insert("x-request-id", HeaderValue::from_static("some_request_id"));
+
+                Some(response)
+            }
         }
+
+        let sut = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Canned(StatusCode::OK, r#"{ "locations": [] }"#));
+
+        let res = sut.locations().list().await.unwrap();
+        assert_eq!(res.request_id(), Some("some_request_id"));
+        assert_eq!(res.meta.unwrap().status, StatusCode::OK);
+
+        let sut = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Canned(
+                StatusCode::NOT_FOUND,
+                r#"{ "errors": [{ "category": "INVALID_REQUEST_ERROR", "code": "NOT_FOUND" }] }"#,
+            ));
+
+        let err = sut.locations().retrieve("some_id".to_string()).await.unwrap_err();
+        assert_eq!(err.request_id(), Some("some_request_id"));
+        assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+        assert_eq!(err.meta().unwrap().header("x-request-id"), Some("some_request_id"));
+        assert_eq!(serde_json::to_value(&err).unwrap()[0]["code"], "NOT_FOUND");
+
+        // nothing listens on the port, so no response arrives
+        let err = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .locations()
+            .list()
+            .await
+            .unwrap_err();
+        assert!(err.meta().is_none());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn test_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(br#"{ "locations": [{ "id": "some_location_id" }] }"#).unwrap();
+        let body = encoder.finish().unwrap();
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-encoding: gzip\r\ncontent-length: {}\r\n\r\n",
+            body.len(),
+        ).into_bytes();
+        response.extend(body);
+
+        let (base_url, received) = serve_once_with(response);
+        let sut = SquareClient::new("some_token").base_url(&base_url);
+
+        let res = sut.locations().list().await.unwrap();
+        assert!(received.recv().unwrap().contains("accept-encoding: gzip"));
+        assert!(matches!(res.response, Some(crate::objects::Response::Locations(_))));
+    }
+
+    #[tokio::test]
+    async fn test_headers() {
+        use crate::client::{HeaderMap, HeaderValue};
+
+        let mut defaults = HeaderMap::new();
+        defaults.insert("x-service-name", HeaderValue::from_static("checkout"));
+        defaults.insert("x-correlation-id", HeaderValue::from_static("default"));
+        let mut headers = HeaderMap::new();
+        headers.insert("x-correlation-id", HeaderValue::from_static("per-call"));
+        headers.insert("square-version", HeaderValue::from_static("2022-09-21"));
+
+        let (base_url, received) = serve_once();
+        let sut = SquareClient::new("some_token").base_url(&base_url).default_headers(defaults);
+
+        sut.request_with_headers(Verb::GET, SquareAPI::Locations("".to_string()), None::<&()>, None, headers)
+            .await
+            .unwrap();
+        let head = received.recv().unwrap();
+
+        assert!(head.contains("x-service-name: checkout"));
+        assert!(head.contains("x-correlation-id: per-call"));
+        assert!(!head.contains("x-correlation-id: default"));
+        assert!(head.contains("square-version: 2022-09-21"));
+        assert!(head.contains("authorization: bearer some_token"));
+    }
+
+    #[tokio::test]
+    async fn test_custom_base_url_timeout() {
+        use std::time::Duration;
+
+        // a server that accepts connections but never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let _connections: Vec<_> = listener.incoming().collect();
+        });
+
+        let sut = SquareClient::new("some_token")
+            .base_url(&format!("http://{}", address))
+            .timeout(Duration::from_millis(200));
+
+        let errors = sut.locations().list().await.unwrap_err().get().unwrap();
+        assert_eq!(errors[0].code, "REQUEST_TIMEOUT");
+    }
+
+    #[tokio::test]
+    async fn test_download_timeout() {
+        use std::time::Duration;
+
+        // a server that accepts connections but never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/invoice", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let _connections: Vec<_> = listener.incoming().collect();
+        });
+
+        let sut = SquareClient::new("some_token").timeout(Duration::from_millis(200));
+
+        let errors = sut.download(&url).await.err().unwrap().get().unwrap();
+        assert_eq!(errors[0].code, "REQUEST_TIMEOUT");
+        let errors = sut.is_available(&url).await.unwrap_err().get().unwrap();
+        assert_eq!(errors[0].code, "REQUEST_TIMEOUT");
     }
 }
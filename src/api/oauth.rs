@@ -0,0 +1,638 @@
+/*!
+OAuth functionality of the [Square API](https://developer.squareup.com).
+
+Applications acting on behalf of other sellers, e.g. those listed on the Square App Marketplace,
+obtain their access tokens through these endpoints. Unlike the rest of the
+[Square API](https://developer.squareup.com) they are not authenticated through the access token
+of the [SquareClient](SquareClient), but through the credentials of the application.
+
+# Example: Obtain and use an access token
+```no_run
+use square_ox::{
+    api::oauth::ObtainTokenBody,
+    builder::Builder,
+    client::SquareClient,
+};
+
+async {
+    let body = Builder::from(ObtainTokenBody::default())
+        .client_id("some_client_id".to_string())
+        .client_secret("some_client_secret".to_string())
+        .authorization_code("some_authorization_code".to_string())
+        .build()
+        .await
+        .unwrap();
+
+    let res = SquareClient::new("")
+        .oauth()
+        .obtain_token(body)
+        .await
+        .unwrap();
+
+    let seller_client = SquareClient::new(&res.access_token.unwrap());
+};
+```
+ */
+
+use crate::client::{Authorization, SquareClient};
+use crate::api::{Verb, SquareAPI};
+use crate::errors::{SquareError, ValidationError};
+use crate::response::SquareResponse;
+use crate::objects::enums::{OAuthGrantType, OAuthPermission};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::builder::{valid_raw_fields, Builder, ParentBuilder, RawFields, Validate};
+
+impl SquareClient {
+    pub fn oauth(&self) -> OAuth<'_> {
+        OAuth {
+            client: self,
+        }
+    }
+}
+
+pub struct OAuth<'a> {
+    client: &'a SquareClient,
+}
+
+impl<'a> OAuth<'a> {
+    /// Obtain an OAuth access token and refresh token, either from an authorization code or by
+    /// refreshing an existing token. The credentials of the application are sent as part of the
+    /// body, so the access token of the client is not used.
+    ///
+    /// The access token, refresh token and their expiry are held by the respective fields of the
+    /// returned [SquareResponse](SquareResponse).
+    /// # Arguments:
+    /// * `body` - The grant to obtain a token for, built through the [Builder](Builder) of an
+    ///   [ObtainTokenBody](ObtainTokenBody).
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/oauth-api/obtain-token)
+    pub async fn obtain_token(self, body: ObtainTokenBody)
+                              -> Result<SquareResponse, SquareError> {
+        self.client.request_with_authorization(
+            Verb::POST,
+            SquareAPI::OAuth("/token".to_string()),
+            Some(&body),
+            None,
+            Authorization::None,
+        ).await
+    }
+
+    /// Revoke an access token, or all access tokens of a seller, issued to the application.
+    /// # Arguments:
+    /// * `client_secret` - The secret of the application, authenticating the request.
+    /// * `body` - The token or seller to revoke the access of, built through the
+    ///   [Builder](Builder) of a [RevokeTokenBody](RevokeTokenBody).
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/oauth-api/revoke-token)
+    pub async fn revoke_token(self, client_secret: String, body: RevokeTokenBody)
+                              -> Result<SquareResponse, SquareError> {
+        self.client.request_with_authorization(
+            Verb::POST,
+            SquareAPI::OAuth("/revoke".to_string()),
+            Some(&body),
+            None,
+            Authorization::ClientSecret(&client_secret),
+        ).await
+    }
+
+    /// Retrieve the status of the access token of the client, i.e. its scopes, expiry and the
+    /// seller and application it belongs to.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/oauth-api/retrieve-token-status)
+    pub async fn retrieve_token_status(self) -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::OAuth("/token/status".to_string()),
+            None::<&ObtainTokenBody>,
+            None,
+        ).await
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// ObtainTokenBody builder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct ObtainTokenBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_secret: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grant_type: Option<OAuthGrantType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redirect_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code_verifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scopes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    short_lived: Option<bool>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for ObtainTokenBody {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        let grant_is_complete = match self.grant_type {
+            Some(OAuthGrantType::AuthorizationCode) => self.code.is_some()
+                && (self.client_secret.is_some() || self.code_verifier.is_some()),
+            Some(OAuthGrantType::RefreshToken) => self.refresh_token.is_some(),
+            Some(OAuthGrantType::MigrationToken) => self.client_secret.is_some(),
+            None => false,
+        };
+
+        if self.client_id.is_some() && grant_is_complete && valid_raw_fields(&mut self) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for ObtainTokenBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<ObtainTokenBody, T> {
+    pub fn client_id(mut self, client_id: String) -> Self {
+        self.body.client_id = Some(client_id);
+
+        self
+    }
+
+    /// The secret of the application. Not required when using PKCE, i.e. when a `code_verifier`
+    /// is given.
+    pub fn client_secret(mut self, client_secret: String) -> Self {
+        self.body.client_secret = Some(client_secret);
+
+        self
+    }
+
+    /// Exchange the authorization code returned to the redirect URL of the application for a
+    /// token.
+    pub fn authorization_code(mut self, code: String) -> Self {
+        self.body.grant_type = Some(OAuthGrantType::AuthorizationCode);
+        self.body.code = Some(code);
+
+        self
+    }
+
+    /// Obtain a new access token through a refresh token.
+    pub fn refresh_token(mut self, refresh_token: String) -> Self {
+        self.body.grant_type = Some(OAuthGrantType::RefreshToken);
+        self.body.refresh_token = Some(refresh_token);
+
+        self
+    }
+
+    pub fn redirect_uri(mut self, redirect_uri: String) -> Self {
+        self.body.redirect_uri = Some(redirect_uri);
+
+        self
+    }
+
+    /// The verifier matching the `code_challenge` of the authorization request when using PKCE.
+    pub fn code_verifier(mut self, code_verifier: String) -> Self {
+        self.body.code_verifier = Some(code_verifier);
+
+        self
+    }
+
+    /// Restrict a refreshed access token to the given scopes.
+    pub fn scopes(mut self, scopes: Vec<String>) -> Self {
+        self.body.scopes = Some(scopes);
+
+        self
+    }
+
+    /// Request an access token that expires after 24 hours instead of 30 days.
+    pub fn short_lived(mut self) -> Self {
+        self.body.short_lived = Some(true);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// RevokeTokenBody builder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct RevokeTokenBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    access_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merchant_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revoke_only_access_token: Option<bool>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for RevokeTokenBody {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.client_id.is_some()
+            && (self.access_token.is_some() ^ self.merchant_id.is_some())
+            && valid_raw_fields(&mut self) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for RevokeTokenBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<RevokeTokenBody, T> {
+    pub fn client_id(mut self, client_id: String) -> Self {
+        self.body.client_id = Some(client_id);
+
+        self
+    }
+
+    /// Revoke the given access token, and all other tokens of the seller it belongs to. Cannot
+    /// be combined with `merchant_id`.
+    pub fn access_token(mut self, access_token: String) -> Self {
+        self.body.access_token = Some(access_token);
+
+        self
+    }
+
+    /// Revoke all tokens of the given seller. Cannot be combined with `access_token`.
+    pub fn merchant_id(mut self, merchant_id: String) -> Self {
+        self.body.merchant_id = Some(merchant_id);
+
+        self
+    }
+
+    /// Only revoke the given access token, keeping the other tokens of the seller valid.
+    pub fn revoke_only_access_token(mut self) -> Self {
+        self.body.revoke_only_access_token = Some(true);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// AuthorizeUrlBuilder implementation
+// -------------------------------------------------------------------------------------------------
+/// Assembles the URL of the Square authorization page, to which sellers are sent to grant the
+/// application the requested permissions.
+///
+/// # Example: Build an authorization URL using PKCE
+/// ```
+/// use square_ox::{
+///     api::oauth::AuthorizeUrlBuilder,
+///     client::SquareClient,
+///     objects::enums::OAuthPermission,
+/// };
+///
+/// let authorize_url = AuthorizeUrlBuilder::new("sq0idp-client".to_string())
+///     .add_scope(OAuthPermission::PaymentsRead)
+///     .add_scope(OAuthPermission::OrdersWrite)
+///     .state("some_state".to_string())
+///     .pkce()
+///     .build(&SquareClient::new("some_token"))
+///     .unwrap();
+///
+/// // keep the verifier to exchange the authorization code for a token later on
+/// let code_verifier = authorize_url.code_verifier.unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct AuthorizeUrlBuilder {
+    client_id: String,
+    scopes: Vec<OAuthPermission>,
+    state: Option<String>,
+    redirect_uri: Option<String>,
+    locale: Option<String>,
+    session: bool,
+    pkce: bool,
+}
+
+impl AuthorizeUrlBuilder {
+    pub fn new(client_id: String) -> Self {
+        AuthorizeUrlBuilder {
+            client_id,
+            scopes: vec![],
+            state: None,
+            redirect_uri: None,
+            locale: None,
+            session: false,
+            pkce: false,
+        }
+    }
+
+    pub fn add_scope(mut self, scope: OAuthPermission) -> Self {
+        if !self.scopes.contains(&scope) {
+            self.scopes.push(scope);
+        }
+
+        self
+    }
+
+    pub fn scopes(mut self, scopes: Vec<OAuthPermission>) -> Self {
+        self.scopes = scopes;
+
+        self
+    }
+
+    /// A value that is returned unchanged to the redirect URL, used to protect against cross-site
+    /// request forgery.
+    pub fn state(mut self, state: String) -> Self {
+        self.state = Some(state);
+
+        self
+    }
+
+    /// The redirect URL of the application. Must match the one registered for the application if
+    /// given.
+    pub fn redirect_uri(mut self, redirect_uri: String) -> Self {
+        self.redirect_uri = Some(redirect_uri);
+
+        self
+    }
+
+    /// The locale of the authorization page, e.g. `en-US`.
+    pub fn locale(mut self, locale: String) -> Self {
+        self.locale = Some(locale);
+
+        self
+    }
+
+    /// Let sellers already signed in to the Square Dashboard skip signing in. By default sellers
+    /// always have to sign in, i.e. `session=false` is sent, as recommended for production
+    /// applications.
+    pub fn keep_session(mut self) -> Self {
+        self.session = true;
+
+        self
+    }
+
+    /// Use PKCE, for applications that cannot keep their client secret confidential. A code
+    /// verifier is generated and its challenge added to the URL.
+    pub fn pkce(mut self) -> Self {
+        self.pkce = true;
+
+        self
+    }
+
+    /// Build the authorization URL for the environment the given client is set to. Fails if the
+    /// [base URL](SquareClient::base_url) of the client does not make for a valid URL.
+    pub fn build(self, client: &SquareClient) -> Result<AuthorizeUrl, SquareError> {
+        let pkce = if self.pkce { Some(PkceChallenge::new()) } else { None };
+
+        let endpoint = client.endpoint(SquareAPI::OAuth("/authorize".to_string()));
+        let mut url = reqwest::Url::parse(&endpoint).map_err(|err| {
+            SquareError::invalid_request(
+                "INVALID_VALUE",
+                "base_url",
+                format!("{} is not a valid URL: {}", endpoint, err),
+            )
+        })?;
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("client_id", &self.client_id);
+            if !self.scopes.is_empty() {
+                let scopes: Vec<String> = self.scopes.iter().map(|scope| scope.to_string()).collect();
+                query.append_pair("scope", &scopes.join(" "));
+            }
+            if let Some(state) = self.state.as_ref() {
+                query.append_pair("state", state);
+            }
+            if let Some(redirect_uri) = self.redirect_uri.as_ref() {
+                query.append_pair("redirect_uri", redirect_uri);
+            }
+            if let Some(locale) = self.locale.as_ref() {
+                query.append_pair("locale", locale);
+            }
+            query.append_pair("session", &self.session.to_string());
+            if let Some(pkce) = pkce.as_ref() {
+                query.append_pair("code_challenge", &pkce.code_challenge);
+            }
+        }
+
+        Ok(AuthorizeUrl {
+            url: url.to_string(),
+            state: self.state,
+            code_verifier: pkce.map(|pkce| pkce.code_verifier),
+        })
+    }
+}
+
+/// An authorization URL built through the [AuthorizeUrlBuilder](AuthorizeUrlBuilder).
+#[derive(Clone, Debug)]
+pub struct AuthorizeUrl {
+    pub url: String,
+    /// The state added to the URL, to be compared with the one returned to the redirect URL.
+    pub state: Option<String>,
+    /// The PKCE code verifier, to be passed to [code_verifier](Builder::code_verifier) when
+    /// obtaining the token.
+    pub code_verifier: Option<String>,
+}
+
+/// A PKCE code verifier along with its `S256` code challenge.
+#[derive(Clone, Debug)]
+pub struct PkceChallenge {
+    pub code_verifier: String,
+    pub code_challenge: String,
+}
+
+impl PkceChallenge {
+    /// Generates a new random code verifier of 43 characters and its challenge.
+    pub fn new() -> Self {
+        let mut random = Uuid::new_v4().as_bytes().to_vec();
+        random.extend_from_slice(Uuid::new_v4().as_bytes());
+
+        let code_verifier = URL_SAFE_NO_PAD.encode(random);
+
+        PkceChallenge {
+            code_challenge: Self::challenge(&code_verifier),
+            code_verifier,
+        }
+    }
+
+    /// The `S256` code challenge of the given code verifier.
+    pub fn challenge(code_verifier: &str) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+    }
+
+    /// Whether the given code challenge belongs to the given code verifier.
+    pub fn verify(code_verifier: &str, code_challenge: &str) -> bool {
+        Self::challenge(code_verifier) == code_challenge
+    }
+}
+
+impl Default for PkceChallenge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test_oauth {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_obtain_token_body_builder() {
+        let expected = ObtainTokenBody {
+            client_id: Some("sq0idp-client".to_string()),
+            client_secret: Some("sq0csp-secret".to_string()),
+            grant_type: Some(OAuthGrantType::RefreshToken),
+            code: None,
+            redirect_uri: None,
+            refresh_token: Some("EQAAl-refresh".to_string()),
+            code_verifier: None,
+            scopes: None,
+            short_lived: Some(true),
+            raw_fields: Default::default(),
+        };
+
+        let actual = Builder::from(ObtainTokenBody::default())
+            .client_id("sq0idp-client".to_string())
+            .client_secret("sq0csp-secret".to_string())
+            .refresh_token("EQAAl-refresh".to_string())
+            .short_lived()
+            .build()
+            .await;
+
+        assert_eq!(format!("{:?}", expected), format!("{:?}", actual.unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_obtain_token_body_builder_fail() {
+        let res = Builder::from(ObtainTokenBody::default())
+            .client_id("sq0idp-client".to_string())
+            .authorization_code("sq0cgp-code".to_string())
+            .build()
+            .await;
+
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_token_body_builder_fail() {
+        let res = Builder::from(RevokeTokenBody::default())
+            .client_id("sq0idp-client".to_string())
+            .access_token("EAAAl-token".to_string())
+            .merchant_id("some_merchant_id".to_string())
+            .build()
+            .await;
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_authorize_url_builder() {
+        let actual = AuthorizeUrlBuilder::new("sq0idp-client".to_string())
+            .add_scope(OAuthPermission::MerchantProfileRead)
+            .add_scope(OAuthPermission::PaymentsWrite)
+            .state("82201dd8d83d23cc8a48caf52b".to_string())
+            .redirect_uri("https://example.com/callback".to_string())
+            .build(&SquareClient::new("some_token").production())
+            .unwrap();
+
+        assert_eq!(
+            "https://connect.squareup.com/oauth2/authorize?client_id=sq0idp-client\
+            &scope=MERCHANT_PROFILE_READ+PAYMENTS_WRITE&state=82201dd8d83d23cc8a48caf52b\
+            &redirect_uri=https%3A%2F%2Fexample.com%2Fcallback&session=false",
+            actual.url,
+        );
+        assert!(actual.code_verifier.is_none());
+    }
+
+    #[test]
+    fn test_authorize_url_builder_pkce() {
+        let actual = AuthorizeUrlBuilder::new("sq0idp-client".to_string())
+            .pkce()
+            .build(&SquareClient::new("some_token"))
+            .unwrap();
+
+        let code_verifier = actual.code_verifier.unwrap();
+        let code_challenge = actual.url.split("code_challenge=").nth(1).unwrap();
+
+        assert_eq!(43, code_verifier.len());
+        assert!(PkceChallenge::verify(&code_verifier, code_challenge));
+    }
+
+    #[test]
+    fn test_authorize_url_builder_invalid_base_url() {
+        let actual = AuthorizeUrlBuilder::new("sq0idp-client".to_string())
+            .build(&SquareClient::new("some_token").base_url("not a url"));
+
+        let errors = actual.unwrap_err().get().unwrap();
+        assert_eq!(errors[0].code, "INVALID_VALUE");
+        assert_eq!(errors[0].field, Some("base_url".to_string()));
+    }
+
+    #[test]
+    fn test_token_response_debug() {
+        let res: SquareResponse = serde_json::from_value(serde_json::json!({
+            "access_token": "EAAAl-token",
+            "refresh_token": "EQAAl-refresh",
+            "merchant_id": "some_merchant_id",
+        })).unwrap();
+
+        let actual = format!("{:?}", res);
+
+        assert!(!actual.contains("EAAAl-token"));
+        assert!(!actual.contains("EQAAl-refresh"));
+        assert!(actual.contains("some_merchant_id"));
+        assert_eq!(res.access_token, Some("EAAAl-token".to_string()));
+    }
+
+    #[test]
+    fn test_pkce_challenge() {
+        // the example of RFC 7636, appendix B
+        let actual = PkceChallenge::challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk");
+
+        assert_eq!("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM", actual);
+        assert!(!PkceChallenge::verify("some_other_verifier", &actual));
+    }
+
+    #[test]
+    fn test_oauth_endpoint() {
+        let sut = SquareClient::new("some_token");
+
+        assert_eq!(
+            "https://connect.squareupsandbox.com/oauth2/token",
+            sut.endpoint(SquareAPI::OAuth("/token".to_string())),
+        );
+        assert_eq!(
+            "https://connect.squareupsandbox.com/v2/events",
+            sut.endpoint(SquareAPI::Events("".to_string())),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_token_status() {
+        use dotenv::dotenv;
+        use std::env;
+
+        dotenv().ok();
+        let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
+        let sut = SquareClient::new(&access_token);
+
+        let res = sut.oauth()
+            .retrieve_token_status()
+            .await;
+
+        assert!(res.is_ok())
+    }
+}
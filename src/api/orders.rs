@@ -2,16 +2,27 @@
 Orders functionality of the [Square API](https://developer.squareup.com).
  */
 
+pub mod promo;
+
 use crate::api::{SquareAPI, Verb};
 use crate::client::SquareClient;
 use crate::errors::{SquareError, ValidationError};
-use crate::objects::{Customer, Order, OrderReward, OrderServiceCharge, SearchOrdersQuery};
-use crate::response::SquareResponse;
-use crate::builder::{Builder, ParentBuilder, Validate, BackIntoBuilder, AddField};
+use crate::api::payment::{payment_from, PaymentRequest};
+use crate::objects::{Customer, Money, Order, OrderFulfillment, OrderFulfillmentShipmentDetails,
+                     OrderLineItem, OrderLineItemDiscount, OrderLineItemTax, OrderReturn,
+                     OrderReward, OrderServiceCharge, Payment, Response, SearchOrdersQuery,
+                     enums::{OrderFulfillmentState, OrderFulfillmentType, OrderState}};
+use crate::response::{ResponseError, SquareResponse};
+use crate::builder::{Builder, ParentBuilder, Validate, BackIntoBuilder, AddField, valid_raw_fields,
+                     RawFields};
 
 use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
 
+// the maximum number of order IDs a single batch retrieval may ask for
+const ORDER_IDS_PER_BATCH: usize = 100;
+
 impl SquareClient {
     pub fn orders(&self) -> Orders {
         Orders {
@@ -62,8 +73,51 @@ impl<'a> Orders<'a> {
         ).await
     }
 
-    /// Retrieves an [Order](Order) by ID.
-    /// [Open in API Reference](https://developer.squareup.com/reference/square/orders/retrieve-order).
+    /// Retrieves several [Order](Order)s by ID, in as many requests as needed to stay within the
+    /// number of IDs Square accepts per request. Orders that do not exist are left out, the others
+    /// are returned in the order of their IDs.
+    /// # Arguments:
+    /// * `location_id` - The location of the orders, which may be omitted.
+    /// * `order_ids` - The IDs of the orders to retrieve.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/orders/batch-retrieve-orders)
+    pub async fn batch_retrieve(self, location_id: Option<String>, order_ids: Vec<String>)
+                                -> Result<Vec<Order>, SquareError> {
+        let mut orders: Vec<Order> = vec![];
+
+        for chunk in order_ids.chunks(ORDER_IDS_PER_BATCH) {
+            let body = BatchRetrieveOrdersBody {
+                location_id: location_id.clone(),
+                order_ids: chunk.to_vec(),
+            };
+
+            let res = self.client.request(
+                Verb::POST,
+                SquareAPI::Orders("/batch-retrieve".to_string()),
+                Some(&body),
+                None,
+            ).await?;
+
+            for response in res.into_responses() {
+                if let Response::Orders(page) = response {
+                    orders.extend(page);
+                }
+            }
+        }
+
+        Ok(in_order_of(orders, &order_ids))
+    }
+
+    /// Updates an open [Order](Order) by adding, replacing or clearing fields. The body holds a
+    /// sparse order with only the fields to change, the version of the order the update applies
+    /// to and the dot-notation paths of the fields to clear, e.g. `metadata.some_key` or
+    /// `line_items[some_uid].note`. The update fails if the order changed since that version.
+    /// # Arguments:
+    /// * `id` - The id of the order to update.
+    /// * `body` - The sparse order, version and fields to clear, best built with the
+    ///   [OrderUpdateBody](OrderUpdateBody) builder.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/orders/update-order).
     pub async fn update(self, id: String, body: OrderUpdateBody)
                       -> Result<SquareResponse, SquareError> {
         self.client.request(
@@ -87,6 +141,54 @@ impl<'a> Orders<'a> {
         ).await
     }
 
+    /// Pays for an [Order](Order) with one or more approved payments, i.e. payments created with
+    /// `autocomplete` set to false, completing them. An order with a total of 0 is settled by
+    /// passing no payments. Returns the paid order.
+    ///
+    /// # Arguments:
+    /// * `order_id` - The id of the order to pay for.
+    /// * `payment_ids` - The ids of the approved payments to pay the order with.
+    /// * `order_version` - The version of the order being paid for. Fails if the order has changed
+    ///   since.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/orders/pay-order)
+    pub async fn pay_with(self, order_id: String, payment_ids: Vec<String>, order_version: i64)
+                          -> Result<Order, SquareError> {
+        let body = Builder::from(PayOrderBody::default())
+            .payment_ids(payment_ids)
+            .order_version(order_version)
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+
+        order_from(self.pay(order_id, body).await?)
+    }
+
+    /// Creates a new draft [Order](Order) with the line items, discounts, taxes and service charges
+    /// of an existing one, e.g. to let a buyer repeat their last purchase. Returns the new order.
+    ///
+    /// # Arguments:
+    /// * `order_id` - The id of the order to clone.
+    /// * `version` - The version of the order to clone, the latest one if `None`. Fails if the
+    ///   order has changed since.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/orders/clone-order)
+    pub async fn clone(self, order_id: String, version: Option<i64>)
+                       -> Result<Order, SquareError> {
+        let body = CloneOrderBody {
+            idempotency_key: Uuid::new_v4().to_string(),
+            order_id,
+            version,
+        };
+
+        order_from(self.client.request(
+            Verb::POST,
+            SquareAPI::Orders("/clone".to_string()),
+            Some(&body),
+            None,
+        ).await?)
+    }
+
     /// Enables applications to preview [Order](Order) pricing without creating an order.
     /// [Open in API Reference](https://developer.squareup.com/reference/square/orders/calculate-order).
     pub async fn calculate(self, body: OrderCalculateBody)
@@ -98,6 +200,280 @@ impl<'a> Orders<'a> {
             None,
         ).await
     }
+
+    /// Previews an [Order](Order) with its totals, taxes and discounts calculated, without
+    /// creating it. Useful for showing a cart before checkout. Fails without sending a request if
+    /// the order has no location.
+    ///
+    /// # Arguments:
+    /// * `order` - The order to calculate.
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::{client::SquareClient, objects::Order};
+    ///
+    /// async {
+    ///     let order = Order {
+    ///         location_id: Some("some_location_id".to_string()),
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     let total = SquareClient::new("some_token")
+    ///         .orders()
+    ///         .preview(order)
+    ///         .await
+    ///         .map(|order| order.total_money);
+    /// };
+    /// ```
+    pub async fn preview(self, order: Order) -> Result<Order, SquareError> {
+        if order.location_id.as_deref().is_none_or(str::is_empty) {
+            return Err(SquareError::invalid_request(
+                "MISSING_REQUIRED_PARAMETER",
+                "order.location_id",
+                "an order can only be calculated for a location".to_string(),
+            ));
+        }
+
+        let body = Builder::from(OrderCalculateBody::default())
+            .order(order)
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+
+        order_from(self.calculate(body).await?)
+    }
+
+    /// Sets the carrier and tracking number of a shipment fulfillment, leaving the rest of the
+    /// [Order](Order) untouched, and returns the updated [OrderFulfillment](OrderFulfillment).
+    ///
+    /// # Arguments:
+    /// * `order_id` - The id of the order the fulfillment belongs to.
+    /// * `fulfillment_uid` - The uid of the shipment fulfillment to update.
+    /// * `carrier` - The shipping carrier, e.g. `UPS`.
+    /// * `tracking_number` - The tracking number of the shipment, as given by the carrier.
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::client::SquareClient;
+    ///
+    /// async {
+    ///     let fulfillment = SquareClient::new("some_token")
+    ///         .orders()
+    ///         .update_tracking(
+    ///             "some_order_id".to_string(),
+    ///             "some_fulfillment_uid".to_string(),
+    ///             "UPS".to_string(),
+    ///             "1Z999AA10123456784".to_string(),
+    ///         )
+    ///         .await;
+    /// };
+    /// ```
+    pub async fn update_tracking(self, order_id: String, fulfillment_uid: String, carrier: String,
+                                 tracking_number: String)
+                                 -> Result<OrderFulfillment, SquareError> {
+        let client = self.client;
+
+        let order = order_from(client.orders().retrieve(order_id.clone()).await?)?;
+        let sparse_order = tracking_update(&order, &fulfillment_uid, carrier, tracking_number)
+            .ok_or_else(|| SquareError::from(None))?;
+
+        let body = Builder::from(OrderUpdateBody::default())
+            .order(sparse_order)
+            .build()
+            .await
+            .map_err(|_| SquareError::from(None))?;
+
+        order_from(client.orders().update(order_id, body).await?)?
+            .fulfillments
+            .unwrap_or_default()
+            .into_iter()
+            .find(|fulfillment| fulfillment.uid.as_ref() == Some(&fulfillment_uid))
+            .ok_or_else(|| SquareError::from(None))
+    }
+
+    /// Moves a fulfillment of an [Order](Order) into a new state, e.g. `PREPARED` once the order
+    /// is ready for pickup, and returns the updated order. The current version of the order is
+    /// retrieved first, and if the order changes before the update lands the update is tried once
+    /// more against the newer version. A fulfillment already in the state is left as it is.
+    ///
+    /// # Arguments:
+    /// * `order_id` - The id of the order the fulfillment belongs to.
+    /// * `fulfillment_uid` - The uid of the fulfillment to update.
+    /// * `state` - The state to move the fulfillment into.
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::{client::SquareClient, objects::enums::OrderFulfillmentState};
+    ///
+    /// async {
+    ///     let order = SquareClient::new("some_token")
+    ///         .orders()
+    ///         .update_fulfillment_state(
+    ///             "some_order_id".to_string(),
+    ///             "some_fulfillment_uid".to_string(),
+    ///             OrderFulfillmentState::Prepared,
+    ///         )
+    ///         .await;
+    /// };
+    /// ```
+    pub async fn update_fulfillment_state(self, order_id: String, fulfillment_uid: String,
+                                          state: OrderFulfillmentState)
+                                          -> Result<Order, SquareError> {
+        let client = self.client;
+        let mut retried = false;
+
+        loop {
+            let order = order_from(client.orders().retrieve(order_id.clone()).await?)?;
+            let sparse_order = match fulfillment_state_update(&order, &fulfillment_uid, &state) {
+                Some(Some(sparse_order)) => sparse_order,
+                Some(None) => return Ok(order),
+                None => return Err(SquareError::invalid_request(
+                    "NOT_FOUND",
+                    "fulfillment_uid",
+                    format!("the order holds no fulfillment with the uid {}", fulfillment_uid),
+                )),
+            };
+
+            let body = Builder::from(OrderUpdateBody::default())
+                .order(sparse_order)
+                .build()
+                .await
+                .map_err(|_| SquareError::from(None))?;
+
+            match client.orders().update(order_id.clone(), body).await {
+                Ok(res) => return order_from(res),
+                Err(err) => match err.errors() {
+                    Some(errors) if !retried && version_mismatch(errors) => retried = true,
+                    _ => return Err(err),
+                },
+            }
+        }
+    }
+
+    /// Pays for an [Order](Order) with several payments, such as a check split between guests.
+    /// The amounts of the payment sources have to add up to the total of the order. Each payment
+    /// is created without being completed and the order is then paid with all of them, which
+    /// completes the payments. If creating one of the payments fails, the ones already created
+    /// are canceled, and so are all of them if paying for the order with them fails, e.g. as the
+    /// order was updated in the meantime. Either way the error is returned.
+    ///
+    /// # Arguments:
+    /// * `order_id` - The id of the order to pay for.
+    /// * `tenders` - The [PaymentSource](PaymentSource)s to charge for the order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::{
+    ///     api::orders::PaymentSource,
+    ///     client::SquareClient,
+    ///     objects::{Money, enums::Currency},
+    /// };
+    ///
+    /// async {
+    ///     let paid_order = SquareClient::new("some_token")
+    ///         .orders()
+    ///         .pay_split("some_order_id".to_string(), vec![
+    ///             PaymentSource {
+    ///                 source_id: "cnon:card-nonce-ok".to_string(),
+    ///                 amount_money: Money { amount: Some(1200), currency: Currency::USD },
+    ///                 tip_money: None,
+    ///             },
+    ///             PaymentSource {
+    ///                 source_id: "CASH".to_string(),
+    ///                 amount_money: Money { amount: Some(800), currency: Currency::USD },
+    ///                 tip_money: None,
+    ///             },
+    ///         ])
+    ///         .await;
+    /// };
+    /// ```
+    pub async fn pay_split(self, order_id: String, tenders: Vec<PaymentSource>)
+                           -> Result<PaidOrder, SquareError> {
+        let client = self.client;
+
+        let order = order_from(client.orders().retrieve(order_id.clone()).await?)?;
+        validate_split(&order, &tenders)?;
+
+        let mut payments: Vec<Payment> = vec![];
+
+        for tender in tenders {
+            let mut request = Builder::from(PaymentRequest::default())
+                .source_id(tender.source_id)
+                .amount(tender.amount_money.amount.unwrap_or(0), tender.amount_money.currency)
+                .order_id(order_id.clone())
+                .autocomplete(false);
+            if let Some(location_id) = order.location_id.clone() {
+                request = request.location_id(location_id);
+            }
+            if let Some(tip_money) = tender.tip_money {
+                request = request.tip_money(tip_money);
+            }
+
+            let created = match request.build().await {
+                Ok(request) => client.payments()
+                    .create(request)
+                    .await
+                    .and_then(payment_from),
+                Err(_) => Err(SquareError::from(None)),
+            };
+
+            match created {
+                Ok(payment) => payments.push(payment),
+                Err(err) => {
+                    cancel_payments(client, payments).await;
+
+                    return Err(err);
+                },
+            }
+        }
+
+        let mut body = Builder::from(PayOrderBody::default())
+            .payment_ids(payments.iter().filter_map(|payment| payment.id.clone()).collect());
+        if let Some(version) = order.version {
+            body = body.order_version(version);
+        }
+
+        let paid = match body.build().await {
+            Ok(body) => client.orders().pay(order_id, body).await.and_then(order_from),
+            Err(_) => Err(SquareError::from(None)),
+        };
+
+        match paid {
+            Ok(order) => Ok(PaidOrder { order, payments }),
+            Err(err) => {
+                cancel_payments(client, payments).await;
+
+                Err(err)
+            },
+        }
+    }
+}
+
+// cancels the payments created by pay_split, on a best-effort basis as the error that made it
+// give up is the one returned
+async fn cancel_payments(client: &SquareClient, payments: Vec<Payment>) {
+    for payment_id in payments.into_iter().filter_map(|payment| payment.id) {
+        let _ = client.payments().cancel(payment_id).await;
+    }
+}
+
+/// A single payment towards an [Order](Order) paid through [pay_split](Orders::pay_split).
+#[derive(Clone, Debug)]
+pub struct PaymentSource {
+    /// The ID of the source to charge, such as a card nonce, a card on file or `CASH`.
+    pub source_id: String,
+    /// The part of the order total this source pays for.
+    pub amount_money: Money,
+    /// The tip paid through this source, on top of its part of the order total.
+    pub tip_money: Option<Money>,
+}
+
+/// An [Order](Order) paid through [pay_split](Orders::pay_split), along with its payments.
+#[derive(Clone, Debug)]
+pub struct PaidOrder {
+    pub order: Order,
+    /// The payments as created, before the order completed them.
+    pub payments: Vec<Payment>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -105,11 +481,13 @@ pub struct CreateOrderBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     idempotency_key: Option<String>,
     order: Order,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for CreateOrderBody {
     fn validate(mut self) -> Result<Self, ValidationError> {
-        if self.order.location_id.is_some(){
+        if self.order.location_id.is_some() && valid_raw_fields(&mut self) {
             self.idempotency_key = Some(Uuid::new_v4().to_string());
             Ok(self)
         } else {
@@ -118,6 +496,12 @@ impl Validate for CreateOrderBody {
     }
 }
 
+impl RawFields for CreateOrderBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<CreateOrderBody, T> {
     pub fn location_id(mut self, location_id: String) -> Self {
         self.body.order.location_id = Some(location_id);
@@ -131,6 +515,22 @@ impl<T: ParentBuilder> Builder<CreateOrderBody, T> {
         self
     }
 
+    /// A client-specified ID to associate the order with an entity in another system.
+    pub fn reference_id(mut self, reference_id: String) -> Self {
+        self.body.order.reference_id = Some(reference_id);
+
+        self
+    }
+
+    pub fn add_line_item(mut self, line_item: OrderLineItem) -> Self {
+        match self.body.order.line_items.as_mut() {
+            Some(line_items) => line_items.push(line_item),
+            None => self.body.order.line_items = Some(vec![line_item]),
+        }
+
+        self
+    }
+
     pub fn add_service_charge(mut self, service_charge: OrderServiceCharge) -> Self {
         if let Some(services_charges) = self.body.order.service_charges.as_mut() {
             services_charges.push(service_charge)
@@ -140,6 +540,30 @@ impl<T: ParentBuilder> Builder<CreateOrderBody, T> {
 
         self
     }
+
+    /// Adds a discount to the order, either one from the catalog or an ad hoc amount or
+    /// percentage. A discount scoped to line items needs a `uid` the line items apply it by.
+    pub fn add_discount(mut self, discount: OrderLineItemDiscount) -> Self {
+        self.body.order.discounts.get_or_insert_with(Vec::new).push(discount);
+
+        self
+    }
+
+    /// Adds a tax to the order, either one from the catalog or an ad hoc percentage. A tax scoped
+    /// to line items needs a `uid` the line items apply it by.
+    pub fn add_tax(mut self, tax: OrderLineItemTax) -> Self {
+        self.body.order.taxes.get_or_insert_with(Vec::new).push(tax);
+
+        self
+    }
+
+    /// Adds the items returned from another order, built through the builder of
+    /// [OrderReturn](OrderReturn). Together with line items, the order is an exchange.
+    pub fn add_return(mut self, order_return: OrderReturn) -> Self {
+        self.body.order.returns.get_or_insert_with(Vec::new).push(order_return);
+
+        self
+    }
 }
 
 // implements the necessary traits to release an OrderServiceCharge builder from a CreateOrderBody
@@ -154,25 +578,55 @@ impl AddField<OrderServiceCharge> for CreateOrderBody {
     }
 }
 
+impl AddField<OrderLineItemDiscount> for CreateOrderBody {
+    fn add_field(&mut self, field: OrderLineItemDiscount) {
+        self.order.discounts.get_or_insert_with(Vec::new).push(field);
+    }
+}
+
+impl AddField<OrderLineItemTax> for CreateOrderBody {
+    fn add_field(&mut self, field: OrderLineItemTax) {
+        self.order.taxes.get_or_insert_with(Vec::new).push(field);
+    }
+}
+
+impl AddField<OrderReturn> for CreateOrderBody {
+    fn add_field(&mut self, field: OrderReturn) {
+        self.order.returns.get_or_insert_with(Vec::new).push(field);
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct SearchOrderBody {
     #[serde(skip_serializing_if = "Option::is_none")]
-    cursor: Option<String>,
+    pub(crate) cursor: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    limit: Option<i32>,
+    pub(crate) limit: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    location_ids: Option<Vec<String>>,
+    pub(crate) location_ids: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    query: Option<SearchOrdersQuery>,
+    pub(crate) query: Option<SearchOrdersQuery>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    return_entries: Option<bool>
+    pub(crate) return_entries: Option<bool>,
+    #[serde(flatten)]
+    pub(crate) raw_fields: Map<String, Value>,
 }
 
 impl Validate for SearchOrderBody {
     fn validate(mut self) -> Result<Self, ValidationError> {
         self.return_entries = Some(true);
 
-        Ok(self)
+        if valid_raw_fields(&mut self) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for SearchOrderBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
     }
 }
 
@@ -227,14 +681,47 @@ impl AddField<SearchOrdersQuery> for SearchOrderBody {
 
 #[derive(Clone, Debug, Serialize, Default)]
 pub struct OrderUpdateBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
     fields_to_clear: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     idempotency_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     order: Option<Order>,
+    // the paths of the fields set through the builder
+    #[serde(skip)]
+    touched: Vec<String>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl OrderUpdateBody {
+    /// The dot-notation paths of the fields set or cleared through the builder, in the order they
+    /// were touched. Fields of an order passed in whole are not tracked.
+    pub fn touched_paths(&self) -> Vec<String> {
+        let mut paths = self.touched.clone();
+        paths.extend(self.fields_to_clear.iter().flatten().cloned());
+
+        paths
+    }
+
+    fn touch(&mut self, path: String) -> &mut Order {
+        if !self.touched.contains(&path) {
+            self.touched.push(path);
+        }
+
+        self.order.get_or_insert_with(Default::default)
+    }
 }
 
 impl Validate for OrderUpdateBody {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.order.is_none() {
+        let versioned = self.order.as_ref().is_some_and(|order| order.version.is_some());
+        // a field cannot be set and cleared, or cleared along with a field it contains
+        let conflicting = self.fields_to_clear.iter()
+            .flatten()
+            .any(|cleared| self.touched.iter().any(|set| contains_path(cleared, set)));
+
+        if !versioned || conflicting || !valid_raw_fields(&mut self) {
             Err(ValidationError)
         } else {
             self.idempotency_key = Some(Uuid::new_v4().to_string());
@@ -244,6 +731,12 @@ impl Validate for OrderUpdateBody {
     }
 }
 
+impl RawFields for OrderUpdateBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<OrderUpdateBody, T> {
     pub fn fields_to_clear(mut self, fields: Vec<String>) -> Self {
         self.body.fields_to_clear = Some(fields);
@@ -251,11 +744,67 @@ impl<T: ParentBuilder> Builder<OrderUpdateBody, T> {
         self
     }
 
+    /// Clears the field at the dot-notation path, e.g. `metadata.some_key`.
+    pub fn clear(mut self, path: String) -> Self {
+        let fields = self.body.fields_to_clear.get_or_insert_with(Vec::new);
+        if !fields.contains(&path) {
+            fields.push(path);
+        }
+
+        self
+    }
+
     pub fn order(mut self, order: Order) -> Self {
         self.body.order = Some(order);
 
         self
     }
+
+    /// The version of the order the update applies to, which is required.
+    pub fn version(mut self, version: i64) -> Self {
+        self.body.order.get_or_insert_with(Default::default).version = Some(version);
+
+        self
+    }
+
+    pub fn customer_id(mut self, customer_id: String) -> Self {
+        self.body.touch("customer_id".to_string()).customer_id = Some(customer_id);
+
+        self
+    }
+
+    pub fn reference_id(mut self, reference_id: String) -> Self {
+        self.body.touch("reference_id".to_string()).reference_id = Some(reference_id);
+
+        self
+    }
+
+    pub fn ticket_name(mut self, ticket_name: String) -> Self {
+        self.body.touch("ticket_name".to_string()).ticket_name = Some(ticket_name);
+
+        self
+    }
+
+    pub fn state(mut self, state: OrderState) -> Self {
+        self.body.touch("state".to_string()).state = Some(state);
+
+        self
+    }
+
+    pub fn metadata(mut self, key: String, value: String) -> Self {
+        self.body.touch(format!("metadata.{}", key))
+            .metadata
+            .get_or_insert_with(Default::default)
+            .insert(key, value);
+
+        self
+    }
+}
+
+// whether the path is the same as, or a parent of, the other path
+fn contains_path(path: &str, other: &str) -> bool {
+    other.strip_prefix(path)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.') || rest.starts_with('['))
 }
 
 // implements the necessary traits to release an Order builder from a OrderUpdateBody
@@ -274,6 +823,8 @@ pub struct PayOrderBody {
     order_version: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     payment_ids: Option<Vec<String>>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for PayOrderBody {
@@ -281,7 +832,8 @@ impl Validate for PayOrderBody {
         self.idempotency_key = Some(Uuid::new_v4().to_string());
 
         if self.order_version.is_some() &&
-            self.payment_ids.is_some() {
+            self.payment_ids.is_some() &&
+            valid_raw_fields(&mut self) {
             Ok(self)
         } else {
             Err(ValidationError)
@@ -291,31 +843,54 @@ impl Validate for PayOrderBody {
     }
 }
 
+impl RawFields for PayOrderBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<PayOrderBody, T> {
-    fn oder_version(mut self, version: i64) -> Self {
+    pub fn order_version(mut self, version: i64) -> Self {
         self.body.order_version = Some(version);
 
         self
     }
 
-    fn payment_ids(mut self, ids: Vec<String>) -> Self {
+    pub fn payment_ids(mut self, ids: Vec<String>) -> Self {
         self.body.payment_ids = Some(ids);
 
         self
     }
 }
 
+#[derive(Clone, Debug, Serialize)]
+struct BatchRetrieveOrdersBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location_id: Option<String>,
+    order_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct CloneOrderBody {
+    idempotency_key: String,
+    order_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<i64>,
+}
+
 #[derive(Clone, Debug, Serialize, Default)]
 pub struct OrderCalculateBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     order: Option<Order>,
     #[serde(skip_serializing_if = "Option::is_none")]
     proposed_rewards: Option<Vec<OrderReward>>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for OrderCalculateBody {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.order.is_some() {
+        if self.order.is_some() && valid_raw_fields(&mut self) {
             Ok(self)
         } else {
             Err(ValidationError)
@@ -323,14 +898,20 @@ impl Validate for OrderCalculateBody {
     }
 }
 
+impl RawFields for OrderCalculateBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<OrderCalculateBody, T> {
-    fn order(mut self, order: Order) -> Self {
+    pub fn order(mut self, order: Order) -> Self {
         self.body.order = Some(order);
 
         self
     }
 
-    fn add_proposed_reward(mut self, reward: OrderReward) -> Self {
+    pub fn add_proposed_reward(mut self, reward: OrderReward) -> Self {
         match self.body.proposed_rewards.as_mut() {
             Some(rewards) => rewards.push(reward),
             None => self.body.proposed_rewards = Some(vec![reward])
@@ -359,6 +940,131 @@ impl AddField<OrderReward> for OrderCalculateBody {
     }
 }
 
+// the orders sorted by the position of their id among the given ids, without duplicates
+fn in_order_of(orders: Vec<Order>, order_ids: &[String]) -> Vec<Order> {
+    let mut sorted: Vec<Order> = vec![];
+
+    for order_id in order_ids {
+        if sorted.iter().any(|order| order.id.as_ref() == Some(order_id)) {
+            continue;
+        }
+        if let Some(order) = orders.iter().find(|order| order.id.as_ref() == Some(order_id)) {
+            sorted.push(order.clone());
+        }
+    }
+
+    sorted
+}
+
+// extracts the order held by a response of the orders endpoints
+pub(crate) fn order_from(response: SquareResponse) -> Result<Order, SquareError> {
+    response.into_responses()
+        .into_iter()
+        .find_map(|response| match response {
+            Response::Order(order) => Some(order),
+            _ => None,
+        })
+        .ok_or_else(|| SquareError::from(None))
+}
+
+// checks that the payment sources add up to the total of the order, in its currency
+fn validate_split(order: &Order, tenders: &[PaymentSource]) -> Result<(), SquareError> {
+    let total = order.total_money.as_ref()
+        .ok_or_else(|| SquareError::invalid_request(
+            "MISSING_REQUIRED_PARAMETER",
+            "total_money",
+            "the order has no total to split".to_string(),
+        ))?;
+
+    if tenders.is_empty() {
+        return Err(SquareError::invalid_request(
+            "MISSING_REQUIRED_PARAMETER",
+            "tenders",
+            "at least one payment source is required".to_string(),
+        ));
+    }
+
+    if tenders.iter().any(|tender| tender.amount_money.currency != total.currency
+        || tender.tip_money.as_ref().is_some_and(|tip| tip.currency != total.currency)) {
+        return Err(SquareError::invalid_request(
+            "CURRENCY_MISMATCH",
+            "amount_money",
+            format!("all payment sources have to be in {:?}", total.currency),
+        ));
+    }
+
+    let paid: i64 = tenders.iter()
+        .map(|tender| tender.amount_money.amount.unwrap_or(0))
+        .sum();
+    let expected = total.amount.unwrap_or(0);
+
+    if paid != expected {
+        return Err(SquareError::invalid_request(
+            "INVALID_VALUE",
+            "amount_money",
+            format!("the payment sources add up to {} instead of the order total of {}", paid, expected),
+        ));
+    }
+
+    Ok(())
+}
+
+// the sparse order setting the tracking information of the given shipment fulfillment, or None if
+// the order holds no such fulfillment
+fn tracking_update(order: &Order, fulfillment_uid: &str, carrier: String, tracking_number: String)
+                   -> Option<Order> {
+    order.fulfillments.as_ref()?
+        .iter()
+        .find(|fulfillment| fulfillment.uid.as_deref() == Some(fulfillment_uid))
+        .filter(|fulfillment| {
+            fulfillment.fulfillment_type == Some(OrderFulfillmentType::Shipment)
+        })?;
+
+    Some(Order {
+        location_id: order.location_id.clone(),
+        version: order.version,
+        fulfillments: Some(vec![OrderFulfillment {
+            uid: Some(fulfillment_uid.to_string()),
+            shipment_details: Some(OrderFulfillmentShipmentDetails {
+                carrier: Some(carrier),
+                tracking_number: Some(tracking_number),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    })
+}
+
+// the sparse order moving the given fulfillment into the state, Some(None) if it already is in the
+// state, or None if the order holds no such fulfillment
+fn fulfillment_state_update(order: &Order, fulfillment_uid: &str, state: &OrderFulfillmentState)
+                            -> Option<Option<Order>> {
+    let fulfillment = order.fulfillments.as_ref()?
+        .iter()
+        .find(|fulfillment| fulfillment.uid.as_deref() == Some(fulfillment_uid))?;
+
+    if fulfillment.state.as_ref() == Some(state) {
+        return Some(None);
+    }
+
+    Some(Some(Order {
+        location_id: order.location_id.clone(),
+        version: order.version,
+        fulfillments: Some(vec![OrderFulfillment {
+            uid: Some(fulfillment_uid.to_string()),
+            state: Some(state.clone()),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    }))
+}
+
+// whether the request failed because the order changed since the version it was based on
+fn version_mismatch(errors: &[ResponseError]) -> bool {
+    errors.iter().any(|error| error.code == "VERSION_MISMATCH")
+}
+
 #[cfg(test)]
 mod test_orders {
     use crate::builder::Nil;
@@ -399,6 +1105,7 @@ mod test_orders {
             metadata: None,
             name: Some("some name".to_string()),
             percentage: None,
+            scope: None,
             taxable: None,
             total_money: None,
             total_tax_money: None,
@@ -417,7 +1124,8 @@ mod test_orders {
             total_tip_money: None,
             updated_at: None,
             version: None
-            }
+            },
+            raw_fields: Default::default(),
             };
 
             let mut actual = Builder::from(CreateOrderBody::default())
@@ -453,6 +1161,129 @@ mod test_orders {
         assert!(actual.is_err());
     }
 
+    #[tokio::test]
+    async fn test_create_order_body_pricing_builders() {
+        use crate::objects::enums::{
+            OrderLineItemDiscountScope, OrderLineItemDiscountType, OrderLineItemTaxScope,
+            OrderLineItemTaxType, OrderServiceChargeScope,
+        };
+
+        let actual = Builder::from(CreateOrderBody::default())
+            .location_id("location_id".to_string())
+            .sub_builder_from(OrderLineItemDiscount::default())
+            .name("Happy hour".to_string())
+            .percentage("12.5".to_string())
+            .scope(OrderLineItemDiscountScope::Order)
+            .into_parent_builder()
+            .unwrap()
+            .sub_builder_from(OrderLineItemDiscount::default())
+            .uid("loyal".to_string())
+            .catalog_object_id("discount_id".to_string())
+            .scope(OrderLineItemDiscountScope::LineItem)
+            .into_parent_builder()
+            .unwrap()
+            .sub_builder_from(OrderLineItemTax::default())
+            .name("State tax".to_string())
+            .percentage("7.25".to_string())
+            .additive()
+            .scope(OrderLineItemTaxScope::Order)
+            .into_parent_builder()
+            .unwrap()
+            .sub_builder_from(OrderServiceCharge::default())
+            .catalog_object_id("service_charge_id".to_string())
+            .subtotal_phase()
+            .scope(OrderServiceChargeScope::Order)
+            .into_parent_builder()
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let discounts = actual.order.discounts.unwrap();
+        assert_eq!(discounts.len(), 2);
+        assert_eq!(discounts[0].discount_type, Some(OrderLineItemDiscountType::FixedPercentage));
+        assert!(discounts[1].discount_type.is_none());
+        let taxes = actual.order.taxes.unwrap();
+        assert!(matches!(taxes[0].calculation_method_type, Some(OrderLineItemTaxType::Additive)));
+        assert_eq!(actual.order.service_charges.unwrap().len(), 1);
+
+        // an amount and a percentage at once
+        assert!(Builder::from(CreateOrderBody::default())
+            .sub_builder_from(OrderLineItemDiscount::default())
+            .name("Happy hour".to_string())
+            .percentage("10".to_string())
+            .amount_money(Money { amount: Some(100), currency: Currency::USD })
+            .into_parent_builder()
+            .is_err());
+        // a percentage out of range
+        assert!(Builder::from(CreateOrderBody::default())
+            .sub_builder_from(OrderLineItemTax::default())
+            .name("State tax".to_string())
+            .percentage("107.25".to_string())
+            .into_parent_builder()
+            .is_err());
+        // a type that does not fit the amount
+        assert!(Builder::from(CreateOrderBody::default())
+            .sub_builder_from(OrderLineItemDiscount::default())
+            .name("Happy hour".to_string())
+            .amount_money(Money { amount: Some(100), currency: Currency::USD })
+            .discount_type(OrderLineItemDiscountType::FixedPercentage)
+            .into_parent_builder()
+            .is_err());
+        // a line item scope without a uid to apply it by
+        assert!(Builder::from(CreateOrderBody::default())
+            .sub_builder_from(OrderServiceCharge::default())
+            .name("Delivery".to_string())
+            .percentage("5".to_string())
+            .total_phase()
+            .scope(OrderServiceChargeScope::LineItem)
+            .into_parent_builder()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_order_body_return_builder() {
+        let source_line_item = OrderLineItem {
+            quantity: "3".to_string(),
+            uid: Some("mug".to_string()),
+            name: Some("Ceramic Mug".to_string()),
+            base_price_money: Some(Money { amount: Some(2400), currency: Currency::USD }),
+            ..Default::default()
+        };
+
+        let actual = Builder::from(CreateOrderBody::default())
+            .location_id("location_id".to_string())
+            .sub_builder_from(OrderReturn::default())
+            .source_order_id("source_order_id".to_string())
+            .return_line_item(&source_line_item, "1".to_string())
+            .into_parent_builder()
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let returns = actual.order.returns.unwrap();
+        let return_line_items = returns[0].return_line_items.as_ref().unwrap();
+        assert_eq!(returns[0].source_order_id, Some("source_order_id".to_string()));
+        assert_eq!(return_line_items[0].quantity, "1");
+        assert_eq!(return_line_items[0].source_line_item_uid, Some("mug".to_string()));
+        assert_eq!(return_line_items[0].base_price_money, source_line_item.base_price_money);
+
+        // no source order
+        assert!(Builder::from(CreateOrderBody::default())
+            .sub_builder_from(OrderReturn::default())
+            .return_line_item(&source_line_item, "1".to_string())
+            .into_parent_builder()
+            .is_err());
+        // nothing returned
+        assert!(Builder::from(CreateOrderBody::default())
+            .sub_builder_from(OrderReturn::default())
+            .source_order_id("source_order_id".to_string())
+            .return_line_item(&source_line_item, "0".to_string())
+            .into_parent_builder()
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_create_order() {
         use dotenv::dotenv;
@@ -492,6 +1323,7 @@ mod test_orders {
                     metadata: None,
                     name: Some("some name".to_string()),
                     percentage: None,
+                    scope: None,
                     taxable: None,
                     total_money: None,
                     total_tax_money: None,
@@ -510,7 +1342,8 @@ mod test_orders {
                 total_tip_money: None,
                 updated_at: None,
                 version: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let res = sut.orders()
@@ -520,6 +1353,69 @@ mod test_orders {
         assert!(res.is_ok())
     }
 
+    #[tokio::test]
+    async fn test_order_fulfillment_builders() {
+        use crate::objects::{OrderFulfillmentDeliveryDetails, OrderFulfillmentPickupDetails,
+                             OrderFulfillmentRecipient};
+
+        let recipient = || OrderFulfillmentRecipient {
+            display_name: Some("Jane Doe".to_string()),
+            phone_number: Some("+15555550100".to_string()),
+            ..Default::default()
+        };
+
+        let actual = Builder::from(Order::default())
+            .location_id("some_location_id".to_string())
+            .sub_builder_from(OrderFulfillment::default())
+            .uid("pickup".to_string())
+            .sub_builder_from(OrderFulfillmentPickupDetails::default())
+            .recipient(recipient())
+            .pickup_at("2022-06-01T12:00:00Z".to_string())
+            .curbside("blue van".to_string())
+            .into_parent_builder()
+            .unwrap()
+            .into_parent_builder()
+            .unwrap()
+            .sub_builder_from(OrderFulfillment::default())
+            .sub_builder_from(OrderFulfillmentShipmentDetails::default())
+            .recipient(recipient())
+            .carrier("UPS".to_string())
+            .into_parent_builder()
+            .unwrap()
+            .into_parent_builder()
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let fulfillments = serde_json::to_value(actual.fulfillments).unwrap();
+        assert_eq!(fulfillments[0]["type"], "PICKUP");
+        assert_eq!(fulfillments[0]["uid"], "pickup");
+        assert_eq!(fulfillments[0]["pickup_details"]["schedule_type"], "SCHEDULED");
+        assert_eq!(fulfillments[0]["pickup_details"]["is_curbside_pickup"], true);
+        assert_eq!(fulfillments[1]["type"], "SHIPMENT");
+        assert_eq!(fulfillments[1]["shipment_details"]["carrier"], "UPS");
+
+        let without_recipient = Builder::from(OrderFulfillment::default())
+            .sub_builder_from(OrderFulfillmentShipmentDetails::default())
+            .carrier("UPS".to_string())
+            .into_parent_builder();
+        assert!(without_recipient.is_err());
+
+        let unscheduled = Builder::from(OrderFulfillment::default())
+            .sub_builder_from(OrderFulfillmentDeliveryDetails::default())
+            .recipient(recipient())
+            .into_parent_builder();
+        assert!(unscheduled.is_err());
+
+        let mismatched = Builder::from(OrderFulfillment {
+            fulfillment_type: Some(OrderFulfillmentType::Pickup),
+            delivery_details: Some(OrderFulfillmentDeliveryDetails::default()),
+            ..Default::default()
+        }).build().await;
+        assert!(mismatched.is_err());
+    }
+
     #[tokio::test]
     async fn test_search_order_body_builder() {
         let expected = SearchOrderBody {
@@ -533,7 +1429,8 @@ mod test_orders {
                     sort_order: Some(SortOrder::Asc)
                 })
             }),
-            return_entries: Some(true)
+            return_entries: Some(true),
+            raw_fields: Default::default(),
         };
 
         let actual = Builder::from(SearchOrderBody::default())
@@ -553,6 +1450,77 @@ mod test_orders {
         assert_eq!(format!("{:?}", expected), format!("{:?}", actual))
     }
 
+    #[tokio::test]
+    async fn test_search_orders_query_builder() {
+        use crate::objects::TimeRange;
+        use crate::objects::enums::{OrderFulfillmentType, OrderState};
+
+        let range = |start_at: &str, end_at: &str| TimeRange {
+            start_at: Some(start_at.to_string()),
+            end_at: Some(end_at.to_string()),
+        };
+
+        let actual = Builder::from(SearchOrdersQuery::default())
+            .states(vec![OrderState::Completed])
+            .customer_ids(vec!["C1".to_string()])
+            .source_names(vec!["POS".to_string()])
+            .fulfillment_types(vec![OrderFulfillmentType::Pickup])
+            .closed_at(range("2022-06-01T00:00:00Z", "2022-07-01T00:00:00Z"))
+            .sort_descending()
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(actual).unwrap(),
+            serde_json::json!({
+                "filter": {
+                    "customer_filter": { "customer_ids": ["C1"] },
+                    "date_time_filter": {
+                        "closed_at": {
+                            "start_at": "2022-06-01T00:00:00Z",
+                            "end_at": "2022-07-01T00:00:00Z",
+                        },
+                    },
+                    "fulfillment_filter": { "fulfillment_types": ["PICKUP"] },
+                    "source_filter": { "source_names": ["POS"] },
+                    "state_filter": { "states": ["COMPLETED"] },
+                },
+                "sort": { "sort_field": "CLOSED_AT", "sort_order": "DESC" },
+            }),
+        );
+
+        let sorted_by_updated_at = Builder::from(SearchOrdersQuery::default())
+            .updated_at(range("2022-06-01T00:00:00Z", "2022-07-01T00:00:00Z"))
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(
+            sorted_by_updated_at.sort.unwrap().sort_field,
+            Some(SearchOrdersSortField::UpdatedAt),
+        );
+
+        let both = Builder::from(SearchOrdersQuery::default())
+            .created_at(range("2022-06-01T00:00:00Z", "2022-07-01T00:00:00Z"))
+            .updated_at(range("2022-06-01T00:00:00Z", "2022-07-01T00:00:00Z"))
+            .build()
+            .await;
+        assert!(both.is_err());
+
+        let mismatched_sort = Builder::from(SearchOrdersQuery::default())
+            .closed_at(range("2022-06-01T00:00:00Z", "2022-07-01T00:00:00Z"))
+            .sort_field(SearchOrdersSortField::CreatedAt)
+            .build()
+            .await;
+        assert!(mismatched_sort.is_err());
+
+        let reversed = Builder::from(SearchOrdersQuery::default())
+            .created_at(range("2022-07-01T00:00:00Z", "2022-06-01T00:00:00Z"))
+            .build()
+            .await;
+        assert!(reversed.is_err());
+    }
+
     #[tokio::test]
     async fn test_search_orders() {
         use dotenv::dotenv;
@@ -573,7 +1541,8 @@ mod test_orders {
                     sort_order: Some(SortOrder::Asc)
                 })
             }),
-            return_entries: Some(true)
+            return_entries: Some(true),
+            raw_fields: Default::default(),
         };
 
         let res = sut.orders()
@@ -614,6 +1583,56 @@ mod test_orders {
         res_vec.into_iter().for_each(|res| assert!(res.is_err()))
     }
 
+    #[tokio::test]
+    async fn test_update_order_body_builder() {
+        let actual = Builder::from(OrderUpdateBody::default())
+            .version(3)
+            .reference_id("some_reference".to_string())
+            .metadata("table".to_string(), "12".to_string())
+            .state(OrderState::Open)
+            .clear("line_items[some_uid].note".to_string())
+            .clear("metadata.seat".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual.touched_paths(),
+            vec!["reference_id", "metadata.table", "state", "line_items[some_uid].note",
+                 "metadata.seat"],
+        );
+
+        let mut json = serde_json::to_value(&actual).unwrap();
+        json.as_object_mut().unwrap().remove("idempotency_key");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "fields_to_clear": ["line_items[some_uid].note", "metadata.seat"],
+                "order": {
+                    "metadata": { "table": "12" },
+                    "reference_id": "some_reference",
+                    "state": "OPEN",
+                    "version": 3,
+                },
+            }),
+        );
+
+        let unversioned = Builder::from(OrderUpdateBody::default())
+            .reference_id("some_reference".to_string())
+            .build()
+            .await;
+        assert!(unversioned.is_err());
+
+        let conflicting = Builder::from(OrderUpdateBody::default())
+            .version(3)
+            .metadata("table".to_string(), "12".to_string())
+            .clear("metadata".to_string())
+            .build()
+            .await;
+        assert!(conflicting.is_err());
+        assert!(!contains_path("metadata.table", "metadata.tables"));
+    }
+
     // #[tokio::test]
     async fn test_update_order() {
         use dotenv::dotenv;
@@ -657,7 +1676,9 @@ mod test_orders {
                 total_tip_money: None,
                 updated_at: None,
                 version: Some(2)
-            })
+            }),
+            touched: vec![],
+            raw_fields: Default::default(),
         };
 
         println!("{:?}", &input);
@@ -675,11 +1696,12 @@ mod test_orders {
         let expected = PayOrderBody {
             idempotency_key: None,
             order_version: Some(3),
-            payment_ids: Some(vec!["some_id".to_string()])
+            payment_ids: Some(vec!["some_id".to_string()]),
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(PayOrderBody::default())
-            .oder_version(3)
+            .order_version(3)
             .payment_ids(vec!["some_id".to_string()])
             .build()
             .await
@@ -692,6 +1714,50 @@ mod test_orders {
         assert_eq!(format!("{:?}", expected), format!("{:?}", actual));
     }
 
+    #[test]
+    fn test_in_order_of() {
+        let orders: Vec<Order> = serde_json::from_value(serde_json::json!([
+            { "id": "B", "location_id": "L1" },
+            { "id": "A", "location_id": "L1" },
+            { "id": "C", "location_id": "L1" },
+        ])).unwrap();
+        let order_ids: Vec<String> = vec!["A", "missing", "C", "B", "A"].into_iter()
+            .map(|id| id.to_string())
+            .collect();
+
+        let actual = in_order_of(orders, &order_ids);
+
+        assert_eq!(
+            actual.into_iter().filter_map(|order| order.id).collect::<Vec<String>>(),
+            vec!["A", "C", "B"],
+        );
+    }
+
+    #[test]
+    fn test_clone_order_body() {
+        let body = CloneOrderBody {
+            idempotency_key: "some_key".to_string(),
+            order_id: "some_order_id".to_string(),
+            version: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({ "idempotency_key": "some_key", "order_id": "some_order_id" }),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preview_without_location() {
+        let actual = SquareClient::new("some_token")
+            .orders()
+            .preview(Order::default())
+            .await;
+
+        let errors = actual.unwrap_err().get().unwrap();
+        assert_eq!(errors[0].field, Some("order.location_id".to_string()));
+    }
+
     #[tokio::test]
     async fn test_order_calculate_body_builder() {
 
@@ -728,6 +1794,7 @@ mod test_orders {
                         metadata: None,
                         name: Some("some name".to_string()),
                         percentage: None,
+                        scope: None,
                         taxable: None,
                         total_money: None,
                         total_tax_money: None,
@@ -748,7 +1815,8 @@ mod test_orders {
                 updated_at: None,
                 version: Some(3)
             }),
-            proposed_rewards: None
+            proposed_rewards: None,
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(OrderCalculateBody::default())
@@ -812,6 +1880,7 @@ mod test_orders {
                         metadata: None,
                         name: Some("some name".to_string()),
                         percentage: None,
+                        scope: None,
                         taxable: None,
                         total_money: None,
                         total_tax_money: None,
@@ -832,7 +1901,8 @@ mod test_orders {
                 updated_at: None,
                 version: Some(3)
             }),
-            proposed_rewards: None
+            proposed_rewards: None,
+            raw_fields: Default::default(),
         };
 
         let res = sut.orders()
@@ -841,5 +1911,242 @@ mod test_orders {
 
         assert!(res.is_ok())
     }
-}
 
+    #[test]
+    fn test_validate_split() {
+        use crate::objects::enums::Currency;
+
+        let order: Order = serde_json::from_value(serde_json::json!({
+            "id": "some_order_id",
+            "location_id": "L1JC53TYHS40Z",
+            "total_money": { "amount": 2000, "currency": "USD" },
+        })).unwrap();
+        let tender = |amount: i64, currency: Currency| PaymentSource {
+            source_id: "cnon:card-nonce-ok".to_string(),
+            amount_money: Money { amount: Some(amount), currency },
+            tip_money: None,
+        };
+
+        assert!(validate_split(&order, &[tender(1200, Currency::USD), tender(800, Currency::USD)]).is_ok());
+        assert!(validate_split(&order, &[]).is_err());
+
+        let short = validate_split(&order, &[tender(1200, Currency::USD), tender(700, Currency::USD)])
+            .unwrap_err()
+            .get()
+            .unwrap();
+
+        assert_eq!(short[0].code, "INVALID_VALUE".to_string());
+
+        let mismatched = validate_split(&order, &[tender(1200, Currency::USD), tender(800, Currency::EUR)])
+            .unwrap_err()
+            .get()
+            .unwrap();
+
+        assert_eq!(mismatched[0].code, "CURRENCY_MISMATCH".to_string());
+    }
+
+    #[test]
+    fn test_tracking_update() {
+        let order: Order = serde_json::from_value(serde_json::json!({
+            "id": "some_order_id",
+            "location_id": "L1JC53TYHS40Z",
+            "version": 4,
+            "fulfillments": [
+                { "uid": "pickup_uid", "type": "PICKUP", "state": "PROPOSED" },
+                {
+                    "uid": "shipment_uid",
+                    "type": "SHIPMENT",
+                    "state": "PROPOSED",
+                    "shipment_details": { "shipping_note": "Leave at the door" },
+                },
+            ],
+        })).unwrap();
+
+        let actual = tracking_update(
+            &order,
+            "shipment_uid",
+            "UPS".to_string(),
+            "1Z999AA10123456784".to_string(),
+        ).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&actual).unwrap(),
+            serde_json::json!({
+                "location_id": "L1JC53TYHS40Z",
+                "version": 4,
+                "fulfillments": [{
+                    "uid": "shipment_uid",
+                    "shipment_details": {
+                        "carrier": "UPS",
+                        "tracking_number": "1Z999AA10123456784",
+                    },
+                }],
+            }),
+        );
+        assert!(tracking_update(&order, "pickup_uid", "UPS".to_string(), "1Z".to_string())
+            .is_none());
+        assert!(tracking_update(&order, "unknown_uid", "UPS".to_string(), "1Z".to_string())
+            .is_none());
+    }
+
+    #[test]
+    fn test_fulfillment_state_update() {
+        let order: Order = serde_json::from_value(serde_json::json!({
+            "id": "some_order_id",
+            "location_id": "L1JC53TYHS40Z",
+            "version": 4,
+            "fulfillments": [
+                { "uid": "pickup_uid", "type": "PICKUP", "state": "RESERVED" },
+                { "uid": "prepared_uid", "type": "PICKUP", "state": "PREPARED" },
+            ],
+        })).unwrap();
+
+        let prepared = OrderFulfillmentState::Prepared;
+        let actual = fulfillment_state_update(&order, "pickup_uid", &prepared).unwrap().unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&actual).unwrap(),
+            serde_json::json!({
+                "location_id": "L1JC53TYHS40Z",
+                "version": 4,
+                "fulfillments": [{ "uid": "pickup_uid", "state": "PREPARED" }],
+            }),
+        );
+        assert!(fulfillment_state_update(&order, "prepared_uid", &prepared).unwrap().is_none());
+        assert!(fulfillment_state_update(&order, "unknown_uid", &prepared).is_none());
+
+        let mismatch = ResponseError {
+            category: "INVALID_REQUEST_ERROR".to_string(),
+            code: "VERSION_MISMATCH".to_string(),
+            detail: None,
+            field: None,
+        };
+        assert!(version_mismatch(&[mismatch]));
+        assert!(!version_mismatch(&[]));
+    }
+
+    // answers the requests of pay_split, failing to pay for the order and recording the
+    // endpoints called
+    struct StaleOrder(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl crate::interceptor::RequestInterceptor for StaleOrder {
+        fn before_request(&self, request: &mut crate::interceptor::InterceptedRequest)
+                          -> Option<crate::interceptor::InterceptedResponse> {
+            self.0.lock().unwrap().push(request.endpoint.clone());
+
+            let (status, body) = match request.endpoint.as_str() {
+                "orders/some_order_id" => (reqwest::StatusCode::OK, serde_json::json!({
+                    "order": {
+                        "id": "some_order_id",
+                        "location_id": "some_location_id",
+                        "version": 1,
+                        "total_money": { "amount": 2000, "currency": "USD" },
+                    },
+                })),
+                "payments" => {
+                    let id = format!("payment_{}", request.body.as_ref()?["source_id"]
+                        .as_str()?);
+
+                    (reqwest::StatusCode::OK, serde_json::json!({ "payment": { "id": id } }))
+                },
+                "orders/some_order_id/pay" => (reqwest::StatusCode::BAD_REQUEST, serde_json::json!({
+                    "errors": [{ "category": "INVALID_REQUEST_ERROR", "code": "VERSION_MISMATCH" }],
+                })),
+                _ => (reqwest::StatusCode::OK, serde_json::json!({ "payment": {} })),
+            };
+
+            Some(crate::interceptor::InterceptedResponse::new(status, body.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pay_split_cancels_payments() {
+        let endpoints = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let sut = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(StaleOrder(endpoints.clone()));
+
+        let tender = |source_id: &str| PaymentSource {
+            source_id: source_id.to_string(),
+            amount_money: Money { amount: Some(1000), currency: Currency::USD },
+            tip_money: None,
+        };
+        let err = sut.orders()
+            .pay_split("some_order_id".to_string(), vec![tender("card"), tender("CASH")])
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.get().unwrap()[0].code, "VERSION_MISMATCH");
+        assert_eq!(&endpoints.lock().unwrap()[3..], [
+            "orders/some_order_id/pay",
+            "payments/payment_card/cancel",
+            "payments/payment_CASH/cancel",
+        ]);
+    }
+
+    // answers the requests of update_fulfillment_state, failing every update of the order as
+    // it changed in the meantime
+    struct ChangingOrder(std::sync::Arc<std::sync::atomic::AtomicU32>);
+
+    impl crate::interceptor::RequestInterceptor for ChangingOrder {
+        fn before_request(&self, request: &mut crate::interceptor::InterceptedRequest)
+                          -> Option<crate::interceptor::InterceptedResponse> {
+            let mut response = match request.verb {
+                Verb::GET => crate::interceptor::InterceptedResponse::new(
+                    reqwest::StatusCode::OK,
+                    serde_json::json!({
+                        "order": {
+                            "id": "some_order_id",
+                            "location_id": "some_location_id",
+                            "version": 1,
+                            "fulfillments": [
+                                { "uid": "pickup_uid", "type": "PICKUP", "state": "RESERVED" },
+                            ],
+                        },
+                    }).to_string(),
+                ),
+                _ => {
+                    self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+                    crate::interceptor::InterceptedResponse::new(
+                        reqwest::StatusCode::CONFLICT,
+                        serde_json::json!({
+                            "errors": [{
+                                "category": "INVALID_REQUEST_ERROR",
+                                "code": "VERSION_MISMATCH",
+                            }],
+                        }).to_string(),
+                    )
+                },
+            };
+            response.headers.insert(
+                "x-request-id",
+                crate::client::HeaderValue::from_static("some_request_id"),
+            );
+
+            Some(response)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_fulfillment_state_conflict() {
+        let updates = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let sut = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(ChangingOrder(updates.clone()));
+
+        let err = sut.orders()
+            .update_fulfillment_state(
+                "some_order_id".to_string(),
+                "pickup_uid".to_string(),
+                OrderFulfillmentState::Prepared,
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(updates.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(err.status(), Some(reqwest::StatusCode::CONFLICT));
+        assert_eq!(err.request_id(), Some("some_request_id"));
+        assert_eq!(err.errors().unwrap()[0].code, "VERSION_MISMATCH");
+    }
+}
@@ -0,0 +1,375 @@
+/*!
+Promo codes built on top of the catalog and orders functionality of the
+[Square API](https://developer.squareup.com).
+
+Square has no notion of a promo code a buyer can enter, so [PromoCodes](PromoCodes) maps codes to
+[CatalogDiscount](crate::objects::CatalogDiscount)s by convention: by default a code matches the
+discount of the same name, ignoring case and surrounding whitespace, and with
+[custom_attribute_key](PromoCodes::custom_attribute_key) it matches the discount holding the code
+in a string custom attribute instead. A [CatalogPricingRule](crate::objects::CatalogPricingRule)
+applying the discount limits the dates the code can be redeemed on.
+
+Redeeming a code adds its discount to an in-progress order. A code that is single use per
+customer is refused if one of the completed orders of the customer at the location of the order
+already carries the discount.
+
+# Example: Redeem a promo code
+```no_run
+use square_ox::{api::orders::promo::PromoCodes, client::SquareClient, objects::Order};
+
+async {
+    let client = SquareClient::new("some_token");
+    let mut order = Order {
+        location_id: Some("some_location_id".to_string()),
+        customer_id: Some("some_customer_id".to_string()),
+        ..Default::default()
+    };
+
+    let promo = PromoCodes::new()
+        .custom_attribute_key("promo_code".to_string())
+        .single_use_per_customer()
+        .redeem(&client, &mut order, " summer10 ")
+        .await;
+};
+```
+ */
+
+use crate::api::catalog::SearchCatalogObjectsBody;
+use crate::api::orders::SearchOrderBody;
+use crate::builder::Builder;
+use crate::client::SquareClient;
+use crate::errors::SquareError;
+use crate::objects::{
+    CatalogObject, Order, OrderLineItemDiscount, Response, SearchOrdersCustomerFilter,
+    SearchOrdersFilter, SearchOrdersQuery, SearchOrdersStateFilter,
+    enums::{CatalogObjectType, OrderLineItemDiscountScope, OrderState},
+};
+
+use chrono::Utc;
+
+/// A promo code along with the discount it maps to.
+#[derive(Clone, Debug)]
+pub struct PromoCode {
+    /// The code as normalized for matching, i.e. trimmed and in upper case.
+    pub code: String,
+    pub discount: CatalogObject,
+    /// The pricing rule applying the discount, if there is one.
+    pub pricing_rule: Option<CatalogObject>,
+}
+
+impl PromoCode {
+    /// Whether the code can be redeemed on the given date, given as `YYYY-MM-DD`, according to
+    /// its pricing rule. A code without a pricing rule is always valid.
+    pub fn valid_on(&self, date: &str) -> bool {
+        let rule = match self.pricing_rule.as_ref()
+            .and_then(|rule| rule.pricing_rule_data.as_ref()) {
+            Some(rule) => rule,
+            None => return true,
+        };
+
+        // ISO 8601 dates sort chronologically
+        rule.valid_from_date.as_deref().is_none_or(|from| from <= date) &&
+            rule.valid_until_date.as_deref().is_none_or(|until| date <= until)
+    }
+
+    /// Adds the discount of the code to the order, applied to the whole order. Adding the same
+    /// code twice has no further effect.
+    pub fn apply(&self, order: &mut Order) {
+        let uid = format!("promo-{}", self.code);
+        let discounts = order.discounts.get_or_insert_with(Vec::new);
+
+        if discounts.iter().any(|discount| discount.uid.as_ref() == Some(&uid)) {
+            return;
+        }
+
+        discounts.push(OrderLineItemDiscount {
+            uid: Some(uid),
+            catalog_object_id: self.discount.id.clone(),
+            catalog_version: self.discount.version,
+            scope: Some(OrderLineItemDiscountScope::Order),
+            ..Default::default()
+        });
+    }
+}
+
+/// Looks up and redeems promo codes.
+#[derive(Clone, Debug, Default)]
+pub struct PromoCodes {
+    custom_attribute_key: Option<String>,
+    single_use_per_customer: bool,
+}
+
+impl PromoCodes {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Match codes against the string custom attribute with the given key instead of against
+    /// the names of the discounts.
+    pub fn custom_attribute_key(mut self, key: String) -> Self {
+        self.custom_attribute_key = Some(key);
+
+        self
+    }
+
+    /// Refuse to redeem a code for a customer who already redeemed it on a completed order.
+    pub fn single_use_per_customer(mut self) -> Self {
+        self.single_use_per_customer = true;
+
+        self
+    }
+
+    /// Finds the discount the code maps to, along with the pricing rule applying it. Yields
+    /// `None` for an unknown code.
+    /// # Arguments:
+    /// * `client` - The client to search the catalog with.
+    /// * `code` - The code as entered by the buyer.
+    pub async fn find(&self, client: &SquareClient, code: &str)
+                      -> Result<Option<PromoCode>, SquareError> {
+        let objects = discounts_and_pricing_rules(client).await?;
+
+        Ok(self.matching(&objects, code))
+    }
+
+    /// Adds the discount the code maps to to an in-progress order. Fails if the code is unknown,
+    /// not valid today, or, for single use codes, already redeemed by the customer of the order.
+    /// # Arguments:
+    /// * `client` - The client to search the catalog, and the orders of the customer, with.
+    /// * `order` - The order to apply the discount to.
+    /// * `code` - The code as entered by the buyer.
+    pub async fn redeem(&self, client: &SquareClient, order: &mut Order, code: &str)
+                        -> Result<PromoCode, SquareError> {
+        let invalid = |detail: &str| {
+            SquareError::invalid_request("INVALID_VALUE", "promo_code", detail.to_string())
+        };
+
+        let promo = self.find(client, code).await?
+            .ok_or_else(|| invalid("the promo code is unknown"))?;
+
+        if !promo.valid_on(&Utc::now().format("%Y-%m-%d").to_string()) {
+            return Err(invalid("the promo code is not valid today"));
+        }
+
+        if self.single_use_per_customer {
+            let (customer_id, location_id) = match (&order.customer_id, &order.location_id) {
+                (Some(customer_id), Some(location_id)) => (customer_id, location_id),
+                _ => return Err(invalid("a single use promo code needs a customer and location")),
+            };
+
+            if redeemed(client, &promo, customer_id, location_id).await? {
+                return Err(invalid("the promo code was already redeemed by the customer"));
+            }
+        }
+
+        promo.apply(order);
+
+        Ok(promo)
+    }
+
+    // the discount matching the code and the pricing rule applying it
+    fn matching(&self, objects: &[CatalogObject], code: &str) -> Option<PromoCode> {
+        let code = normalize(code);
+        if code.is_empty() {
+            return None;
+        }
+
+        let matches = |value: Option<&str>| value.map(normalize).as_ref() == Some(&code);
+
+        let discount = objects.iter()
+            .filter(|object| object.discount_data.is_some() && object.is_deleted != Some(true))
+            .find(|object| match self.custom_attribute_key.as_ref() {
+                Some(key) => object.custom_attributes_values.iter()
+                    .flatten()
+                    .filter(|(name, value)| *name == key || value.key.as_ref() == Some(key))
+                    .any(|(_, value)| matches(value.string_value.as_deref())),
+                None => matches(object.discount_data.as_ref()
+                    .and_then(|discount| discount.name.as_deref())),
+            })?;
+
+        let pricing_rule = objects.iter()
+            .filter(|object| object.is_deleted != Some(true))
+            .find(|object| object.pricing_rule_data.as_ref()
+                .is_some_and(|rule| rule.discount_id.is_some() && rule.discount_id == discount.id))
+            .cloned();
+
+        Some(PromoCode { code, discount: discount.clone(), pricing_rule })
+    }
+}
+
+fn normalize(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+// every discount and pricing rule of the catalog, following the cursor through every page
+async fn discounts_and_pricing_rules(client: &SquareClient)
+                                     -> Result<Vec<CatalogObject>, SquareError> {
+    let mut objects = vec![];
+    let mut cursor = None;
+
+    loop {
+        let mut search_body = Builder::from(SearchCatalogObjectsBody::default())
+            .set_object_types(vec![CatalogObjectType::Discount, CatalogObjectType::PricingRule])
+            .exclude_deleted_objects();
+        if let Some(cursor) = cursor {
+            search_body = search_body.cursor(cursor);
+        }
+        let search_body = search_body.build().await.map_err(|_| SquareError::from(None))?;
+
+        let res = client.catalog().search_objects(search_body).await?;
+        cursor = res.cursor.clone();
+
+        for response in res.into_responses() {
+            if let Response::Objects(page) = response {
+                objects.extend(page);
+            }
+        }
+
+        if cursor.is_none() {
+            return Ok(objects);
+        }
+    }
+}
+
+// whether a completed order of the customer at the location carries the discount of the code
+async fn redeemed(client: &SquareClient, promo: &PromoCode, customer_id: &str, location_id: &str)
+                  -> Result<bool, SquareError> {
+    let mut cursor = None;
+
+    loop {
+        let res = client.orders()
+            .search(search_body(customer_id, location_id, cursor))
+            .await?;
+        cursor = res.cursor.clone();
+
+        for response in res.into_responses() {
+            if let Response::Orders(orders) = response {
+                if orders.iter().any(|order| carries(order, promo)) {
+                    return Ok(true);
+                }
+            }
+        }
+
+        if cursor.is_none() {
+            return Ok(false);
+        }
+    }
+}
+
+// a search for the completed orders of the customer at the location
+fn search_body(customer_id: &str, location_id: &str, cursor: Option<String>) -> SearchOrderBody {
+    SearchOrderBody {
+        cursor,
+        limit: None,
+        location_ids: Some(vec![location_id.to_string()]),
+        query: Some(SearchOrdersQuery {
+            filter: Some(SearchOrdersFilter {
+                customer_filter: Some(SearchOrdersCustomerFilter {
+                    customer_ids: Some(vec![customer_id.to_string()]),
+                }),
+                state_filter: Some(SearchOrdersStateFilter {
+                    states: Some(vec![OrderState::Completed]),
+                }),
+                ..Default::default()
+            }),
+            sort: None,
+        }),
+        return_entries: Some(false),
+        raw_fields: Default::default(),
+    }
+}
+
+// whether the order carries the discount of the code
+fn carries(order: &Order, promo: &PromoCode) -> bool {
+    order.discounts.iter()
+        .flatten()
+        .any(|discount| discount.catalog_object_id.is_some() &&
+            discount.catalog_object_id == promo.discount.id)
+}
+
+#[cfg(test)]
+mod test_promo {
+    use super::*;
+
+    fn catalog() -> Vec<CatalogObject> {
+        serde_json::from_value(serde_json::json!([
+            {
+                "id": "D1",
+                "type": "DISCOUNT",
+                "version": 3,
+                "discount_data": { "name": "Summer10", "percentage": "10.0" },
+                "custom_attributes_values": {
+                    "promo_code": { "key": "promo_code", "string_value": "SUNNY" },
+                },
+            },
+            {
+                "id": "D2",
+                "type": "DISCOUNT",
+                "is_deleted": true,
+                "discount_data": { "name": "Winter10" },
+            },
+            {
+                "id": "R1",
+                "type": "PRICING_RULE",
+                "pricing_rule_data": {
+                    "discount_id": "D1",
+                    "valid_from_date": "2022-06-01",
+                    "valid_until_date": "2022-08-31",
+                },
+            },
+        ])).unwrap()
+    }
+
+    #[test]
+    fn test_matching() {
+        let by_name = PromoCodes::new().matching(&catalog(), "  summer10 ").unwrap();
+
+        assert_eq!(by_name.code, "SUMMER10");
+        assert_eq!(by_name.discount.id, Some("D1".to_string()));
+        assert_eq!(by_name.pricing_rule.unwrap().id, Some("R1".to_string()));
+        assert!(PromoCodes::new().matching(&catalog(), "winter10").is_none());
+        assert!(PromoCodes::new().matching(&catalog(), "sunny").is_none());
+        assert!(PromoCodes::new().matching(&catalog(), " ").is_none());
+
+        let by_attribute = PromoCodes::new().custom_attribute_key("promo_code".to_string());
+
+        assert_eq!(
+            by_attribute.matching(&catalog(), "Sunny").unwrap().discount.id,
+            Some("D1".to_string()),
+        );
+        assert!(by_attribute.matching(&catalog(), "summer10").is_none());
+    }
+
+    #[test]
+    fn test_valid_on_and_apply() {
+        let promo = PromoCodes::new().matching(&catalog(), "summer10").unwrap();
+
+        assert!(promo.valid_on("2022-06-01"));
+        assert!(promo.valid_on("2022-08-31"));
+        assert!(!promo.valid_on("2022-09-01"));
+
+        let mut order = Order::default();
+        promo.apply(&mut order);
+        promo.apply(&mut order);
+
+        let discounts = order.discounts.clone().unwrap();
+        assert_eq!(discounts.len(), 1);
+        assert_eq!(discounts[0].catalog_object_id, Some("D1".to_string()));
+        assert_eq!(discounts[0].catalog_version, Some(3));
+        assert!(carries(&order, &promo));
+        assert!(!carries(&Order::default(), &promo));
+    }
+
+    #[test]
+    fn test_search_body() {
+        let actual = serde_json::to_value(search_body("C1", "L1", None)).unwrap();
+
+        assert_eq!(actual["location_ids"], serde_json::json!(["L1"]));
+        assert_eq!(
+            actual["query"]["filter"],
+            serde_json::json!({
+                "customer_filter": { "customer_ids": ["C1"] },
+                "state_filter": { "states": ["COMPLETED"] },
+            }),
+        );
+    }
+}
@@ -2,17 +2,21 @@
 Payment functionality of the [Square API](https://developer.squareup.com).
 */
 
+pub mod holds;
+
 use crate::client::SquareClient;
 use crate::api::{Verb, SquareAPI};
 use crate::errors::{PaymentBuildError, ValidationError};
 use crate::errors::SquareError;
-use crate::objects::{Address, CashPaymentDetails, enums::Currency, ExternalPaymentDetails, Money, Payment};
+use crate::objects::{Address, CashPaymentDetails, enums::Currency, ExternalPaymentDetails, Money, Payment,
+                     Response};
 use crate::response::SquareResponse;
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
-use crate::builder::{Builder, ParentBuilder, Validate};
-use crate::objects::enums::SortOrder;
+use crate::builder::{valid_raw_fields, Builder, ParentBuilder, RawFields, Validate};
+use crate::objects::enums::{PaymentDelayAction, PaymentSourceType, SortOrder};
 
 impl SquareClient {
     pub fn payments(&self) -> Payments {
@@ -32,7 +36,31 @@ impl<'a> Payments<'a> {
     ///
     /// # Arguments
     /// * `parameters` - A vector of parameters created through the
-    /// [ListPaymentsParametersBuilder](ListPaymentsParametersBuilder)
+    ///   [ListPaymentsParametersBuilder](ListPaymentsParametersBuilder)
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::{
+    ///     api::payment::ListPaymentsParametersBuilder,
+    ///     client::SquareClient,
+    /// };
+    ///
+    /// async {
+    ///     let parameters = ListPaymentsParametersBuilder::new()
+    ///         .begin_time("2022-07-01T00:00:00Z".to_string())
+    ///         .location_id("some_loc_id".to_string())
+    ///         .card_brand("VISA".to_string())
+    ///         .sort_descending()
+    ///         .limit(50)
+    ///         .build()
+    ///         .await;
+    ///
+    ///     let payments = SquareClient::new("some_token")
+    ///         .payments()
+    ///         .list(Some(parameters))
+    ///         .await;
+    /// };
+    /// ```
     pub async fn list(self, parameters: Option<Vec<(String, String)>>) -> Result<SquareResponse, SquareError> {
         self.client.request(
             Verb::GET,
@@ -57,10 +85,43 @@ impl<'a> Payments<'a> {
     }
 
     /// Cancels (voids) a payment identified by the idempotency key that is specified in the request.
+    /// Use this when the outcome of a [create](Payments::create) call is unknown, for instance
+    /// after a network failure, and no payment ID is available. Canceling a payment that was never
+    /// created still succeeds, and any payment created later with the same key is rejected.
     /// [Open in API Reference](https://developer.squareup.com/reference/square/payments/cancel-payment-by-idempotency-key)
     ///
     /// # Arguments
-    /// * `idempotency_key` - The idempotency key identifying the payment to be canceled.
+    /// * `idempotency_key` - The idempotency key identifying the payment to be canceled, as
+    ///   returned by [PaymentRequest::idempotency_key](PaymentRequest::idempotency_key).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use square_ox::{
+    ///     api::payment::PaymentRequest,
+    ///     builder::Builder,
+    ///     client::SquareClient,
+    ///     objects::enums::Currency,
+    /// };
+    ///
+    /// async {
+    ///     let client = SquareClient::new("some_token");
+    ///
+    ///     let payment = Builder::from(PaymentRequest::default())
+    ///         .source_id("cnon:card-nonce-ok".to_string())
+    ///         .amount(1000, Currency::USD)
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///     let idempotency_key = payment.idempotency_key().unwrap().to_string();
+    ///
+    ///     if client.payments().create(payment).await.is_err() {
+    ///         client.payments()
+    ///             .cancel_by_idempotency_key(idempotency_key)
+    ///             .await
+    ///             .unwrap();
+    ///     }
+    /// };
+    /// ```
     pub async fn cancel_by_idempotency_key(self, idempotency_key: String) -> Result<SquareResponse, SquareError> {
         self.client.request(
             Verb::POST,
@@ -74,7 +135,7 @@ impl<'a> Payments<'a> {
     /// [Open in API Reference](https://developer.squareup.com/reference/square/payments/get-payment)
     ///
     /// # Arguments
-    /// * `payment_id` - The idempotency key identifying the payment to be canceled.
+    /// * `payment_id` - The unique ID of the payment to be retrieved.
     pub async fn get(self, payment_id: String) -> Result<SquareResponse, SquareError> {
         self.client.request(
             Verb::GET,
@@ -86,10 +147,10 @@ impl<'a> Payments<'a> {
 
     /// Updates a payment with the APPROVED status.
     /// You can update the `amount_money` and `tip_money` using this endpoint.
-    /// [Open in API Reference](https://developer.squareup.com/reference/square/payments/get-payment)
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/payments/update-payment)
     ///
     /// # Arguments
-    /// * `payment_id` - The idempotency key identifying the payment to be updated.
+    /// * `payment_id` - The unique ID of the payment to be updated.
     /// * `body` - The request body with the updated [Payment](Payment) object.
     pub async fn update(self, payment_id: String, body: UpdatePaymentBody)
         -> Result<SquareResponse, SquareError> {
@@ -105,7 +166,7 @@ impl<'a> Payments<'a> {
     /// [Open in API Reference](https://developer.squareup.com/reference/square/payments/cancel-payment)
     ///
     /// # Arguments
-    /// * `payment_id` - The idempotency key identifying the payment to be canceled.
+    /// * `payment_id` - The unique ID of the payment to be canceled.
     pub async fn cancel(self, payment_id: String)
         -> Result<SquareResponse, SquareError> {
         self.client.request(
@@ -116,11 +177,12 @@ impl<'a> Payments<'a> {
         ).await
     }
 
-    /// Cancels (voids) a payment.
-    /// [Open in API Reference](https://developer.squareup.com/reference/square/payments/cancel-payment)
+    /// Completes (captures) a payment with the APPROVED status, such as one created with
+    /// `autocomplete` set to false.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/payments/complete-payment)
     ///
     /// # Arguments
-    /// * `payment_id` - The idempotency key identifying the payment to be completed.
+    /// * `payment_id` - The unique ID of the payment to be completed.
     /// * `version_token` - Used for optimistic concurrency. This opaque token identifies the
     /// current [Payment](Payment) version that the caller expects. If the server has a different
     /// version of the [Payment](Payment), the update fails and a response with a VERSION_MISMATCH
@@ -136,6 +198,97 @@ impl<'a> Payments<'a> {
             None,
         ).await
     }
+
+    /// Creates a payment that is only authorized, holding the funds until it is captured or voided
+    /// through the returned [Authorization](Authorization). The `autocomplete` setting of the
+    /// request is overridden so the payment is not completed right away.
+    ///
+    /// # Arguments
+    /// * `payment` - The [PaymentRequest](PaymentRequest) to authorize. Its `delay_duration` and
+    ///   `delay_action` decide what happens if the payment is neither captured nor voided in time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::{
+    ///     api::payment::PaymentRequest,
+    ///     builder::Builder,
+    ///     client::SquareClient,
+    ///     objects::enums::{Currency, PaymentDelayAction},
+    /// };
+    ///
+    /// async {
+    ///     let client = SquareClient::new("some_token");
+    ///
+    ///     let payment = Builder::from(PaymentRequest::default())
+    ///         .source_id("cnon:card-nonce-ok".to_string())
+    ///         .amount(1000, Currency::USD)
+    ///         .delay_duration("PT24H".to_string())
+    ///         .delay_action(PaymentDelayAction::Cancel)
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     let authorization = client.payments()
+    ///         .authorize_then_capture(payment)
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     // once the goods have shipped
+    ///     let payment = authorization.capture().await;
+    /// };
+    /// ```
+    pub async fn authorize_then_capture(self, mut payment: PaymentRequest)
+                                        -> Result<Authorization<'a>, SquareError> {
+        payment.autocomplete = Some(false);
+
+        let client = self.client;
+        let payment = payment_from(client.payments().create(payment).await?)?;
+
+        Ok(Authorization { client, payment })
+    }
+}
+
+/// A payment that was authorized through
+/// [authorize_then_capture](Payments::authorize_then_capture) and is waiting to be captured or
+/// voided.
+pub struct Authorization<'a> {
+    client: &'a SquareClient,
+    payment: Payment,
+}
+
+impl<'a> Authorization<'a> {
+    /// The authorized payment.
+    pub fn payment(&self) -> &Payment {
+        &self.payment
+    }
+
+    /// Completes the payment, charging the authorized funds.
+    pub async fn capture(self) -> Result<Payment, SquareError> {
+        let payment_id = self.payment.id.ok_or_else(|| SquareError::from(None))?;
+
+        payment_from(
+            self.client.payments()
+                .complete(payment_id, self.payment.version_token)
+                .await?
+        )
+    }
+
+    /// Cancels the payment, releasing the authorized funds.
+    pub async fn void(self) -> Result<Payment, SquareError> {
+        let payment_id = self.payment.id.ok_or_else(|| SquareError::from(None))?;
+
+        payment_from(self.client.payments().cancel(payment_id).await?)
+    }
+}
+
+pub(crate) fn payment_from(response: SquareResponse) -> Result<Payment, SquareError> {
+    response.into_responses()
+        .into_iter()
+        .find_map(|response| match response {
+            Response::Payment(payment) => Some(payment),
+            _ => None,
+        })
+        .ok_or_else(|| SquareError::from(None))
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -148,7 +301,7 @@ pub struct ListPaymentsParametersBuilder {
     sort_order: Option<SortOrder>,
     cursor: Option<String>,
     location_id: Option<String>,
-    total: Option<i32>,
+    total: Option<i64>,
     last_4: Option<String>,
     card_brand: Option<String>,
     limit: Option<i32>,
@@ -168,7 +321,7 @@ impl ListPaymentsParametersBuilder {
     }
 
     /// The timestamp for the end of the reporting period, in RFC 3339 format.
-    // Default: The current time.
+    /// Default: The current time.
     pub fn end_time(mut self, end_time: String) -> Self {
         self.end_time = Some(end_time);
 
@@ -205,8 +358,9 @@ impl ListPaymentsParametersBuilder {
         self
     }
 
-    /// The exact amount in the total_money for a payment.
-    pub fn total(mut self, total: i32) -> Self {
+    /// The exact amount in the total_money for a payment, in the smallest denomination of the
+    /// currency.
+    pub fn total(mut self, total: i64) -> Self {
         self.total = Some(total);
 
         self
@@ -314,7 +468,7 @@ pub struct PaymentRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     customer_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    delay_action: Option<String>,
+    delay_action: Option<PaymentDelayAction>,
     #[serde(skip_serializing_if = "Option::is_none")]
     delay_duration: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -337,13 +491,49 @@ pub struct PaymentRequest {
     tip_money: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
     verification_token: Option<String>,
+    // the kind of source set through the builder, if it needs checks of its own
+    #[serde(skip)]
+    source_type: Option<PaymentSourceType>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl PaymentRequest {
+    /// The idempotency key of the request, which is generated when it is built unless one was set.
+    /// Keep it to [cancel](Payments::cancel_by_idempotency_key) the payment if the outcome of the
+    /// request is unknown.
+    pub fn idempotency_key(&self) -> Option<&str> {
+        self.idempotency_key.as_deref()
+    }
 }
 
 impl Validate for PaymentRequest {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        // only card payments can be partially authorized, and bank transfers settle through ACH
+        // so they can not be delayed either
+        let valid_source = match self.source_type {
+            Some(PaymentSourceType::BankAccount) => self.autocomplete != Some(false) &&
+                self.delay_duration.is_none() &&
+                self.delay_action.is_none() &&
+                self.accept_partial_authorization.is_none(),
+            Some(PaymentSourceType::Wallet) | Some(PaymentSourceType::BuyNowPayLater) => {
+                self.accept_partial_authorization.is_none()
+            },
+            _ => true,
+        };
+
         if self.source_id.is_some() &&
-            self.amount_money.is_some() {
-            self.idempotency_key = Some(Uuid::new_v4().to_string());
+            self.amount_money.is_some() &&
+            valid_source &&
+            valid_raw_fields(&mut self) &&
+            valid_app_fee(
+                self.amount_money.as_ref(),
+                self.tip_money.as_ref(),
+                self.app_fee_money.as_ref(),
+            ) {
+            if self.idempotency_key.is_none() {
+                self.idempotency_key = Some(Uuid::new_v4().to_string());
+            }
 
             Ok(self)
         } else {
@@ -352,9 +542,77 @@ impl Validate for PaymentRequest {
     }
 }
 
+impl RawFields for PaymentRequest {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+// the app fee can not exceed the total of the payment, including the tip, and has to be in the
+// same currency
+fn valid_app_fee(amount_money: Option<&Money>,
+                 tip_money: Option<&Money>,
+                 app_fee_money: Option<&Money>) -> bool {
+    let (amount_money, app_fee_money) = match (amount_money, app_fee_money) {
+        (_, None) => return true,
+        (None, Some(_)) => return false,
+        (Some(amount_money), Some(app_fee_money)) => (amount_money, app_fee_money),
+    };
+
+    let tip = match tip_money {
+        Some(tip_money) if tip_money.currency != amount_money.currency => return false,
+        Some(tip_money) => tip_money.amount.unwrap_or_default(),
+        None => 0,
+    };
+    let total = amount_money.amount.unwrap_or_default() + tip;
+    let app_fee = app_fee_money.amount.unwrap_or_default();
+
+    app_fee_money.currency == amount_money.currency && app_fee >= 0 && app_fee <= total
+}
+
 impl<T: ParentBuilder> Builder<PaymentRequest, T> {
     pub fn source_id(mut self, source_id: String) -> Self {
         self.body.source_id = Some(source_id);
+        self.body.source_type = None;
+
+        self
+    }
+
+    /// Pays by ACH bank transfer. The payment can not be delayed or partially authorized, so
+    /// building fails if it is. Square reports the bank account that was debited in the
+    /// [bank_account_details](crate::objects::Payment::bank_account_details) of the payment.
+    /// # Arguments:
+    /// * `source_id` - A bank account token obtained through the ACH payment method of the Web
+    ///   Payments SDK, or the ID of a bank account on file of the customer.
+    pub fn bank_account_source(mut self, source_id: String) -> Self {
+        self.body.source_id = Some(source_id);
+        self.body.source_type = Some(PaymentSourceType::BankAccount);
+
+        self
+    }
+
+    /// Pays through a digital wallet such as Cash App Pay. Square reports the wallet in the
+    /// [wallet_details](crate::objects::Payment::wallet_details) of the payment.
+    /// # Arguments:
+    /// * `source_id` - A payment token obtained through the Cash App Pay method of the Web
+    ///   Payments SDK.
+    pub fn wallet_source(mut self, source_id: String) -> Self {
+        self.body.source_id = Some(source_id);
+        self.body.source_type = Some(PaymentSourceType::Wallet);
+
+        self
+    }
+
+    /// Pays through a buy now pay later service such as Afterpay or Clearpay. Square reports the
+    /// service in the
+    /// [buy_now_pay_later_details](crate::objects::Payment::buy_now_pay_later_details) of the
+    /// payment.
+    /// # Arguments:
+    /// * `source_id` - A payment token obtained through the Afterpay/Clearpay method of the Web
+    ///   Payments SDK.
+    pub fn buy_now_pay_later_source(mut self, source_id: String) -> Self {
+        self.body.source_id = Some(source_id);
+        self.body.source_type = Some(PaymentSourceType::BuyNowPayLater);
 
         self
     }
@@ -370,6 +628,97 @@ impl<T: ParentBuilder> Builder<PaymentRequest, T> {
 
         self
     }
+
+    pub fn customer_id(mut self, customer_id: String) -> Self {
+        self.body.customer_id = Some(customer_id);
+
+        self
+    }
+
+    /// The location ID to associate with the payment. Defaults to the main location of the
+    /// seller if not set.
+    pub fn location_id(mut self, location_id: String) -> Self {
+        self.body.location_id = Some(location_id);
+
+        self
+    }
+
+    /// Associates the payment with an existing order, whose total the `amount` must match.
+    pub fn order_id(mut self, order_id: String) -> Self {
+        self.body.order_id = Some(order_id);
+
+        self
+    }
+
+    /// A user-defined ID to associate the payment with an entity in another system.
+    pub fn reference_id(mut self, reference_id: String) -> Self {
+        self.body.reference_id = Some(reference_id);
+
+        self
+    }
+
+    /// The tip on top of the `amount`, in the same currency.
+    pub fn tip_money(mut self, tip_money: Money) -> Self {
+        self.body.tip_money = Some(tip_money);
+
+        self
+    }
+
+    /// The part of the payment the developer of the application collects, in the same currency as
+    /// the `amount`. Building fails if it exceeds the total of the payment, including the tip.
+    /// Requires the `PAYMENTS_WRITE_ADDITIONAL_RECIPIENTS` permission.
+    pub fn app_fee_money(mut self, app_fee_money: Money) -> Self {
+        self.body.app_fee_money = Some(app_fee_money);
+
+        self
+    }
+
+    /// Text appended to the name of the seller on the card statement of the buyer, up to 20
+    /// characters.
+    pub fn statement_description_identifier(mut self, identifier: String) -> Self {
+        self.body.statement_description_identifier = Some(identifier);
+
+        self
+    }
+
+    /// The ID of the team member taking the payment.
+    pub fn team_member_id(mut self, team_member_id: String) -> Self {
+        self.body.team_member_id = Some(team_member_id);
+
+        self
+    }
+
+    /// Whether the payment is completed right away. Payments that are not have to be completed
+    /// or canceled later, and are required to pay for an order with more than one payment.
+    pub fn autocomplete(mut self, autocomplete: bool) -> Self {
+        self.body.autocomplete = Some(autocomplete);
+
+        self
+    }
+
+    /// The RFC 3339 duration after which a payment that is not completed right away is
+    /// completed or canceled, according to its `delay_action`. For example, `PT36H`.
+    pub fn delay_duration(mut self, delay_duration: String) -> Self {
+        self.body.delay_duration = Some(delay_duration);
+
+        self
+    }
+
+    /// What happens to a payment that is not completed right away once its `delay_duration`
+    /// elapsed. Defaults to [Cancel](PaymentDelayAction::Cancel).
+    pub fn delay_action(mut self, delay_action: PaymentDelayAction) -> Self {
+        self.body.delay_action = Some(delay_action);
+
+        self
+    }
+
+    /// Sets the idempotency key instead of generating one, for instance to retry a request whose
+    /// outcome is unknown without risking a duplicate payment.
+    pub fn idempotency_key(mut self, idempotency_key: String) -> Self {
+        self.body.idempotency_key = Some(idempotency_key);
+
+        self
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -386,17 +735,28 @@ struct CancelByIdempotencyKey {
 #[derive(Serialize, Debug, Deserialize, Default)]
 pub struct UpdatePaymentBody {
     idempotency_key: Option<String>,
-    payment: Payment
+    payment: Payment,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for UpdatePaymentBody {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if !valid_raw_fields(&mut self) {
+            return Err(ValidationError);
+        }
         self.idempotency_key = Some(Uuid::new_v4().to_string());
 
         Ok(self)
     }
 }
 
+impl RawFields for UpdatePaymentBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<UpdatePaymentBody, T> {
     pub fn amount_money(mut self, amount_money: Money) -> Self {
         self.body.payment.amount_money = Some(amount_money);
@@ -437,12 +797,17 @@ impl<T: ParentBuilder> Builder<UpdatePaymentBody, T> {
 
 #[derive(Serialize, Debug, Deserialize)]
 struct CompletePaymentBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
     version_token: Option<String>,
 }
 
 #[cfg(test)]
 mod test_payments {
     use super::*;
+    use crate::objects::enums::{
+        BankAccountTransferType, BankAccountType, BuyNowPayLaterBrand, DigitalWalletBrand,
+        DigitalWalletStatus,
+    };
     
     #[tokio::test]
     async fn test_create_payment() {
@@ -475,7 +840,9 @@ mod test_payments {
             statement_description_identifier: None,
             team_member_id: None,
             tip_money: None,
-            verification_token: None
+            verification_token: None,
+            source_type: None,
+            raw_fields: Default::default(),
         };
 
         let res = sut.payments()
@@ -541,6 +908,221 @@ mod test_payments {
         assert!(res.is_ok())
     }
 
+    #[tokio::test]
+    async fn test_app_fee_payment_request() {
+        let actual = Builder::from(PaymentRequest::default())
+            .source_id("cnon:card-nonce-ok".to_string())
+            .amount(1000, Currency::USD)
+            .tip_money(Money { amount: Some(200), currency: Currency::USD })
+            .app_fee_money(Money { amount: Some(1200), currency: Currency::USD })
+            .statement_description_identifier("ORDER 42".to_string())
+            .team_member_id("some_team_member_id".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        let actual = serde_json::to_value(&actual).unwrap();
+
+        assert_eq!(
+            actual["app_fee_money"],
+            serde_json::json!({ "amount": 1200, "currency": "USD" }),
+        );
+        assert_eq!(actual["tip_money"], serde_json::json!({ "amount": 200, "currency": "USD" }));
+        assert_eq!(actual["statement_description_identifier"], serde_json::json!("ORDER 42"));
+        assert_eq!(actual["team_member_id"], serde_json::json!("some_team_member_id"));
+
+        let invalid = vec![
+            Money { amount: Some(1201), currency: Currency::USD },
+            Money { amount: Some(-1), currency: Currency::USD },
+            Money { amount: Some(100), currency: Currency::EUR },
+        ];
+
+        for app_fee_money in invalid {
+            let actual = Builder::from(PaymentRequest::default())
+                .source_id("cnon:card-nonce-ok".to_string())
+                .amount(1000, Currency::USD)
+                .tip_money(Money { amount: Some(200), currency: Currency::USD })
+                .app_fee_money(app_fee_money)
+                .build()
+                .await;
+
+            assert!(actual.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_payment_request_raw_field() {
+        let actual = Builder::from(PaymentRequest::default())
+            .source_id("cnon:card-nonce-ok".to_string())
+            .amount(1000, Currency::USD)
+            .raw_field("brand_new_field", serde_json::json!({ "enabled": true }))
+            .raw_field("note", serde_json::json!("set before the builder knew about it"))
+            .build()
+            .await
+            .unwrap();
+        let actual = serde_json::to_value(&actual).unwrap();
+
+        assert_eq!(actual["brand_new_field"], serde_json::json!({ "enabled": true }));
+        assert_eq!(actual["note"], serde_json::json!("set before the builder knew about it"));
+        assert_eq!(actual["source_id"], serde_json::json!("cnon:card-nonce-ok"));
+
+        // a raw field shadowing a typed one that is set
+        assert!(Builder::from(PaymentRequest::default())
+            .source_id("cnon:card-nonce-ok".to_string())
+            .amount(1000, Currency::USD)
+            .raw_field("amount_money", serde_json::json!({}))
+            .build()
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_payment_request_idempotency_key() {
+        let generated = Builder::from(PaymentRequest::default())
+            .source_id("cnon:card-nonce-ok".to_string())
+            .amount(10, Currency::USD)
+            .build()
+            .await
+            .unwrap();
+
+        assert!(generated.idempotency_key().is_some());
+
+        let supplied = Builder::from(PaymentRequest::default())
+            .source_id("cnon:card-nonce-ok".to_string())
+            .amount(10, Currency::USD)
+            .idempotency_key("d56c4e7f-c9f2-4204-b757-30abbcfae419".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(supplied.idempotency_key(), Some("d56c4e7f-c9f2-4204-b757-30abbcfae419"));
+    }
+
+    #[tokio::test]
+    async fn test_delayed_capture_payment_request() {
+        let actual = Builder::from(PaymentRequest::default())
+            .source_id("cnon:card-nonce-ok".to_string())
+            .amount(10, Currency::USD)
+            .autocomplete(false)
+            .delay_duration("PT24H".to_string())
+            .delay_action(PaymentDelayAction::Complete)
+            .idempotency_key("d56c4e7f-c9f2-4204-b757-30abbcfae419".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&actual).unwrap(),
+            serde_json::json!({
+                "source_id": "cnon:card-nonce-ok",
+                "idempotency_key": "d56c4e7f-c9f2-4204-b757-30abbcfae419",
+                "amount_money": { "amount": 10, "currency": "USD" },
+                "autocomplete": false,
+                "delay_action": "COMPLETE",
+                "delay_duration": "PT24H",
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bank_account_payment_request() {
+        let actual = Builder::from(PaymentRequest::default())
+            .bank_account_source("bnon:bank-nonce-ok".to_string())
+            .amount(2500, Currency::USD)
+            .idempotency_key("4935a656-a929-4792-b97c-8848be85c27c".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&actual).unwrap(),
+            serde_json::json!({
+                "source_id": "bnon:bank-nonce-ok",
+                "idempotency_key": "4935a656-a929-4792-b97c-8848be85c27c",
+                "amount_money": { "amount": 2500, "currency": "USD" },
+            }),
+        );
+
+        let delayed = Builder::from(PaymentRequest::default())
+            .bank_account_source("bnon:bank-nonce-ok".to_string())
+            .amount(2500, Currency::USD)
+            .autocomplete(false)
+            .build()
+            .await;
+
+        assert!(delayed.is_err());
+    }
+
+    #[test]
+    fn test_bank_account_payment_details() {
+        let payment: Payment = serde_json::from_value(serde_json::json!({
+            "id": "some_payment_id",
+            "source_type": "BANK_ACCOUNT",
+            "bank_account_details": {
+                "bank_name": "Citizens Bank",
+                "transfer_type": "ACH",
+                "account_ownership_type": "INDIVIDUAL",
+                "fingerprint": "sq-1-some-fingerprint",
+                "country": "US",
+                "statement_description": "SQ* Coffee Shop",
+                "ach_details": {
+                    "routing_number": "011401533",
+                    "account_number_suffix": "000",
+                    "account_type": "CHECKING",
+                },
+            },
+        })).unwrap();
+
+        let details = payment.bank_account_details.unwrap();
+        let ach_details = details.ach_details.unwrap();
+
+        assert_eq!(details.transfer_type, Some(BankAccountTransferType::Ach));
+        assert_eq!(ach_details.account_type, Some(BankAccountType::Checking));
+        assert_eq!(ach_details.account_number_suffix, Some("000".to_string()));
+    }
+
+    #[test]
+    fn test_wallet_payment_details() {
+        let payment: Payment = serde_json::from_value(serde_json::json!({
+            "id": "some_payment_id",
+            "source_type": "WALLET",
+            "wallet_details": {
+                "status": "CAPTURED",
+                "brand": "CASH_APP",
+                "cash_app_details": {
+                    "buyer_full_name": "John Doe",
+                    "buyer_country_code": "US",
+                    "buyer_cashtag": "$johndoe",
+                },
+            },
+        })).unwrap();
+
+        assert_eq!(payment.source_type, Some(PaymentSourceType::Wallet));
+
+        let details = payment.wallet_details.unwrap();
+
+        assert_eq!(details.brand, Some(DigitalWalletBrand::CashApp));
+        assert_eq!(details.status, Some(DigitalWalletStatus::Captured));
+        assert_eq!(details.cash_app_details.unwrap().buyer_cashtag, Some("$johndoe".to_string()));
+
+        let payment: Payment = serde_json::from_value(serde_json::json!({
+            "id": "some_payment_id",
+            "source_type": "BUY_NOW_PAY_LATER",
+            "buy_now_pay_later_details": {
+                "brand": "AFTERPAY",
+                "afterpay_details": { "email_address": "john.doe@example.com" },
+            },
+        })).unwrap();
+
+        let details = payment.buy_now_pay_later_details.unwrap();
+
+        assert_eq!(details.brand, Some(BuyNowPayLaterBrand::Afterpay));
+        assert_eq!(
+            details.afterpay_details.unwrap().email_address,
+            Some("john.doe@example.com".to_string()),
+        );
+    }
+
     #[tokio::test]
     async fn test_get_payment() {
         use dotenv::dotenv;
@@ -601,7 +1183,8 @@ mod test_payments {
                 updated_at: None,
                 version_token: None,
                 wallet_details: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(UpdatePaymentBody::default())
@@ -668,7 +1251,8 @@ mod test_payments {
                 updated_at: None,
                 version_token: None,
                 wallet_details: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let res = sut.payments()
@@ -0,0 +1,286 @@
+/*!
+Monitoring of pre-authorization holds, built on top of the payments functionality of the
+[Square API](https://developer.squareup.com).
+
+A payment created with `autocomplete` set to false, such as the deposit of a rental or a hotel
+stay, stays `APPROVED` until it is completed or canceled. Once its `delayed_until` time has come
+Square applies its `delay_action`, which by default cancels the payment and releases the funds.
+[monitor_holds](Payments::monitor_holds) finds the approved payments whose hold runs out within
+the warning period of a [HoldMonitor](HoldMonitor), calls its callbacks for each of them and
+completes or cancels them according to its [HoldPolicy](HoldPolicy).
+
+Run it more often than the warning period, e.g. every hour with a warning period of a day. A hold
+is reported on every run until it was completed, canceled or ran out.
+
+# Example: Capture holds before they run out
+```no_run
+use square_ox::{
+    api::payment::holds::{HoldMonitor, HoldPolicy},
+    client::SquareClient,
+};
+use chrono::Duration;
+
+async {
+    let monitor = HoldMonitor::new()
+        .warn_before(Duration::hours(6))
+        .policy(HoldPolicy::Complete)
+        .on_expiring(|hold| {
+            println!("{:?} runs out at {}", hold.payment.id, hold.delayed_until);
+        });
+
+    let outcomes = SquareClient::new("some_token")
+        .payments()
+        .monitor_holds(&monitor, Some("some_location_id".to_string()))
+        .await
+        .unwrap();
+};
+```
+ */
+
+use crate::api::payment::{payment_from, ListPaymentsParametersBuilder, Payments};
+use crate::errors::SquareError;
+use crate::objects::{Payment, Response};
+
+use chrono::{DateTime, Duration, FixedOffset, SecondsFormat, Utc};
+
+// the status of a payment that was authorized but neither completed nor canceled
+const APPROVED: &str = "APPROVED";
+
+// the warning period and how far back to look for approved payments unless told otherwise
+const DEFAULT_WARN_BEFORE_HOURS: i64 = 24;
+const DEFAULT_LOOKBACK_DAYS: i64 = 7;
+
+type Callback = Box<dyn Fn(&ExpiringHold) + Send + Sync>;
+
+/// What to do about a hold that runs out within the warning period.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HoldPolicy {
+    /// Only call the callbacks, leaving the payment to its `delay_action`.
+    Warn,
+    /// Complete the payment, charging the authorized funds.
+    Complete,
+    /// Cancel the payment, releasing the authorized funds.
+    Cancel,
+}
+
+/// When to warn about approved payments whose hold runs out, and what to do about them.
+pub struct HoldMonitor {
+    warn_before: Duration,
+    lookback: Duration,
+    policy: HoldPolicy,
+    callbacks: Vec<Callback>,
+}
+
+impl Default for HoldMonitor {
+    fn default() -> Self {
+        HoldMonitor {
+            warn_before: Duration::hours(DEFAULT_WARN_BEFORE_HOURS),
+            lookback: Duration::days(DEFAULT_LOOKBACK_DAYS),
+            policy: HoldPolicy::Warn,
+            callbacks: vec![],
+        }
+    }
+}
+
+impl HoldMonitor {
+    /// A monitor warning 24 hours before a hold runs out about the payments of the past 7 days,
+    /// and leaving them to their `delay_action`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// How long before its `delayed_until` time a hold is reported.
+    pub fn warn_before(mut self, warn_before: Duration) -> Self {
+        self.warn_before = warn_before;
+
+        self
+    }
+
+    /// How far back to look for approved payments. Holds longer than that are not found.
+    pub fn lookback(mut self, lookback: Duration) -> Self {
+        self.lookback = lookback;
+
+        self
+    }
+
+    /// What to do about the holds that are reported.
+    pub fn policy(mut self, policy: HoldPolicy) -> Self {
+        self.policy = policy;
+
+        self
+    }
+
+    /// Registers a callback called with every hold that is reported, before the policy is
+    /// applied to it. Callbacks run in the order they were registered.
+    pub fn on_expiring<F>(mut self, callback: F) -> Self
+        where F: Fn(&ExpiringHold) + Send + Sync + 'static {
+        self.callbacks.push(Box::new(callback));
+
+        self
+    }
+
+    /// The hold of the payment if it is approved and runs out within the warning period at the
+    /// given time, including holds that have already run out but were not applied yet.
+    pub fn check(&self, payment: &Payment, now: DateTime<Utc>) -> Option<ExpiringHold> {
+        if payment.status.as_deref() != Some(APPROVED) {
+            return None;
+        }
+
+        let delayed_until = payment.delayed_until.as_deref()
+            .and_then(|delayed_until| DateTime::parse_from_rfc3339(delayed_until).ok())?;
+        if delayed_until - self.warn_before > now {
+            return None;
+        }
+
+        Some(ExpiringHold {
+            payment: payment.clone(),
+            delayed_until,
+            remaining: delayed_until.with_timezone(&Utc) - now,
+        })
+    }
+}
+
+/// An approved payment whose hold runs out within the warning period.
+#[derive(Clone, Debug)]
+pub struct ExpiringHold {
+    pub payment: Payment,
+    /// The time at which Square applies the `delay_action` of the payment.
+    pub delayed_until: DateTime<FixedOffset>,
+    /// The time left until then, negative if it has passed.
+    pub remaining: Duration,
+}
+
+/// A reported hold along with the result of applying the policy to it.
+#[derive(Debug)]
+pub struct HoldOutcome {
+    pub hold: ExpiringHold,
+    /// The completed or canceled payment, if the policy is not [Warn](HoldPolicy::Warn).
+    pub payment: Option<Result<Payment, SquareError>>,
+}
+
+impl<'a> Payments<'a> {
+    /// Lists the approved payments created at or after the given time, i.e. those that were
+    /// authorized but neither completed nor canceled yet.
+    /// # Arguments:
+    /// * `location_id` - The location to list the payments of, the main location if `None`.
+    /// * `begin_time` - The earliest creation time of the payments, in RFC 3339 format.
+    pub async fn approved(self, location_id: Option<String>, begin_time: String)
+                          -> Result<Vec<Payment>, SquareError> {
+        let mut payments = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut parameters = ListPaymentsParametersBuilder::new()
+                .begin_time(begin_time.clone());
+            if let Some(location_id) = location_id.clone() {
+                parameters = parameters.location_id(location_id);
+            }
+            if let Some(cursor) = cursor {
+                parameters = parameters.cursor(cursor);
+            }
+
+            let res = self.client.payments().list(Some(parameters.build().await)).await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::Payments(page) = response {
+                    payments.extend(page.into_iter()
+                        .filter(|payment| payment.status.as_deref() == Some(APPROVED)));
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(payments);
+            }
+        }
+    }
+
+    /// Runs the monitor over the approved payments at the location, calling its callbacks for
+    /// every hold that runs out within the warning period and applying its policy to them.
+    /// # Arguments:
+    /// * `monitor` - When to report the holds and what to do about them.
+    /// * `location_id` - The location to monitor, the main location if `None`.
+    pub async fn monitor_holds(self, monitor: &HoldMonitor, location_id: Option<String>)
+                               -> Result<Vec<HoldOutcome>, SquareError> {
+        let now = Utc::now();
+        let begin_time = (now - monitor.lookback).to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut outcomes = vec![];
+
+        for payment in self.client.payments().approved(location_id, begin_time).await? {
+            let hold = match monitor.check(&payment, now) {
+                Some(hold) => hold,
+                None => continue,
+            };
+
+            for callback in monitor.callbacks.iter() {
+                callback(&hold);
+            }
+
+            let payment = match (monitor.policy, hold.payment.id.clone()) {
+                (HoldPolicy::Warn, _) | (_, None) => None,
+                (HoldPolicy::Complete, Some(payment_id)) => Some(
+                    self.client.payments()
+                        .complete(payment_id, hold.payment.version_token.clone())
+                        .await
+                        .and_then(payment_from)
+                ),
+                (HoldPolicy::Cancel, Some(payment_id)) => Some(
+                    self.client.payments().cancel(payment_id).await.and_then(payment_from)
+                ),
+            };
+
+            outcomes.push(HoldOutcome { hold, payment });
+        }
+
+        Ok(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod test_holds {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn payment(status: &str, delayed_until: &str) -> Payment {
+        serde_json::from_value(serde_json::json!({
+            "id": "some_payment_id",
+            "status": status,
+            "delay_action": "CANCEL",
+            "delayed_until": delayed_until,
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_check() {
+        let sut = HoldMonitor::new().warn_before(Duration::hours(6));
+        let at = |day, hour| Utc.with_ymd_and_hms(2022, 6, day, hour, 0, 0).unwrap();
+        let approved = payment("APPROVED", "2022-06-08T12:00:00Z");
+
+        assert!(sut.check(&approved, at(8, 5)).is_none());
+
+        let hold = sut.check(&approved, at(8, 6)).unwrap();
+        assert_eq!(hold.delayed_until, at(8, 12).fixed_offset());
+        assert_eq!(hold.remaining, Duration::hours(6));
+
+        assert_eq!(sut.check(&approved, at(8, 13)).unwrap().remaining, Duration::hours(-1));
+        assert!(sut.check(&payment("COMPLETED", "2022-06-08T12:00:00Z"), at(8, 13)).is_none());
+        assert!(sut.check(&payment("APPROVED", "tomorrow"), at(8, 13)).is_none());
+    }
+
+    #[test]
+    fn test_on_expiring() {
+        use std::sync::{Arc, Mutex};
+
+        let reported = Arc::new(Mutex::new(vec![]));
+        let sink = reported.clone();
+        let sut = HoldMonitor::new()
+            .policy(HoldPolicy::Cancel)
+            .on_expiring(move |hold| sink.lock().unwrap().push(hold.payment.id.clone()));
+
+        let hold = sut.check(&payment("APPROVED", "2022-06-08T12:00:00Z"), Utc::now()).unwrap();
+        sut.callbacks.iter().for_each(|callback| callback(&hold));
+
+        assert_eq!(sut.policy, HoldPolicy::Cancel);
+        assert_eq!(*reported.lock().unwrap(), vec![Some("some_payment_id".to_string())]);
+    }
+}
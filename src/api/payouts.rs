@@ -0,0 +1,160 @@
+/*!
+Payouts functionality of the [Square API](https://developer.squareup.com).
+ */
+
+use crate::client::SquareClient;
+use crate::api::{Verb, SquareAPI};
+use crate::errors::SquareError;
+use crate::response::SquareResponse;
+use crate::objects::enums::SortOrder;
+
+impl SquareClient {
+    pub fn payouts(&self) -> Payouts<'_> {
+        Payouts {
+            client: self,
+        }
+    }
+}
+
+pub struct Payouts<'a> {
+    client: &'a SquareClient,
+}
+
+impl<'a> Payouts<'a> {
+    /// Retrieves the details of a specific [Payout](crate::objects::Payout).
+    /// # Arguments:
+    /// * `payout_id` - The ID of the payout to retrieve the information for.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/payouts-api/get-payout)
+    pub async fn get(self, payout_id: String) -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Payouts(format!("/{}", payout_id)),
+            None::<&()>,
+            None,
+        ).await
+    }
+
+    /// Retrieves a list of all [PayoutEntry](crate::objects::PayoutEntry)s for a specific payout.
+    /// # Arguments:
+    /// * `payout_id` - The ID of the payout to retrieve the entries for.
+    /// * `parameters` - A vector of parameters created through the
+    ///   [ListPayoutEntriesParametersBuilder](ListPayoutEntriesParametersBuilder).
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/payouts-api/list-payout-entries)
+    pub async fn list_entries(self, payout_id: String, parameters: Option<Vec<(String, String)>>)
+                              -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Payouts(format!("/{}/payout-entries", payout_id)),
+            None::<&()>,
+            parameters,
+        ).await
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// ListPayoutEntriesParametersBuilder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Default)]
+pub struct ListPayoutEntriesParametersBuilder {
+    sort_order: Option<SortOrder>,
+    cursor: Option<String>,
+    limit: Option<i32>,
+}
+
+impl ListPayoutEntriesParametersBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The order in which the entries are listed, by the time they became effective.
+    pub fn sort_ascending(mut self) -> Self {
+        self.sort_order = Some(SortOrder::Asc);
+
+        self
+    }
+
+    /// The order in which the entries are listed, by the time they became effective.
+    pub fn sort_descending(mut self) -> Self {
+        self.sort_order = Some(SortOrder::Desc);
+
+        self
+    }
+
+    /// A pagination cursor returned by a previous call to this endpoint.
+    /// Provide this cursor to retrieve the next set of results for the original query.
+    pub fn cursor(mut self, cursor: String) -> Self {
+        self.cursor = Some(cursor);
+
+        self
+    }
+
+    /// The maximum number of results to be returned in a single page, up to 100.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+
+        self
+    }
+
+    pub async fn build(self) -> Vec<(String, String)> {
+        let ListPayoutEntriesParametersBuilder {
+            sort_order,
+            cursor,
+            limit,
+        } = self;
+
+        let mut res = vec![];
+
+        if let Some(sort_order) = sort_order {
+            res.push(("sort_order".to_string(), sort_order.to_string()))
+        }
+        if let Some(cursor) = cursor {
+            res.push(("cursor".to_string(), cursor))
+        }
+        if let Some(limit) = limit {
+            res.push(("limit".to_string(), limit.to_string()))
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod test_payouts {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_payout_entries_parameters_builder() {
+        let expected = vec![
+            ("sort_order".to_string(), "ASC".to_string()),
+            ("cursor".to_string(), "some_cursor".to_string()),
+            ("limit".to_string(), "50".to_string()),
+        ];
+
+        let actual = ListPayoutEntriesParametersBuilder::new()
+            .limit(50)
+            .cursor("some_cursor".to_string())
+            .sort_ascending()
+            .build()
+            .await;
+
+        assert_eq!(expected, actual)
+    }
+
+    #[tokio::test]
+    async fn test_list_payout_entries() {
+        use dotenv::dotenv;
+        use std::env;
+
+        dotenv().ok();
+        let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
+        let sut = SquareClient::new(&access_token);
+
+        let res = sut.payouts()
+            .list_entries("po_f3c0fb38-a5ce-427d-b858-52b925b72e45".to_string(), None)
+            .await;
+
+        assert!(res.is_ok())
+    }
+}
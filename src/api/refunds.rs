@@ -0,0 +1,184 @@
+/*!
+Refunds functionality of the [Square API](https://developer.squareup.com).
+ */
+
+use crate::client::SquareClient;
+use crate::api::{Verb, SquareAPI};
+use crate::builder::{valid_raw_fields, Builder, ParentBuilder, RawFields, Validate};
+use crate::errors::{SquareError, ValidationError};
+use crate::objects::{Money, OrderReturn};
+use crate::response::SquareResponse;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+impl SquareClient {
+    pub fn refunds(&self) -> Refunds<'_> {
+        Refunds {
+            client: self,
+        }
+    }
+}
+
+pub struct Refunds<'a> {
+    client: &'a SquareClient,
+}
+
+impl<'a> Refunds<'a> {
+    /// Retrieves a specific [PaymentRefund](crate::objects::PaymentRefund) using the `refund_id`.
+    /// # Arguments:
+    /// * `refund_id` - The unique ID for the desired refund.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/refunds-api/get-payment-refund)
+    pub async fn get(self, refund_id: String) -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Refunds(format!("/{}", refund_id)),
+            None::<&()>,
+            None,
+        ).await
+    }
+
+    /// Refunds a payment in full or in part. To refund the items of a return, create the order
+    /// holding the [OrderReturn](crate::objects::OrderReturn) first and refund the payment of the
+    /// source order by the amount of the return.
+    /// # Arguments:
+    /// * `body` - The refund, built through the builder of [RefundPaymentBody](RefundPaymentBody).
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/refunds-api/refund-payment)
+    pub async fn refund_payment(self, body: RefundPaymentBody)
+                                -> Result<SquareResponse, SquareError> {
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Refunds("".to_string()),
+            Some(&body),
+            None,
+        ).await
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct RefundPaymentBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idempotency_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amount_money: Option<Money>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payment_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for RefundPaymentBody {
+    fn validate(mut self) -> Result<Self, ValidationError> {
+        let amount = self.amount_money.as_ref().and_then(|money| money.amount);
+
+        if self.payment_id.is_some()
+            && amount.is_some_and(|amount| amount > 0)
+            && valid_raw_fields(&mut self) {
+            self.idempotency_key = Some(Uuid::new_v4().to_string());
+
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for RefundPaymentBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<RefundPaymentBody, T> {
+    /// The ID of the payment to refund.
+    pub fn payment_id(mut self, payment_id: String) -> Self {
+        self.body.payment_id = Some(payment_id);
+
+        self
+    }
+
+    /// The amount to refund, which may not exceed what is left of the payment.
+    pub fn amount_money(mut self, amount_money: Money) -> Self {
+        self.body.amount_money = Some(amount_money);
+
+        self
+    }
+
+    pub fn reason(mut self, reason: String) -> Self {
+        self.body.reason = Some(reason);
+
+        self
+    }
+
+    /// Refunds the total of a return, as calculated by Square once the order holding the return
+    /// was created.
+    pub fn for_return(mut self, order_return: &OrderReturn) -> Self {
+        if let Some(total_money) = order_return.return_amounts.as_ref()
+            .and_then(|amounts| amounts.total_money.clone()) {
+            self.body.amount_money = Some(total_money);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_refunds {
+    use super::*;
+    use crate::objects::{enums::Currency, OrderMoneyAmounts};
+
+    #[tokio::test]
+    async fn test_refund_payment_body_builder() {
+        let order_return = OrderReturn {
+            source_order_id: Some("source_order_id".to_string()),
+            return_amounts: Some(OrderMoneyAmounts {
+                total_money: Some(Money { amount: Some(1250), currency: Currency::USD }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let actual = Builder::from(RefundPaymentBody::default())
+            .payment_id("payment_id".to_string())
+            .reason("Returned items".to_string())
+            .for_return(&order_return)
+            .build()
+            .await
+            .unwrap();
+
+        assert!(actual.idempotency_key.is_some());
+        assert_eq!(
+            actual.amount_money,
+            Some(Money { amount: Some(1250), currency: Currency::USD }),
+        );
+
+        // a return whose amounts are not calculated yet leaves nothing to refund
+        assert!(Builder::from(RefundPaymentBody::default())
+            .payment_id("payment_id".to_string())
+            .for_return(&OrderReturn::default())
+            .build()
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_refund() {
+        use dotenv::dotenv;
+        use std::env;
+
+        dotenv().ok();
+        let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN to be set");
+        let sut = SquareClient::new(&access_token);
+
+        let res = sut.refunds()
+            .get("bP9mAsEMYPUGjjGNaNO5ZDVyLhSZY_69MmgHubkLqx9wGhnmenRUHOaKitE6llfZuxcWYjGxd".to_string())
+            .await;
+
+        assert!(res.is_ok())
+    }
+}
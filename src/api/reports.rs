@@ -0,0 +1,170 @@
+/*!
+Reports built on top of several endpoints of the [Square API](https://developer.squareup.com).
+
+Rather than mapping to a single endpoint, each report cross-references the objects returned by
+multiple endpoints into typed rows.
+ */
+
+use crate::client::SquareClient;
+use crate::api::payouts::ListPayoutEntriesParametersBuilder;
+use crate::errors::SquareError;
+use crate::objects::{Payment, PaymentRefund, PayoutEntry, Response};
+use crate::objects::enums::PayoutEntryType;
+
+impl SquareClient {
+    pub fn reports(&self) -> Reports<'_> {
+        Reports {
+            client: self,
+        }
+    }
+}
+
+pub struct Reports<'a> {
+    client: &'a SquareClient,
+}
+
+/// A single refund that made up part of a payout, along with the payment it was refunded from.
+#[derive(Clone, Debug)]
+pub struct PayoutRefund {
+    /// The payout entry of type `REFUND`, holding the amounts deducted from the payout.
+    pub entry: PayoutEntry,
+    pub refund: PaymentRefund,
+    /// The original payment the refund was issued against.
+    pub payment: Payment,
+}
+
+impl<'a> Reports<'a> {
+    /// Lists every refund deducted from the given payout, joining each payout entry of type
+    /// `REFUND` with its [PaymentRefund](PaymentRefund) and the original [Payment](Payment).
+    /// # Arguments:
+    /// * `payout_id` - The ID of the payout to reconcile.
+    ///
+    /// # Example
+    /// ```rust
+    /// use square_ox::client::SquareClient;
+    ///
+    /// async {
+    ///     let refunds = SquareClient::new("some_token")
+    ///         .reports()
+    ///         .refunds_by_payout("po_f3c0fb38-a5ce-427d-b858-52b925b72e45".to_string())
+    ///         .await;
+    /// };
+    /// ```
+    pub async fn refunds_by_payout(self, payout_id: String)
+                                   -> Result<Vec<PayoutRefund>, SquareError> {
+        let entries = self.payout_entries(&payout_id).await?;
+
+        let mut rows = vec![];
+
+        for (entry, refund_id) in refund_entries(entries) {
+            let refund = self.client.refunds()
+                .get(refund_id)
+                .await?
+                .into_responses()
+                .into_iter()
+                .find_map(|response| match response {
+                    Response::Refund(refund) => Some(refund),
+                    _ => None,
+                })
+                .ok_or_else(|| SquareError::from(None))?;
+
+            let payment_id = entry.type_refund_details.as_ref()
+                .and_then(|details| details.payment_id.clone())
+                .or_else(|| refund.payment_id.clone())
+                .ok_or_else(|| SquareError::from(None))?;
+
+            let payment = self.client.payments()
+                .get(payment_id)
+                .await?
+                .into_responses()
+                .into_iter()
+                .find_map(|response| match response {
+                    Response::Payment(payment) => Some(payment),
+                    _ => None,
+                })
+                .ok_or_else(|| SquareError::from(None))?;
+
+            rows.push(PayoutRefund { entry, refund, payment });
+        }
+
+        Ok(rows)
+    }
+
+    // all of the entries of the payout, following the cursor through every page
+    async fn payout_entries(&self, payout_id: &str) -> Result<Vec<PayoutEntry>, SquareError> {
+        let mut entries = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut parameters = ListPayoutEntriesParametersBuilder::new().limit(100);
+            if let Some(cursor) = cursor {
+                parameters = parameters.cursor(cursor);
+            }
+
+            let res = self.client.payouts()
+                .list_entries(payout_id.to_string(), Some(parameters.build().await))
+                .await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::PayoutEntries(page) = response {
+                    entries.extend(page);
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(entries);
+            }
+        }
+    }
+}
+
+// the entries of type `REFUND` paired with the ID of the refund they stem from
+fn refund_entries(entries: Vec<PayoutEntry>) -> Vec<(PayoutEntry, String)> {
+    entries.into_iter()
+        .filter(|entry| entry.type_name == Some(PayoutEntryType::Refund))
+        .filter_map(|entry| {
+            let refund_id = entry.type_refund_details.as_ref()?.refund_id.clone()?;
+
+            Some((entry, refund_id))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_reports {
+    use super::*;
+
+    #[test]
+    fn test_refund_entries() {
+        let entries: Vec<PayoutEntry> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "poe_ZQWcw41d0SGJS6IWd4cSi8mKHk",
+                "payout_id": "po_f3c0fb38-a5ce-427d-b858-52b925b72e45",
+                "type": "CHARGE",
+                "type_charge_details": { "payment_id": "HVdG62HeMlti8YYf94oxrN" },
+            },
+            {
+                "id": "poe_EibbY9Ob1d0SGJS6IWd4cSiSi6wkaPk",
+                "payout_id": "po_f3c0fb38-a5ce-427d-b858-52b925b72e45",
+                "type": "REFUND",
+                "net_amount_money": { "amount": -1000, "currency": "USD" },
+                "type_refund_details": {
+                    "payment_id": "HVdG62HeMlti8YYf94oxrN",
+                    "refund_id": "HVdG62HeMlti8YYf94oxrN_dR8Fdm9NqBMW8r7ne7Vu1V7ZQ",
+                },
+            },
+            {
+                "id": "poe_Ib1d0SGJS6IWd4cSiSi6wkaPkdkI",
+                "payout_id": "po_f3c0fb38-a5ce-427d-b858-52b925b72e45",
+                "type": "REFUND",
+            },
+        ])).unwrap();
+
+        let actual = refund_entries(entries);
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].0.id, Some("poe_EibbY9Ob1d0SGJS6IWd4cSiSi6wkaPk".to_string()));
+        assert_eq!(actual[0].1, "HVdG62HeMlti8YYf94oxrN_dR8Fdm9NqBMW8r7ne7Vu1V7ZQ".to_string());
+    }
+}
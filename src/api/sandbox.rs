@@ -0,0 +1,658 @@
+/*!
+Test data seeding for the sandbox of the [Square API](https://developer.squareup.com).
+
+A [SeedPlan](SeedPlan) describes a coherent dataset: locations, catalog items with variations,
+customers with a card on file, and orders paid for with those cards. [Seeding](Sandbox::seed) the
+plan creates all of it and tags every object with the id of the plan, so the dataset can be
+[torn down](Sandbox::teardown) again by that id alone, e.g. after a demo or once a bug report has
+been reproduced.
+
+Both only work with a sandboxed [SquareClient](SquareClient) and fail for a production one.
+
+# Example: Seed and tear down a dataset
+```no_run
+use square_ox::{
+    api::sandbox::SeedPlan,
+    builder::Builder,
+    client::SquareClient,
+};
+
+async {
+    let client = SquareClient::new("some_sandbox_token");
+
+    let plan = Builder::from(SeedPlan::default())
+        .locations(2)
+        .items(5)
+        .variations_per_item(2)
+        .customers(3)
+        .orders(4)
+        .build()
+        .await
+        .unwrap();
+
+    let seeded = client.sandbox().seed(plan).await.unwrap();
+
+    // ... run the demo ...
+
+    client.sandbox().teardown(seeded.plan_id).await.unwrap();
+};
+```
+ */
+
+use crate::api::cards::{CardWrapper, ListCardsQueryBuilder};
+use crate::api::catalog::{ObjectUpsertRequest, SearchCatalogObjectsBody};
+use crate::api::customers::{CustomerDelete, CustomerSearchQuery};
+use crate::api::locations::LocationCreationWrapper;
+use crate::api::orders::{order_from, CreateOrderBody};
+use crate::api::payment::PaymentRequest;
+use crate::builder::{Builder, ParentBuilder, Validate};
+use crate::client::{ClientMode, SquareClient};
+use crate::errors::{BuildError, SquareError, ValidationError};
+use crate::objects::{
+    Card, CatalogItem, CatalogItemVariation, CatalogObject, CatalogObjectVariation, CatalogQuery,
+    CatalogQueryPrefix, Customer, Location, Money, Order, OrderLineItem, Payment, Response,
+};
+use crate::objects::enums::{CatalogObjectType, CatalogPricingType, Currency, LocationStatus};
+
+use uuid::Uuid;
+
+/// The card nonce the sandbox accepts to store a valid card on file.
+pub const SANDBOX_CARD_NONCE: &str = "cnon:card-nonce-ok";
+
+impl SquareClient {
+    pub fn sandbox(&self) -> Sandbox<'_> {
+        Sandbox {
+            client: self,
+        }
+    }
+}
+
+pub struct Sandbox<'a> {
+    client: &'a SquareClient,
+}
+
+// -------------------------------------------------------------------------------------------------
+// SeedPlan builder implementation
+// -------------------------------------------------------------------------------------------------
+/// The dataset to [seed](Sandbox::seed), built through the [Builder](Builder) of a
+/// [SeedPlan](SeedPlan).
+#[derive(Clone, Debug, Default)]
+pub struct SeedPlan {
+    plan_id: Option<String>,
+    locations: usize,
+    items: usize,
+    variations_per_item: usize,
+    customers: usize,
+    orders: usize,
+    currency: Option<Currency>,
+}
+
+impl Validate for SeedPlan {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.items > 0 && self.variations_per_item == 0 {
+            self.variations_per_item = 1;
+        }
+
+        if self.orders > 0 && (self.items == 0 || self.customers == 0) {
+            return Err(ValidationError);
+        }
+
+        if self.plan_id.is_none() {
+            self.plan_id = Some(format!("seed-{}", &Uuid::new_v4().to_simple().to_string()[..8]));
+        }
+
+        Ok(self)
+    }
+}
+
+impl<T: ParentBuilder> Builder<SeedPlan, T> {
+    /// The id every seeded object is tagged with. A random one is generated if not set.
+    pub fn plan_id(mut self, plan_id: String) -> Self {
+        self.body.plan_id = Some(plan_id);
+
+        self
+    }
+
+    /// The number of locations to create. Without any, the existing main location is used.
+    pub fn locations(mut self, locations: usize) -> Self {
+        self.body.locations = locations;
+
+        self
+    }
+
+    pub fn items(mut self, items: usize) -> Self {
+        self.body.items = items;
+
+        self
+    }
+
+    /// The number of variations of each item, at least one.
+    pub fn variations_per_item(mut self, variations_per_item: usize) -> Self {
+        self.body.variations_per_item = variations_per_item;
+
+        self
+    }
+
+    /// The number of customers to create, each with a card on file.
+    pub fn customers(mut self, customers: usize) -> Self {
+        self.body.customers = customers;
+
+        self
+    }
+
+    /// The number of orders to create and pay for, which requires items and customers.
+    pub fn orders(mut self, orders: usize) -> Self {
+        self.body.orders = orders;
+
+        self
+    }
+
+    /// The currency of the item prices. Defaults to USD, and must match the currency of the
+    /// locations.
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.body.currency = Some(currency);
+
+        self
+    }
+}
+
+/// The objects created by [seeding](Sandbox::seed) a [SeedPlan](SeedPlan).
+#[derive(Clone, Debug, Default)]
+pub struct SeededData {
+    pub plan_id: String,
+    pub locations: Vec<Location>,
+    pub items: Vec<CatalogObject>,
+    pub customers: Vec<Customer>,
+    pub cards: Vec<Card>,
+    pub orders: Vec<Order>,
+    pub payments: Vec<Payment>,
+}
+
+/// The ids of the objects removed by [tearing down](Sandbox::teardown) a seeded dataset.
+#[derive(Clone, Debug, Default)]
+pub struct Teardown {
+    pub disabled_card_ids: Vec<String>,
+    pub deleted_customer_ids: Vec<String>,
+    pub deleted_item_ids: Vec<String>,
+    pub deactivated_location_ids: Vec<String>,
+}
+
+impl<'a> Sandbox<'a> {
+    /// Creates the dataset described by the [SeedPlan](SeedPlan).
+    ///
+    /// Locations and items are named after the id of the plan, while customers, cards, orders and
+    /// payments carry it as their `reference_id`. Orders are spread over the locations and
+    /// customers, each holding a single item variation and paid for in full with the card of its
+    /// customer.
+    pub async fn seed(self, plan: SeedPlan) -> Result<SeededData, SquareError> {
+        self.ensure_sandbox()?;
+
+        let plan_id = plan.plan_id.clone().ok_or_else(|| SquareError::invalid_request(
+            "MISSING_REQUIRED_PARAMETER",
+            "plan_id",
+            "the seed plan has no id, build it through its Builder to generate one".to_string(),
+        ))?;
+        let currency = plan.currency.clone().unwrap_or(Currency::USD);
+        let mut seeded = SeededData { plan_id: plan_id.clone(), ..Default::default() };
+
+        for index in 0..plan.locations {
+            let location = Builder::from(LocationCreationWrapper::default())
+                .name(format!("{} Location {}", plan_id, index + 1))
+                .description(format!("Seeded by the sandbox plan {}.", plan_id))
+                .build()
+                .await
+                .map_err(invalid_body("location"))?;
+
+            let location = response_of(
+                self.client.locations().create(location).await?,
+                "location",
+                |response| match response {
+                    Response::Location(location) => Some(location),
+                    _ => None,
+                },
+            )?;
+            seeded.locations.push(location);
+        }
+        if seeded.locations.is_empty() {
+            seeded.locations.push(self.main_location().await?);
+        }
+
+        for index in 0..plan.items {
+            let object = item_object(&plan_id, index, plan.variations_per_item, &currency);
+            let mut upsert = Builder::from(ObjectUpsertRequest::default())
+                .id(object.id.clone().unwrap_or_default())
+                .object_type(CatalogObjectType::Item);
+            if let Some(item_data) = object.item_data {
+                upsert = upsert.item_data(item_data);
+            }
+            let upsert = upsert.build().await.map_err(invalid_body("catalog_object"))?;
+
+            let item = response_of(
+                self.client.catalog().upsert_object(upsert).await?,
+                "catalog_object",
+                |response| match response {
+                    Response::CatalogObject(object) => Some(object),
+                    _ => None,
+                },
+            )?;
+            seeded.items.push(item);
+        }
+
+        for index in 0..plan.customers {
+            let customer = Builder::from(Customer::default())
+                .given_name("Seed".to_string())
+                .family_name(format!("Customer {}", index + 1))
+                .email_address(format!("{}+{}@example.com", plan_id, index + 1))
+                .reference_id(plan_id.clone())
+                .build()
+                .await
+                .map_err(invalid_body("customer"))?;
+
+            let customer = response_of(
+                self.client.customers().create(customer).await?,
+                "customer",
+                |response| match response {
+                    Response::Customer(customer) => Some(customer),
+                    _ => None,
+                },
+            )?;
+
+            let card = Builder::from(CardWrapper::default())
+                .source_id(SANDBOX_CARD_NONCE.to_string())
+                .customer_id(customer.id.clone().unwrap_or_default())
+                .reference_id(plan_id.clone())
+                .build()
+                .await
+                .map_err(invalid_body("card"))?;
+
+            let card = response_of(
+                self.client.cards().create(card).await?,
+                "card",
+                |response| match response {
+                    Response::Card(card) => Some(card),
+                    _ => None,
+                },
+            )?;
+
+            seeded.customers.push(customer);
+            seeded.cards.push(card);
+        }
+
+        for index in 0..plan.orders {
+            let location = &seeded.locations[index % seeded.locations.len()];
+            let customer = &seeded.customers[index % seeded.customers.len()];
+            let card = &seeded.cards[index % seeded.cards.len()];
+            let variation_id = first_variation_id(&seeded.items[index % seeded.items.len()])
+                .ok_or_else(|| SquareError::invalid_request(
+                    "NOT_FOUND",
+                    "variations",
+                    "a seeded item has no variation to order".to_string(),
+                ))?;
+
+            let order = Builder::from(CreateOrderBody::default())
+                .location_id(location.id.clone().unwrap_or_default())
+                .customer_id(customer.id.clone().unwrap_or_default())
+                .reference_id(plan_id.clone())
+                .add_line_item(OrderLineItem {
+                    quantity: "1".to_string(),
+                    catalog_object_id: Some(variation_id),
+                    ..Default::default()
+                })
+                .build()
+                .await
+                .map_err(invalid_body("order"))?;
+            let order = order_from(self.client.orders().create(order).await?)?;
+
+            let Money { amount, currency } = order.total_money.clone()
+                .ok_or_else(|| SquareError::invalid_request(
+                    "MISSING_REQUIRED_PARAMETER",
+                    "total_money",
+                    "the seeded order has no total to pay for".to_string(),
+                ))?;
+            let payment = Builder::from(PaymentRequest::default())
+                .source_id(card.id.clone().unwrap_or_default())
+                .amount(amount.unwrap_or_default(), currency)
+                .customer_id(customer.id.clone().unwrap_or_default())
+                .location_id(location.id.clone().unwrap_or_default())
+                .order_id(order.id.clone().unwrap_or_default())
+                .reference_id(plan_id.clone())
+                .build()
+                .await
+                .map_err(invalid_body("payment"))?;
+
+            let payment = response_of(
+                self.client.payments().create(payment).await?,
+                "payment",
+                |response| match response {
+                    Response::Payment(payment) => Some(payment),
+                    _ => None,
+                },
+            )?;
+
+            seeded.orders.push(order);
+            seeded.payments.push(payment);
+        }
+
+        Ok(seeded)
+    }
+
+    /// Removes the dataset seeded under the given plan id, as far as the
+    /// [Square API](https://developer.squareup.com) allows: cards are disabled, customers and
+    /// items deleted, and locations deactivated. Orders and payments cannot be deleted and are
+    /// left in place.
+    pub async fn teardown(self, plan_id: String) -> Result<Teardown, SquareError> {
+        self.ensure_sandbox()?;
+
+        let mut teardown = Teardown::default();
+
+        for card in self.plan_cards(&plan_id).await? {
+            let card_id = card.id.unwrap_or_default();
+            self.client.cards().disable(card_id.clone()).await?;
+            teardown.disabled_card_ids.push(card_id);
+        }
+
+        for customer in self.plan_customers(&plan_id).await? {
+            let customer_id = customer.id.unwrap_or_default();
+            let delete = Builder::from(CustomerDelete::default())
+                .customer_id(customer_id.clone())
+                .build()
+                .await
+                .map_err(invalid_body("customer"))?;
+            self.client.customers().delete(delete).await?;
+            teardown.deleted_customer_ids.push(customer_id);
+        }
+
+        for item in self.plan_items(&plan_id).await? {
+            let item_id = item.id.unwrap_or_default();
+            self.client.catalog().delete_object(item_id.clone()).await?;
+            teardown.deleted_item_ids.push(item_id);
+        }
+
+        for location in self.plan_locations(&plan_id).await? {
+            let location_id = location.id.unwrap_or_default();
+            let deactivated = Builder::from(LocationCreationWrapper::default())
+                .name(location.name.unwrap_or_default())
+                .status(LocationStatus::INACTIVE)
+                .build()
+                .await
+                .map_err(invalid_body("location"))?;
+            self.client.locations().update(deactivated, location_id.clone()).await?;
+            teardown.deactivated_location_ids.push(location_id);
+        }
+
+        Ok(teardown)
+    }
+
+    fn ensure_sandbox(&self) -> Result<(), SquareError> {
+        match self.client.client_mode {
+            ClientMode::Sandboxed => Ok(()),
+            ClientMode::Production | ClientMode::Custom(_) => Err(SquareError::invalid_request(
+                "INVALID_VALUE",
+                "client_mode",
+                "test data is only seeded and torn down through a sandboxed client".to_string(),
+            )),
+        }
+    }
+
+    async fn all_locations(&self) -> Result<Vec<Location>, SquareError> {
+        Ok(self.client.locations().list().await?
+            .into_responses()
+            .into_iter()
+            .find_map(|response| match response {
+                Response::Locations(locations) => Some(locations),
+                _ => None,
+            })
+            .unwrap_or_default())
+    }
+
+    async fn main_location(&self) -> Result<Location, SquareError> {
+        self.all_locations().await?
+            .into_iter()
+            .find(|location| matches!(location.status, Some(LocationStatus::ACTIVE)))
+            .ok_or_else(|| SquareError::invalid_request(
+                "NOT_FOUND",
+                "location_id",
+                "the seller has no active location to seed the orders at".to_string(),
+            ))
+    }
+
+    async fn plan_locations(&self, plan_id: &str) -> Result<Vec<Location>, SquareError> {
+        Ok(self.all_locations().await?
+            .into_iter()
+            .filter(|location| matches!(location.status, Some(LocationStatus::ACTIVE)))
+            .filter(|location| is_seeded_name(plan_id, location.name.as_deref()))
+            .collect())
+    }
+
+    // the seeded items, following the cursor through every page
+    async fn plan_items(&self, plan_id: &str) -> Result<Vec<CatalogObject>, SquareError> {
+        let mut items = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut search_body = Builder::from(SearchCatalogObjectsBody::default())
+                .set_object_types(vec![CatalogObjectType::Item])
+                .query(CatalogQuery {
+                    prefix_query: Some(CatalogQueryPrefix {
+                        attribute_name: "name".to_string(),
+                        attribute_prefix: plan_id.to_string(),
+                    }),
+                    ..Default::default()
+                });
+            if let Some(cursor) = cursor {
+                search_body = search_body.cursor(cursor);
+            }
+            let search_body = search_body.build().await.map_err(invalid_body("query"))?;
+
+            let res = self.client.catalog().search_objects(search_body).await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::Objects(page) = response {
+                    items.extend(page.into_iter().filter(|object| is_seeded_name(
+                        plan_id,
+                        object.item_data.as_ref().and_then(|item| item.name.as_deref()),
+                    )));
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(items);
+            }
+        }
+    }
+
+    // the seeded customers, following the cursor through every page
+    async fn plan_customers(&self, plan_id: &str) -> Result<Vec<Customer>, SquareError> {
+        let mut customers = vec![];
+        let mut cursor = None;
+
+        loop {
+            let mut search_query = Builder::from(CustomerSearchQuery::default())
+                .exact_reference_id(plan_id.to_string());
+            if let Some(cursor) = cursor {
+                search_query = search_query.cursor(cursor);
+            }
+            let search_query = search_query.build().await.map_err(invalid_body("query"))?;
+
+            let res = self.client.customers().search(search_query).await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::Customers(page) = response {
+                    customers.extend(page);
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(customers);
+            }
+        }
+    }
+
+    // the enabled seeded cards
+    async fn plan_cards(&self, plan_id: &str) -> Result<Vec<Card>, SquareError> {
+        self.client.cards()
+            .list(ListCardsQueryBuilder::new()
+                .reference_id(plan_id.to_string())
+                .exclude_disabled())
+            .await
+    }
+}
+
+// the first response matching the extractor, failing with the name of the object expected
+fn response_of<T>(
+    res: crate::response::SquareResponse,
+    name: &str,
+    extract: fn(Response) -> Option<T>,
+) -> Result<T, SquareError> {
+    res.into_responses()
+        .into_iter()
+        .find_map(extract)
+        .ok_or_else(|| SquareError::invalid_request(
+            "NOT_FOUND",
+            name,
+            format!("the response holds no {}", name),
+        ))
+}
+
+// the error of a request body of the plan failing its own validation
+fn invalid_body(name: &str) -> impl FnOnce(BuildError) -> SquareError + '_ {
+    move |_| SquareError::invalid_request(
+        "INVALID_VALUE",
+        name,
+        format!("the {} of the plan failed its validation", name),
+    )
+}
+
+// whether the name is one given to an object seeded under the plan id
+fn is_seeded_name(plan_id: &str, name: Option<&str>) -> bool {
+    name.is_some_and(|name| name.starts_with(&format!("{} ", plan_id)))
+}
+
+// the catalog item to seed, with temporary ids so it is created along with its variations
+fn item_object(plan_id: &str, index: usize, variations: usize, currency: &Currency)
+               -> CatalogObject {
+    let item_id = format!("#item-{}", index + 1);
+
+    let variations = (0..variations)
+        .map(|variation| CatalogObjectVariation {
+            id: Some(format!("{}-variation-{}", item_id, variation + 1)),
+            type_name: Some(CatalogObjectType::ItemVariation),
+            item_variation_data: Some(CatalogItemVariation {
+                item_id: Some(item_id.clone()),
+                name: Some(format!("Variation {}", variation + 1)),
+                pricing_type: Some(CatalogPricingType::FixedPricing),
+                price_money: Some(Money {
+                    amount: Some(((index + 1) * 100 + variation * 50) as i64),
+                    currency: currency.clone(),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .collect();
+
+    CatalogObject {
+        id: Some(item_id),
+        type_name: Some(CatalogObjectType::Item),
+        item_data: Some(CatalogItem {
+            name: Some(format!("{} Item {}", plan_id, index + 1)),
+            description: Some(format!("Seeded by the sandbox plan {}.", plan_id)),
+            variations: Some(variations),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+fn first_variation_id(item: &CatalogObject) -> Option<String> {
+    item.item_data.as_ref()?
+        .variations.as_ref()?
+        .first()?
+        .id.clone()
+}
+
+#[cfg(test)]
+mod test_sandbox {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_seed_plan_builder() {
+        let actual = Builder::from(SeedPlan::default())
+            .items(2)
+            .customers(1)
+            .orders(3)
+            .build()
+            .await
+            .unwrap();
+
+        assert!(actual.plan_id.as_ref().unwrap().starts_with("seed-"));
+        assert_eq!(actual.variations_per_item, 1);
+
+        let actual = Builder::from(SeedPlan::default())
+            .plan_id("seed-demo".to_string())
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(actual.plan_id, Some("seed-demo".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_seed_plan_builder_fail() {
+        let res = Builder::from(SeedPlan::default())
+            .items(2)
+            .orders(1)
+            .build()
+            .await;
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_item_object() {
+        let actual = item_object("seed-demo", 1, 2, &Currency::USD);
+        let item_data = actual.item_data.as_ref().unwrap();
+        let variations = item_data.variations.as_ref().unwrap();
+
+        assert_eq!(actual.id, Some("#item-2".to_string()));
+        assert_eq!(item_data.name, Some("seed-demo Item 2".to_string()));
+        assert_eq!(variations.len(), 2);
+        assert_eq!(
+            variations[1].item_variation_data.as_ref().unwrap().price_money.as_ref().unwrap().amount,
+            Some(250),
+        );
+        assert_eq!(first_variation_id(&actual), Some("#item-2-variation-1".to_string()));
+        assert!(is_seeded_name("seed-demo", item_data.name.as_deref()));
+        assert!(!is_seeded_name("seed-dem", item_data.name.as_deref()));
+    }
+
+    #[tokio::test]
+    async fn test_teardown_production() {
+        let sut = SquareClient::new("some_token").production();
+
+        let res = sut.sandbox()
+            .teardown("seed-demo".to_string())
+            .await;
+
+        let errors = res.err().unwrap().get().unwrap();
+        assert_eq!(errors[0].code, "INVALID_VALUE");
+        assert_eq!(errors[0].field, Some("client_mode".to_string()));
+    }
+
+    #[test]
+    fn test_response_of() {
+        let res: crate::response::SquareResponse = serde_json::from_value(serde_json::json!({
+            "customer": { "id": "some_customer_id" },
+        })).unwrap();
+
+        let errors = response_of(res, "card", |response| match response {
+            Response::Card(card) => Some(card),
+            _ => None,
+        }).err().unwrap().get().unwrap();
+
+        assert_eq!(errors[0].code, "NOT_FOUND");
+        assert_eq!(errors[0].detail, Some("the response holds no card".to_string()));
+    }
+}
@@ -5,15 +5,18 @@ Terminals functionality of the [Square API](https://developer.squareup.com).
 use crate::api::{SquareAPI, Verb};
 use crate::client::SquareClient;
 use crate::errors::{SquareError, ValidationError};
-use crate::objects::{DeviceCheckoutOptions, Money, PaymentOptions, TerminalCheckout,
-                     TerminalCheckoutQuery, TerminalRefund, TerminalRefundQuery};
-use crate::objects::enums::{CheckoutOptionsPaymentType, TerminalCheckoutStatus};
+use crate::objects::{DeviceCheckoutOptions, Money, PaymentOptions, TerminalAction,
+                     TerminalCheckout, TerminalCheckoutQuery, TerminalRefund,
+                     TerminalRefundQuery};
+use crate::objects::enums::{CheckoutOptionsPaymentType, TerminalActionActionType,
+                            TerminalCheckoutStatus};
 use crate::response::SquareResponse;
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
 use crate::objects::TimeRange;
-use crate::builder::{AddField, Builder, ParentBuilder, Validate};
+use crate::builder::{valid_raw_fields, AddField, Builder, ParentBuilder, RawFields, Validate};
 
 impl SquareClient {
     pub fn terminal(&self) -> Terminal {
@@ -45,10 +48,15 @@ impl<'a> Terminal<'a> {
     /// request. <br/>
     /// Only Terminal checkout requests created for the merchant scoped to the OAuth token are
     /// returned. Terminal checkout requests are available for 30 days.
+    /// # Arguments:
+    /// * `body` - A [SearchTerminalCheckoutBody](SearchTerminalCheckoutBody) created through its
+    ///   builder, which validates the query before it is sent.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/terminal/search-terminal-checkouts)
     pub async fn search_checkout(self, body: SearchTerminalCheckoutBody)
                               -> Result<SquareResponse, SquareError>{
         self.client.request(
-            Verb::GET,
+            Verb::POST,
             SquareAPI::Terminals("/checkouts/search".to_string()),
             Some(&body),
             None,
@@ -78,6 +86,36 @@ impl<'a> Terminal<'a> {
         ).await
     }
 
+    /// Creates a Terminal action request and sends it to the specified device, e.g. to print or
+    /// show a receipt for a payment.
+    /// # Arguments:
+    /// * `body` - A [CreateTerminalActionBody](CreateTerminalActionBody) created through its
+    ///   builder.
+    ///
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/terminal/create-terminal-action)
+    pub async fn create_action(self, body: CreateTerminalActionBody)
+                              -> Result<SquareResponse, SquareError>{
+        self.client.request(
+            Verb::POST,
+            SquareAPI::Terminals("/actions".to_string()),
+            Some(&body),
+            None,
+        ).await
+    }
+
+    /// Retrieves a Terminal action request by `action_id`.<br/>
+    /// Terminal action requests are available for 30 days.
+    /// [Open in API Reference](https://developer.squareup.com/reference/square/terminal/get-terminal-action)
+    pub async fn get_action(self, action_id: String)
+                              -> Result<SquareResponse, SquareError>{
+        self.client.request(
+            Verb::GET,
+            SquareAPI::Terminals(format!("/actions/{}", action_id)),
+            None::<&CreateTerminalActionBody>,
+            None,
+        ).await
+    }
+
     /// Creates a request to refund an Interac payment completed on a Square Terminal. <br/>
     /// Refunds for Interac payments on a Square Terminal are supported only for Interac debit cards
     /// in Canada. Other refunds for Terminal payments should use the Refunds API. For more
@@ -94,6 +132,10 @@ impl<'a> Terminal<'a> {
 
     /// Retrieves a filtered list of Interac Terminal refund requests created by the seller making
     /// the request.
+    /// # Arguments:
+    /// * `body` - A [SearchTerminalRefundBody](SearchTerminalRefundBody) created through its
+    ///   builder, which validates the query before it is sent.
+    ///
     /// [Open in API Reference](https://developer.squareup.com/reference/square/terminal/search-terminal-refunds)
     pub async fn search_refunds(self, body: SearchTerminalRefundBody)
                               -> Result<SquareResponse, SquareError>{
@@ -138,6 +180,8 @@ impl<'a> Terminal<'a> {
 pub struct CreateTerminalCheckoutBody {
     idempotency_key: Option<String>,
     checkout: TerminalCheckout,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Default for CreateTerminalCheckoutBody {
@@ -145,6 +189,7 @@ impl Default for CreateTerminalCheckoutBody {
         CreateTerminalCheckoutBody {
             idempotency_key: None,
             checkout: TerminalCheckout::default(),
+            raw_fields: Map::new(),
         }
     }
 }
@@ -152,7 +197,8 @@ impl Default for CreateTerminalCheckoutBody {
 impl Validate for CreateTerminalCheckoutBody {
     fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
         if self.checkout.amount_money.is_some() &&
-            self.checkout.device_options.is_some() {
+            self.checkout.device_options.is_some() &&
+            valid_raw_fields(&mut self) {
             self.idempotency_key = Some(Uuid::new_v4().to_string());
             Ok(self)
         } else {
@@ -161,6 +207,12 @@ impl Validate for CreateTerminalCheckoutBody {
     }
 }
 
+impl RawFields for CreateTerminalCheckoutBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<CreateTerminalCheckoutBody, T> {
     pub fn amount_money(mut self, amount: Money) -> Self {
         self.body.checkout.amount_money = Some(amount);
@@ -231,14 +283,27 @@ pub struct SearchTerminalCheckoutBody {
     query: Option<TerminalCheckoutQuery>,
     cursor: Option<String>,
     limit: Option<i32>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for SearchTerminalCheckoutBody {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if !valid_page(self.cursor.as_ref(), self.limit) || !valid_raw_fields(&mut self) {
+            return Err(ValidationError)
+        }
+        self.query = self.query.map(|query| query.validate()).transpose()?;
+
         Ok(self)
     }
 }
 
+impl RawFields for SearchTerminalCheckoutBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<SearchTerminalCheckoutBody, T> {
     pub fn query(mut self, query: TerminalCheckoutQuery) -> Self {
         self.body.query = Some(query);
@@ -272,6 +337,8 @@ impl AddField<TerminalCheckoutQuery> for SearchTerminalCheckoutBody {
 pub struct CreateTerminalRefundBody {
     idempotency_key: Option<String>,
     refund: TerminalRefund,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for CreateTerminalRefundBody {
@@ -279,7 +346,8 @@ impl Validate for CreateTerminalRefundBody {
         if self.refund.device_id.is_some() &&
             self.refund.amount_money.is_some() &&
             self.refund.reason.is_some() &&
-            self.refund.payment_id.is_some() {
+            self.refund.payment_id.is_some() &&
+            valid_raw_fields(&mut self) {
             self.idempotency_key = Some(Uuid::new_v4().to_string());
 
             Ok(self)
@@ -289,6 +357,12 @@ impl Validate for CreateTerminalRefundBody {
     }
 }
 
+impl RawFields for CreateTerminalRefundBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<CreateTerminalRefundBody, T> {
     pub fn amount_money(mut self, amount_money: Money) -> Self {
         self.body.refund.amount_money = Some(amount_money);
@@ -321,6 +395,84 @@ impl<T: ParentBuilder> Builder<CreateTerminalRefundBody, T> {
     }
 }
 
+// -------------------------------------------------------------------------------------------------
+// CreateTerminalActionBody builder implementation
+// -------------------------------------------------------------------------------------------------
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CreateTerminalActionBody {
+    idempotency_key: Option<String>,
+    action: TerminalAction,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
+}
+
+impl Validate for CreateTerminalActionBody {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        // a receipt action needs the payment to show the receipt of
+        let valid_receipt = self.action.action_type != Some(TerminalActionActionType::Receipt) ||
+            self.action.receipt_options.as_ref()
+                .and_then(|options| options.payment_id.as_ref())
+                .is_some_and(|payment_id| !payment_id.is_empty());
+
+        if self.action.device_id.is_some() &&
+            self.action.action_type.is_some() &&
+            valid_receipt &&
+            valid_raw_fields(&mut self) {
+            self.idempotency_key = Some(Uuid::new_v4().to_string());
+
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl RawFields for CreateTerminalActionBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
+impl<T: ParentBuilder> Builder<CreateTerminalActionBody, T> {
+    pub fn device_id(mut self, device_id: String) -> Self {
+        self.body.action.device_id = Some(device_id);
+
+        self
+    }
+
+    pub fn deadline_duration(mut self, deadline_duration: String) -> Self {
+        self.body.action.deadline_duration = Some(deadline_duration);
+
+        self
+    }
+
+    /// Makes the action show the receipt of the payment, letting the buyer choose between a
+    /// printed and a digital receipt.
+    pub fn receipt(mut self, payment_id: String) -> Self {
+        self.body.action.action_type = Some(TerminalActionActionType::Receipt);
+        self.body.action.receipt_options.get_or_insert_with(Default::default)
+            .payment_id = Some(payment_id);
+
+        self
+    }
+
+    /// Prints a paper receipt without offering a digital one.
+    pub fn print_only(mut self) -> Self {
+        self.body.action.receipt_options.get_or_insert_with(Default::default)
+            .print_only = Some(true);
+
+        self
+    }
+
+    /// Marks the receipt as a reprint.
+    pub fn duplicate(mut self) -> Self {
+        self.body.action.receipt_options.get_or_insert_with(Default::default)
+            .is_duplicate = Some(true);
+
+        self
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // SearchTerminalRefundBody builder implementation
 // -------------------------------------------------------------------------------------------------
@@ -329,14 +481,27 @@ pub struct SearchTerminalRefundBody {
     cursor: Option<String>,
     limit: Option<i32>,
     query: Option<TerminalRefundQuery>,
+    #[serde(flatten)]
+    raw_fields: Map<String, Value>,
 }
 
 impl Validate for SearchTerminalRefundBody {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        if !valid_page(self.cursor.as_ref(), self.limit) || !valid_raw_fields(&mut self) {
+            return Err(ValidationError)
+        }
+        self.query = self.query.map(|query| query.validate()).transpose()?;
+
         Ok(self)
     }
 }
 
+impl RawFields for SearchTerminalRefundBody {
+    fn raw_fields(&mut self) -> &mut Map<String, Value> {
+        &mut self.raw_fields
+    }
+}
+
 impl<T: ParentBuilder> Builder<SearchTerminalRefundBody, T> {
     pub fn query(mut self, query: TerminalRefundQuery) -> Self {
         self.body.query = Some(query);
@@ -363,6 +528,12 @@ impl AddField<TerminalRefundQuery> for SearchTerminalRefundBody {
     }
 }
 
+// terminal searches return at most 100 results per page and need a non-empty cursor
+fn valid_page(cursor: Option<&String>, limit: Option<i32>) -> bool {
+    cursor.is_none_or(|cursor| !cursor.is_empty())
+        && limit.is_none_or(|limit| (1..=100).contains(&limit))
+}
+
 #[cfg(test)]
 mod test_terminals {
     use crate::builder::BackIntoBuilder;
@@ -402,7 +573,8 @@ mod test_terminals {
                 reference_id: None,
                 status: None,
                 updated_at: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(CreateTerminalCheckoutBody::default())
@@ -438,7 +610,8 @@ mod test_terminals {
                 })
             }),
             cursor: None,
-            limit: Some(10)
+            limit: Some(10),
+            raw_fields: Default::default(),
         };
 
         let actual = Builder::from(SearchTerminalCheckoutBody::default())
@@ -476,7 +649,8 @@ mod test_terminals {
                 })
             }),
             cursor: None,
-            limit: Some(10)
+            limit: Some(10),
+            raw_fields: Default::default(),
         };
 
         let res = sut.terminal()
@@ -508,7 +682,8 @@ mod test_terminals {
                 refund_id: None,
                 status: None,
                 updated_at: None
-            }
+            },
+            raw_fields: Default::default(),
         };
 
         let mut actual = Builder::from(CreateTerminalRefundBody::default())
@@ -551,7 +726,8 @@ mod test_terminals {
                     status: Some(TerminalCheckoutStatus::CancelRequested)
                 }),
                 sort: Some(TerminalCheckoutQuerySort { sort_order: Some(SortOrder::Desc) })
-            })
+            }),
+            raw_fields: Default::default(),
         };
 
         let actual = Builder::from(SearchTerminalRefundBody::default())
@@ -569,5 +745,92 @@ mod test_terminals {
 
         assert_eq!(format!("{:?}", expected), format!("{:?}", actual))
     }
+
+    #[tokio::test]
+    async fn test_search_terminal_body_builder_validation() {
+        let actual = Builder::from(SearchTerminalRefundBody::default())
+            .sub_builder_from(TerminalRefundQuery::default())
+            .status(TerminalCheckoutStatus::Completed)
+            .created_at(TimeRange {
+                start_at: Some("2022-06-01T00:00:00Z".to_string()),
+                end_at: Some("2022-07-01T00:00:00Z".to_string()),
+            })
+            .into_parent_builder()
+            .unwrap()
+            .build()
+            .await;
+
+        assert!(actual.is_ok());
+
+        let reversed_range = Builder::from(SearchTerminalCheckoutBody::default())
+            .sub_builder_from(TerminalCheckoutQuery::default())
+            .created_at(TimeRange {
+                start_at: Some("2022-07-01T00:00:00Z".to_string()),
+                end_at: Some("2022-06-01T00:00:00Z".to_string()),
+            })
+            .into_parent_builder();
+
+        assert!(reversed_range.is_err());
+
+        let empty_device_id = Builder::from(SearchTerminalRefundBody::default())
+            .query(TerminalRefundQuery {
+                filter: Some(crate::objects::TerminalRefundQueryFilter {
+                    created_at: None,
+                    device_id: Some("".to_string()),
+                    status: None,
+                }),
+                sort: None,
+            })
+            .build()
+            .await;
+
+        assert!(empty_device_id.is_err());
+
+        let limit_too_large = Builder::from(SearchTerminalCheckoutBody::default())
+            .limit(101)
+            .build()
+            .await;
+
+        assert!(limit_too_large.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_terminal_action_body_builder() {
+        let actual = Builder::from(CreateTerminalActionBody::default())
+            .device_id("some_device_id".to_string())
+            .receipt("some_payment_id".to_string())
+            .print_only()
+            .duplicate()
+            .build()
+            .await
+            .unwrap();
+
+        assert!(actual.idempotency_key.is_some());
+        assert_eq!(
+            serde_json::to_value(&actual.action).unwrap(),
+            serde_json::json!({
+                "device_id": "some_device_id",
+                "type": "RECEIPT",
+                "receipt_options": {
+                    "payment_id": "some_payment_id",
+                    "print_only": true,
+                    "is_duplicate": true,
+                },
+            }),
+        );
+
+        let without_payment = Builder::from(CreateTerminalActionBody::default())
+            .device_id("some_device_id".to_string())
+            .receipt("".to_string())
+            .build()
+            .await;
+        assert!(without_payment.is_err());
+
+        let without_device = Builder::from(CreateTerminalActionBody::default())
+            .receipt("some_payment_id".to_string())
+            .build()
+            .await;
+        assert!(without_device.is_err());
+    }
 }
 
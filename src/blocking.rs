@@ -0,0 +1,193 @@
+/*!
+A blocking [SquareClient](SquareClient), available with the `blocking` feature, for CLI tools and
+scripts that do not want to run an async runtime of their own.
+
+It wraps an async [SquareClient](crate::client::SquareClient) along with a single threaded
+[tokio](https://docs.rs/tokio) runtime, and blocks the calling thread until the requests it sends
+completed. Every part of the async client is available through [call](SquareClient::call), which
+runs any of its methods to completion, while plain requests can be sent through
+[request](SquareClient::request) directly.
+
+The client is configured as the async one it wraps, so everything the async client offers, such
+as retries, rate limiting or interceptors, works the same way.
+
+The blocking client must not be used from within an async runtime, as blocking a thread of the
+runtime on another one panics. Creating one fails if the runtime cannot be started, e.g. because
+the process ran out of file descriptors.
+
+# Example: List the locations of an account
+```no_run
+use square_ox::{blocking::SquareClient, client};
+use std::convert::TryFrom;
+
+let client = SquareClient::try_from(
+    client::SquareClient::new("your_square_access_token").production(),
+).unwrap();
+
+let locations = client.call(|client| client.locations().list()).unwrap();
+```
+ */
+
+use crate::api::{SquareAPI, Verb};
+use crate::client::{HeaderMap, RequestOptions};
+use crate::errors::SquareError;
+use crate::response::SquareResponse;
+
+use serde::Serialize;
+use std::convert::TryFrom;
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use tokio::runtime::{Builder, Runtime};
+
+/// A [SquareClient](crate::client::SquareClient) whose requests block the calling thread. Clones
+/// share the underlying HTTP client and runtime.
+#[derive(Clone)]
+pub struct SquareClient {
+    client: crate::client::SquareClient,
+    runtime: Arc<Runtime>,
+}
+
+impl SquareClient {
+    /// Create a new blocking client with the default configuration of the async
+    /// [SquareClient](crate::client::SquareClient), failing if its runtime cannot be started.
+    ///
+    /// # Arguments
+    /// * `access_token` - The access token for the Square App you want to use the client with.
+    pub fn new(access_token: &str) -> io::Result<Self> {
+        Self::try_from(crate::client::SquareClient::new(access_token))
+    }
+
+    /// The async client the requests are sent through.
+    pub fn as_async(&self) -> &crate::client::SquareClient {
+        &self.client
+    }
+
+    /// Runs a method of the async client to completion, blocking the calling thread until it did.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use square_ox::blocking::SquareClient;
+    ///
+    /// let client = SquareClient::new("your_square_access_token").unwrap();
+    ///
+    /// let customers = client.call(|client| client.customers().list(vec![])).unwrap();
+    /// ```
+    pub fn call<'a, F, Fut>(&'a self, f: F) -> Fut::Output
+    where
+        F: FnOnce(&'a crate::client::SquareClient) -> Fut,
+        Fut: Future,
+    {
+        self.runtime.block_on(f(&self.client))
+    }
+
+    /// Sends a request to a given [SquareAPI](crate::api::SquareAPI), as
+    /// [request](crate::client::SquareClient::request) of the async client does.
+    pub fn request<T>(
+        &self,
+        verb: Verb,
+        endpoint: SquareAPI,
+        json: Option<&T>,
+        parameters: Option<Vec<(String, String)>>,
+    ) -> Result<SquareResponse, SquareError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.call(|client| client.request(verb, endpoint, json, parameters))
+    }
+
+    /// Sends a request to a given [SquareAPI](crate::api::SquareAPI) with additional headers, as
+    /// [request_with_headers](crate::client::SquareClient::request_with_headers) of the async
+    /// client does.
+    pub fn request_with_headers<T>(
+        &self,
+        verb: Verb,
+        endpoint: SquareAPI,
+        json: Option<&T>,
+        parameters: Option<Vec<(String, String)>>,
+        headers: HeaderMap,
+    ) -> Result<SquareResponse, SquareError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.call(|client| client.request_with_headers(verb, endpoint, json, parameters, headers))
+    }
+
+    /// Sends a request to a given [SquareAPI](crate::api::SquareAPI) with the given overrides, as
+    /// [request_with_options](crate::client::SquareClient::request_with_options) of the async
+    /// client does.
+    pub fn request_with_options<T>(
+        &self,
+        verb: Verb,
+        endpoint: SquareAPI,
+        json: Option<&T>,
+        parameters: Option<Vec<(String, String)>>,
+        options: RequestOptions,
+    ) -> Result<SquareResponse, SquareError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.call(|client| client.request_with_options(verb, endpoint, json, parameters, options))
+    }
+}
+
+/// Wraps an async client as configured, e.g. for production or with a retry policy, failing if
+/// the runtime of the blocking client cannot be started.
+impl TryFrom<crate::client::SquareClient> for SquareClient {
+    type Error = io::Error;
+
+    fn try_from(client: crate::client::SquareClient) -> io::Result<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        Ok(SquareClient {
+            client,
+            runtime: Arc::new(runtime),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_blocking {
+    use super::*;
+    use crate::interceptor::{InterceptedRequest, InterceptedResponse, RequestInterceptor};
+    use crate::objects::Response;
+    use reqwest::StatusCode;
+
+    struct Canned;
+
+    impl RequestInterceptor for Canned {
+        fn before_request(&self, _: &mut InterceptedRequest) -> Option<InterceptedResponse> {
+            Some(InterceptedResponse::new(
+                StatusCode::OK,
+                r#"{ "locations": [{ "id": "some_location_id" }] }"#.to_string(),
+            ))
+        }
+    }
+
+    fn has_locations(res: SquareResponse) -> bool {
+        res.into_responses()
+            .iter()
+            .any(|response| matches!(response, Response::Locations(_)))
+    }
+
+    #[test]
+    fn test_blocking_client() {
+        let sut = SquareClient::try_from(crate::client::SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Canned))
+            .unwrap();
+
+        assert!(has_locations(sut.call(|client| client.locations().list()).unwrap()));
+        assert!(has_locations(sut.clone()
+            .request(Verb::GET, SquareAPI::Locations("".to_string()), None::<&()>, None)
+            .unwrap()));
+
+        // nothing listens on the port
+        let unreachable = SquareClient::try_from(crate::client::SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1"))
+            .unwrap();
+        assert!(unreachable.call(|client| client.locations().list()).is_err());
+    }
+}
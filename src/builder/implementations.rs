@@ -1,20 +1,26 @@
 use super::*;
-use crate::objects::{TimeRange, DeviceCheckoutOptions, Money, Order, OrderLineItem, OrderServiceCharge, SearchOrdersFilter, SearchOrdersQuery, SearchOrdersSort, TerminalCheckoutQuery, TerminalCheckoutQueryFilter, TerminalCheckoutQuerySort, TerminalRefundQuery, TerminalRefundQueryFilter, TipSettings, InventoryChange, InventoryPhysicalCount, InventoryAdjustment, InventoryTransfer};
-use crate::objects::enums::{InventoryChangeType, OrderServiceChargeCalculationPhase, SearchOrdersSortField, SortOrder, TerminalCheckoutStatus};
+use crate::objects::{CatalogCustomAttributeDefinition, CatalogDiscount, CatalogPricingRule, CatalogProductSet, CatalogTimePeriod, CatalogSubscriptionPlan, CatalogSubscriptionPlanVariation, SubscriptionPhase, SubscriptionPricing, CatalogCustomAttributeDefinitionNumberConfig, CatalogCustomAttributeDefinitionSelectionConfig, CatalogCustomAttributeDefinitionSelectionConfigCustomAttributeSelection, CatalogCustomAttributeDefinitionStringConfig, TimeRange, DeviceCheckoutOptions, Money, Order, OrderFulfillment, OrderFulfillmentDeliveryDetails, OrderFulfillmentPickupDetails, OrderFulfillmentPickupDetailsCurbsidePickupDetails, OrderFulfillmentRecipient, OrderFulfillmentShipmentDetails, OrderLineItem, OrderLineItemDiscount, OrderLineItemTax, OrderReturn, OrderReturnLineItem, OrderServiceCharge, SearchOrdersCustomerFilter, SearchOrdersDateTimeFilter, SearchOrdersFilter, SearchOrdersQuery, SearchOrdersSort, SearchOrdersSourceFilter, SearchOrdersStateFilter, TerminalCheckoutQuery, TerminalCheckoutQueryFilter, TerminalCheckoutQuerySort, TerminalRefundQuery, TerminalRefundQueryFilter, TipSettings, InventoryChange, InventoryPhysicalCount, InventoryAdjustment, InventoryTransfer, CatalogQuery, CatalogQueryExact, CatalogQueryItemVariationsForItemOptionValues, CatalogQueryItemsForItemOptions, CatalogQueryItemsForModifierList, CatalogQueryItemsForTax, CatalogQueryPrefix, CatalogQueryRange, CatalogQuerySet, CatalogQuerySortedAttribute, CatalogQueryText, CustomAttributeFilter, Range, CustomerQuery, CustomerFilter, CustomerTextFilter, CreationSource, FilterValue};
+use crate::objects::enums::{CatalogCustomAttributeDefinitionType, CatalogDiscountModifyTaxBasis, CatalogDiscountType, ExcludeStrategy, CatalogObjectType, CustomerCreationSource, CustomerSortField, InventoryChangeType, InventoryState, OrderFulfillmentDeliveryDetailsScheduleType, OrderFulfillmentPickupDetailsScheduleType, OrderFulfillmentState, OrderFulfillmentType, OrderLineItemDiscountScope, OrderLineItemDiscountType, OrderLineItemTaxScope, OrderLineItemTaxType, OrderServiceChargeCalculationPhase, OrderServiceChargeScope, OrderState, SearchOrdersSortField, SortOrder, SubscriptionCadence, SubscriptionPricingType, TerminalCheckoutStatus};
+
+use chrono::{DateTime, Duration, NaiveDateTime, Weekday};
 
 // -------------------------------------------------------------------------------------------------
 // OrderServiceCharge builder implementation
 // -------------------------------------------------------------------------------------------------
 impl Validate for OrderServiceCharge {
     fn validate(self) -> Result<Self, ValidationError> {
-        println!("{:?}", &self);
-        if self.amount_money.is_some() &&
-            self.name.is_some() &&
-            self.calculation_phase.is_some() {
-            println!("no error");
+        let line_item_scope = matches!(self.scope, Some(OrderServiceChargeScope::LineItem));
+
+        if self.calculation_phase.is_some() &&
+            valid_adjustment(
+                self.catalog_object_id.as_ref(),
+                self.name.as_ref(),
+                self.amount_money.is_some(),
+                self.percentage.as_ref(),
+            ) &&
+            (!line_item_scope || self.uid.is_some()) {
             Ok(self)
         } else {
-            println!("error");
             Err(ValidationError)
         }
     }
@@ -58,133 +64,148 @@ impl<T: ParentBuilder> Builder<OrderServiceCharge, T> {
 
         self
     }
-}
 
-// -------------------------------------------------------------------------------------------------
-// SearchOrdersQuery builder implementation
-// -------------------------------------------------------------------------------------------------
-impl Validate for SearchOrdersQuery {
-    fn validate(self) -> Result<Self, ValidationError> {
-        Ok(self)
+    /// A unique ID of the service charge within the order, needed to apply a service charge
+    /// scoped to line items.
+    pub fn uid(mut self, uid: String) -> Self {
+        self.body.uid = Some(uid);
+
+        self
     }
-}
 
-impl<T: ParentBuilder> Builder<SearchOrdersQuery, T> {
-    pub fn filter(mut self, filter: SearchOrdersFilter) -> Self {
-        self.body.filter = Some(filter);
+    /// Charges the service charge from the catalog, which provides its name and amount.
+    pub fn catalog_object_id(mut self, catalog_object_id: String) -> Self {
+        self.body.catalog_object_id = Some(catalog_object_id);
 
         self
     }
 
-    pub fn sort_ascending(mut self) -> Self {
-        match self.body.sort.as_mut() {
-            Some(sort) => sort.sort_order= Some(SortOrder::Asc),
-            None => self.body.sort = Some(SearchOrdersSort {
-                sort_field: Some(SearchOrdersSortField::CreatedAt),
-                sort_order: Some(SortOrder::Asc),
-            })
-        }
+    pub fn catalog_version(mut self, catalog_version: i64) -> Self {
+        self.body.catalog_version = Some(catalog_version);
 
         self
     }
 
-    pub fn sort_descending(mut self) -> Self {
-        match self.body.sort.as_mut() {
-            Some(sort) => sort.sort_order= Some(SortOrder::Desc),
-            None => self.body.sort = Some(SearchOrdersSort {
-                sort_field: Some(SearchOrdersSortField::CreatedAt),
-                sort_order: Some(SortOrder::Desc),
-            })
-        }
+    /// The percentage of the order to charge, as a decimal string such as "7.25".
+    pub fn percentage(mut self, percentage: String) -> Self {
+        self.body.percentage = Some(percentage);
 
         self
     }
 
-    pub fn sort_field(mut self, sort_field: SearchOrdersSortField) -> Self {
-        match self.body.sort.as_mut() {
-            Some(sort) => sort.sort_field = Some(sort_field),
-            None => self.body.sort = Some(SearchOrdersSort {
-                sort_field: Some(sort_field),
-                sort_order: None,
-            })
-        }
+    pub fn scope(mut self, scope: OrderServiceChargeScope) -> Self {
+        self.body.scope = Some(scope);
 
         self
     }
 }
+
 // -------------------------------------------------------------------------------------------------
-// Order builder implementation
+// OrderLineItemDiscount builder implementation
 // -------------------------------------------------------------------------------------------------
-impl Validate for Order {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.location_id.is_some(){
-            Ok(self)
-        } else {
-            Err(ValidationError)
+impl Validate for OrderLineItemDiscount {
+    fn validate(mut self) -> Result<Self, ValidationError> {
+        if !valid_adjustment(
+            self.catalog_object_id.as_ref(),
+            self.name.as_ref(),
+            self.amount_money.is_some(),
+            self.percentage.as_ref(),
+        ) || (matches!(self.scope, Some(OrderLineItemDiscountScope::LineItem)) &&
+            self.uid.is_none()) {
+            return Err(ValidationError);
+        }
+
+        // an ad hoc discount is fixed to the amount or percentage it was given
+        if self.catalog_object_id.is_none() {
+            let discount_type = if self.amount_money.is_some() {
+                OrderLineItemDiscountType::FixedAmount
+            } else {
+                OrderLineItemDiscountType::FixedPercentage
+            };
+
+            match &self.discount_type {
+                None => self.discount_type = Some(discount_type),
+                Some(given) if *given != discount_type => return Err(ValidationError),
+                _ => {},
+            }
         }
+
+        Ok(self)
     }
 }
 
-impl<T: ParentBuilder> Builder<Order, T> {
-    pub fn location_id(mut self, location_id: String) -> Self {
-        self.body.location_id = Some(location_id);
+impl<T: ParentBuilder> Builder<OrderLineItemDiscount, T> {
+    /// A unique ID of the discount within the order, needed to apply a discount scoped to line
+    /// items.
+    pub fn uid(mut self, uid: String) -> Self {
+        self.body.uid = Some(uid);
 
         self
     }
 
-    pub fn version(mut self, version: i64) -> Self {
-        self.body.version = Some(version);
+    /// Applies the discount from the catalog, which provides its name and amount or percentage.
+    pub fn catalog_object_id(mut self, catalog_object_id: String) -> Self {
+        self.body.catalog_object_id = Some(catalog_object_id);
 
         self
     }
 
-    pub fn add_service_charge(mut self, service_charge: OrderServiceCharge) -> Self {
-        if let Some(service_charges) = self.body.service_charges.as_mut() {
-            service_charges.push(service_charge);
-        } else {
-            self.body.service_charges = Some(vec![service_charge])
-        };
+    pub fn catalog_version(mut self, catalog_version: i64) -> Self {
+        self.body.catalog_version = Some(catalog_version);
 
         self
     }
 
-    pub fn add_order_item(mut self, order_item: OrderLineItem) -> Self {
-        if let Some(line_items) = self.body.line_items.as_mut() {
-            line_items.push(order_item);
-        } else {
-            self.body.line_items = Some(vec![order_item])
-        };
+    pub fn name(mut self, name: String) -> Self {
+        self.body.name = Some(name);
 
         self
     }
-}
 
-impl AddField<OrderServiceCharge> for Order {
-    fn add_field(&mut self, field: OrderServiceCharge) {
-        if let Some(service_charges) = self.service_charges.as_mut() {
-            service_charges.push(field);
-        } else {
-            self.service_charges = Some(vec![field]);
-        }
+    /// The amount to take off, for an ad hoc discount or a variable amount discount from the
+    /// catalog.
+    pub fn amount_money(mut self, amount_money: Money) -> Self {
+        self.body.amount_money = Some(amount_money);
+
+        self
     }
-}
 
-impl AddField<OrderLineItem> for Order {
-    fn add_field(&mut self, field: OrderLineItem) {
-        if let Some(line_items) = self.line_items.as_mut() {
-            line_items.push(field);
-        } else {
-            self.line_items = Some(vec![field]);
-        }
+    /// The percentage to take off as a decimal string such as "7.25", for an ad hoc discount or a
+    /// variable percentage discount from the catalog.
+    pub fn percentage(mut self, percentage: String) -> Self {
+        self.body.percentage = Some(percentage);
+
+        self
+    }
+
+    pub fn discount_type(mut self, discount_type: OrderLineItemDiscountType) -> Self {
+        self.body.discount_type = Some(discount_type);
+
+        self
+    }
+
+    pub fn scope(mut self, scope: OrderLineItemDiscountScope) -> Self {
+        self.body.scope = Some(scope);
+
+        self
     }
 }
 
 // -------------------------------------------------------------------------------------------------
-// DeviceCheckoutOptions builder implementation
+// OrderLineItemTax builder implementation
 // -------------------------------------------------------------------------------------------------
-impl Validate for DeviceCheckoutOptions {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        if self.device_id.is_some() {
+impl Validate for OrderLineItemTax {
+    fn validate(self) -> Result<Self, ValidationError> {
+        let line_item_scope = matches!(self.scope, Some(OrderLineItemTaxScope::LineItem));
+
+        // a tax is always a percentage
+        if valid_adjustment(
+            self.catalog_object_id.as_ref(),
+            self.name.as_ref(),
+            false,
+            self.percentage.as_ref(),
+        ) && (self.catalog_object_id.is_some() || self.percentage.is_some()) &&
+            (!line_item_scope || self.uid.is_some()) {
             Ok(self)
         } else {
             Err(ValidationError)
@@ -192,255 +213,2067 @@ impl Validate for DeviceCheckoutOptions {
     }
 }
 
-impl<T: ParentBuilder> Builder<DeviceCheckoutOptions, T> {
-    pub fn device_id(mut self, device_id: String) -> Self {
-        self.body.device_id = Some(device_id);
-
-        self
-    }
-
-    pub fn collect_signature(mut self) -> Self {
-        self.body.collect_signature = Some(true);
+impl<T: ParentBuilder> Builder<OrderLineItemTax, T> {
+    /// A unique ID of the tax within the order, needed to apply a tax scoped to line items.
+    pub fn uid(mut self, uid: String) -> Self {
+        self.body.uid = Some(uid);
 
         self
     }
 
-    pub fn show_itemized_cart(mut self) -> Self {
-        self.body.show_itemized_cart = Some(true);
+    /// Applies the tax from the catalog, which provides its name and percentage.
+    pub fn catalog_object_id(mut self, catalog_object_id: String) -> Self {
+        self.body.catalog_object_id = Some(catalog_object_id);
 
         self
     }
 
-    pub fn skip_receipt_screen(mut self) -> Self {
-        self.body.skip_receipt_screen = Some(true);
+    pub fn catalog_version(mut self, catalog_version: i64) -> Self {
+        self.body.catalog_version = Some(catalog_version);
 
         self
     }
 
-    pub fn tip_settings(mut self, tip_settings: TipSettings) -> Self {
-        self.body.tip_settings = Some(tip_settings);
+    pub fn name(mut self, name: String) -> Self {
+        self.body.name = Some(name);
 
         self
     }
-}
-
-// -------------------------------------------------------------------------------------------------
-// TerminalCheckoutQuery builder implementation
-// -------------------------------------------------------------------------------------------------
-impl Validate for TerminalCheckoutQuery {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        Ok(self)
-    }
-}
 
-impl<T: ParentBuilder> Builder<TerminalCheckoutQuery, T> {
-    pub fn sort_ascending(mut self) -> Self {
-        self.body.sort = Some(TerminalCheckoutQuerySort { sort_order: Some(SortOrder::Asc) });
+    /// The percentage of the tax as a decimal string such as "7.25".
+    pub fn percentage(mut self, percentage: String) -> Self {
+        self.body.percentage = Some(percentage);
 
         self
     }
 
-    pub fn sort_descending(mut self) -> Self {
-        self.body.sort = Some(TerminalCheckoutQuerySort { sort_order: Some(SortOrder::Desc) });
+    /// The tax is added on top of the price.
+    pub fn additive(mut self) -> Self {
+        self.body.calculation_method_type = Some(OrderLineItemTaxType::Additive);
 
         self
     }
 
-    pub fn created_at(mut self, created_at: TimeRange) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.created_at = Some(created_at);
-        } else {
-            self.body.filter = Some(TerminalCheckoutQueryFilter {
-                created_at: Some(created_at),
-                device_id: None,
-                status: None
-            })
-        };
+    /// The tax is already included in the price.
+    pub fn inclusive(mut self) -> Self {
+        self.body.calculation_method_type = Some(OrderLineItemTaxType::Inclusive);
 
         self
     }
 
-    pub fn device_id(mut self, device_id: String) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.device_id = Some(device_id);
-        } else {
-            self.body.filter = Some(TerminalCheckoutQueryFilter {
-                created_at: None,
-                device_id: Some(device_id),
-                status: None
-            })
-        };
+    pub fn scope(mut self, scope: OrderLineItemTaxScope) -> Self {
+        self.body.scope = Some(scope);
 
         self
     }
+}
 
-    pub fn status(mut self, status: TerminalCheckoutStatus) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.status = Some(status);
-        } else {
-            self.body.filter = Some(TerminalCheckoutQueryFilter {
-                created_at: None,
-                device_id: None,
-                status: Some(status)
-            })
-        };
+// an ad hoc adjustment has a name and exactly one of an amount and a percentage, one from the
+// catalog gets those from the catalog and may only override them
+fn valid_adjustment(catalog_object_id: Option<&String>, name: Option<&String>, amount: bool,
+                    percentage: Option<&String>) -> bool {
+    let valid_percentage = percentage.is_none_or(|percentage| {
+        percentage.parse::<f64>().is_ok_and(|percentage| (0.0..=100.0).contains(&percentage))
+    });
 
-        self
-    }
+    valid_percentage && !(amount && percentage.is_some()) &&
+        (catalog_object_id.is_some() || (name.is_some() && (amount || percentage.is_some())))
 }
 
 // -------------------------------------------------------------------------------------------------
 // SearchOrdersQuery builder implementation
 // -------------------------------------------------------------------------------------------------
-impl Validate for TerminalRefundQuery {
-    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+impl Validate for SearchOrdersQuery {
+    fn validate(mut self) -> Result<Self, ValidationError> {
+        let date_time_filter = self.filter.as_ref()
+            .and_then(|filter| filter.date_time_filter.as_ref());
+
+        // a query may filter on only one of the timestamps, and has to sort on that same one
+        let sort_field = match date_time_filter {
+            Some(filter) => {
+                let ranges = [
+                    (&filter.created_at, SearchOrdersSortField::CreatedAt),
+                    (&filter.updated_at, SearchOrdersSortField::UpdatedAt),
+                    (&filter.closed_at, SearchOrdersSortField::ClosedAt),
+                ];
+                let mut set = ranges.iter().filter(|(range, _)| range.is_some());
+
+                match (set.next(), set.next()) {
+                    (Some((Some(range), field)), None) if valid_time_range(range) => {
+                        Some(field.clone())
+                    },
+                    (None, None) => None,
+                    _ => return Err(ValidationError),
+                }
+            },
+            None => None,
+        };
+
+        if let Some(sort_field) = sort_field {
+            match self.sort.as_mut() {
+                Some(SearchOrdersSort { sort_field: Some(field), .. }) if *field != sort_field => {
+                    return Err(ValidationError);
+                },
+                Some(sort) => sort.sort_field = Some(sort_field),
+                None => self.sort = Some(SearchOrdersSort {
+                    sort_field: Some(sort_field),
+                    sort_order: None,
+                }),
+            }
+        }
+
         Ok(self)
     }
 }
 
-impl<T: ParentBuilder> Builder<TerminalRefundQuery, T> {
-    pub fn created_at(mut self, created_at: TimeRange) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.created_at = Some(created_at)
-        } else {
-            self.body.filter = Some(TerminalRefundQueryFilter {
-                created_at: Some(created_at),
-                device_id: None,
-                status: None
-            })
-        }
+impl<T: ParentBuilder> Builder<SearchOrdersQuery, T> {
+    pub fn filter(mut self, filter: SearchOrdersFilter) -> Self {
+        self.body.filter = Some(filter);
 
         self
     }
 
-    pub fn device_id(mut self, device_id: String) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.device_id = Some(device_id)
-        } else {
-            self.body.filter = Some(TerminalRefundQueryFilter {
-                created_at: None,
-                device_id: Some(device_id),
-                status: None
+    pub fn sort_ascending(mut self) -> Self {
+        match self.body.sort.as_mut() {
+            Some(sort) => sort.sort_order= Some(SortOrder::Asc),
+            None => self.body.sort = Some(SearchOrdersSort {
+                sort_field: Some(SearchOrdersSortField::CreatedAt),
+                sort_order: Some(SortOrder::Asc),
             })
         }
 
         self
     }
 
-    pub fn pending(mut self) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.status = Some(TerminalCheckoutStatus::Pending)
-        } else {
-            self.body.filter = Some(TerminalRefundQueryFilter {
-                created_at: None,
-                device_id: None,
-                status: Some(TerminalCheckoutStatus::Pending)
+    pub fn sort_descending(mut self) -> Self {
+        match self.body.sort.as_mut() {
+            Some(sort) => sort.sort_order= Some(SortOrder::Desc),
+            None => self.body.sort = Some(SearchOrdersSort {
+                sort_field: Some(SearchOrdersSortField::CreatedAt),
+                sort_order: Some(SortOrder::Desc),
             })
         }
 
         self
     }
 
-    pub fn in_progress(mut self) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.status = Some(TerminalCheckoutStatus::InProgress)
-        } else {
-            self.body.filter = Some(TerminalRefundQueryFilter {
-                created_at: None,
-                device_id: None,
-                status: Some(TerminalCheckoutStatus::InProgress)
+    pub fn sort_field(mut self, sort_field: SearchOrdersSortField) -> Self {
+        match self.body.sort.as_mut() {
+            Some(sort) => sort.sort_field = Some(sort_field),
+            None => self.body.sort = Some(SearchOrdersSort {
+                sort_field: Some(sort_field),
+                sort_order: None,
             })
         }
 
         self
     }
 
-    pub fn cancel_requested(mut self) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.status = Some(TerminalCheckoutStatus::CancelRequested)
-        } else {
-            self.body.filter = Some(TerminalRefundQueryFilter {
-                created_at: None,
-                device_id: None,
-                status: Some(TerminalCheckoutStatus::CancelRequested)
-            })
-        }
+    pub fn states(mut self, states: Vec<OrderState>) -> Self {
+        self.filter_mut().state_filter = Some(SearchOrdersStateFilter { states: Some(states) });
 
         self
     }
 
-    pub fn canceled(mut self) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.status = Some(TerminalCheckoutStatus::Canceled)
-        } else {
-            self.body.filter = Some(TerminalRefundQueryFilter {
-                created_at: None,
-                device_id: None,
-                status: Some(TerminalCheckoutStatus::Canceled)
-            })
-        }
+    pub fn customer_ids(mut self, customer_ids: Vec<String>) -> Self {
+        self.filter_mut().customer_filter = Some(SearchOrdersCustomerFilter {
+            customer_ids: Some(customer_ids),
+        });
 
         self
     }
 
-    pub fn completed(mut self) -> Self {
-        if let Some(filter) = self.body.filter.as_mut() {
-            filter.status = Some(TerminalCheckoutStatus::Completed)
-        } else {
-            self.body.filter = Some(TerminalRefundQueryFilter {
-                created_at: None,
-                device_id: None,
-                status: Some(TerminalCheckoutStatus::Completed)
-            })
+    pub fn source_names(mut self, source_names: Vec<String>) -> Self {
+        self.filter_mut().source_filter = Some(SearchOrdersSourceFilter {
+            source_names: Some(source_names),
+        });
+
+        self
+    }
+
+    pub fn fulfillment_states(mut self, states: Vec<OrderFulfillmentState>) -> Self {
+        self.filter_mut().fulfillment_filter.get_or_insert_with(Default::default)
+            .fulfillment_states = Some(states);
+
+        self
+    }
+
+    pub fn fulfillment_types(mut self, types: Vec<OrderFulfillmentType>) -> Self {
+        self.filter_mut().fulfillment_filter.get_or_insert_with(Default::default)
+            .fulfillment_types = Some(types);
+
+        self
+    }
+
+    /// Only orders created within the time range, sorted on `CREATED_AT` as the API requires. Cannot be
+    /// combined with the other time ranges.
+    pub fn created_at(mut self, created_at: TimeRange) -> Self {
+        self.date_time_filter_mut().created_at = Some(created_at);
+
+        self.sort_field(SearchOrdersSortField::CreatedAt)
+    }
+
+    /// Only orders updated within the time range, sorted on `UPDATED_AT` as the API requires. Cannot be
+    /// combined with the other time ranges.
+    pub fn updated_at(mut self, updated_at: TimeRange) -> Self {
+        self.date_time_filter_mut().updated_at = Some(updated_at);
+
+        self.sort_field(SearchOrdersSortField::UpdatedAt)
+    }
+
+    /// Only orders closed within the time range, sorted on `CLOSED_AT` as the API requires. Cannot be
+    /// combined with the other time ranges.
+    pub fn closed_at(mut self, closed_at: TimeRange) -> Self {
+        self.date_time_filter_mut().closed_at = Some(closed_at);
+
+        self.sort_field(SearchOrdersSortField::ClosedAt)
+    }
+
+    fn filter_mut(&mut self) -> &mut SearchOrdersFilter {
+        self.body.filter.get_or_insert_with(Default::default)
+    }
+
+    fn date_time_filter_mut(&mut self) -> &mut SearchOrdersDateTimeFilter {
+        self.filter_mut().date_time_filter.get_or_insert_with(Default::default)
+    }
+}
+
+// a time range has to be bounded on at least one side, with the start not after the end. Both
+// bounds are RFC 3339 timestamps, which compare chronologically as long as they share an offset.
+fn valid_time_range(range: &TimeRange) -> bool {
+    match (&range.start_at, &range.end_at) {
+        (Some(start_at), Some(end_at)) => start_at <= end_at,
+        (None, None) => false,
+        _ => true,
+    }
+}
+// -------------------------------------------------------------------------------------------------
+// CustomerQuery builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for CustomerQuery {
+    fn validate(self) -> Result<Self, ValidationError> {
+        if let Some(filter) = self.filter.as_ref() {
+            // a text filter matches either exactly or fuzzily, never both
+            let text_filters = [&filter.email_address, &filter.phone_number, &filter.reference_id];
+            let valid_text = text_filters.iter()
+                .all(|text| text.as_ref().is_none_or(|text| text.exact.is_some() != text.fuzzy.is_some()));
+            let valid_ranges = [&filter.created_at, &filter.updated_at].iter()
+                .all(|range| range.as_ref().is_none_or(valid_time_range));
+            let valid_groups = filter.group_ids.as_ref().is_none_or(|group_ids| {
+                group_ids.all.is_some() || group_ids.any.is_some() || group_ids.none.is_some()
+            });
+
+            if !valid_text || !valid_ranges || !valid_groups {
+                return Err(ValidationError);
+            }
         }
 
+        Ok(self)
+    }
+}
+
+impl<T: ParentBuilder> Builder<CustomerQuery, T> {
+    pub fn filter(mut self, filter: CustomerFilter) -> Self {
+        self.body.filter = Some(filter);
+
+        self
+    }
+
+    pub fn exact_email_address(mut self, email_address: String) -> Self {
+        self.filter_mut().email_address = Some(exact(email_address));
+
+        self
+    }
+
+    pub fn fuzzy_email_address(mut self, email_address: String) -> Self {
+        self.filter_mut().email_address = Some(fuzzy(email_address));
+
+        self
+    }
+
+    pub fn exact_phone_number(mut self, phone_number: String) -> Self {
+        self.filter_mut().phone_number = Some(exact(phone_number));
+
+        self
+    }
+
+    pub fn fuzzy_phone_number(mut self, phone_number: String) -> Self {
+        self.filter_mut().phone_number = Some(fuzzy(phone_number));
+
+        self
+    }
+
+    pub fn exact_reference_id(mut self, reference_id: String) -> Self {
+        self.filter_mut().reference_id = Some(exact(reference_id));
+
+        self
+    }
+
+    pub fn fuzzy_reference_id(mut self, reference_id: String) -> Self {
+        self.filter_mut().reference_id = Some(fuzzy(reference_id));
+
+        self
+    }
+
+    /// Only customers belonging to all of the groups.
+    pub fn in_all_groups(mut self, group_ids: Vec<String>) -> Self {
+        self.group_ids_mut().all = Some(group_ids);
+
+        self
+    }
+
+    /// Only customers belonging to at least one of the groups.
+    pub fn in_any_group(mut self, group_ids: Vec<String>) -> Self {
+        self.group_ids_mut().any = Some(group_ids);
+
+        self
+    }
+
+    /// Only customers belonging to none of the groups.
+    pub fn in_no_group(mut self, group_ids: Vec<String>) -> Self {
+        self.group_ids_mut().none = Some(group_ids);
+
+        self
+    }
+
+    /// Only customers created through one of the sources.
+    pub fn include_creation_sources(mut self, sources: Vec<CustomerCreationSource>) -> Self {
+        self.filter_mut().creation_source = Some(CreationSource {
+            rule: Some("INCLUDE".to_string()),
+            values: Some(sources),
+        });
+
+        self
+    }
+
+    /// Only customers created through none of the sources.
+    pub fn exclude_creation_sources(mut self, sources: Vec<CustomerCreationSource>) -> Self {
+        self.filter_mut().creation_source = Some(CreationSource {
+            rule: Some("EXCLUDE".to_string()),
+            values: Some(sources),
+        });
+
+        self
+    }
+
+    pub fn created_at(mut self, created_at: TimeRange) -> Self {
+        self.filter_mut().created_at = Some(created_at);
+
+        self
+    }
+
+    pub fn updated_at(mut self, updated_at: TimeRange) -> Self {
+        self.filter_mut().updated_at = Some(updated_at);
+
+        self
+    }
+
+    pub fn sort_field(mut self, field: CustomerSortField) -> Self {
+        self.body.sort.get_or_insert_with(Default::default).field = Some(field);
+
         self
     }
 
     pub fn sort_ascending(mut self) -> Self {
-        self.body.sort = Some(TerminalCheckoutQuerySort{ sort_order: Some(SortOrder::Asc) });
+        self.body.sort.get_or_insert_with(Default::default).order = Some(SortOrder::Asc);
 
         self
     }
 
     pub fn sort_descending(mut self) -> Self {
-        self.body.sort = Some(TerminalCheckoutQuerySort{ sort_order: Some(SortOrder::Desc) });
+        self.body.sort.get_or_insert_with(Default::default).order = Some(SortOrder::Desc);
 
         self
     }
+
+    fn filter_mut(&mut self) -> &mut CustomerFilter {
+        self.body.filter.get_or_insert_with(Default::default)
+    }
+
+    fn group_ids_mut(&mut self) -> &mut FilterValue {
+        self.filter_mut().group_ids.get_or_insert_with(Default::default)
+    }
+}
+
+fn exact(value: String) -> CustomerTextFilter {
+    CustomerTextFilter { exact: Some(value), fuzzy: None }
+}
+
+fn fuzzy(value: String) -> CustomerTextFilter {
+    CustomerTextFilter { exact: None, fuzzy: Some(value) }
 }
 
 // -------------------------------------------------------------------------------------------------
-// SearchOrdersQuery builder implementation
+// Order builder implementation
 // -------------------------------------------------------------------------------------------------
-impl Validate for InventoryChange {
+impl Validate for Order {
     fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
-        Ok(self)
+        if self.location_id.is_some(){
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
     }
 }
 
-impl<T: ParentBuilder> Builder<InventoryChange, T> {
-    pub fn change_type(mut self, change_type: InventoryChangeType) -> Self {
-        self.body.inventory_change_type = change_type;
+impl<T: ParentBuilder> Builder<Order, T> {
+    pub fn location_id(mut self, location_id: String) -> Self {
+        self.body.location_id = Some(location_id);
 
         self
     }
 
-    pub fn physical_count(mut self, physical_count: InventoryPhysicalCount) -> Self {
-        self.body.physical_count = Some(physical_count);
+    pub fn version(mut self, version: i64) -> Self {
+        self.body.version = Some(version);
 
         self
     }
 
-    pub fn adjustment(mut self, adjustment: InventoryAdjustment) -> Self {
-        self.body.adjustment = Some(adjustment);
+    pub fn add_service_charge(mut self, service_charge: OrderServiceCharge) -> Self {
+        if let Some(service_charges) = self.body.service_charges.as_mut() {
+            service_charges.push(service_charge);
+        } else {
+            self.body.service_charges = Some(vec![service_charge])
+        };
 
         self
     }
 
-    pub fn transfer(mut self, transfer: InventoryTransfer) -> Self {
-        self.body.transfer = Some(transfer);
+    pub fn add_order_item(mut self, order_item: OrderLineItem) -> Self {
+        if let Some(line_items) = self.body.line_items.as_mut() {
+            line_items.push(order_item);
+        } else {
+            self.body.line_items = Some(vec![order_item])
+        };
+
+        self
+    }
+
+    pub fn add_fulfillment(mut self, fulfillment: OrderFulfillment) -> Self {
+        self.body.fulfillments.get_or_insert_with(Vec::new).push(fulfillment);
+
+        self
+    }
+
+    pub fn add_discount(mut self, discount: OrderLineItemDiscount) -> Self {
+        self.body.discounts.get_or_insert_with(Vec::new).push(discount);
+
+        self
+    }
+
+    pub fn add_tax(mut self, tax: OrderLineItemTax) -> Self {
+        self.body.taxes.get_or_insert_with(Vec::new).push(tax);
+
+        self
+    }
+
+    /// Adds the items returned from another order. Together with line items, the order is an
+    /// exchange.
+    pub fn add_return(mut self, order_return: OrderReturn) -> Self {
+        self.body.returns.get_or_insert_with(Vec::new).push(order_return);
+
+        self
+    }
+}
+
+impl AddField<OrderServiceCharge> for Order {
+    fn add_field(&mut self, field: OrderServiceCharge) {
+        if let Some(service_charges) = self.service_charges.as_mut() {
+            service_charges.push(field);
+        } else {
+            self.service_charges = Some(vec![field]);
+        }
+    }
+}
+
+impl AddField<OrderLineItem> for Order {
+    fn add_field(&mut self, field: OrderLineItem) {
+        if let Some(line_items) = self.line_items.as_mut() {
+            line_items.push(field);
+        } else {
+            self.line_items = Some(vec![field]);
+        }
+    }
+}
+
+impl AddField<OrderFulfillment> for Order {
+    fn add_field(&mut self, field: OrderFulfillment) {
+        self.fulfillments.get_or_insert_with(Vec::new).push(field);
+    }
+}
+
+impl AddField<OrderLineItemDiscount> for Order {
+    fn add_field(&mut self, field: OrderLineItemDiscount) {
+        self.discounts.get_or_insert_with(Vec::new).push(field);
+    }
+}
+
+impl AddField<OrderLineItemTax> for Order {
+    fn add_field(&mut self, field: OrderLineItemTax) {
+        self.taxes.get_or_insert_with(Vec::new).push(field);
+    }
+}
+
+impl AddField<OrderReturn> for Order {
+    fn add_field(&mut self, field: OrderReturn) {
+        self.returns.get_or_insert_with(Vec::new).push(field);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// OrderReturn builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for OrderReturn {
+    fn validate(self) -> Result<Self, ValidationError> {
+        // an itemized return names the line items of the source order it returns, an ad hoc one
+        // names and prices the items itself
+        let valid_line_item = |line_item: &OrderReturnLineItem| {
+            line_item.quantity.parse::<f64>().is_ok_and(|quantity| quantity > 0.0) &&
+                (line_item.source_line_item_uid.is_some() ||
+                    (line_item.name.is_some() && line_item.base_price_money.is_some()))
+        };
+
+        match self.return_line_items.as_ref() {
+            Some(line_items) if self.source_order_id.is_some() && !line_items.is_empty() &&
+                line_items.iter().all(valid_line_item) => Ok(self),
+            _ => Err(ValidationError),
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<OrderReturn, T> {
+    pub fn uid(mut self, uid: String) -> Self {
+        self.body.uid = Some(uid);
+
+        self
+    }
+
+    /// The ID of the order the items are returned from.
+    pub fn source_order_id(mut self, source_order_id: String) -> Self {
+        self.body.source_order_id = Some(source_order_id);
+
+        self
+    }
+
+    /// Returns a quantity of a line item of the source order, which needs its `uid`.
+    /// # Arguments:
+    /// * `line_item` - The line item of the source order.
+    /// * `quantity` - The quantity returned, as a decimal string such as "1".
+    pub fn return_line_item(mut self, line_item: &OrderLineItem, quantity: String) -> Self {
+        self.body.return_line_items.get_or_insert_with(Vec::new).push(OrderReturnLineItem {
+            quantity,
+            base_price_money: line_item.base_price_money.clone(),
+            catalog_object_id: line_item.catalog_object_id.clone(),
+            catalog_version: line_item.catalog_version,
+            item_type: line_item.item_type.clone(),
+            name: line_item.name.clone(),
+            quantity_unit: line_item.quantity_unit.clone(),
+            source_line_item_uid: line_item.uid.clone(),
+            variation_name: line_item.variation_name.clone(),
+            ..Default::default()
+        });
+
+        self
+    }
+
+    pub fn add_return_line_item(mut self, return_line_item: OrderReturnLineItem) -> Self {
+        self.body.return_line_items.get_or_insert_with(Vec::new).push(return_line_item);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// OrderFulfillment builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for OrderFulfillment {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        // a fulfillment carries the details of its own type and no others
+        let details = [
+            (OrderFulfillmentType::Pickup, self.pickup_details.is_some()),
+            (OrderFulfillmentType::Shipment, self.shipment_details.is_some()),
+            (OrderFulfillmentType::Delivery, self.delivery_details.is_some()),
+        ];
+
+        let matching = details.iter().all(|(fulfillment_type, is_some)| {
+            *is_some == (self.fulfillment_type.as_ref() == Some(fulfillment_type))
+        });
+
+        if matching {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<OrderFulfillment, T> {
+    pub fn uid(mut self, uid: String) -> Self {
+        self.body.uid = Some(uid);
+
+        self
+    }
+
+    pub fn pickup_details(mut self, details: OrderFulfillmentPickupDetails) -> Self {
+        self.body.add_field(details);
+
+        self
+    }
+
+    pub fn shipment_details(mut self, details: OrderFulfillmentShipmentDetails) -> Self {
+        self.body.add_field(details);
+
+        self
+    }
+
+    pub fn delivery_details(mut self, details: OrderFulfillmentDeliveryDetails) -> Self {
+        self.body.add_field(details);
+
+        self
+    }
+
+    pub fn add_metadata(mut self, key: String, value: String) -> Self {
+        self.body.metadata.get_or_insert_with(Default::default).insert(key, value);
 
         self
     }
-}
\ No newline at end of file
+}
+
+impl AddField<OrderFulfillmentPickupDetails> for OrderFulfillment {
+    fn add_field(&mut self, field: OrderFulfillmentPickupDetails) {
+        self.fulfillment_type = Some(OrderFulfillmentType::Pickup);
+        self.pickup_details = Some(field);
+    }
+}
+
+impl AddField<OrderFulfillmentShipmentDetails> for OrderFulfillment {
+    fn add_field(&mut self, field: OrderFulfillmentShipmentDetails) {
+        self.fulfillment_type = Some(OrderFulfillmentType::Shipment);
+        self.shipment_details = Some(field);
+    }
+}
+
+impl AddField<OrderFulfillmentDeliveryDetails> for OrderFulfillment {
+    fn add_field(&mut self, field: OrderFulfillmentDeliveryDetails) {
+        self.fulfillment_type = Some(OrderFulfillmentType::Delivery);
+        self.delivery_details = Some(field);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// OrderFulfillmentPickupDetails builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for OrderFulfillmentPickupDetails {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        let scheduled = self.schedule_type != Some(OrderFulfillmentPickupDetailsScheduleType::Asap);
+
+        if valid_recipient(self.recipient.as_ref()) && (!scheduled || self.pickup_at.is_some()) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<OrderFulfillmentPickupDetails, T> {
+    pub fn recipient(mut self, recipient: OrderFulfillmentRecipient) -> Self {
+        self.body.recipient = Some(recipient);
+
+        self
+    }
+
+    /// Schedules the pickup for the given RFC 3339 timestamp.
+    pub fn pickup_at(mut self, pickup_at: String) -> Self {
+        self.body.schedule_type = Some(OrderFulfillmentPickupDetailsScheduleType::Scheduled);
+        self.body.pickup_at = Some(pickup_at);
+
+        self
+    }
+
+    /// Makes the order ready for pickup as soon as possible.
+    pub fn asap(mut self) -> Self {
+        self.body.schedule_type = Some(OrderFulfillmentPickupDetailsScheduleType::Asap);
+
+        self
+    }
+
+    pub fn pickup_window_duration(mut self, duration: String) -> Self {
+        self.body.pickup_window_duration = Some(duration);
+
+        self
+    }
+
+    pub fn prep_time_duration(mut self, duration: String) -> Self {
+        self.body.prep_time_duration = Some(duration);
+
+        self
+    }
+
+    pub fn note(mut self, note: String) -> Self {
+        self.body.note = Some(note);
+
+        self
+    }
+
+    pub fn curbside(mut self, curbside_details: String) -> Self {
+        self.body.is_curbside_pickup = Some(true);
+        self.body.curbside_pickup_details =
+            Some(OrderFulfillmentPickupDetailsCurbsidePickupDetails {
+                buyer_arrived_at: None,
+                curbside_details: Some(curbside_details),
+            });
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// OrderFulfillmentShipmentDetails builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for OrderFulfillmentShipmentDetails {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        if valid_recipient(self.recipient.as_ref()) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<OrderFulfillmentShipmentDetails, T> {
+    pub fn recipient(mut self, recipient: OrderFulfillmentRecipient) -> Self {
+        self.body.recipient = Some(recipient);
+
+        self
+    }
+
+    pub fn carrier(mut self, carrier: String) -> Self {
+        self.body.carrier = Some(carrier);
+
+        self
+    }
+
+    pub fn tracking_number(mut self, tracking_number: String) -> Self {
+        self.body.tracking_number = Some(tracking_number);
+
+        self
+    }
+
+    pub fn tracking_url(mut self, tracking_url: String) -> Self {
+        self.body.tracking_url = Some(tracking_url);
+
+        self
+    }
+
+    pub fn shipping_type(mut self, shipping_type: String) -> Self {
+        self.body.shipping_type = Some(shipping_type);
+
+        self
+    }
+
+    pub fn shipping_note(mut self, shipping_note: String) -> Self {
+        self.body.shipping_note = Some(shipping_note);
+
+        self
+    }
+
+    pub fn expected_shipped_at(mut self, expected_shipped_at: String) -> Self {
+        self.body.expected_shipped_at = Some(expected_shipped_at);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// OrderFulfillmentDeliveryDetails builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for OrderFulfillmentDeliveryDetails {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        let scheduled =
+            self.schedule_type != Some(OrderFulfillmentDeliveryDetailsScheduleType::Asap);
+
+        if valid_recipient(self.recipient.as_ref()) && (!scheduled || self.deliver_at.is_some()) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<OrderFulfillmentDeliveryDetails, T> {
+    pub fn recipient(mut self, recipient: OrderFulfillmentRecipient) -> Self {
+        self.body.recipient = Some(recipient);
+
+        self
+    }
+
+    /// Schedules the delivery for the given RFC 3339 timestamp.
+    pub fn deliver_at(mut self, deliver_at: String) -> Self {
+        self.body.schedule_type = Some(OrderFulfillmentDeliveryDetailsScheduleType::Scheduled);
+        self.body.deliver_at = Some(deliver_at);
+
+        self
+    }
+
+    /// Delivers the order as soon as possible.
+    pub fn asap(mut self) -> Self {
+        self.body.schedule_type = Some(OrderFulfillmentDeliveryDetailsScheduleType::Asap);
+
+        self
+    }
+
+    pub fn delivery_window_duration(mut self, duration: String) -> Self {
+        self.body.delivery_window_duration = Some(duration);
+
+        self
+    }
+
+    pub fn prep_time_duration(mut self, duration: String) -> Self {
+        self.body.prep_time_duration = Some(duration);
+
+        self
+    }
+
+    pub fn note(mut self, note: String) -> Self {
+        self.body.note = Some(note);
+
+        self
+    }
+
+    pub fn dropoff_notes(mut self, dropoff_notes: String) -> Self {
+        self.body.dropoff_notes = Some(dropoff_notes);
+
+        self
+    }
+
+    pub fn no_contact(mut self) -> Self {
+        self.body.is_no_contact_delivery = Some(true);
+
+        self
+    }
+}
+
+// Square needs to know who the order is fulfilled for, either through a customer profile or a name
+// along with a way to reach them
+fn valid_recipient(recipient: Option<&OrderFulfillmentRecipient>) -> bool {
+    recipient.is_some_and(|recipient| {
+        recipient.customer_id.is_some() ||
+            (recipient.display_name.is_some() &&
+                (recipient.email_address.is_some() || recipient.phone_number.is_some()))
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+// DeviceCheckoutOptions builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for DeviceCheckoutOptions {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.device_id.is_some() {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<DeviceCheckoutOptions, T> {
+    pub fn device_id(mut self, device_id: String) -> Self {
+        self.body.device_id = Some(device_id);
+
+        self
+    }
+
+    pub fn collect_signature(mut self) -> Self {
+        self.body.collect_signature = Some(true);
+
+        self
+    }
+
+    pub fn show_itemized_cart(mut self) -> Self {
+        self.body.show_itemized_cart = Some(true);
+
+        self
+    }
+
+    pub fn skip_receipt_screen(mut self) -> Self {
+        self.body.skip_receipt_screen = Some(true);
+
+        self
+    }
+
+    pub fn tip_settings(mut self, tip_settings: TipSettings) -> Self {
+        self.body.tip_settings = Some(tip_settings);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// TerminalCheckoutQuery builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for TerminalCheckoutQuery {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        match self.filter.as_ref() {
+            Some(filter) if !valid_terminal_filter(
+                filter.created_at.as_ref(),
+                filter.device_id.as_ref(),
+            ) => Err(ValidationError),
+            _ => Ok(self),
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<TerminalCheckoutQuery, T> {
+    pub fn sort_ascending(mut self) -> Self {
+        self.body.sort = Some(TerminalCheckoutQuerySort { sort_order: Some(SortOrder::Asc) });
+
+        self
+    }
+
+    pub fn sort_descending(mut self) -> Self {
+        self.body.sort = Some(TerminalCheckoutQuerySort { sort_order: Some(SortOrder::Desc) });
+
+        self
+    }
+
+    pub fn created_at(mut self, created_at: TimeRange) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.created_at = Some(created_at);
+        } else {
+            self.body.filter = Some(TerminalCheckoutQueryFilter {
+                created_at: Some(created_at),
+                device_id: None,
+                status: None
+            })
+        };
+
+        self
+    }
+
+    pub fn device_id(mut self, device_id: String) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.device_id = Some(device_id);
+        } else {
+            self.body.filter = Some(TerminalCheckoutQueryFilter {
+                created_at: None,
+                device_id: Some(device_id),
+                status: None
+            })
+        };
+
+        self
+    }
+
+    pub fn status(mut self, status: TerminalCheckoutStatus) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.status = Some(status);
+        } else {
+            self.body.filter = Some(TerminalCheckoutQueryFilter {
+                created_at: None,
+                device_id: None,
+                status: Some(status)
+            })
+        };
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// TerminalRefundQuery builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for TerminalRefundQuery {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        match self.filter.as_ref() {
+            Some(filter) if !valid_terminal_filter(
+                filter.created_at.as_ref(),
+                filter.device_id.as_ref(),
+            ) => Err(ValidationError),
+            _ => Ok(self),
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<TerminalRefundQuery, T> {
+    pub fn created_at(mut self, created_at: TimeRange) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.created_at = Some(created_at)
+        } else {
+            self.body.filter = Some(TerminalRefundQueryFilter {
+                created_at: Some(created_at),
+                device_id: None,
+                status: None
+            })
+        }
+
+        self
+    }
+
+    pub fn device_id(mut self, device_id: String) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.device_id = Some(device_id)
+        } else {
+            self.body.filter = Some(TerminalRefundQueryFilter {
+                created_at: None,
+                device_id: Some(device_id),
+                status: None
+            })
+        }
+
+        self
+    }
+
+    pub fn status(mut self, status: TerminalCheckoutStatus) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.status = Some(status)
+        } else {
+            self.body.filter = Some(TerminalRefundQueryFilter {
+                created_at: None,
+                device_id: None,
+                status: Some(status)
+            })
+        }
+
+        self
+    }
+
+    pub fn pending(mut self) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.status = Some(TerminalCheckoutStatus::Pending)
+        } else {
+            self.body.filter = Some(TerminalRefundQueryFilter {
+                created_at: None,
+                device_id: None,
+                status: Some(TerminalCheckoutStatus::Pending)
+            })
+        }
+
+        self
+    }
+
+    pub fn in_progress(mut self) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.status = Some(TerminalCheckoutStatus::InProgress)
+        } else {
+            self.body.filter = Some(TerminalRefundQueryFilter {
+                created_at: None,
+                device_id: None,
+                status: Some(TerminalCheckoutStatus::InProgress)
+            })
+        }
+
+        self
+    }
+
+    pub fn cancel_requested(mut self) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.status = Some(TerminalCheckoutStatus::CancelRequested)
+        } else {
+            self.body.filter = Some(TerminalRefundQueryFilter {
+                created_at: None,
+                device_id: None,
+                status: Some(TerminalCheckoutStatus::CancelRequested)
+            })
+        }
+
+        self
+    }
+
+    pub fn canceled(mut self) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.status = Some(TerminalCheckoutStatus::Canceled)
+        } else {
+            self.body.filter = Some(TerminalRefundQueryFilter {
+                created_at: None,
+                device_id: None,
+                status: Some(TerminalCheckoutStatus::Canceled)
+            })
+        }
+
+        self
+    }
+
+    pub fn completed(mut self) -> Self {
+        if let Some(filter) = self.body.filter.as_mut() {
+            filter.status = Some(TerminalCheckoutStatus::Completed)
+        } else {
+            self.body.filter = Some(TerminalRefundQueryFilter {
+                created_at: None,
+                device_id: None,
+                status: Some(TerminalCheckoutStatus::Completed)
+            })
+        }
+
+        self
+    }
+
+    pub fn sort_ascending(mut self) -> Self {
+        self.body.sort = Some(TerminalCheckoutQuerySort{ sort_order: Some(SortOrder::Asc) });
+
+        self
+    }
+
+    pub fn sort_descending(mut self) -> Self {
+        self.body.sort = Some(TerminalCheckoutQuerySort{ sort_order: Some(SortOrder::Desc) });
+
+        self
+    }
+}
+
+// a terminal search filter needs a non-empty device ID and a valid time range
+fn valid_terminal_filter(created_at: Option<&TimeRange>, device_id: Option<&String>) -> bool {
+    created_at.is_none_or(valid_time_range) &&
+        device_id.is_none_or(|device_id| !device_id.is_empty())
+}
+
+// -------------------------------------------------------------------------------------------------
+// SearchOrdersQuery builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for InventoryChange {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        // a change holds exactly the record its type names
+        let valid = match self.inventory_change_type {
+            InventoryChangeType::PhysicalCount => self.adjustment.is_none() &&
+                self.transfer.is_none() &&
+                self.physical_count.as_ref().is_some_and(|count| {
+                    valid_occurred_at(&count.occurred_at) &&
+                        valid_quantity(&count.quantity, true) &&
+                        PHYSICAL_COUNT_STATES.contains(&count.state)
+                }),
+            InventoryChangeType::Adjustment => self.physical_count.is_none() &&
+                self.transfer.is_none() &&
+                self.adjustment.as_ref().is_some_and(|adjustment| {
+                    adjustment.occurred_at.as_deref().is_some_and(valid_occurred_at) &&
+                        adjustment.quantity.as_deref()
+                            .is_some_and(|quantity| valid_quantity(quantity, false)) &&
+                        match (&adjustment.from_state, &adjustment.to_state) {
+                            (Some(from), Some(to)) => ADJUSTMENT_TRANSITIONS.contains(&(from, to)),
+                            _ => false,
+                        }
+                }),
+            InventoryChangeType::Transfer => self.physical_count.is_none() &&
+                self.adjustment.is_none() &&
+                self.transfer.as_ref().is_some_and(|transfer| {
+                    valid_occurred_at(&transfer.occurred_at) &&
+                        valid_quantity(&transfer.quantity, false) &&
+                        transfer.from_location_id != transfer.to_location_id &&
+                        transfer.state == InventoryState::InStock
+                }),
+        };
+
+        if valid {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+// the states a physical count can set the quantity of
+const PHYSICAL_COUNT_STATES: [InventoryState; 2] = [InventoryState::InStock, InventoryState::Waste];
+
+// the moves between states Square accepts for an adjustment
+const ADJUSTMENT_TRANSITIONS: [(&InventoryState, &InventoryState); 9] = [
+    (&InventoryState::None, &InventoryState::InStock),
+    (&InventoryState::InStock, &InventoryState::Sold),
+    (&InventoryState::InStock, &InventoryState::Waste),
+    (&InventoryState::InStock, &InventoryState::None),
+    (&InventoryState::Sold, &InventoryState::ReturnedByCustomer),
+    (&InventoryState::ReturnedByCustomer, &InventoryState::InStock),
+    (&InventoryState::ReturnedByCustomer, &InventoryState::Waste),
+    (&InventoryState::UnlinkedReturn, &InventoryState::InStock),
+    (&InventoryState::UnlinkedReturn, &InventoryState::Waste),
+];
+
+// the time a change occurred at has to be given as an RFC 3339 timestamp
+fn valid_occurred_at(occurred_at: &str) -> bool {
+    DateTime::parse_from_rfc3339(occurred_at).is_ok()
+}
+
+// a decimal quantity, which only a physical count may set to zero
+fn valid_quantity(quantity: &str, allow_zero: bool) -> bool {
+    match quantity.parse::<f64>() {
+        Ok(quantity) => quantity > 0.0 || (allow_zero && quantity == 0.0),
+        Err(_) => false,
+    }
+}
+
+impl<T: ParentBuilder> Builder<InventoryChange, T> {
+    pub fn change_type(mut self, change_type: InventoryChangeType) -> Self {
+        self.body.inventory_change_type = change_type;
+
+        self
+    }
+
+    pub fn physical_count(mut self, physical_count: InventoryPhysicalCount) -> Self {
+        self.body.physical_count = Some(physical_count);
+
+        self
+    }
+
+    pub fn adjustment(mut self, adjustment: InventoryAdjustment) -> Self {
+        self.body.adjustment = Some(adjustment);
+
+        self
+    }
+
+    pub fn transfer(mut self, transfer: InventoryTransfer) -> Self {
+        self.body.transfer = Some(transfer);
+
+        self
+    }
+}
+// -------------------------------------------------------------------------------------------------
+// CatalogCustomAttributeDefinition builder implementation
+// -------------------------------------------------------------------------------------------------
+// the highest number of decimal places Square allows for a number custom attribute
+const MAX_NUMBER_PRECISION: i32 = 5;
+
+impl Validate for CatalogCustomAttributeDefinition {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        let valid_config = match self.type_name {
+            Some(CatalogCustomAttributeDefinitionType::String) => self.number_config.is_none() &&
+                self.selection_config.is_none(),
+            Some(CatalogCustomAttributeDefinitionType::Boolean) => self.number_config.is_none() &&
+                self.selection_config.is_none() &&
+                self.string_config.is_none(),
+            Some(CatalogCustomAttributeDefinitionType::Number) => self.selection_config.is_none() &&
+                self.string_config.is_none() &&
+                self.number_config.as_ref()
+                    .and_then(|config| config.precision)
+                    .is_none_or(|precision| (0..=MAX_NUMBER_PRECISION).contains(&precision)),
+            Some(CatalogCustomAttributeDefinitionType::Selection) => self.number_config.is_none() &&
+                self.string_config.is_none() &&
+                self.selection_config.as_ref().is_some_and(valid_selection_config),
+            None => false,
+        };
+
+        if valid_config && self.name.as_ref().is_some_and(|name| !name.is_empty()) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<CatalogCustomAttributeDefinition, T> {
+    pub fn key(mut self, key: String) -> Self {
+        self.body.key = Some(key);
+
+        self
+    }
+
+    pub fn name(mut self, name: String) -> Self {
+        self.body.name = Some(name);
+
+        self
+    }
+
+    pub fn description(mut self, description: String) -> Self {
+        self.body.description = Some(description);
+
+        self
+    }
+
+    /// Adds a type of catalog object the attribute can be set on.
+    pub fn allowed_object_type(mut self, object_type: CatalogObjectType) -> Self {
+        self.body.allowed_object_types.get_or_insert_with(Vec::new).push(object_type);
+
+        self
+    }
+
+    /// Makes the attribute a string attribute.
+    /// # Arguments:
+    /// * `enforce_uniqueness` - Whether no two objects may hold the same value.
+    pub fn string(mut self, enforce_uniqueness: bool) -> Self {
+        self.body.type_name = Some(CatalogCustomAttributeDefinitionType::String);
+        self.body.string_config = Some(CatalogCustomAttributeDefinitionStringConfig {
+            enforce_uniqueness: Some(enforce_uniqueness),
+        });
+
+        self
+    }
+
+    /// Makes the attribute a boolean attribute.
+    pub fn boolean(mut self) -> Self {
+        self.body.type_name = Some(CatalogCustomAttributeDefinitionType::Boolean);
+
+        self
+    }
+
+    /// Makes the attribute a number attribute.
+    /// # Arguments:
+    /// * `precision` - The number of decimal places the values may have, from 0 up to 5.
+    pub fn number(mut self, precision: i32) -> Self {
+        self.body.type_name = Some(CatalogCustomAttributeDefinitionType::Number);
+        self.body.number_config = Some(CatalogCustomAttributeDefinitionNumberConfig {
+            precision: Some(precision),
+        });
+
+        self
+    }
+
+    /// Makes the attribute a selection attribute.
+    /// # Arguments:
+    /// * `allowed_selections` - The names of the values that can be selected, which have to be
+    ///   unique.
+    /// * `max_allowed_selections` - The most values an object may select at once, which can not
+    ///   exceed the number of allowed selections.
+    pub fn selection(mut self, allowed_selections: Vec<String>, max_allowed_selections: i32)
+                     -> Self {
+        self.body.type_name = Some(CatalogCustomAttributeDefinitionType::Selection);
+        self.body.selection_config = Some(CatalogCustomAttributeDefinitionSelectionConfig {
+            allowed_selections: Some(allowed_selections.into_iter()
+                .map(|name| CatalogCustomAttributeDefinitionSelectionConfigCustomAttributeSelection {
+                    name: Some(name),
+                    uid: None,
+                })
+                .collect()),
+            max_allowed_selections: Some(max_allowed_selections),
+        });
+
+        self
+    }
+}
+
+// a selection config needs at least one allowed selection, each with a unique non-empty name, and
+// a maximum number of selections between one and the number of allowed selections
+fn valid_selection_config(config: &CatalogCustomAttributeDefinitionSelectionConfig) -> bool {
+    let selections = config.allowed_selections.as_deref().unwrap_or_default();
+    let mut names: Vec<&str> = selections.iter()
+        .filter_map(|selection| selection.name.as_deref())
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    !selections.is_empty() &&
+        names.len() == selections.len() &&
+        config.max_allowed_selections
+            .is_none_or(|max| max >= 1 && max as usize <= selections.len())
+}
+
+// -------------------------------------------------------------------------------------------------
+// CatalogQuery builder implementation
+// -------------------------------------------------------------------------------------------------
+// the highest number of keywords Square allows in a text query
+const MAX_TEXT_QUERY_KEYWORDS: usize = 3;
+
+impl Validate for CatalogQuery {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        let named = |name: &String| !name.is_empty();
+        let listed = |ids: &Vec<String>| !ids.is_empty();
+
+        // Square takes exactly one kind of query
+        let queries = [
+            self.exact_query.as_ref().map(|query| named(&query.attribute_name)),
+            self.set_query.as_ref()
+                .map(|query| named(&query.attribute_name) && listed(&query.attribute_values)),
+            self.prefix_query.as_ref()
+                .map(|query| named(&query.attribute_name) && !query.attribute_prefix.is_empty()),
+            self.range_query.as_ref().map(|query| named(&query.attribute_name) &&
+                (query.attribute_min_value.is_some() || query.attribute_max_value.is_some()) &&
+                query.attribute_min_value.zip(query.attribute_max_value)
+                    .is_none_or(|(min, max)| min <= max)),
+            self.text_query.as_ref().map(|query| listed(&query.keywords) &&
+                query.keywords.len() <= MAX_TEXT_QUERY_KEYWORDS),
+            self.sorted_attribute_query.as_ref().map(|query| named(&query.attribute_name)),
+            self.items_for_tax_query.as_ref().map(|query| listed(&query.tax_ids)),
+            self.items_for_modifier_list_query.as_ref()
+                .map(|query| listed(&query.modifier_list_ids)),
+            self.items_for_item_options_query.as_ref()
+                .map(|query| query.item_option_ids.as_ref().is_some_and(listed)),
+            self.item_variations_for_item_option_values_query.as_ref()
+                .map(|query| query.item_option_value_ids.as_ref().is_some_and(listed)),
+        ];
+        let set = queries.iter().flatten().collect::<Vec<&bool>>();
+
+        if set.len() == 1 && *set[0] {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<CatalogQuery, T> {
+    /// Matches the objects whose attribute has the value, ignoring case.
+    pub fn exact(mut self, attribute_name: String, attribute_value: String) -> Self {
+        self.body.exact_query = Some(CatalogQueryExact { attribute_name, attribute_value });
+
+        self
+    }
+
+    /// Matches the objects whose attribute has exactly one of the values, ignoring case.
+    pub fn set(mut self, attribute_name: String, attribute_values: Vec<String>) -> Self {
+        self.body.set_query = Some(CatalogQuerySet { attribute_name, attribute_values });
+
+        self
+    }
+
+    /// Matches the objects whose attribute starts with the prefix, ignoring case.
+    pub fn prefix(mut self, attribute_name: String, attribute_prefix: String) -> Self {
+        self.body.prefix_query = Some(CatalogQueryPrefix { attribute_name, attribute_prefix });
+
+        self
+    }
+
+    /// Matches the objects whose numeric attribute lies within the range, both ends included.
+    /// At least one end has to be given.
+    pub fn range(mut self, attribute_name: String, attribute_min_value: Option<i64>,
+                 attribute_max_value: Option<i64>) -> Self {
+        self.body.range_query = Some(CatalogQueryRange {
+            attribute_name,
+            attribute_max_value,
+            attribute_min_value,
+        });
+
+        self
+    }
+
+    /// Matches the objects whose searchable attributes, such as the name or the SKU, contain all
+    /// of the keywords, in any order and ignoring case. Takes up to 3 keywords.
+    pub fn text(mut self, keywords: Vec<String>) -> Self {
+        self.body.text_query = Some(CatalogQueryText { keywords });
+
+        self
+    }
+
+    /// Matches all objects, sorted by the attribute, starting at the initial value if one is
+    /// given.
+    pub fn sorted_attribute(mut self, attribute_name: String,
+                            initial_attribute_value: Option<String>,
+                            sort_order: Option<SortOrder>) -> Self {
+        self.body.sorted_attribute_query = Some(CatalogQuerySortedAttribute {
+            attribute_name,
+            initial_attribute_value,
+            sort_order,
+        });
+
+        self
+    }
+
+    /// Matches the items any of the taxes apply to.
+    pub fn items_for_tax(mut self, tax_ids: Vec<String>) -> Self {
+        self.body.items_for_tax_query = Some(CatalogQueryItemsForTax { tax_ids });
+
+        self
+    }
+
+    /// Matches the items any of the modifier lists are enabled on.
+    pub fn items_for_modifier_list(mut self, modifier_list_ids: Vec<String>) -> Self {
+        self.body.items_for_modifier_list_query = Some(CatalogQueryItemsForModifierList {
+            modifier_list_ids,
+        });
+
+        self
+    }
+
+    /// Matches the items any of the item options are used by.
+    pub fn items_for_item_options(mut self, item_option_ids: Vec<String>) -> Self {
+        self.body.items_for_item_options_query = Some(CatalogQueryItemsForItemOptions {
+            item_option_ids: Some(item_option_ids),
+        });
+
+        self
+    }
+
+    /// Matches the item variations with all of the item option values.
+    pub fn item_variations_for_item_option_values(mut self, item_option_value_ids: Vec<String>)
+                                                  -> Self {
+        self.body.item_variations_for_item_option_values_query =
+            Some(CatalogQueryItemVariationsForItemOptionValues {
+                item_option_value_ids: Some(item_option_value_ids),
+            });
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CustomAttributeFilter builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for CustomAttributeFilter {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        let attributes = [&self.custom_attribute_definition_id, &self.key].iter()
+            .filter(|attribute| attribute.as_ref().is_some_and(|attribute| !attribute.is_empty()))
+            .count();
+        let filters = [
+            self.string_filter.is_some(),
+            self.number_filter.as_ref()
+                .is_some_and(|range| range.min.is_some() || range.max.is_some()),
+            self.selection_uids_filter.as_ref().is_some_and(|uids| !uids.is_empty()),
+            self.bool_filter.is_some(),
+        ].iter().filter(|set| **set).count();
+
+        // a filter names its attribute one way, and filters on it one way
+        if attributes == 1 && filters == 1 {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<CustomAttributeFilter, T> {
+    /// Filters on the attribute with the definition of this ID.
+    pub fn custom_attribute_definition_id(mut self, custom_attribute_definition_id: String)
+                                          -> Self {
+        self.body.custom_attribute_definition_id = Some(custom_attribute_definition_id);
+
+        self
+    }
+
+    /// Filters on the attribute with this key.
+    pub fn key(mut self, key: String) -> Self {
+        self.body.key = Some(key);
+
+        self
+    }
+
+    /// Matches a string attribute against the text.
+    pub fn string_filter(mut self, string_filter: String) -> Self {
+        self.body.string_filter = Some(string_filter);
+
+        self
+    }
+
+    /// Matches a number attribute within the range, both ends included. The ends are decimal
+    /// numbers in strings, such as `"9.99"`, and at least one has to be given.
+    pub fn number_filter(mut self, min: Option<String>, max: Option<String>) -> Self {
+        self.body.number_filter = Some(Range { max, min });
+
+        self
+    }
+
+    /// Matches a selection attribute with any of the selections of these UIDs.
+    pub fn selection_uids_filter(mut self, selection_uids: Vec<String>) -> Self {
+        self.body.selection_uids_filter = Some(selection_uids);
+
+        self
+    }
+
+    pub fn bool_filter(mut self, bool_filter: bool) -> Self {
+        self.body.bool_filter = Some(bool_filter);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CatalogSubscriptionPlan builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for CatalogSubscriptionPlan {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        if self.name.as_ref().is_some_and(|name| !name.is_empty()) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<CatalogSubscriptionPlan, T> {
+    pub fn name(mut self, name: String) -> Self {
+        self.body.name = Some(name);
+
+        self
+    }
+
+    /// Adds an item the plan can be subscribed to with.
+    pub fn add_eligible_item_id(mut self, item_id: String) -> Self {
+        self.body.eligible_item_ids.get_or_insert_with(Vec::new).push(item_id);
+
+        self
+    }
+
+    /// Adds a category whose items the plan can be subscribed to with.
+    pub fn add_eligible_category_id(mut self, category_id: String) -> Self {
+        self.body.eligible_category_ids.get_or_insert_with(Vec::new).push(category_id);
+
+        self
+    }
+
+    /// Whether the plan can be subscribed to with any item.
+    pub fn all_items(mut self, all_items: bool) -> Self {
+        self.body.all_items = Some(all_items);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CatalogSubscriptionPlanVariation builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for CatalogSubscriptionPlanVariation {
+    fn validate(mut self) -> Result<Self, ValidationError> where Self: Sized {
+        let phases = self.phases.get_or_insert_with(Vec::new);
+
+        // only the last phase may run until the subscription is canceled
+        let bounded = phases.iter()
+            .rev()
+            .skip(1)
+            .all(|phase| phase.periods.is_some_and(|periods| periods > 0));
+
+        if phases.is_empty() ||
+            !bounded ||
+            self.name.as_ref().is_none_or(|name| name.is_empty()) {
+            return Err(ValidationError);
+        }
+
+        for (ordinal, phase) in phases.iter_mut().enumerate() {
+            phase.ordinal.get_or_insert(ordinal as i64);
+        }
+
+        Ok(self)
+    }
+}
+
+impl<T: ParentBuilder> Builder<CatalogSubscriptionPlanVariation, T> {
+    pub fn name(mut self, name: String) -> Self {
+        self.body.name = Some(name);
+
+        self
+    }
+
+    /// The ID of the [CatalogSubscriptionPlan](CatalogSubscriptionPlan) the variation belongs to.
+    pub fn subscription_plan_id(mut self, subscription_plan_id: String) -> Self {
+        self.body.subscription_plan_id = Some(subscription_plan_id);
+
+        self
+    }
+
+    /// The day of the month, from 1 up to 31, on which monthly subscriptions are billed.
+    pub fn monthly_billing_anchor_date(mut self, monthly_billing_anchor_date: i64) -> Self {
+        self.body.monthly_billing_anchor_date = Some(monthly_billing_anchor_date);
+
+        self
+    }
+
+    /// Whether the first and last bills are prorated to the billing anchor date.
+    pub fn can_prorate(mut self, can_prorate: bool) -> Self {
+        self.body.can_prorate = Some(can_prorate);
+
+        self
+    }
+
+    /// The ID of the variation subscriptions move on to once this one is retired.
+    pub fn successor_plan_variation_id(mut self, successor_plan_variation_id: String) -> Self {
+        self.body.successor_plan_variation_id = Some(successor_plan_variation_id);
+
+        self
+    }
+
+    /// Adds a phase after those added before. Phases without an ordinal are given their position.
+    pub fn add_phase(mut self, phase: SubscriptionPhase) -> Self {
+        self.body.phases.get_or_insert_with(Vec::new).push(phase);
+
+        self
+    }
+}
+
+impl AddField<SubscriptionPhase> for CatalogSubscriptionPlanVariation {
+    fn add_field(&mut self, field: SubscriptionPhase) {
+        self.phases.get_or_insert_with(Vec::new).push(field);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// SubscriptionPhase builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for SubscriptionPhase {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        let priced = self.pricing.as_ref().is_none_or(|pricing| match pricing.type_name {
+            Some(SubscriptionPricingType::Static) => pricing.price_money.is_some(),
+            _ => true,
+        });
+
+        if self.cadence.is_some() && priced {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<SubscriptionPhase, T> {
+    pub fn cadence(mut self, cadence: SubscriptionCadence) -> Self {
+        self.body.cadence = Some(cadence);
+
+        self
+    }
+
+    /// The number of times the phase bills, forever if not set.
+    pub fn periods(mut self, periods: i32) -> Self {
+        self.body.periods = Some(periods);
+
+        self
+    }
+
+    /// Bills the same price every period.
+    pub fn static_price(mut self, price_money: Money) -> Self {
+        self.body.pricing = Some(SubscriptionPricing {
+            type_name: Some(SubscriptionPricingType::Static),
+            discount_ids: None,
+            price_money: Some(price_money),
+        });
+
+        self
+    }
+
+    /// Bills the price of the item subscribed to, with the discounts of these IDs applied.
+    pub fn relative_price(mut self, discount_ids: Vec<String>) -> Self {
+        self.body.pricing = Some(SubscriptionPricing {
+            type_name: Some(SubscriptionPricingType::Relative),
+            discount_ids: Some(discount_ids),
+            price_money: None,
+        });
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CatalogDiscount builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for CatalogDiscount {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        let amount = self.amount_money.is_some();
+        let percentage = self.percentage.is_some();
+        let maximum = self.maximum_amount_money.is_some();
+
+        // a fixed discount holds its value, a variable one is given its value at the time of sale
+        let valid_value = match self.discount_type {
+            Some(CatalogDiscountType::FixedPercentage) => percentage && !amount,
+            Some(CatalogDiscountType::FixedAmount) => amount && !percentage && !maximum,
+            Some(CatalogDiscountType::VariablePercentage) => !amount,
+            Some(CatalogDiscountType::VariableAmount) => !amount && !percentage && !maximum,
+            None => false,
+        };
+
+        if valid_value && self.name.as_ref().is_some_and(|name| !name.is_empty()) {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<CatalogDiscount, T> {
+    pub fn name(mut self, name: String) -> Self {
+        self.body.name = Some(name);
+
+        self
+    }
+
+    /// Takes the percentage off, as a decimal string such as "7.25".
+    pub fn fixed_percentage(mut self, percentage: String) -> Self {
+        self.body.discount_type = Some(CatalogDiscountType::FixedPercentage);
+        self.body.percentage = Some(percentage);
+        self.body.amount_money = None;
+
+        self
+    }
+
+    /// Takes the amount off.
+    pub fn fixed_amount(mut self, amount_money: Money) -> Self {
+        self.body.discount_type = Some(CatalogDiscountType::FixedAmount);
+        self.body.amount_money = Some(amount_money);
+        self.body.percentage = None;
+
+        self
+    }
+
+    /// Takes off a percentage entered at the time of sale.
+    /// # Arguments:
+    /// * `default_percentage` - The percentage suggested at the time of sale, if any.
+    pub fn variable_percentage(mut self, default_percentage: Option<String>) -> Self {
+        self.body.discount_type = Some(CatalogDiscountType::VariablePercentage);
+        self.body.percentage = default_percentage;
+        self.body.amount_money = None;
+
+        self
+    }
+
+    /// Takes off an amount entered at the time of sale.
+    pub fn variable_amount(mut self) -> Self {
+        self.body.discount_type = Some(CatalogDiscountType::VariableAmount);
+        self.body.amount_money = None;
+        self.body.percentage = None;
+
+        self
+    }
+
+    /// The most a percentage discount takes off.
+    pub fn maximum_amount_money(mut self, maximum_amount_money: Money) -> Self {
+        self.body.maximum_amount_money = Some(maximum_amount_money);
+
+        self
+    }
+
+    /// The color of the discount on the point of sale, as a hex code such as "9da2a6".
+    pub fn label_color(mut self, label_color: String) -> Self {
+        self.body.label_color = Some(label_color);
+
+        self
+    }
+
+    pub fn pin_required(mut self, pin_required: bool) -> Self {
+        self.body.pin_required = Some(pin_required);
+
+        self
+    }
+
+    pub fn modify_tax_basis(mut self, modify_tax_basis: CatalogDiscountModifyTaxBasis) -> Self {
+        self.body.modify_tax_basis = Some(modify_tax_basis);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CatalogProductSet builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for CatalogProductSet {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        let matches = [
+            self.product_ids_any.as_ref().is_some_and(|ids| !ids.is_empty()),
+            self.product_ids_all.as_ref().is_some_and(|ids| !ids.is_empty()),
+            self.all_products.unwrap_or(false),
+        ].iter().filter(|set| **set).count();
+
+        // an exact quantity rules out a range of quantities
+        let valid_quantity = match (self.quantity_exact, self.quantity_min, self.quantity_max) {
+            (Some(exact), None, None) => exact > 0,
+            (Some(_), _, _) => false,
+            (None, min, max) => min.is_none_or(|min| min >= 0) &&
+                max.is_none_or(|max| min.is_none_or(|min| min <= max)),
+        };
+
+        if matches == 1 && valid_quantity {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<CatalogProductSet, T> {
+    pub fn name(mut self, name: String) -> Self {
+        self.body.name = Some(name);
+
+        self
+    }
+
+    /// Adds a product, i.e. an item, variation, category or other product set, any of which
+    /// matches the set.
+    pub fn add_product_id_any(mut self, product_id: String) -> Self {
+        self.body.product_ids_any.get_or_insert_with(Vec::new).push(product_id);
+
+        self
+    }
+
+    /// Adds a product, i.e. an item, variation, category or other product set, all of which have
+    /// to be present for the set to match.
+    pub fn add_product_id_all(mut self, product_id: String) -> Self {
+        self.body.product_ids_all.get_or_insert_with(Vec::new).push(product_id);
+
+        self
+    }
+
+    /// Matches any product.
+    pub fn all_products(mut self, all_products: bool) -> Self {
+        self.body.all_products = Some(all_products);
+
+        self
+    }
+
+    /// Matches exactly this many of the products.
+    pub fn quantity_exact(mut self, quantity_exact: i64) -> Self {
+        self.body.quantity_exact = Some(quantity_exact);
+
+        self
+    }
+
+    pub fn quantity_min(mut self, quantity_min: i64) -> Self {
+        self.body.quantity_min = Some(quantity_min);
+
+        self
+    }
+
+    pub fn quantity_max(mut self, quantity_max: i64) -> Self {
+        self.body.quantity_max = Some(quantity_max);
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CatalogTimePeriod builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for CatalogTimePeriod {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        let valid_event = self.event.as_ref().is_some_and(|event| {
+            event.starts_with("BEGIN:VEVENT") &&
+                event.trim_end().ends_with("END:VEVENT") &&
+                event.contains("DTSTART:")
+        });
+
+        if valid_event {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<CatalogTimePeriod, T> {
+    /// The period as an iCalendar VEVENT, such as
+    /// `"BEGIN:VEVENT\nDTSTART:20190101T170000\nDURATION:PT2H\nRRULE:FREQ=WEEKLY;BYDAY=FR\nEND:VEVENT"`.
+    pub fn event(mut self, event: String) -> Self {
+        self.body.event = Some(event);
+
+        self
+    }
+
+    /// A period recurring every week on the given days, such as a happy hour.
+    /// # Arguments:
+    /// * `start` - The local date and time of the first period.
+    /// * `duration` - How long each period lasts.
+    /// * `days` - The days of the week the period recurs on.
+    pub fn weekly(mut self, start: NaiveDateTime, duration: Duration, days: &[Weekday]) -> Self {
+        let days: Vec<&str> = days.iter()
+            .map(|day| match day {
+                Weekday::Mon => "MO",
+                Weekday::Tue => "TU",
+                Weekday::Wed => "WE",
+                Weekday::Thu => "TH",
+                Weekday::Fri => "FR",
+                Weekday::Sat => "SA",
+                Weekday::Sun => "SU",
+            })
+            .collect();
+
+        self.body.event = Some(format!(
+            "BEGIN:VEVENT\nDTSTART:{}\nDURATION:PT{}M\nRRULE:FREQ=WEEKLY;BYDAY={}\nEND:VEVENT",
+            start.format("%Y%m%dT%H%M%S"),
+            duration.num_minutes(),
+            days.join(","),
+        ));
+
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// CatalogPricingRule builder implementation
+// -------------------------------------------------------------------------------------------------
+impl Validate for CatalogPricingRule {
+    fn validate(self) -> Result<Self, ValidationError> where Self: Sized {
+        // dates are YYYY-MM-DD, which compare chronologically
+        let valid_dates = match (&self.valid_from_date, &self.valid_until_date) {
+            (Some(from), Some(until)) => from <= until,
+            _ => true,
+        };
+
+        if self.discount_id.is_some() &&
+            self.match_products_id.is_some() &&
+            (self.exclude_strategy.is_none() || self.exclude_products_id.is_some()) &&
+            valid_dates {
+            Ok(self)
+        } else {
+            Err(ValidationError)
+        }
+    }
+}
+
+impl<T: ParentBuilder> Builder<CatalogPricingRule, T> {
+    pub fn name(mut self, name: String) -> Self {
+        self.body.name = Some(name);
+
+        self
+    }
+
+    /// The [CatalogDiscount](CatalogDiscount) the rule applies.
+    pub fn discount_id(mut self, discount_id: String) -> Self {
+        self.body.discount_id = Some(discount_id);
+
+        self
+    }
+
+    /// The [CatalogProductSet](CatalogProductSet) an order has to contain for the rule to apply.
+    pub fn match_products_id(mut self, match_products_id: String) -> Self {
+        self.body.match_products_id = Some(match_products_id);
+
+        self
+    }
+
+    /// The [CatalogProductSet](CatalogProductSet) the discount applies to, the matched products
+    /// if not set.
+    pub fn apply_products_id(mut self, apply_products_id: String) -> Self {
+        self.body.apply_products_id = Some(apply_products_id);
+
+        self
+    }
+
+    /// Leaves the products of the [CatalogProductSet](CatalogProductSet) matched by this one out
+    /// of the discount, picking them by the strategy.
+    pub fn exclude_products(mut self, exclude_products_id: String, strategy: ExcludeStrategy)
+                            -> Self {
+        self.body.exclude_products_id = Some(exclude_products_id);
+        self.body.exclude_strategy = Some(strategy);
+
+        self
+    }
+
+    /// Adds a [CatalogTimePeriod](CatalogTimePeriod) during which the rule applies. Without any
+    /// the rule applies at all times.
+    pub fn add_time_period_id(mut self, time_period_id: String) -> Self {
+        self.body.time_period_ids.get_or_insert_with(Vec::new).push(time_period_id);
+
+        self
+    }
+
+    /// The first day the rule applies, as YYYY-MM-DD.
+    pub fn valid_from_date(mut self, valid_from_date: String) -> Self {
+        self.body.valid_from_date = Some(valid_from_date);
+
+        self
+    }
+
+    /// The time of day the rule applies from on its first day, as HH:MM:SS.
+    pub fn valid_from_local_time(mut self, valid_from_local_time: String) -> Self {
+        self.body.valid_from_local_time = Some(valid_from_local_time);
+
+        self
+    }
+
+    /// The last day the rule applies, as YYYY-MM-DD.
+    pub fn valid_until_date(mut self, valid_until_date: String) -> Self {
+        self.body.valid_until_date = Some(valid_until_date);
+
+        self
+    }
+
+    /// The time of day the rule applies until on its last day, as HH:MM:SS.
+    pub fn valid_until_local_time(mut self, valid_until_local_time: String) -> Self {
+        self.body.valid_until_local_time = Some(valid_until_local_time);
+
+        self
+    }
+
+    /// The smallest subtotal an order needs for the rule to apply.
+    pub fn minimum_order_subtotal_money(mut self, minimum_order_subtotal_money: Money) -> Self {
+        self.body.minimum_order_subtotal_money = Some(minimum_order_subtotal_money);
+
+        self
+    }
+
+    /// Adds a customer group whose members the rule applies to. Without any the rule applies to
+    /// all customers.
+    pub fn add_customer_group_id(mut self, customer_group_id: String) -> Self {
+        self.body.customer_group_ids_any.get_or_insert_with(Vec::new).push(customer_group_id);
+
+        self
+    }
+}
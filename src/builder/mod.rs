@@ -1,6 +1,9 @@
 use crate::errors::{BuildError, ValidationError};
 pub mod implementations;
 
+use serde::Serialize;
+use serde_json::{Map, Value};
+
 // Any Object that is buildable implements this trait
 // When implemented it allows the use of the object with the Builder::from() method
 pub trait Validate {
@@ -14,6 +17,13 @@ pub trait AddField<T> {
     fn add_field(&mut self, field: T);
 }
 
+// When implemented, the body of a request can carry fields beyond its typed ones, which are
+// serialized alongside them.
+// Allows the use of the .raw_field() method.
+pub trait RawFields {
+    fn raw_fields(&mut self) -> &mut Map<String, Value>;
+}
+
 // This trait allows a builder to release a sub builder and allows that sub builder to add its field
 // to the releasing builders body.
 pub trait BackIntoBuilder<T: Validate, U: ParentBuilder + BackIntoBuilder<T, U>> {
@@ -52,6 +62,37 @@ impl<T: Validate, U: ParentBuilder> Builder<T, U> {
     }
 }
 
+// gives the builders of bodies implementing RawFields an escape hatch for fields they do not know
+impl<T: Validate + RawFields, U: ParentBuilder> Builder<T, U> {
+    /// Sets a field of the request body that the builder does not know about yet, such as one
+    /// Square added only recently. The value is sent as is next to the typed fields, and replaces
+    /// a raw field of the same name set before. Building fails if the name is that of a typed
+    /// field which is set as well.
+    /// # Arguments:
+    /// * `name` - The name of the field in the body of the request.
+    /// * `value` - The value of the field, any JSON such as `json!({ "enabled": true })`.
+    pub fn raw_field(mut self, name: &str, value: Value) -> Self {
+        self.body.raw_fields().insert(name.to_string(), value);
+
+        self
+    }
+}
+
+// whether none of the raw fields of the body shadows one of its typed fields
+pub(crate) fn valid_raw_fields<T: Serialize + RawFields>(body: &mut T) -> bool {
+    if body.raw_fields().is_empty() {
+        return true;
+    }
+
+    let raw_fields = std::mem::take(body.raw_fields());
+    let typed = serde_json::to_value(&*body).unwrap_or(Value::Null);
+    let valid = raw_fields.keys()
+        .all(|name| typed.get(name).is_none_or(|value| value.is_null()));
+    *body.raw_fields() = raw_fields;
+
+    valid
+}
+
 // Allows a builder that holds a parent builder that implements the BackIntoBuilder trait to return
 // the builder it is holding while also validating and adding its content to the body of the parent
 // builder.
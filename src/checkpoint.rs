@@ -0,0 +1,217 @@
+/*!
+Checkpoints of the cursors of long-running exports from the
+[Square API](https://developer.squareup.com).
+
+An export pages through a list or search endpoint by passing the `cursor` of each response to the
+next request. A [CheckpointStore](CheckpointStore) keeps the latest cursor of each named export
+job, so an export that was interrupted picks up at the page it stopped at instead of starting over.
+Save the cursor once the page before it has been processed, and clear the checkpoint once the last
+page has been.
+
+[MemoryCheckpointStore](MemoryCheckpointStore) keeps the checkpoints for the lifetime of the
+process, [FileCheckpointStore](FileCheckpointStore) keeps them in a directory across restarts. Both
+can be shared between tasks, and the file store writes every checkpoint to a temporary file first
+and then moves it in place, so a checkpoint is never read half written.
+
+# Example: Resume a nightly export of orders
+```no_run
+use square_ox::{
+    api::orders::SearchOrderBody,
+    builder::Builder,
+    checkpoint::{CheckpointStore, FileCheckpointStore},
+    client::SquareClient,
+};
+
+async {
+    let client = SquareClient::new("some_token");
+    let store = FileCheckpointStore::new("checkpoints").unwrap();
+    let mut cursor = store.load("nightly-orders").unwrap();
+
+    loop {
+        let mut body = Builder::from(SearchOrderBody::default())
+            .add_location_id("some_location_id".to_string());
+        if let Some(cursor) = cursor {
+            body = body.cursor(cursor);
+        }
+
+        let res = client.orders().search(body.build().await.unwrap()).await.unwrap();
+        cursor = res.cursor.clone();
+        // process the orders of the page
+
+        match cursor.as_deref() {
+            Some(cursor) => store.save("nightly-orders", cursor).unwrap(),
+            None => break store.clear("nightly-orders").unwrap(),
+        }
+    }
+};
+```
+ */
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+/// Keeps the latest cursor of each named export job.
+pub trait CheckpointStore: Send + Sync {
+    /// The cursor saved for the job, `None` if the job has no checkpoint.
+    fn load(&self, job: &str) -> io::Result<Option<String>>;
+
+    /// Saves the cursor for the job, replacing the one saved before.
+    fn save(&self, job: &str, cursor: &str) -> io::Result<()>;
+
+    /// Removes the checkpoint of the job, so its next run starts from the first page.
+    fn clear(&self, job: &str) -> io::Result<()>;
+}
+
+/// Keeps checkpoints in memory for the lifetime of the process.
+#[derive(Debug, Default)]
+pub struct MemoryCheckpointStore {
+    cursors: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryCheckpointStore {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl CheckpointStore for MemoryCheckpointStore {
+    fn load(&self, job: &str) -> io::Result<Option<String>> {
+        Ok(lock(&self.cursors)?.get(job).cloned())
+    }
+
+    fn save(&self, job: &str, cursor: &str) -> io::Result<()> {
+        lock(&self.cursors)?.insert(job.to_string(), cursor.to_string());
+
+        Ok(())
+    }
+
+    fn clear(&self, job: &str) -> io::Result<()> {
+        lock(&self.cursors)?.remove(job);
+
+        Ok(())
+    }
+}
+
+/// Keeps checkpoints in a directory, one file per job.
+#[derive(Debug)]
+pub struct FileCheckpointStore {
+    directory: PathBuf,
+    // serializes the writes of the tasks sharing the store
+    writing: Mutex<()>,
+}
+
+impl FileCheckpointStore {
+    /// A store keeping its checkpoints in the directory, which is created if it does not exist.
+    /// # Arguments:
+    /// * `directory` - The directory to keep the checkpoints in.
+    pub fn new<P: AsRef<Path>>(directory: P) -> io::Result<Self> {
+        fs::create_dir_all(directory.as_ref())?;
+
+        Ok(FileCheckpointStore {
+            directory: directory.as_ref().to_path_buf(),
+            writing: Mutex::new(()),
+        })
+    }
+
+    fn path(&self, job: &str) -> PathBuf {
+        self.directory.join(format!("{}.cursor", file_name(job)))
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    fn load(&self, job: &str) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.path(job)) {
+            Ok(cursor) => Ok(Some(cursor)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save(&self, job: &str, cursor: &str) -> io::Result<()> {
+        let _writing = lock(&self.writing)?;
+
+        // a rename replaces the checkpoint at once, so it is never read half written
+        let temporary = self.directory.join(format!(".{}.tmp", Uuid::new_v4()));
+        fs::write(&temporary, cursor)?;
+        fs::rename(&temporary, self.path(job)).inspect_err(|_| {
+            let _ = fs::remove_file(&temporary);
+        })
+    }
+
+    fn clear(&self, job: &str) -> io::Result<()> {
+        let _writing = lock(&self.writing)?;
+
+        match fs::remove_file(self.path(job)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> io::Result<std::sync::MutexGuard<'_, T>> {
+    mutex.lock().map_err(|_| io::Error::other("a checkpoint store lock was poisoned"))
+}
+
+// the job name with every character that is not safe in a file name escaped, so distinct jobs
+// never share a file
+fn file_name(job: &str) -> String {
+    job.bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_checkpoint {
+    use super::*;
+
+    fn round_trip(store: &dyn CheckpointStore) {
+        assert_eq!(store.load("orders").unwrap(), None);
+
+        store.save("orders", "first").unwrap();
+        store.save("orders", "second").unwrap();
+        store.save("payments", "other").unwrap();
+
+        assert_eq!(store.load("orders").unwrap(), Some("second".to_string()));
+        assert_eq!(store.load("payments").unwrap(), Some("other".to_string()));
+
+        store.clear("orders").unwrap();
+        store.clear("orders").unwrap();
+
+        assert_eq!(store.load("orders").unwrap(), None);
+        assert_eq!(store.load("payments").unwrap(), Some("other".to_string()));
+    }
+
+    #[test]
+    fn test_memory_checkpoint_store() {
+        round_trip(&MemoryCheckpointStore::new());
+    }
+
+    #[test]
+    fn test_file_checkpoint_store() {
+        let directory = std::env::temp_dir().join(format!("square-ox-{}", Uuid::new_v4()));
+        let store = FileCheckpointStore::new(&directory).unwrap();
+
+        round_trip(&store);
+        assert_eq!(
+            FileCheckpointStore::new(&directory).unwrap().load("payments").unwrap(),
+            Some("other".to_string()),
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name("nightly-orders_2"), "nightly-orders_2");
+        assert_eq!(file_name("../a b"), "%2E%2E%2Fa%20b");
+        assert_ne!(file_name("a/b"), file_name("a%2Fb"));
+    }
+}
@@ -0,0 +1,301 @@
+/*!
+Failing fast while the [Square API](https://developer.squareup.com) is unavailable.
+
+During an incident at Square every request may take until it times out before it fails, so the
+requests of a service depending on Square pile up behind them. A
+[CircuitBreaker](CircuitBreaker) registered on a client counts the consecutive requests that
+failed with a server error, timed out or failed to connect, and once they reach its threshold
+the circuit opens: requests then fail right away, without being sent, with an error of the code
+`SERVICE_UNAVAILABLE`. After a while the circuit lets a single request through as a probe, which
+closes the circuit again if it succeeds and keeps it open otherwise.
+
+Every attempt of a retried request counts on its own, while an attempt abandoned by the caller
+before it completed, e.g. through a timeout of its own, counts neither way. The breaker is shared
+by all clones of a client, so they all fail fast once Square is considered unavailable.
+
+# Example: Fail fast after 5 consecutive failures, probing Square again every 30 seconds
+```
+use square_ox::{circuit_breaker::CircuitBreaker, client::SquareClient};
+use std::time::Duration;
+
+let client = SquareClient::new("your_square_access_token")
+    .circuit_breaker(CircuitBreaker::new(5, Duration::from_secs(30)));
+```
+ */
+
+use crate::platform::Instant;
+use crate::transport::{HttpResponse, TransportError};
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The state of a [CircuitBreaker](CircuitBreaker).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent, and consecutive failures counted.
+    Closed,
+    /// Requests fail without being sent.
+    Open,
+    /// A single request is let through as a probe, while the others fail without being sent.
+    HalfOpen,
+}
+
+/// Opens the circuit after a number of consecutive failures, see the [module](self). Clones
+/// share their state.
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_for: Duration,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { probing: bool },
+}
+
+// what became of a request the breaker let through
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Success,
+    Failure,
+    /// The request failed for a reason that tells nothing about Square, or was never sent.
+    Inconclusive,
+}
+
+impl Outcome {
+    // a server error, a timeout or a failure to connect count against Square, any other
+    // response for it
+    pub(crate) fn of(sent: &Result<HttpResponse, TransportError>) -> Self {
+        match sent {
+            Ok(response) if response.status.is_server_error() => Outcome::Failure,
+            Ok(_) => Outcome::Success,
+            Err(err) if err.is_timeout() || err.is_connect() => Outcome::Failure,
+            Err(_) => Outcome::Inconclusive,
+        }
+    }
+}
+
+// a request the breaker let through, whose outcome is recorded once it is dropped, as
+// inconclusive unless told otherwise, so a request abandoned by its caller, e.g. through a
+// timeout, does not hold on to the probe of the circuit for good
+pub(crate) struct Admission {
+    breaker: CircuitBreaker,
+    outcome: Option<Outcome>,
+}
+
+impl Admission {
+    pub(crate) fn record(mut self, outcome: Outcome) {
+        self.outcome = Some(outcome);
+    }
+}
+
+impl Drop for Admission {
+    fn drop(&mut self) {
+        self.breaker.record(self.outcome.unwrap_or(Outcome::Inconclusive), Instant::now());
+    }
+}
+
+impl CircuitBreaker {
+    /// # Arguments:
+    /// * `failure_threshold` - The number of consecutive failures opening the circuit, at least 1.
+    /// * `open_for` - How long the circuit stays open before a probe is let through.
+    pub fn new(failure_threshold: u32, open_for: Duration) -> Self {
+        CircuitBreaker {
+            failure_threshold: failure_threshold.max(1),
+            open_for,
+            state: Arc::new(Mutex::new(State::Closed { failures: 0 })),
+        }
+    }
+
+    /// The current state of the circuit, e.g. to report it in a health check.
+    pub fn state(&self) -> CircuitState {
+        self.state_at(Instant::now())
+    }
+
+    fn state_at(&self, now: Instant) -> CircuitState {
+        match *self.state.lock().unwrap() {
+            State::Closed { .. } => CircuitState::Closed,
+            State::Open { until } if until > now => CircuitState::Open,
+            State::Open { .. } | State::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    // lets a request through at the given time, `None` while the circuit is open
+    pub(crate) fn admit(&self, now: Instant) -> Option<Admission> {
+        self.try_acquire(now).then(|| Admission {
+            breaker: self.clone(),
+            outcome: None,
+        })
+    }
+
+    // whether a request may be sent at the given time, which makes it the probe of a circuit
+    // that is no longer open
+    fn try_acquire(&self, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap();
+
+        match *state {
+            State::Closed { .. } => true,
+            State::Open { until } if until > now => false,
+            State::Open { .. } | State::HalfOpen { probing: false } => {
+                *state = State::HalfOpen { probing: true };
+
+                true
+            },
+            State::HalfOpen { probing: true } => false,
+        }
+    }
+
+    // records what became of a request let through at the given time
+    fn record(&self, outcome: Outcome, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+
+        let next = match (&mut *state, outcome) {
+            (State::Closed { failures }, Outcome::Failure) => {
+                *failures += 1;
+                if *failures < self.failure_threshold {
+                    return;
+                }
+
+                State::Open { until: now + self.open_for }
+            },
+            (State::Closed { .. }, Outcome::Success) => State::Closed { failures: 0 },
+            (State::HalfOpen { .. }, Outcome::Success) => State::Closed { failures: 0 },
+            (State::HalfOpen { .. }, Outcome::Failure) => State::Open { until: now + self.open_for },
+            // the next request becomes the probe instead
+            (State::HalfOpen { .. }, Outcome::Inconclusive) => State::HalfOpen { probing: false },
+            // requests let through before the circuit opened change nothing
+            _ => return,
+        };
+
+        *state = next;
+    }
+}
+
+#[cfg(test)]
+mod test_circuit_breaker {
+    use super::*;
+    use crate::client::SquareClient;
+    use crate::interceptor::{InterceptedRequest, InterceptedResponse, RequestInterceptor};
+    use crate::transport::{HttpRequest, HttpTransport, TransportFuture};
+    use reqwest::StatusCode;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_circuit_breaker() {
+        let start = Instant::now();
+        let sut = CircuitBreaker::new(2, Duration::from_secs(30));
+
+        assert!(sut.try_acquire(start));
+        sut.record(Outcome::Failure, start);
+        sut.record(Outcome::Success, start);
+        sut.record(Outcome::Failure, start);
+        assert_eq!(sut.state_at(start), CircuitState::Closed);
+
+        sut.record(Outcome::Failure, start);
+        assert_eq!(sut.state_at(start), CircuitState::Open);
+        assert!(!sut.try_acquire(start + Duration::from_secs(29)));
+
+        // a single probe is let through, and opens the circuit again if it fails
+        let later = start + Duration::from_secs(30);
+        assert_eq!(sut.state_at(later), CircuitState::HalfOpen);
+        assert!(sut.try_acquire(later));
+        assert!(!sut.try_acquire(later));
+        sut.record(Outcome::Failure, later);
+        assert!(!sut.try_acquire(later));
+
+        // an inconclusive probe hands over to the next request, a successful one closes it
+        let much_later = later + Duration::from_secs(30);
+        assert!(sut.try_acquire(much_later));
+        sut.record(Outcome::Inconclusive, much_later);
+        assert!(sut.clone().try_acquire(much_later));
+        sut.record(Outcome::Success, much_later);
+        assert_eq!(sut.state_at(much_later), CircuitState::Closed);
+        assert!(sut.try_acquire(much_later));
+    }
+
+    // answers every request with a server error, counting them
+    struct Outage(Arc<AtomicU32>);
+
+    impl RequestInterceptor for Outage {
+        fn before_request(&self, _: &mut InterceptedRequest) -> Option<InterceptedResponse> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+
+            Some(InterceptedResponse::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                r#"{ "errors": [{ "category": "API_ERROR", "code": "SERVICE_UNAVAILABLE" }] }"#
+                    .to_string(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast() {
+        let sent = Arc::new(AtomicU32::new(0));
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+        let sut = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Outage(sent.clone()))
+            .circuit_breaker(breaker.clone());
+
+        for _ in 0..3 {
+            assert!(sut.locations().list().await.is_err());
+        }
+
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let err = sut.locations().list().await.unwrap_err().get().unwrap();
+        assert_eq!(err[0].code, "SERVICE_UNAVAILABLE");
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+    }
+
+    // never answers
+    struct Hang;
+
+    impl HttpTransport for Hang {
+        fn send(&self, _: HttpRequest) -> TransportFuture<'_> {
+            Box::pin(std::future::pending())
+        }
+    }
+
+    // answers every request with an empty list of locations
+    struct Recovered;
+
+    impl RequestInterceptor for Recovered {
+        fn before_request(&self, _: &mut InterceptedRequest) -> Option<InterceptedResponse> {
+            Some(InterceptedResponse::new(StatusCode::OK, r#"{ "locations": [] }"#.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dropped_probe() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(10));
+        let failing = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Outage(Arc::new(AtomicU32::new(0))))
+            .circuit_breaker(breaker.clone());
+
+        assert!(failing.locations().list().await.is_err());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // the caller gives up on the probe before it completes
+        let hanging = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .transport(Hang)
+            .circuit_breaker(breaker.clone());
+        let probe = hanging.locations().list();
+        assert!(tokio::time::timeout(Duration::from_millis(10), probe).await.is_err());
+
+        let recovered = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Recovered)
+            .circuit_breaker(breaker.clone());
+
+        assert!(recovered.locations().list().await.is_ok());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
@@ -15,19 +15,56 @@ let client = SquareClient::new(ACCESS_TOKEN);
 ```
 After creating a client you will be able to use all of the clients methods.
 
+# Example: Configuring a client
+A client configured beyond its access token is best created through
+[SquareClient::builder](crate::client::SquareClient::builder).
+
+```rust
+use square_ox::client::SquareClient;
+use std::time::Duration;
+
+let client = SquareClient::builder()
+    .access_token("your_square_access_token")
+    .production()
+    .timeout(Duration::from_secs(30))
+    .build()
+    .unwrap();
+```
+
 */
 use crate::api::{SquareAPI, Verb};
+use crate::api::catalog::images::ImageFile;
+use crate::circuit_breaker::{CircuitBreaker, Outcome};
+use crate::drift::DriftDetector;
+use crate::interceptor::{InterceptedRequest, RequestInterceptor};
+use crate::metrics::{MetricsSink, RequestMetrics};
+use crate::platform::{self, Instant};
+use crate::rate_limit::{self, Priority, RateLimiter};
 use crate::errors::SquareError;
-use crate::response::SquareResponse;
+use crate::response::{BinaryResponse, ResponseMeta, SquareResponse};
+use crate::retry::{self, RetryPolicy};
+use crate::token::{StaticToken, TokenProvider};
+use crate::transport::{HttpRequest, HttpTransport, ReqwestTransport};
+#[cfg(feature = "tracing")]
+use crate::trace;
 
-use reqwest::{header, Client};
+use chrono::Utc;
+use reqwest::{header, Client, StatusCode};
+pub use reqwest::header::{HeaderMap, HeaderValue};
 use serde::Serialize;
 use std::default::Default;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
 
-#[derive(Copy, Clone)]
+#[derive(Clone, Debug)]
 pub enum ClientMode {
     Production,
     Sandboxed,
+    /// Any other base URL speaking the Square API, such as a local mock server or a gateway in
+    /// front of Square, e.g. `http://localhost:8080`. The `v2/` path of the endpoints is
+    /// appended to it.
+    Custom(String),
 }
 
 /// The default mode we start a client in is Sandboxed
@@ -37,12 +74,290 @@ impl Default for ClientMode {
     }
 }
 
+/// The version of the [Square API](https://developer.squareup.com) the crate is written against,
+/// sent along with every request unless the client is told otherwise.
+pub const SQUARE_VERSION: &str = "2022-10-19";
+
+// how long connecting to, and hearing back from, Square may take unless told otherwise
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+// how long an unused connection is kept in the pool unless told otherwise
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// How long a request may take. Connecting is bounded by the connect timeout of the
+/// [HttpConfig](HttpConfig).
+#[derive(Clone, Debug)]
+pub(crate) struct Timeouts {
+    /// The whole of a single attempt, from connecting until the response body has been read.
+    pub(crate) request: Option<Duration>,
+    /// Reading the response body once its headers have arrived.
+    pub(crate) read: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Timeouts {
+            request: Some(DEFAULT_REQUEST_TIMEOUT),
+            read: None,
+        }
+    }
+}
+
+/// Overrides of the configuration of a [SquareClient](SquareClient) for a single request, see
+/// [request_with_options](SquareClient::request_with_options). Whatever is not overridden is
+/// taken from the client.
+///
+/// # Example
+/// ```
+/// use square_ox::{client::{HeaderValue, RequestOptions}, retry::RetryPolicy};
+/// use std::time::Duration;
+///
+/// let options = RequestOptions::new()
+///     .timeout(Duration::from_secs(120))
+///     .retry_policy(RetryPolicy::none())
+///     .square_version("2022-09-21")
+///     .header("x-correlation-id", HeaderValue::from_static("some_correlation_id"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RequestOptions {
+    timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    square_version: Option<String>,
+    priority: Option<Priority>,
+    headers: HeaderMap,
+}
+
+impl RequestOptions {
+    /// Options overriding nothing.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// See [SquareClient::timeout](SquareClient::timeout).
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// See [SquareClient::read_timeout](SquareClient::read_timeout).
+    pub fn read_timeout(self, timeout: Duration) -> Self {
+        Self {
+            read_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// See [SquareClient::retry_policy](SquareClient::retry_policy).
+    pub fn retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy: Some(retry_policy),
+            ..self
+        }
+    }
+
+    /// See [SquareClient::square_version](SquareClient::square_version).
+    pub fn square_version(self, square_version: &str) -> Self {
+        Self {
+            square_version: Some(square_version.to_string()),
+            ..self
+        }
+    }
+
+    /// See [SquareClient::priority](SquareClient::priority).
+    pub fn priority(self, priority: Priority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+
+    /// Send the given header along with the request, replacing the default headers of the
+    /// client, and the headers it sets itself, of the same name.
+    pub fn header<K: header::IntoHeaderName>(mut self, name: K, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+
+        self
+    }
+
+    /// Send the given headers along with the request, see [header](RequestOptions::header).
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+
+        self
+    }
+}
+
+/// Connection level settings of the HTTP client used to talk to the
+/// [Square API](https://developer.squareup.com).
+///
+/// By default HTTP/1.1 is spoken with the `native-tls` feature, as reqwest 0.11 offers no
+/// protocol through ALPN with it, while with `rustls-tls` HTTP/2 is negotiated through ALPN. TCP
+/// keep-alive is disabled, addresses are tried as described for
+/// [local_address](SquareClient::local_address) and connecting times out after 10 seconds. The
+/// pool keeps any number of idle connections per host, each for up to 90 seconds. Compressed
+/// responses are asked for with the `gzip` and `brotli` features of the crate.
+///
+/// On `wasm32` the connections are managed by the `fetch` of the JavaScript host instead, and
+/// these settings have no effect.
+///
+/// Settings the HTTP client cannot be built from fail [SquareClientBuilder::build], while when set
+/// on a [SquareClient](SquareClient) they fail every request sent through it instead.
+///
+/// A request on a pooled connection saves a round trip over one on a new connection, two or three
+/// with TLS, as measured by the `connection_latency` example of the repository.
+#[derive(Clone, Debug)]
+pub struct HttpConfig {
+    pub(crate) http2_prior_knowledge: bool,
+    pub(crate) http2_keep_alive_interval: Option<Duration>,
+    pub(crate) http2_keep_alive_timeout: Option<Duration>,
+    pub(crate) tcp_keepalive: Option<Duration>,
+    pub(crate) local_address: Option<IpAddr>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) pool_max_idle_per_host: Option<usize>,
+    pub(crate) pool_idle_timeout: Option<Duration>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            http2_prior_knowledge: false,
+            http2_keep_alive_interval: None,
+            http2_keep_alive_timeout: None,
+            tcp_keepalive: None,
+            local_address: None,
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+        }
+    }
+}
+
+impl HttpConfig {
+    // fails if reqwest rejects the settings, e.g. as its TLS backend cannot be initialized
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn client(&self) -> Result<Client, reqwest::Error> {
+        let mut builder = Client::builder()
+            .tcp_keepalive(self.tcp_keepalive)
+            .local_address(self.local_address)
+            .pool_idle_timeout(self.pool_idle_timeout);
+
+        // rustls is asked for explicitly, so it is used also when native-tls is enabled along
+        #[cfg(feature = "rustls-tls")]
+        {
+            builder = builder.use_rustls_tls();
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder = builder
+                .http2_keep_alive_interval(interval)
+                .http2_keep_alive_while_idle(true);
+        }
+        if let Some(timeout) = self.http2_keep_alive_timeout {
+            builder = builder.http2_keep_alive_timeout(timeout);
+        }
+        if let Some(max_idle) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+
+        builder.build()
+    }
+
+    // the connections are left to the fetch of the host on wasm32, which takes none of the settings
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn client(&self) -> Result<Client, reqwest::Error> {
+        Ok(Client::new())
+    }
+}
+
+/// How a request authenticates itself with the [Square API](https://developer.squareup.com).
+pub(crate) enum Authorization<'a> {
+    /// Through the access token of the [SquareClient](SquareClient).
+    AccessToken,
+    /// Through the secret of the application, as required by some of the OAuth endpoints.
+    ClientSecret(&'a str),
+    /// Without an `Authorization` header, e.g. when the credentials are part of the body.
+    None,
+}
+
+// the body of a request
+pub(crate) enum RequestBody<'a> {
+    Json(serde_json::Value),
+    /// A JSON part named `request` and an optional file part of the given name.
+    Multipart {
+        request: serde_json::Value,
+        file: Option<(&'a str, &'a ImageFile)>,
+    },
+}
+
+impl RequestBody<'_> {
+    // the JSON of the body, which for a multipart form is its `request` part
+    fn json(&self) -> &serde_json::Value {
+        match self {
+            RequestBody::Json(body) => body,
+            RequestBody::Multipart { request, .. } => request,
+        }
+    }
+}
+
+// encodes the JSON `request` and, if given, the file as a multipart form, returning the content
+// type along with it
+fn multipart_body(request: &serde_json::Value, file: Option<(&str, &ImageFile)>)
+                  -> (String, Vec<u8>) {
+    let boundary = uuid::Uuid::new_v4().to_simple().to_string();
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"request\"\r\n\
+        Content-Type: application/json\r\n\r\n{}\r\n",
+        boundary, request,
+    ).into_bytes();
+
+    if let Some((name, file)) = file {
+        body.extend(format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+            Content-Type: {}\r\n\r\n",
+            boundary, name, file.file_name, file.content_type,
+        ).into_bytes());
+        body.extend(&file.bytes);
+        body.extend(b"\r\n");
+    }
+    body.extend(format!("--{}--\r\n", boundary).into_bytes());
+
+    (format!("multipart/form-data; boundary={}", boundary), body)
+}
+
 /// The SquareClient contains many useful methods allowing for convenient
 /// use of the [Square API](https://developer.squareup.com).
+///
+/// The underlying HTTP client, and with it its connection pool, is shared between all clones of
+/// a SquareClient.
 #[derive(Clone)]
 pub struct SquareClient {
-    access_token: String,
+    pub(crate) token_provider: Arc<dyn TokenProvider>,
     pub(crate) client_mode: ClientMode,
+    pub(crate) http_config: HttpConfig,
+    // the error of building the HTTP client from the http_config is returned by every request
+    // sent through it, as the setters changing the config cannot fail
+    pub(crate) http_client: Result<Client, String>,
+    pub(crate) timeouts: Timeouts,
+    pub(crate) square_version: String,
+    pub(crate) default_headers: HeaderMap,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) rate_limiter: RateLimiter,
+    pub(crate) priority: Priority,
+    pub(crate) circuit_breaker: Option<CircuitBreaker>,
+    pub(crate) interceptors: Vec<Arc<dyn RequestInterceptor>>,
+    pub(crate) metrics_sink: Option<Arc<dyn MetricsSink>>,
+    pub(crate) transport: Option<Arc<dyn HttpTransport>>,
+    pub(crate) drift_detector: Option<DriftDetector>,
 }
 
 impl SquareClient {
@@ -60,10 +375,50 @@ impl SquareClient {
     /// let client = SquareClient::new(ACCESS_TOKEN);
     /// ```
     pub fn new(access_token: &str) -> Self {
-        Self {
-            access_token: access_token.to_string(),
-            client_mode: Default::default(),
-        }
+        Self::with_token_provider(StaticToken::new(access_token))
+    }
+
+    /// Create a new [SquareClient](SquareClient) asking the given
+    /// [TokenProvider](crate::token::TokenProvider) for the access token before every request.
+    ///
+    /// This never fails: should the HTTP client not be buildable on the platform, every request
+    /// returns the error instead. Use [SquareClient::builder](SquareClient::builder) to see it
+    /// straight away.
+    ///
+    /// # Example: Read the access token from the environment
+    /// ```
+    /// use square_ox::{client::SquareClient, token::EnvToken};
+    ///
+    /// let client = SquareClient::with_token_provider(EnvToken::new("SQUARE_ACCESS_TOKEN"));
+    /// ```
+    pub fn with_token_provider<P: TokenProvider + 'static>(token_provider: P) -> Self {
+        Self::builder().assemble(
+            Arc::new(token_provider),
+            SQUARE_VERSION.to_string(),
+            HeaderMap::new(),
+        )
+    }
+
+    /// Start configuring a [SquareClient](SquareClient) through a
+    /// [SquareClientBuilder](SquareClientBuilder), which sets everything at once and builds the
+    /// HTTP client only when done.
+    ///
+    /// # Example: Create a client for production
+    /// ```
+    /// use square_ox::{client::SquareClient, retry::RetryPolicy};
+    /// use std::time::Duration;
+    ///
+    /// let client = SquareClient::builder()
+    ///     .access_token("your_square_access_token")
+    ///     .production()
+    ///     .timeout(Duration::from_secs(30))
+    ///     .retry_policy(RetryPolicy::default())
+    ///     .user_agent("my-shop/1.0")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> SquareClientBuilder {
+        SquareClientBuilder::default()
     }
 
     /// Set the client to Production Mode
@@ -80,11 +435,348 @@ impl SquareClient {
     /// ```
     pub fn production(self) -> Self {
         Self {
-            access_token: self.access_token,
             client_mode: ClientMode::Production,
+            ..self
+        }
+    }
+
+    /// Send all requests to the given base URL instead of Square's production or sandbox host,
+    /// see [ClientMode::Custom](ClientMode::Custom).
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::client::SquareClient;
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .base_url("http://localhost:8080");
+    /// ```
+    pub fn base_url(self, base_url: &str) -> Self {
+        Self {
+            client_mode: ClientMode::Custom(base_url.to_string()),
+            ..self
+        }
+    }
+
+    /// Pin the requests of the client to the given version of the
+    /// [Square API](https://developer.squareup.com), sent as the `Square-Version` header. Without
+    /// it the client uses [SQUARE_VERSION](SQUARE_VERSION), the version the crate is written
+    /// against, rather than whichever version is the default of the application.
+    ///
+    /// A single request can be sent with a different version through
+    /// [RequestOptions](RequestOptions), and the requests of a part of an application through a
+    /// clone of the client, which shares its connection pool.
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::client::SquareClient;
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .square_version("2022-09-21");
+    ///
+    /// let newer = client.clone().square_version("2022-10-19");
+    /// ```
+    pub fn square_version(self, square_version: &str) -> Self {
+        Self {
+            square_version: square_version.to_string(),
+            ..self
+        }
+    }
+
+    /// Send the given headers along with every request, e.g. to identify the calling service to
+    /// a gateway. They are added to the headers set before, and replace the headers the client
+    /// sets itself of the same name, such as `Square-Version`.
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::client::{HeaderMap, HeaderValue, SquareClient};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-service-name", HeaderValue::from_static("checkout"));
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .default_headers(headers);
+    /// ```
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+
+        self
+    }
+
+    /// Speak HTTP/2 to the [Square API](https://developer.squareup.com) right away instead of
+    /// negotiating the protocol during the TLS handshake.
+    ///
+    /// With the `native-tls` feature this is the only way to speak HTTP/2, as reqwest 0.11 offers
+    /// no protocol through ALPN with it, while with `rustls-tls` HTTP/2 is negotiated anyway, at
+    /// no extra round trip. All requests of the client are then multiplexed over a single
+    /// connection, instead of opening a new one whenever all pooled HTTP/1.1 connections are busy.
+    /// Do not use this with a base URL that only speaks HTTP/1.1.
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::client::SquareClient;
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .production()
+    ///     .http2_prior_knowledge();
+    /// ```
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.http_config.http2_prior_knowledge = true;
+
+        self.rebuild_http_client()
+    }
+
+    /// Send HTTP/2 PING frames at the given interval, also while no request is in flight, so idle
+    /// connections are not silently dropped by intermediaries between two bursts of requests.
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http_config.http2_keep_alive_interval = Some(interval);
+
+        self.rebuild_http_client()
+    }
+
+    /// Close an HTTP/2 connection whose PING frames, sent at the
+    /// [keep-alive interval](SquareClient::http2_keep_alive_interval), are not answered within
+    /// the given duration, 20 seconds unless told otherwise.
+    pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.http_config.http2_keep_alive_timeout = Some(timeout);
+
+        self.rebuild_http_client()
+    }
+
+    /// Keep at most the given number of idle connections to a host in the pool, closing the
+    /// connections freed up beyond it, e.g. after a burst of requests.
+    ///
+    /// A service sending bursts of concurrent requests over HTTP/1.1 saves the handshakes of new
+    /// connections by keeping as many around as a burst has requests, while one sending few keeps
+    /// fewer sockets open by keeping fewer.
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::client::SquareClient;
+    /// use std::time::Duration;
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .pool_max_idle_per_host(32)
+    ///     .pool_idle_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.http_config.pool_max_idle_per_host = Some(max_idle);
+
+        self.rebuild_http_client()
+    }
+
+    /// Close pooled connections that have not been used for the given duration, 90 seconds
+    /// unless told otherwise. A request after a longer pause pays for a new connection. It should
+    /// stay below the idle timeout of any proxy or load balancer in between, which would otherwise
+    /// close connections the pool still hands out.
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.http_config.pool_idle_timeout = Some(timeout);
+
+        self.rebuild_http_client()
+    }
+
+    /// Enable TCP keep-alive probes on the pooled connections, sent after the connection has been
+    /// idle for the given duration.
+    ///
+    /// The probes change the latency of no request by themselves. They keep NAT gateways and
+    /// firewalls in between from silently dropping idle connections of the pool, which would
+    /// cost the next request on them a new connection.
+    pub fn tcp_keepalive(mut self, idle: Duration) -> Self {
+        self.http_config.tcp_keepalive = Some(idle);
+
+        self.rebuild_http_client()
+    }
+
+    /// Bind outgoing connections to the given local address.
+    ///
+    /// reqwest 0.11 offers no toggle for "happy eyeballs". Its connector first tries the
+    /// addresses of the family resolved first, and starts trying the other family only if that
+    /// has not connected after 300 ms, which cannot be changed. On hosts with broken IPv6 routing
+    /// every new connection may pay for that delay. Binding to a local IPv4 address connects to
+    /// IPv4 addresses only, without falling back to IPv6, which avoids it; an IPv6 address
+    /// likewise pins connections to IPv6.
+    pub fn local_address(mut self, address: IpAddr) -> Self {
+        self.http_config.local_address = Some(address);
+
+        self.rebuild_http_client()
+    }
+
+    /// Give up connecting to Square after the given duration, 10 seconds unless told otherwise.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.http_config.connect_timeout = Some(timeout);
+
+        self.rebuild_http_client()
+    }
+
+    /// Give up on a request once it took the given duration, from connecting until its response
+    /// has been read. The timeout applies to every attempt of a retried request on its own, and is
+    /// 60 seconds unless told otherwise. On `wasm32` it is left to the JavaScript host, which may
+    /// impose limits of its own, while the [read timeout](SquareClient::read_timeout) applies.
+    ///
+    /// A single request can be given a different timeout through [RequestOptions](RequestOptions),
+    /// and the requests of a part of an application through a clone of the client, which shares
+    /// its connection pool.
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::client::SquareClient;
+    /// use std::time::Duration;
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .timeout(Duration::from_secs(30));
+    ///
+    /// // a report that is known to take a while
+    /// let patient = client.clone().timeout(Duration::from_secs(120));
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.request = Some(timeout);
+
+        self
+    }
+
+    /// Give up on a request whose response body takes longer than the given duration to read once
+    /// its headers have arrived, e.g. because Square stalls in the middle of a large response.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.read = Some(timeout);
+
+        self
+    }
+
+    /// Retry failed requests according to the given [RetryPolicy](crate::retry::RetryPolicy).
+    ///
+    /// Requests that are not safe to send twice, such as creating a payment without an
+    /// idempotency key, are never retried after they reached Square, whatever the policy says.
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::{client::SquareClient, retry::RetryPolicy};
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .retry_policy(RetryPolicy::default());
+    /// ```
+    pub fn retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy,
+            ..self
+        }
+    }
+
+    /// Hold requests back according to the given [RateLimiter](crate::rate_limit::RateLimiter),
+    /// which is shared with every clone of the client made afterwards.
+    ///
+    /// The `Retry-After` header of a `429 Too Many Requests` response is honored either way.
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::{client::SquareClient, rate_limit::RateLimiter};
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .rate_limiter(RateLimiter::token_bucket(10.0, 20));
+    /// ```
+    pub fn rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        Self {
+            rate_limiter,
+            ..self
+        }
+    }
+
+    /// Queue the requests of the client as of the given [Priority](crate::rate_limit::Priority)
+    /// in its [RateLimiter](crate::rate_limit::RateLimiter), which is
+    /// [Interactive](crate::rate_limit::Priority::Interactive) unless told otherwise. A clone of
+    /// the client sending background traffic can yield to the requests of the original through
+    /// the [Batch](crate::rate_limit::Priority::Batch) priority.
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::{client::SquareClient, rate_limit::{Priority, RateLimiter}};
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .rate_limiter(RateLimiter::token_bucket(10.0, 20));
+    /// let sync = client.clone().priority(Priority::Batch);
+    /// ```
+    pub fn priority(self, priority: Priority) -> Self {
+        Self {
+            priority,
+            ..self
+        }
+    }
+
+    /// Fail requests without sending them while Square is considered unavailable, according to
+    /// the given [CircuitBreaker](crate::circuit_breaker::CircuitBreaker), which is shared with
+    /// every clone of the client made afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::{circuit_breaker::CircuitBreaker, client::SquareClient};
+    /// use std::time::Duration;
+    ///
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .circuit_breaker(CircuitBreaker::new(5, Duration::from_secs(30)));
+    /// ```
+    pub fn circuit_breaker(self, circuit_breaker: CircuitBreaker) -> Self {
+        Self {
+            circuit_breaker: Some(circuit_breaker),
+            ..self
+        }
+    }
+
+    /// Register a [RequestInterceptor](crate::interceptor::RequestInterceptor) called around
+    /// every request of the client, after the ones registered before.
+    pub fn interceptor<I: RequestInterceptor + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+
+        self
+    }
+
+    /// Hand the [RequestMetrics](crate::metrics::RequestMetrics) of every request of the client to
+    /// the given [MetricsSink](crate::metrics::MetricsSink), replacing the one set before.
+    pub fn metrics_sink<M: MetricsSink + 'static>(self, sink: M) -> Self {
+        Self {
+            metrics_sink: Some(Arc::new(sink)),
+            ..self
+        }
+    }
+
+    /// Send the requests to the [Square API](https://developer.squareup.com) through the given
+    /// [HttpTransport](crate::transport::HttpTransport) instead of the client's own connection
+    /// pool, e.g. to use another HTTP client or to answer them from memory in tests.
+    pub fn transport<T: HttpTransport + 'static>(self, transport: T) -> Self {
+        Self {
+            transport: Some(Arc::new(transport)),
+            ..self
+        }
+    }
+
+    /// Record the differences between the responses received and the typed responses they are
+    /// deserialized into in the given [DriftDetector](crate::drift::DriftDetector).
+    ///
+    /// # Example
+    /// ```
+    /// use square_ox::{client::SquareClient, drift::DriftDetector};
+    ///
+    /// let detector = DriftDetector::new();
+    /// let client = SquareClient::new("your_square_access_token")
+    ///     .detect_schema_drift(detector.clone());
+    /// ```
+    pub fn detect_schema_drift(self, detector: DriftDetector) -> Self {
+        Self {
+            drift_detector: Some(detector),
+            ..self
         }
     }
 
+    fn rebuild_http_client(self) -> Self {
+        Self {
+            http_client: self.http_config.client().map_err(|err| err.to_string()),
+            ..self
+        }
+    }
+
+    // the HTTP client, failing if it could not be built from the http_config
+    fn http_client(&self) -> Result<&Client, SquareError> {
+        self.http_client.as_ref().map_err(|err| SquareError::invalid_http_client(err.clone()))
+    }
+
     /// Sends a request to a given [SquareAPI](crate::api::SquareAPI)
     /// # Arguments
     /// * `api` - The [SquareAPI](crate::api::SquareAPI) to send the request to
@@ -120,57 +812,707 @@ impl SquareClient {
     where
         T: Serialize + ?Sized,
     {
+        self.request_with_authorization(verb, endpoint, json, parameters, Authorization::AccessToken)
+            .await
+    }
+
+    /// Sends a request to a given [SquareAPI](crate::api::SquareAPI), authenticating it as given
+    /// instead of always through the access token of the client.
+    pub(crate) async fn request_with_authorization<T>(
+        &self,
+        verb: Verb,
+        endpoint: SquareAPI,
+        json: Option<&T>,
+        parameters: Option<Vec<(String, String)>>,
+        authorization: Authorization<'_>,
+    ) -> Result<SquareResponse, SquareError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let body = json.map(serde_json::to_value).transpose()?.map(RequestBody::Json);
+
+        self.send(verb, endpoint, body, parameters, authorization, RequestOptions::new()).await
+    }
+
+    /// Sends a request to a given [SquareAPI](crate::api::SquareAPI) like
+    /// [request](SquareClient::request), along with the given headers. They replace the default
+    /// headers of the client, and the headers it sets itself, of the same name.
+    ///
+    /// # Example:
+    /// ```
+    /// use square_ox::{
+    ///     api::{Verb, SquareAPI},
+    ///     client::{HeaderMap, HeaderValue, SquareClient},
+    /// };
+    ///
+    /// async {
+    ///     let mut headers = HeaderMap::new();
+    ///     headers.insert("x-correlation-id", HeaderValue::from_static("some_correlation_id"));
+    ///
+    ///     let res = SquareClient::new("your_square_access_token")
+    ///         .request_with_headers(
+    ///             Verb::GET,
+    ///             SquareAPI::Locations("".to_string()),
+    ///             None::<&()>,
+    ///             None,
+    ///             headers,
+    ///         )
+    ///         .await;
+    /// };
+    /// ```
+    pub async fn request_with_headers<T>(
+        &self,
+        verb: Verb,
+        endpoint: SquareAPI,
+        json: Option<&T>,
+        parameters: Option<Vec<(String, String)>>,
+        headers: HeaderMap,
+    ) -> Result<SquareResponse, SquareError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.request_with_options(verb, endpoint, json, parameters, RequestOptions::new().headers(headers))
+            .await
+    }
+
+    /// Sends a request to a given [SquareAPI](crate::api::SquareAPI) like
+    /// [request](SquareClient::request), overriding the configuration of the client as given for
+    /// this request only.
+    ///
+    /// # Example:
+    /// ```
+    /// use square_ox::{
+    ///     api::{Verb, SquareAPI},
+    ///     client::{RequestOptions, SquareClient},
+    /// };
+    /// use std::time::Duration;
+    ///
+    /// async {
+    ///     // a report that is known to take a while
+    ///     let res = SquareClient::new("your_square_access_token")
+    ///         .request_with_options(
+    ///             Verb::POST,
+    ///             SquareAPI::Orders("/search".to_string()),
+    ///             Some(&serde_json::json!({ "location_ids": ["some_location_id"] })),
+    ///             None,
+    ///             RequestOptions::new().timeout(Duration::from_secs(120)),
+    ///         )
+    ///         .await;
+    /// };
+    /// ```
+    pub async fn request_with_options<T>(
+        &self,
+        verb: Verb,
+        endpoint: SquareAPI,
+        json: Option<&T>,
+        parameters: Option<Vec<(String, String)>>,
+        options: RequestOptions,
+    ) -> Result<SquareResponse, SquareError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let body = json.map(serde_json::to_value).transpose()?.map(RequestBody::Json);
+
+        self.send(verb, endpoint, body, parameters, Authorization::AccessToken, options).await
+    }
+
+    /// Sends a request to a given [SquareAPI](crate::api::SquareAPI) as a multipart form of the
+    /// JSON `request` and, if given, the file, as taken by the endpoints uploading files.
+    pub(crate) async fn request_multipart<T>(
+        &self,
+        verb: Verb,
+        endpoint: SquareAPI,
+        json: &T,
+        file: Option<(&str, &ImageFile)>,
+    ) -> Result<SquareResponse, SquareError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let body = RequestBody::Multipart {
+            request: serde_json::to_value(json)?,
+            file,
+        };
+
+        self.send(verb, endpoint, Some(body), None, Authorization::AccessToken, RequestOptions::new())
+            .await
+    }
+
+    // sends the request, within its own span with the tracing feature
+    async fn send(
+        &self,
+        verb: Verb,
+        endpoint: SquareAPI,
+        body: Option<RequestBody<'_>>,
+        parameters: Option<Vec<(String, String)>>,
+        authorization: Authorization<'_>,
+        options: RequestOptions,
+    ) -> Result<SquareResponse, SquareError> {
+        #[cfg(feature = "tracing")]
+        let span = trace::request_span(verb, &endpoint.to_string());
+
+        let sent = self.execute(verb, endpoint, body, parameters, authorization, options);
+
+        #[cfg(feature = "tracing")]
+        let sent = tracing::Instrument::instrument(sent, span);
+
+        sent.await
+    }
+
+    // sends the request, retrying it according to the retry policy, and deserializes the response
+    async fn execute(
+        &self,
+        verb: Verb,
+        endpoint: SquareAPI,
+        body: Option<RequestBody<'_>>,
+        parameters: Option<Vec<(String, String)>>,
+        authorization: Authorization<'_>,
+        options: RequestOptions,
+    ) -> Result<SquareResponse, SquareError> {
+        let started = Instant::now();
+        let retry_policy = options.retry_policy.as_ref().unwrap_or(&self.retry_policy);
+        let priority = options.priority.unwrap_or(self.priority);
+        let timeouts = Timeouts {
+            request: options.timeout.or(self.timeouts.request),
+            read: options.read_timeout.or(self.timeouts.read),
+        };
+
+        // a request that reached Square is only retried if sending it twice is harmless
+        let retries = if retry::may_retry(&verb, &endpoint, body.as_ref().map(RequestBody::json)) {
+            retry_policy.max_retries
+        } else {
+            0
+        };
+
+        let api = endpoint.name();
+        let label = endpoint.to_string();
         let url = self.endpoint(endpoint).clone();
-        let authorization_header = format!("Bearer {}", &self.access_token);
+        // the token the request is authenticated with, which is invalidated if Square rejects it
+        let access_token = match authorization {
+            Authorization::AccessToken => Some(self.token_provider.access_token().await?),
+            _ => None,
+        };
+        let authorization_header = match (authorization, access_token.as_ref()) {
+            (Authorization::AccessToken, Some(token)) => Some(format!("Bearer {}", token)),
+            (Authorization::ClientSecret(client_secret), _) => Some(format!("Client {}", client_secret)),
+            _ => None,
+        };
 
         // Add the headers to the request
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::AUTHORIZATION,
-            header::HeaderValue::from_str(&authorization_header)?,
-        );
-
-        // Create a client with the appropriate headers
-        let client = Client::builder().default_headers(headers).build()?;
-
-        println!("url: {}", &url);
-
-        // Send the request to the Square API, and get the response
-        let mut builder = match verb {
-            Verb::GET => client.get(&url),
-            Verb::POST => client.post(&url),
-            Verb::PUT => client.put(&url),
-            Verb::PATCH => client.patch(&url),
-            Verb::DELETE => client.delete(&url),
+        let mut headers = HeaderMap::new();
+        let square_version = options.square_version.as_deref().unwrap_or(&self.square_version);
+        headers.insert("Square-Version", header::HeaderValue::from_str(square_version)?);
+        if let Some(authorization_header) = authorization_header {
+            headers.insert(
+                header::AUTHORIZATION,
+                header::HeaderValue::from_str(&authorization_header)?,
+            );
+        }
+        // the headers given to the client and then those given to the request take precedence
+        headers.extend(self.default_headers.clone());
+        headers.extend(options.headers);
+
+        let mut attempt = 0;
+        let response = loop {
+            // wait while Square asked to back off or the client is above its own rate
+            self.rate_limiter.acquire_with_priority(priority).await;
+
+            // fail fast while Square is considered unavailable, otherwise the admission records
+            // what became of the attempt
+            let admission = match self.circuit_breaker.as_ref() {
+                Some(breaker) => match breaker.admit(Instant::now()) {
+                    Some(admission) => Some(admission),
+                    None => {
+                        self.record_metrics(verb, api, &label, None, attempt, started);
+                        return Err(SquareError::unavailable(
+                            "the circuit breaker is open after repeated failures".to_string(),
+                        ))
+                    },
+                },
+                None => None,
+            };
+
+            let mut request = InterceptedRequest {
+                verb,
+                endpoint: label.clone(),
+                url: url.clone(),
+                parameters: parameters.clone(),
+                headers: headers.clone(),
+                body: body.as_ref().map(|body| body.json().clone()),
+                attempt,
+            };
+
+            // an interceptor may answer the request itself, in which case it is not sent
+            let intercepted = self.interceptors.iter()
+                .find_map(|interceptor| interceptor.before_request(&mut request));
+
+            #[cfg(feature = "tracing")]
+            trace::attempt(&request);
+
+            // a request that failed to connect never reached Square, so it can always be retried
+            let sent = match intercepted {
+                Some(response) => Ok(response),
+                None => {
+                    let http_request = match self.http_request(&request, body.as_ref(), &timeouts) {
+                        Ok(http_request) => http_request,
+                        Err(err) => {
+                            self.record_metrics(verb, api, &label, None, attempt, started);
+                            return Err(err)
+                        },
+                    };
+
+                    match (self.transport.as_ref(), self.http_client()) {
+                        (Some(transport), _) => transport.send(http_request).await,
+                        (None, Ok(http_client)) => ReqwestTransport::new(http_client.clone())
+                            .send(http_request)
+                            .await,
+                        (None, Err(err)) => {
+                            self.record_metrics(verb, api, &label, None, attempt, started);
+                            return Err(err)
+                        },
+                    }
+                },
+            };
+
+            let sent = sent.map(|mut response| {
+                for interceptor in self.interceptors.iter() {
+                    interceptor.after_response(&request, &mut response);
+                }
+
+                response
+            });
+
+            if let Some(admission) = admission {
+                admission.record(Outcome::of(&sent));
+            }
+
+            if let Ok(response) = sent.as_ref() {
+                if response.status == StatusCode::TOO_MANY_REQUESTS {
+                    if let Some(wait) = rate_limit::retry_after(&response.headers, Utc::now()) {
+                        self.rate_limiter.block_for(wait);
+                    }
+                }
+            }
+
+            match sent {
+                Ok(response) if attempt < retries && retry::retryable_status(response.status) => {},
+                Ok(response) => break response,
+                Err(err) if attempt < retry_policy.max_retries && err.is_connect() => {},
+                Err(err) if attempt < retries && err.is_timeout() => {},
+                Err(err) => {
+                    self.record_metrics(verb, api, &label, None, attempt, started);
+                    return Err(err.into())
+                },
+            }
+
+            platform::sleep(retry_policy.backoff(attempt)).await;
+            attempt += 1;
         };
 
-        // Add query parameters if there are any
-        if let Some(parameters) = parameters {
-            builder = builder.query(&parameters);
-        }
+        self.record_metrics(verb, api, &label, Some(response.status), attempt, started);
 
-        // Add a json body if there is one
-        if let Some(json) = json {
-            builder = builder.json(json)
+        // a rejected token is not handed out again
+        if let (StatusCode::UNAUTHORIZED, Some(access_token)) = (response.status, access_token) {
+            self.token_provider.invalidate(&access_token);
         }
 
-        // Deserialize the response into a SquareResponse
-        // let response: SquareResponse = builder.send().await?.json().await?;
-
-        // TODO remove the debug code!
-        let response = builder.send().await?.text().await?;
+        #[cfg(feature = "tracing")]
+        trace::response(&response, attempt + 1, started.elapsed());
 
-        println!("{:?}", response);
+        let meta = ResponseMeta::new(response.status, response.headers);
+        let response = response.body;
 
-        let response: SquareResponse = serde_json::from_str(&response)?;
+        if let Some(detector) = self.drift_detector.as_ref() {
+            detector.inspect(&label, &response);
+        }
 
-        println!("{:?}", response);
+        let mut response: SquareResponse = match serde_json::from_str(&response) {
+            Ok(response) => response,
+            Err(err) => {
+                let err: SquareError = err.into();
+                return Err(err.with_meta(meta))
+            },
+        };
 
         // handle the possibility of an error being returned by the Square API
         if response.errors.is_some() && response.errors.as_ref().unwrap().len() > 0 {
-            return Err(SquareError::from(response.errors))
+            return Err(SquareError::from(response.errors).with_meta(meta))
         }
 
+        response.meta = Some(meta);
+
         Ok(response)
     }
+
+    // hands what the request took to the metrics sink, if there is one
+    fn record_metrics(&self, verb: Verb, api: &'static str, endpoint: &str,
+                      status: Option<StatusCode>, retries: u32, started: Instant) {
+        if let Some(sink) = self.metrics_sink.as_ref() {
+            sink.record(&RequestMetrics {
+                verb,
+                api,
+                endpoint: endpoint.to_string(),
+                status,
+                retries,
+                duration: started.elapsed(),
+            });
+        }
+    }
+
+    // the attempt of the request as the interceptors left it, as it is handed to the transport
+    fn http_request(&self, request: &InterceptedRequest, body: Option<&RequestBody<'_>>,
+                    timeouts: &Timeouts) -> Result<HttpRequest, SquareError> {
+        let mut url = reqwest::Url::parse(&request.url).map_err(|_| SquareError::from(None))?;
+        if let Some(parameters) = request.parameters.as_ref() {
+            url.query_pairs_mut().extend_pairs(parameters);
+        }
+
+        // a multipart body is sent as given, whatever the interceptors changed
+        let mut headers = request.headers.clone();
+        let body = match (body, request.body.as_ref()) {
+            (Some(RequestBody::Multipart { request, file }), _) => {
+                let (content_type, body) = multipart_body(request, *file);
+                headers.insert(header::CONTENT_TYPE, HeaderValue::from_str(&content_type)?);
+
+                Some(body)
+            },
+            (_, Some(json)) => {
+                headers.entry(header::CONTENT_TYPE)
+                    .or_insert(HeaderValue::from_static("application/json"));
+
+                Some(serde_json::to_vec(json)?)
+            },
+            (_, None) => None,
+        };
+
+        Ok(HttpRequest {
+            verb: request.verb,
+            url: url.to_string(),
+            headers,
+            body,
+            timeout: timeouts.request,
+            read_timeout: timeouts.read,
+        })
+    }
+
+    /// Fetches a document hosted by Square, such as the public page of an invoice, as is.
+    ///
+    /// The access token is not sent along, as these documents are public and may be served from
+    /// outside of the [Square API](https://developer.squareup.com). Redirects are followed and a
+    /// response with an error status fails the request. The document is fetched directly, not
+    /// through the [transport](SquareClient::transport), as it need not be text, but within the
+    /// [request timeout](SquareClient::timeout) of the client.
+    pub(crate) async fn download(&self, url: &str) -> Result<BinaryResponse, SquareError> {
+        self.within_timeout(async {
+            let response = self.http_client()?.get(url)
+                .send()
+                .await?
+                .error_for_status()?;
+
+            let url = response.url().to_string();
+            let content_type = response.headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|content_type| content_type.to_str().ok())
+                .map(|content_type| content_type.to_string());
+            let bytes = response.bytes().await?.to_vec();
+
+            Ok(BinaryResponse {
+                url,
+                content_type,
+                bytes,
+            })
+        }).await
+    }
+
+    /// Whether a document hosted by Square, such as an image of the catalog, is available. Only its
+    /// headers are requested, and like [download](SquareClient::download) the access token is not
+    /// sent along. An error status means it is not available, failing to reach the host fails.
+    pub(crate) async fn is_available(&self, url: &str) -> Result<bool, SquareError> {
+        self.within_timeout(async {
+            let response = self.http_client()?.head(url).send().await?;
+
+            Ok(response.status().is_success())
+        }).await
+    }
+
+    // gives up on a request sent outside of the transport once the request timeout passed
+    async fn within_timeout<T, F>(&self, request: F) -> Result<T, SquareError>
+        where F: std::future::Future<Output = Result<T, SquareError>> {
+        match self.timeouts.request {
+            Some(timeout) => platform::timeout(timeout, request).await.unwrap_or_else(|| {
+                Err(SquareError::timed_out(format!("no response within {:?}", timeout)))
+            }),
+            None => request.await,
+        }
+    }
+}
+
+/// Configures a [SquareClient](SquareClient) in a single chain, created through
+/// [SquareClient::builder](SquareClient::builder).
+///
+/// Everything but the access token starts out as the defaults of
+/// [SquareClient::new](SquareClient::new), and the client can still be configured further once
+/// built.
+#[derive(Default)]
+pub struct SquareClientBuilder {
+    token_provider: Option<Arc<dyn TokenProvider>>,
+    client_mode: ClientMode,
+    http_config: HttpConfig,
+    timeouts: Timeouts,
+    square_version: Option<String>,
+    user_agent: Option<String>,
+    default_headers: HeaderMap,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: RateLimiter,
+    priority: Priority,
+    circuit_breaker: Option<CircuitBreaker>,
+    interceptors: Vec<Arc<dyn RequestInterceptor>>,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
+    transport: Option<Arc<dyn HttpTransport>>,
+}
+
+impl SquareClientBuilder {
+    /// Authenticate the requests with the given, fixed access token.
+    pub fn access_token(self, access_token: &str) -> Self {
+        self.token_provider(StaticToken::new(access_token))
+    }
+
+    /// Ask the given [TokenProvider](crate::token::TokenProvider) for the access token before
+    /// every request, see [SquareClient::with_token_provider](SquareClient::with_token_provider).
+    pub fn token_provider<P: TokenProvider + 'static>(self, token_provider: P) -> Self {
+        Self {
+            token_provider: Some(Arc::new(token_provider)),
+            ..self
+        }
+    }
+
+    /// Send the requests to Square's production host.
+    pub fn production(self) -> Self {
+        Self {
+            client_mode: ClientMode::Production,
+            ..self
+        }
+    }
+
+    /// Send the requests to Square's sandbox host, which is the default.
+    pub fn sandbox(self) -> Self {
+        Self {
+            client_mode: ClientMode::Sandboxed,
+            ..self
+        }
+    }
+
+    /// See [SquareClient::base_url](SquareClient::base_url).
+    pub fn base_url(self, base_url: &str) -> Self {
+        Self {
+            client_mode: ClientMode::Custom(base_url.to_string()),
+            ..self
+        }
+    }
+
+    /// See [SquareClient::square_version](SquareClient::square_version).
+    pub fn square_version(self, square_version: &str) -> Self {
+        Self {
+            square_version: Some(square_version.to_string()),
+            ..self
+        }
+    }
+
+    /// Identify the calling application through the `User-Agent` header of every request.
+    pub fn user_agent(self, user_agent: &str) -> Self {
+        Self {
+            user_agent: Some(user_agent.to_string()),
+            ..self
+        }
+    }
+
+    /// See [SquareClient::default_headers](SquareClient::default_headers).
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+
+        self
+    }
+
+    /// See [SquareClient::connect_timeout](SquareClient::connect_timeout).
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.http_config.connect_timeout = Some(timeout);
+
+        self
+    }
+
+    /// See [SquareClient::pool_max_idle_per_host](SquareClient::pool_max_idle_per_host).
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.http_config.pool_max_idle_per_host = Some(max_idle);
+
+        self
+    }
+
+    /// See [SquareClient::pool_idle_timeout](SquareClient::pool_idle_timeout).
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.http_config.pool_idle_timeout = Some(timeout);
+
+        self
+    }
+
+    /// See [SquareClient::tcp_keepalive](SquareClient::tcp_keepalive).
+    pub fn tcp_keepalive(mut self, idle: Duration) -> Self {
+        self.http_config.tcp_keepalive = Some(idle);
+
+        self
+    }
+
+    /// See [SquareClient::http2_prior_knowledge](SquareClient::http2_prior_knowledge).
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.http_config.http2_prior_knowledge = true;
+
+        self
+    }
+
+    /// See [SquareClient::http2_keep_alive_interval](SquareClient::http2_keep_alive_interval).
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http_config.http2_keep_alive_interval = Some(interval);
+
+        self
+    }
+
+    /// See [SquareClient::http2_keep_alive_timeout](SquareClient::http2_keep_alive_timeout).
+    pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.http_config.http2_keep_alive_timeout = Some(timeout);
+
+        self
+    }
+
+    /// See [SquareClient::timeout](SquareClient::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.request = Some(timeout);
+
+        self
+    }
+
+    /// See [SquareClient::read_timeout](SquareClient::read_timeout).
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.read = Some(timeout);
+
+        self
+    }
+
+    /// See [SquareClient::retry_policy](SquareClient::retry_policy).
+    pub fn retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy: Some(retry_policy),
+            ..self
+        }
+    }
+
+    /// See [SquareClient::rate_limiter](SquareClient::rate_limiter).
+    pub fn rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        Self {
+            rate_limiter,
+            ..self
+        }
+    }
+
+    /// See [SquareClient::priority](SquareClient::priority).
+    pub fn priority(self, priority: Priority) -> Self {
+        Self {
+            priority,
+            ..self
+        }
+    }
+
+    /// See [SquareClient::circuit_breaker](SquareClient::circuit_breaker).
+    pub fn circuit_breaker(self, circuit_breaker: CircuitBreaker) -> Self {
+        Self {
+            circuit_breaker: Some(circuit_breaker),
+            ..self
+        }
+    }
+
+    /// See [SquareClient::interceptor](SquareClient::interceptor).
+    pub fn interceptor<I: RequestInterceptor + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+
+        self
+    }
+
+    /// See [SquareClient::metrics_sink](SquareClient::metrics_sink).
+    pub fn metrics_sink<M: MetricsSink + 'static>(self, sink: M) -> Self {
+        Self {
+            metrics_sink: Some(Arc::new(sink)),
+            ..self
+        }
+    }
+
+    /// See [SquareClient::transport](SquareClient::transport).
+    pub fn transport<T: HttpTransport + 'static>(self, transport: T) -> Self {
+        Self {
+            transport: Some(Arc::new(transport)),
+            ..self
+        }
+    }
+
+    /// Builds the client, failing if no access token was given, the API version or user agent
+    /// cannot be sent as a header, or the HTTP client cannot be built from the connection
+    /// settings.
+    pub fn build(mut self) -> Result<SquareClient, SquareError> {
+        let token_provider = self.token_provider.take().ok_or_else(|| {
+            SquareError::invalid_request(
+                "MISSING_REQUIRED_PARAMETER",
+                "access_token",
+                "an access token or token provider is required".to_string(),
+            )
+        })?;
+        let square_version = self.square_version.take()
+            .unwrap_or_else(|| SQUARE_VERSION.to_string());
+        HeaderValue::from_str(&square_version).map_err(|err| SquareError::invalid_request(
+            "INVALID_VALUE",
+            "square_version",
+            err.to_string(),
+        ))?;
+
+        let mut default_headers = HeaderMap::new();
+        if let Some(user_agent) = self.user_agent.take() {
+            default_headers.insert(
+                header::USER_AGENT,
+                HeaderValue::from_str(&user_agent).map_err(|err| SquareError::invalid_request(
+                    "INVALID_VALUE",
+                    "user_agent",
+                    err.to_string(),
+                ))?,
+            );
+        }
+
+        let client = self.assemble(token_provider, square_version, default_headers);
+        client.http_client()?;
+
+        Ok(client)
+    }
+
+    /// Puts the client together from the already validated parts. An HTTP client that cannot be
+    /// built is kept as an error, which every request then fails with.
+    fn assemble(
+        self,
+        token_provider: Arc<dyn TokenProvider>,
+        square_version: String,
+        mut default_headers: HeaderMap,
+    ) -> SquareClient {
+        default_headers.extend(self.default_headers);
+
+        SquareClient {
+            token_provider,
+            client_mode: self.client_mode,
+            http_client: self.http_config.client().map_err(|err| err.to_string()),
+            http_config: self.http_config,
+            timeouts: self.timeouts,
+            square_version,
+            default_headers,
+            retry_policy: self.retry_policy.unwrap_or_else(RetryPolicy::none),
+            rate_limiter: self.rate_limiter,
+            priority: self.priority,
+            circuit_breaker: self.circuit_breaker,
+            interceptors: self.interceptors,
+            metrics_sink: self.metrics_sink,
+            transport: self.transport,
+            drift_detector: None,
+        }
+    }
 }
@@ -0,0 +1,210 @@
+/*!
+Detection of changes to the response schema of the [Square API](https://developer.squareup.com).
+
+Square adds fields to its responses over time, and the typed responses of this crate silently drop
+any field they do not know. A [DriftDetector](DriftDetector) set on a
+[SquareClient](crate::client::SquareClient) compares every response as received with the typed
+response it was deserialized into and records where they differ:
+* unknown fields, which are in the response but not in the typed response and so are dropped;
+* missing fields, which the typed response holds a default for but the response did not contain;
+* responses that could not be deserialized at all.
+
+Fields are recorded by their path, such as `order.fulfillments[].delivery_details`, along with how
+often they were seen, and are summed up in a [DriftReport](DriftReport). Detection parses every
+response a second time, so it is meant for a staging environment or a CI run rather than for
+production.
+
+# Example: Report schema drift after a test run
+```no_run
+use square_ox::{client::SquareClient, drift::DriftDetector};
+
+async {
+    let detector = DriftDetector::new();
+    let client = SquareClient::new("some_token").detect_schema_drift(detector.clone());
+
+    let _ = client.locations().list().await;
+
+    let report = detector.report();
+    for (path, count) in report.unknown_fields.iter() {
+        println!("unknown field {} seen {} times", path, count);
+    }
+    assert!(report.is_empty());
+};
+```
+ */
+
+use crate::response::SquareResponse;
+
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Records the differences between the responses received and the typed responses they were
+/// deserialized into. Clones share their records.
+#[derive(Clone, Debug, Default)]
+pub struct DriftDetector {
+    report: Arc<Mutex<DriftReport>>,
+}
+
+/// A summary of the schema drift seen by a [DriftDetector](DriftDetector).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DriftReport {
+    /// The number of responses inspected.
+    pub responses: usize,
+    /// The paths of fields dropped during deserialization, with how often each was seen.
+    pub unknown_fields: BTreeMap<String, usize>,
+    /// The paths of fields that were defaulted because the response lacked them, with how often
+    /// each was seen.
+    pub missing_fields: BTreeMap<String, usize>,
+    /// The endpoints whose responses could not be deserialized, along with the error.
+    pub failures: Vec<(String, String)>,
+}
+
+impl DriftReport {
+    /// Whether no drift was seen.
+    pub fn is_empty(&self) -> bool {
+        self.unknown_fields.is_empty() && self.missing_fields.is_empty() && self.failures.is_empty()
+    }
+}
+
+impl DriftDetector {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The drift seen so far.
+    pub fn report(&self) -> DriftReport {
+        self.report.lock().map(|report| report.clone()).unwrap_or_default()
+    }
+
+    /// Forgets the drift seen so far.
+    pub fn reset(&self) {
+        if let Ok(mut report) = self.report.lock() {
+            *report = DriftReport::default();
+        }
+    }
+
+    // compares a response as received from the endpoint with the typed response it deserializes
+    // into
+    pub(crate) fn inspect(&self, endpoint: &str, raw: &str) {
+        let drift = drift(raw);
+
+        let mut report = match self.report.lock() {
+            Ok(report) => report,
+            Err(_) => return,
+        };
+        report.responses += 1;
+
+        match drift {
+            Ok((unknown, missing)) => {
+                for path in unknown {
+                    *report.unknown_fields.entry(path).or_insert(0) += 1;
+                }
+                for path in missing {
+                    *report.missing_fields.entry(path).or_insert(0) += 1;
+                }
+            },
+            Err(e) => report.failures.push((endpoint.to_string(), e.to_string())),
+        }
+    }
+}
+
+// the paths of the unknown and of the missing fields of a response
+fn drift(raw: &str) -> Result<(Vec<String>, Vec<String>), serde_json::Error> {
+    let received: Value = serde_json::from_str(raw)?;
+    let typed = serde_json::to_value(serde_json::from_value::<SquareResponse>(received.clone())?)?;
+
+    let mut unknown = vec![];
+    let mut missing = vec![];
+    compare("", &received, &typed, &mut unknown, &mut missing);
+
+    Ok((unknown, missing))
+}
+
+fn compare(path: &str, received: &Value, typed: &Value, unknown: &mut Vec<String>,
+           missing: &mut Vec<String>) {
+    match (received, typed) {
+        (Value::Object(received), Value::Object(typed)) => {
+            compare_objects(path, received, typed, unknown, missing)
+        },
+        // arrays keep their order, so their elements are compared pairwise
+        (Value::Array(received), Value::Array(typed)) => {
+            let path = format!("{}[]", path);
+            for (received, typed) in received.iter().zip(typed) {
+                compare(&path, received, typed, unknown, missing);
+            }
+        },
+        _ => {},
+    }
+}
+
+fn compare_objects(path: &str, received: &Map<String, Value>, typed: &Map<String, Value>,
+                   unknown: &mut Vec<String>, missing: &mut Vec<String>) {
+    let field = |key: &str| if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    };
+
+    for (key, value) in received {
+        match typed.get(key) {
+            Some(typed) => compare(&field(key), value, typed, unknown, missing),
+            // a null is dropped like any other absent optional field
+            None if !value.is_null() => unknown.push(field(key)),
+            None => {},
+        }
+    }
+
+    for (key, value) in typed {
+        if !received.contains_key(key) && !value.is_null() {
+            missing.push(field(key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_drift {
+    use super::*;
+
+    #[test]
+    fn test_drift() {
+        let (unknown, missing) = drift(r#"{
+            "location": {
+                "id": "L1",
+                "brand_new_field": { "nested": true },
+                "description": null
+            },
+            "cursor": "some_cursor"
+        }"#).unwrap();
+
+        assert_eq!(unknown, vec!["location.brand_new_field"]);
+        assert!(missing.is_empty());
+
+        let (unknown, _) = drift(r#"{
+            "locations": [{ "id": "L1" }, { "id": "L2", "new_flag": true }]
+        }"#).unwrap();
+
+        assert_eq!(unknown, vec!["locations[].new_flag"]);
+        assert!(drift("not json").is_err());
+    }
+
+    #[test]
+    fn test_inspect() {
+        let sut = DriftDetector::new();
+        let shared = sut.clone();
+
+        shared.inspect("locations", r#"{ "location": { "id": "L1", "new_field": 1 } }"#);
+        shared.inspect("locations", r#"{ "location": { "id": "L2", "new_field": 2 } }"#);
+        shared.inspect("locations", "<html>");
+
+        let report = sut.report();
+        assert_eq!(report.responses, 3);
+        assert_eq!(report.unknown_fields.get("location.new_field"), Some(&2));
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].0, "locations");
+        assert!(!report.is_empty());
+
+        sut.reset();
+        assert!(sut.report().is_empty());
+    }
+}
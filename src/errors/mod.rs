@@ -3,39 +3,150 @@ The errors returned by components of the crate.
  */
 
 use serde::{Deserialize, Serialize};
-use crate::response::ResponseError;
+use crate::response::{ResponseError, ResponseMeta};
+use crate::transport::TransportError;
+
+use reqwest::StatusCode;
 
 #[derive(Serialize, Deserialize, Debug)]
-pub struct SquareError(Option<Vec<ResponseError>>);
+#[serde(transparent)]
+pub struct SquareError {
+    errors: Option<Vec<ResponseError>>,
+    // boxed to keep the results carrying the error small
+    #[serde(skip)]
+    meta: Option<Box<ResponseMeta>>,
+}
 
 impl SquareError {
     pub fn from(response_errors: Option<Vec<ResponseError>>) -> Self {
-        Self(response_errors)
+        Self {
+            errors: response_errors,
+            meta: None,
+        }
     }
 
     pub fn get(self) -> Option<Vec<ResponseError>> {
-        self.0
+        self.errors
+    }
+
+    /// The errors as returned by Square, without giving up the error along with its
+    /// [meta](SquareError::meta).
+    pub fn errors(&self) -> Option<&[ResponseError]> {
+        self.errors.as_deref()
+    }
+
+    /// The status and headers of the response the error was read from, `None` if the error
+    /// arose before a response arrived.
+    pub fn meta(&self) -> Option<&ResponseMeta> {
+        self.meta.as_deref()
+    }
+
+    /// The status of the response the error was read from.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.meta.as_ref().map(|meta| meta.status)
+    }
+
+    /// The id Square assigned to the failed request, see
+    /// [ResponseMeta::request_id](crate::response::ResponseMeta::request_id).
+    pub fn request_id(&self) -> Option<&str> {
+        self.meta().and_then(ResponseMeta::request_id)
+    }
+
+    // the error along with the response it was read from
+    pub(crate) fn with_meta(self, meta: ResponseMeta) -> Self {
+        Self {
+            meta: Some(Box::new(meta)),
+            ..self
+        }
+    }
+
+    // an error caught before a request is sent, shaped like those returned by the Square API
+    pub(crate) fn invalid_request(code: &str, field: &str, detail: String) -> Self {
+        Self::from(Some(vec![ResponseError {
+            category: "INVALID_REQUEST_ERROR".to_string(),
+            code: code.to_string(),
+            detail: Some(detail),
+            field: Some(field.to_string()),
+        }]))
+    }
+
+    // a request that did not complete in time, shaped like the timeouts returned by the Square API
+    pub(crate) fn timed_out(detail: String) -> Self {
+        Self::from(Some(vec![ResponseError {
+            category: "API_ERROR".to_string(),
+            code: "REQUEST_TIMEOUT".to_string(),
+            detail: Some(detail),
+            field: None,
+        }]))
+    }
+
+    // a request not sent as Square is considered unavailable, shaped like the errors returned by
+    // the Square API during an outage
+    pub(crate) fn unavailable(detail: String) -> Self {
+        Self::from(Some(vec![ResponseError {
+            category: "API_ERROR".to_string(),
+            code: "SERVICE_UNAVAILABLE".to_string(),
+            detail: Some(detail),
+            field: None,
+        }]))
+    }
+
+    // an HTTP client that cannot be built from the connection settings of a client
+    pub(crate) fn invalid_http_client(detail: String) -> Self {
+        Self::from(Some(vec![ResponseError {
+            category: "INVALID_REQUEST_ERROR".to_string(),
+            code: "INVALID_VALUE".to_string(),
+            detail: Some(detail),
+            field: Some("http_config".to_string()),
+        }]))
+    }
+
+    // a request that cannot be authenticated, shaped like the errors returned by the Square API
+    pub(crate) fn unauthorized(detail: String) -> Self {
+        Self::from(Some(vec![ResponseError {
+            category: "AUTHENTICATION_ERROR".to_string(),
+            code: "UNAUTHORIZED".to_string(),
+            detail: Some(detail),
+            field: None,
+        }]))
     }
 }
 
 impl From<reqwest::Error> for SquareError {
     fn from(r: reqwest::Error) -> Self {
         eprintln!("Reqwest Failed: {:?}", r);
-        SquareError(None)
+        if r.is_timeout() {
+            return SquareError::timed_out(r.to_string());
+        }
+
+        SquareError::from(None)
+    }
+}
+
+impl From<TransportError> for SquareError {
+    fn from(t: TransportError) -> Self {
+        #[cfg(feature = "tracing")]
+        crate::trace::transport_error(&t);
+
+        if t.is_timeout() {
+            return SquareError::timed_out(t.to_string());
+        }
+
+        SquareError::from(None)
     }
 }
 
 impl From<reqwest::header::InvalidHeaderValue> for SquareError {
     fn from(r: reqwest::header::InvalidHeaderValue) -> Self {
         eprintln!("Reqwest Header Failed: {:?}", r);
-        SquareError(None)
+        SquareError::from(None)
     }
 }
 
 impl From<serde_json::Error> for SquareError {
     fn from(s: serde_json::Error) -> Self {
         eprintln!("Serde JSON Failed: {:?}", s);
-        SquareError(None)
+        SquareError::from(None)
     }
 }
 
@@ -122,4 +233,7 @@ pub struct CreateTerminalRefundBodyBuildError;
 pub struct BuildError;
 
 #[derive(Serialize, Deserialize, Debug)]
-pub struct ValidationError;
\ No newline at end of file
+pub struct ValidationError;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BusinessHoursError;
\ No newline at end of file
@@ -0,0 +1,128 @@
+/*!
+Hooks into the requests a [SquareClient](crate::client::SquareClient) sends to the
+[Square API](https://developer.squareup.com).
+
+A [RequestInterceptor](RequestInterceptor) registered on a client sees every attempt of every
+request right before it is sent, and every response right after it arrived, before it is checked
+for errors, retried or deserialized. Interceptors can
+* inspect both, e.g. to log them;
+* change the request, such as its headers to swap the credentials it is sent with;
+* answer the request themselves, e.g. from a cache or with a made up failure to test how the
+  caller copes with it, in which case it is not sent at all;
+* change the response, such as its status or body.
+
+Interceptors run in the order they were registered. The first one answering a request stops the
+others from seeing it, and the responses pass all interceptors, also the ones answered by an
+interceptor.
+
+# Example: Log every request and its status
+```
+use square_ox::{
+    client::SquareClient,
+    interceptor::{InterceptedRequest, InterceptedResponse, RequestInterceptor},
+};
+
+struct Logger;
+
+impl RequestInterceptor for Logger {
+    fn after_response(&self, request: &InterceptedRequest, response: &mut InterceptedResponse) {
+        println!("{:?} {} -> {}", request.verb, request.url, response.status);
+    }
+}
+
+let client = SquareClient::new("your_square_access_token").interceptor(Logger);
+```
+ */
+
+use crate::api::Verb;
+use crate::transport::HttpResponse;
+
+use reqwest::header::HeaderMap;
+
+/// An attempt of a request, as it is about to be sent.
+#[derive(Clone, Debug)]
+pub struct InterceptedRequest {
+    pub verb: Verb,
+    /// The endpoint the request is sent to, relative to the API version, e.g. `payments/some_id`.
+    pub endpoint: String,
+    pub url: String,
+    pub parameters: Option<Vec<(String, String)>>,
+    /// The headers, including the `Authorization` header.
+    pub headers: HeaderMap,
+    /// The JSON body. For a multipart upload it is its `request` part, which is sent as is
+    /// whatever an interceptor changes.
+    pub body: Option<serde_json::Value>,
+    /// The zero based number of the attempt, greater than 0 for retries.
+    pub attempt: u32,
+}
+
+/// A response, as it was received or made up by an interceptor.
+pub type InterceptedResponse = HttpResponse;
+
+/// Hooks called around every attempt of every request of the client it is registered on. Both do
+/// nothing unless implemented.
+pub trait RequestInterceptor: Send + Sync {
+    /// Called right before the request is sent, with the request as it will be sent. Returning a
+    /// response answers the request without sending it.
+    fn before_request(&self, request: &mut InterceptedRequest) -> Option<InterceptedResponse> {
+        let _ = request;
+
+        None
+    }
+
+    /// Called right after the response arrived, before it is checked for errors.
+    fn after_response(&self, request: &InterceptedRequest, response: &mut InterceptedResponse) {
+        let _ = (request, response);
+    }
+}
+
+#[cfg(test)]
+mod test_interceptor {
+    use super::*;
+    use crate::api::SquareAPI;
+    use crate::client::SquareClient;
+    use reqwest::StatusCode;
+    use std::sync::{Arc, Mutex};
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl RequestInterceptor for Recorder {
+        fn after_response(&self, request: &InterceptedRequest, response: &mut InterceptedResponse) {
+            self.0.lock().unwrap().push(format!("{} {}", request.endpoint, response.status));
+        }
+    }
+
+    // answers every request with the canned body without sending it
+    struct Canned(&'static str);
+
+    impl RequestInterceptor for Canned {
+        fn before_request(&self, _: &mut InterceptedRequest) -> Option<InterceptedResponse> {
+            Some(InterceptedResponse::new(StatusCode::OK, self.0.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interceptors() {
+        let recorded = Arc::new(Mutex::new(vec![]));
+        let sut = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Canned(r#"{ "locations": [{ "id": "some_location_id" }] }"#))
+            .interceptor(Recorder(recorded.clone()));
+
+        let res = sut.request(Verb::GET, SquareAPI::Locations("".to_string()), None::<&()>, None)
+            .await
+            .unwrap();
+
+        assert!(res.into_responses()
+            .iter()
+            .any(|response| matches!(response, crate::objects::Response::Locations(_))));
+        assert_eq!(*recorded.lock().unwrap(), vec!["locations 200 OK".to_string()]);
+
+        let failing = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Canned(r#"{ "errors": [{ "category": "API_ERROR", "code": "INTERNAL_SERVER_ERROR" }] }"#));
+
+        let errors = failing.locations().list().await.unwrap_err().get().unwrap();
+        assert_eq!(errors[0].code, "INTERNAL_SERVER_ERROR");
+    }
+}
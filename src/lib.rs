@@ -13,12 +13,56 @@
 //! For examples of how this library can be used, see the `examples` directory in the repository.
 //! The examples are a work in progress, although the `payments_api` example provides a good use case.
 //! It can be run with `cargo run --example payments_api`.
+//!
+//! # WebAssembly
+//! The crate compiles to `wasm32-unknown-unknown`, e.g. for Cloudflare Workers, where requests
+//! are sent through the `fetch` of the JavaScript host. The connection settings and the request
+//! timeout of the client are left to the host there, and the blocking client is not available.
+//!
+//! # TLS
+//! Requests are sent over the TLS stack of the platform, i.e. OpenSSL on Linux, unless told
+//! otherwise through the features of the crate:
+//! * `native-tls`, enabled by default, links the TLS stack of the platform dynamically.
+//! * `vendored` builds OpenSSL from source and links it statically.
+//! * `rustls-tls` uses [rustls](https://docs.rs/rustls) instead, which needs no TLS library of
+//!   the system and suits static builds, e.g. for musl. It is used whenever it is enabled, also
+//!   along with `native-tls`.
+//! ```toml
+//! [dependencies]
+//! square-ox = { version = "0.1.0", default-features = false, features = ["rustls-tls"] }
+//! ```
+//!
+//! # Compression
+//! The responses of Square, such as large catalog listings, are sent uncompressed unless asked
+//! for otherwise through the `gzip` and `brotli` features, which make the client accept, and
+//! decompress, responses compressed either way. On `wasm32` compression is left to the
+//! JavaScript host.
+//! ```toml
+//! [dependencies]
+//! square-ox = { version = "0.1.0", features = ["gzip", "brotli"] }
+//! ```
 
 #![deny(clippy::all)]
 
 pub mod client;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
 pub mod api;
 pub mod errors;
 pub mod response;
 pub mod objects;
 pub mod builder;
+pub mod retry;
+pub mod rate_limit;
+pub mod circuit_breaker;
+pub mod interceptor;
+pub mod metrics;
+pub mod transport;
+pub mod token;
+mod platform;
+#[cfg(feature = "tracing")]
+pub mod trace;
+pub mod checkpoint;
+pub mod drift;
+pub mod webhooks;
+pub mod utils;
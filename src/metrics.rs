@@ -0,0 +1,127 @@
+/*!
+Metrics of the requests a [SquareClient](crate::client::SquareClient) sends to the
+[Square API](https://developer.squareup.com).
+
+A [MetricsSink](MetricsSink) registered on a client is handed the [RequestMetrics](RequestMetrics)
+of every request once it completed, whether it succeeded or not, so they can be fed to Prometheus,
+StatsD or the like without wrapping every call site. A request counts once, however often it was
+retried.
+
+The sink is called on the task sending the request, so it should hand the metrics off rather
+than block.
+
+# Example: Count the requests per API and status
+```
+use square_ox::{
+    client::SquareClient,
+    metrics::{MetricsSink, RequestMetrics},
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct Counter(Mutex<HashMap<(&'static str, Option<u16>), u64>>);
+
+impl MetricsSink for Counter {
+    fn record(&self, metrics: &RequestMetrics) {
+        let key = (metrics.api, metrics.status.map(|status| status.as_u16()));
+        *self.0.lock().unwrap().entry(key).or_default() += 1;
+    }
+}
+
+let client = SquareClient::new("your_square_access_token").metrics_sink(Counter::default());
+```
+ */
+
+use crate::api::Verb;
+
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// What a request took, as handed to a [MetricsSink](MetricsSink).
+#[derive(Clone, Debug)]
+pub struct RequestMetrics {
+    pub verb: Verb,
+    /// The API the request was sent to, e.g. `payments`, which makes a label of few values.
+    pub api: &'static str,
+    /// The endpoint the request was sent to, relative to the API version, e.g.
+    /// `payments/some_id`.
+    pub endpoint: String,
+    /// The status of the last response, `None` if none arrived, e.g. because the request timed
+    /// out or failed to connect.
+    pub status: Option<StatusCode>,
+    /// The number of times the request was retried.
+    pub retries: u32,
+    /// How long the request took, including its retries and the waits in between.
+    pub duration: Duration,
+}
+
+/// Receives the [RequestMetrics](RequestMetrics) of every request of the client it is registered
+/// on.
+pub trait MetricsSink: Send + Sync {
+    fn record(&self, metrics: &RequestMetrics);
+}
+
+#[cfg(test)]
+mod test_metrics {
+    use super::*;
+    use crate::client::SquareClient;
+    use crate::interceptor::{InterceptedRequest, InterceptedResponse, RequestInterceptor};
+    use std::sync::{Arc, Mutex};
+
+    struct Recorder(Arc<Mutex<Vec<RequestMetrics>>>);
+
+    impl MetricsSink for Recorder {
+        fn record(&self, metrics: &RequestMetrics) {
+            self.0.lock().unwrap().push(metrics.clone());
+        }
+    }
+
+    // answers every request without sending it
+    struct Canned(StatusCode, &'static str);
+
+    impl RequestInterceptor for Canned {
+        fn before_request(&self, _: &mut InterceptedRequest) -> Option<InterceptedResponse> {
+            Some(InterceptedResponse::new(self.0, self.1.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_sink() {
+        let recorded = Arc::new(Mutex::new(vec![]));
+        let sut = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Canned(StatusCode::OK, r#"{ "locations": [] }"#))
+            .metrics_sink(Recorder(recorded.clone()));
+
+        sut.locations().list().await.unwrap();
+
+        let failing = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .interceptor(Canned(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                r#"{ "errors": [{ "category": "API_ERROR", "code": "INTERNAL_SERVER_ERROR" }] }"#,
+            ))
+            .metrics_sink(Recorder(recorded.clone()));
+
+        failing.locations().list().await.unwrap_err();
+
+        // nothing listens on the port, so no response arrives
+        let unreachable = SquareClient::new("some_token")
+            .base_url("http://127.0.0.1:1")
+            .metrics_sink(Recorder(recorded.clone()));
+
+        unreachable.locations().list().await.unwrap_err();
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded.len(), 3);
+        assert!(recorded.iter().all(|metrics| metrics.verb == Verb::GET));
+        assert!(recorded.iter().all(|metrics| metrics.api == "locations"));
+        assert!(recorded.iter().all(|metrics| metrics.endpoint == "locations"));
+        assert!(recorded.iter().all(|metrics| metrics.retries == 0));
+        assert_eq!(
+            recorded.iter().map(|metrics| metrics.status).collect::<Vec<_>>(),
+            vec![Some(StatusCode::OK), Some(StatusCode::INTERNAL_SERVER_ERROR), None],
+        );
+    }
+}
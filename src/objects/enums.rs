@@ -6,13 +6,33 @@ use serde::{Deserialize, Serialize};
 
 /// The Currency code corresponding to the amount of Money.
 #[non_exhaustive]
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum Currency {
     GBP,
     USD,
     EUR,
     JPY,
-    SGD
+    SGD,
+    CAD,
+    AUD,
+    NZD,
+    CHF,
+}
+
+impl Currency {
+    /// The number of decimal places of the currency's lowest denomination, i.e. the number of
+    /// digits an amount in [Money](crate::objects::Money) holds after the decimal point.
+    pub fn decimal_places(&self) -> u32 {
+        match self {
+            Currency::JPY => 0,
+            _ => 2,
+        }
+    }
+
+    /// The amount of one whole unit of the currency in its lowest denomination.
+    pub fn unit(&self) -> i64 {
+        10_i64.pow(self.decimal_places())
+    }
 }
 
 /// The CustomerCreationSource type, indicating how the customer was created.
@@ -153,6 +173,13 @@ impl fmt::Display for SortOrder {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CustomerSortField {
+    Default,
+    CreatedAt,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum BusinessBookingProfileBookingPolicy {
@@ -189,7 +216,7 @@ pub enum OrderLineItemDiscountScope {
     Order,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderLineItemDiscountType {
     UnknownDiscount,
@@ -206,13 +233,20 @@ pub enum OrderFulfillmentFulfillmentLineItemApplication {
     EntryList
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderFulfillmentPickupDetailsScheduleType {
     Scheduled,
     Asap
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderFulfillmentDeliveryDetailsScheduleType {
+    Scheduled,
+    Asap
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderLineItemItemType {
@@ -226,6 +260,7 @@ pub enum OrderLineItemItemType {
 pub enum RefundStatus {
     Pending,
     Approved,
+    Completed,
     Rejected,
     Failed,
 }
@@ -269,6 +304,14 @@ pub enum OrderServiceChargeType {
     Custom,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderServiceChargeScope {
+    OtherServiceChargeScope,
+    LineItem,
+    Order,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TenderType {
@@ -300,7 +343,7 @@ pub enum TenderCardDetailsEntryMethod {
     Contactless
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CatalogObjectType {
     Item,
@@ -319,9 +362,10 @@ pub enum CatalogObjectType {
     ItemOptionVal,
     CustomAttributeDefinition,
     QuickAmountSetting,
+    SubscriptionPlanVariation,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CatalogCustomAttributeDefinitionType {
     String,
@@ -345,7 +389,7 @@ pub enum InventoryAlertType {
     LowQuantity,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CatalogPricingType {
     FixedPricing,
@@ -359,7 +403,7 @@ pub enum SearchCatalogItemsRequestStockLevel {
     Low,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum InventoryState {
     Custom,
@@ -433,7 +477,7 @@ pub enum BankAccountOwnershipType {
     Unknown
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum BankAccountType {
     Checking,
@@ -441,7 +485,14 @@ pub enum BankAccountType {
     Unknown,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BankAccountTransferType {
+    Ach,
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum BuyNowPayLaterBrand {
     Afterpay,
@@ -456,6 +507,15 @@ pub enum CCVStatus {
     CvvNotChecked,
 }
 
+/// What happens to a payment that was not completed or canceled before its `delay_duration`
+/// elapsed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PaymentDelayAction {
+    Cancel,
+    Complete,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PaymentStatus {
@@ -499,7 +559,7 @@ pub enum RiskEvaluationRiskLevel {
     High,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PaymentSourceType {
     BankAccount,
@@ -510,14 +570,14 @@ pub enum PaymentSourceType {
     External,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum DigitalWalletBrand {
     CashApp,
     Unknown
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum DigitalWalletStatus {
     Authorized,
@@ -555,25 +615,39 @@ pub enum TerminalCheckoutStatus {
     Completed,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TerminalActionActionType {
+    QrCode,
+    PingDevice,
+    SaveCard,
+    Signature,
+    Confirmation,
+    Receipt,
+    DataCollection,
+    Select,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderFulfillmentState {
     Proposed,
-    Preserved,
+    Reserved,
     Prepared,
     Completed,
     Canceled,
     Failed
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum OrderFulfillmentType {
     Pickup,
     Shipment,
+    Delivery,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SearchOrdersSortField {
     CreatedAt,
@@ -581,3 +655,414 @@ pub enum SearchOrdersSortField {
     ClosedAt,
 }
 
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SearchEventsSortField {
+    Default,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EventTypeReleaseStatus {
+    Public,
+    Beta,
+    Deprecated,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthGrantType {
+    AuthorizationCode,
+    RefreshToken,
+    MigrationToken,
+}
+
+/// The permissions an application can request from a seller through OAuth.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OAuthPermission {
+    BankAccountsRead,
+    BookingsRead,
+    BookingsWrite,
+    CashDrawerRead,
+    CustomersRead,
+    CustomersWrite,
+    DeviceCredentialManagement,
+    DisputesRead,
+    DisputesWrite,
+    EmployeesRead,
+    EmployeesWrite,
+    GiftcardsRead,
+    GiftcardsWrite,
+    InventoryRead,
+    InventoryWrite,
+    InvoicesRead,
+    InvoicesWrite,
+    ItemsRead,
+    ItemsWrite,
+    LoyaltyRead,
+    LoyaltyWrite,
+    MerchantProfileRead,
+    MerchantProfileWrite,
+    OnlineStoreSiteRead,
+    OnlineStoreSnippetsRead,
+    OnlineStoreSnippetsWrite,
+    OrdersRead,
+    OrdersWrite,
+    PaymentsRead,
+    PaymentsWrite,
+    PaymentsWriteAdditionalRecipients,
+    PaymentsWriteInPerson,
+    PayoutsRead,
+    SubscriptionsRead,
+    SubscriptionsWrite,
+    TimecardsRead,
+    TimecardsSettingsRead,
+    TimecardsSettingsWrite,
+    TimecardsWrite,
+    VendorRead,
+    VendorWrite,
+}
+
+impl fmt::Display for OAuthPermission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OAuthPermission::BankAccountsRead => write!(f, "BANK_ACCOUNTS_READ"),
+            OAuthPermission::BookingsRead => write!(f, "BOOKINGS_READ"),
+            OAuthPermission::BookingsWrite => write!(f, "BOOKINGS_WRITE"),
+            OAuthPermission::CashDrawerRead => write!(f, "CASH_DRAWER_READ"),
+            OAuthPermission::CustomersRead => write!(f, "CUSTOMERS_READ"),
+            OAuthPermission::CustomersWrite => write!(f, "CUSTOMERS_WRITE"),
+            OAuthPermission::DeviceCredentialManagement => write!(f, "DEVICE_CREDENTIAL_MANAGEMENT"),
+            OAuthPermission::DisputesRead => write!(f, "DISPUTES_READ"),
+            OAuthPermission::DisputesWrite => write!(f, "DISPUTES_WRITE"),
+            OAuthPermission::EmployeesRead => write!(f, "EMPLOYEES_READ"),
+            OAuthPermission::EmployeesWrite => write!(f, "EMPLOYEES_WRITE"),
+            OAuthPermission::GiftcardsRead => write!(f, "GIFTCARDS_READ"),
+            OAuthPermission::GiftcardsWrite => write!(f, "GIFTCARDS_WRITE"),
+            OAuthPermission::InventoryRead => write!(f, "INVENTORY_READ"),
+            OAuthPermission::InventoryWrite => write!(f, "INVENTORY_WRITE"),
+            OAuthPermission::InvoicesRead => write!(f, "INVOICES_READ"),
+            OAuthPermission::InvoicesWrite => write!(f, "INVOICES_WRITE"),
+            OAuthPermission::ItemsRead => write!(f, "ITEMS_READ"),
+            OAuthPermission::ItemsWrite => write!(f, "ITEMS_WRITE"),
+            OAuthPermission::LoyaltyRead => write!(f, "LOYALTY_READ"),
+            OAuthPermission::LoyaltyWrite => write!(f, "LOYALTY_WRITE"),
+            OAuthPermission::MerchantProfileRead => write!(f, "MERCHANT_PROFILE_READ"),
+            OAuthPermission::MerchantProfileWrite => write!(f, "MERCHANT_PROFILE_WRITE"),
+            OAuthPermission::OnlineStoreSiteRead => write!(f, "ONLINE_STORE_SITE_READ"),
+            OAuthPermission::OnlineStoreSnippetsRead => write!(f, "ONLINE_STORE_SNIPPETS_READ"),
+            OAuthPermission::OnlineStoreSnippetsWrite => write!(f, "ONLINE_STORE_SNIPPETS_WRITE"),
+            OAuthPermission::OrdersRead => write!(f, "ORDERS_READ"),
+            OAuthPermission::OrdersWrite => write!(f, "ORDERS_WRITE"),
+            OAuthPermission::PaymentsRead => write!(f, "PAYMENTS_READ"),
+            OAuthPermission::PaymentsWrite => write!(f, "PAYMENTS_WRITE"),
+            OAuthPermission::PaymentsWriteAdditionalRecipients => write!(f, "PAYMENTS_WRITE_ADDITIONAL_RECIPIENTS"),
+            OAuthPermission::PaymentsWriteInPerson => write!(f, "PAYMENTS_WRITE_IN_PERSON"),
+            OAuthPermission::PayoutsRead => write!(f, "PAYOUTS_READ"),
+            OAuthPermission::SubscriptionsRead => write!(f, "SUBSCRIPTIONS_READ"),
+            OAuthPermission::SubscriptionsWrite => write!(f, "SUBSCRIPTIONS_WRITE"),
+            OAuthPermission::TimecardsRead => write!(f, "TIMECARDS_READ"),
+            OAuthPermission::TimecardsSettingsRead => write!(f, "TIMECARDS_SETTINGS_READ"),
+            OAuthPermission::TimecardsSettingsWrite => write!(f, "TIMECARDS_SETTINGS_WRITE"),
+            OAuthPermission::TimecardsWrite => write!(f, "TIMECARDS_WRITE"),
+            OAuthPermission::VendorRead => write!(f, "VENDOR_READ"),
+            OAuthPermission::VendorWrite => write!(f, "VENDOR_WRITE"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayoutStatus {
+    Sent,
+    Failed,
+    Paid,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayoutType {
+    Batch,
+    Simple,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[non_exhaustive]
+pub enum PayoutEntryType {
+    Adjustment,
+    AppFeeRefund,
+    AppFeeRevenue,
+    AutomaticBitcoinConversions,
+    AutomaticBitcoinConversionsReversed,
+    AutomaticSavings,
+    AutomaticSavingsReversed,
+    BalanceFoldersTransfer,
+    BalanceFoldersTransferReversed,
+    Charge,
+    CreditCardRepayment,
+    CreditCardRepaymentReversed,
+    DepositFee,
+    DepositFeeReversed,
+    Dispute,
+    Escheatment,
+    Fee,
+    FreeProcessing,
+    GiftCardLoadFee,
+    GiftCardLoadFeeRefund,
+    GiftCardPoolTransfer,
+    GiftCardPoolTransferReversed,
+    HoldAdjustment,
+    InitialBalanceChange,
+    LocalOffersCashback,
+    LocalOffersFee,
+    MoneyTransfer,
+    MoneyTransferReversal,
+    OpenDispute,
+    Other,
+    OtherAdjustment,
+    PaidServiceFee,
+    PaidServiceFeeRefund,
+    Payout,
+    PercentageProcessingDeactivation,
+    PercentageProcessingEnrollment,
+    PercentageProcessingRepayment,
+    PercentageProcessingRepaymentReversed,
+    ProcessingFee,
+    ProcessingFeeRefund,
+    RedemptionCode,
+    Refund,
+    ReleaseAdjustment,
+    ReserveHold,
+    ReserveRelease,
+    ReturnedPayout,
+    SquareCapitalPayment,
+    SquareCapitalReversedPayment,
+    SquarePayrollTransfer,
+    SquarePayrollTransferReversed,
+    SubscriptionFee,
+    SubscriptionFeePaidRefund,
+    SubscriptionFeeRefund,
+    TaxOnFee,
+    ThirdPartyFee,
+    ThirdPartyFeeRefund,
+    UndoGiftCardLoadFeeRefund,
+    UndoProcessingFeeRefund,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CustomAttributeVisibility {
+    VisibilityHidden,
+    VisibilityReadOnly,
+    VisibilityReadWriteValues,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GiftCardType {
+    Physical,
+    Digital,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GiftCardGanSource {
+    Square,
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GiftCardState {
+    Active,
+    Deactivated,
+    Blocked,
+    Pending,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GiftCardActivityType {
+    Activate,
+    Load,
+    Redeem,
+    ClearBalance,
+    Deactivate,
+    AdjustIncrement,
+    AdjustDecrement,
+    Refund,
+    UnlinkedActivityRefund,
+    Import,
+    Block,
+    Unblock,
+    ImportReversal,
+    TransferBalanceFrom,
+    TransferBalanceTo,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GiftCardRedeemStatus {
+    Pending,
+    Completed,
+    Canceled,
+}
+
+/// The digital wallets and buy now pay later services a checkout can offer on top of cards.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AcceptedPaymentMethod {
+    ApplePay,
+    GooglePay,
+    CashAppPay,
+    AfterpayClearpay,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LoyaltyProgramStatus {
+    Inactive,
+    Active,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LoyaltyProgramAccrualRuleType {
+    Visit,
+    Spend,
+    ItemVariation,
+    Category,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InvoiceStatus {
+    Draft,
+    Unpaid,
+    Scheduled,
+    PartiallyPaid,
+    Paid,
+    PartiallyRefunded,
+    Refunded,
+    Canceled,
+    Failed,
+    PaymentPending,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InvoiceDeliveryMethod {
+    Email,
+    ShareManually,
+    Sms,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InvoiceRequestType {
+    Balance,
+    Deposit,
+    Installment,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InvoiceAutomaticPaymentSource {
+    None,
+    CardOnFile,
+    BankOnFile,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InvoiceSortField {
+    #[default]
+    InvoiceSortDate,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CheckoutLocationSettingsBrandingHeaderType {
+    BusinessName,
+    FramedLogo,
+    FullWidthLogo,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CheckoutLocationSettingsBrandingButtonShape {
+    Squared,
+    Rounded,
+    Pill,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DeviceCodeProductType {
+    #[default]
+    TerminalApi,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DeviceCodeStatus {
+    Unknown,
+    Unpaired,
+    Paired,
+    Expired,
+}
+
+/// How often a subscription bills during a [SubscriptionPhase](crate::objects::SubscriptionPhase).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SubscriptionCadence {
+    Daily,
+    Weekly,
+    EveryTwoWeeks,
+    ThirtyDays,
+    SixtyDays,
+    NinetyDays,
+    Monthly,
+    EveryTwoMonths,
+    Quarterly,
+    EveryFourMonths,
+    EverySixMonths,
+    Annual,
+    EveryTwoYears,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SubscriptionPricingType {
+    Static,
+    Relative,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CatalogDiscountType {
+    FixedPercentage,
+    FixedAmount,
+    VariablePercentage,
+    VariableAmount,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CatalogDiscountModifyTaxBasis {
+    ModifyTaxBasis,
+    DoNotModifyTaxBasis,
+}
+
+/// Which of the matched products a [CatalogPricingRule](crate::objects::CatalogPricingRule) leaves
+/// out when it excludes some.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExcludeStrategy {
+    LeastExpensive,
+    MostExpensive,
+}
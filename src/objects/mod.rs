@@ -11,22 +11,34 @@ use crate::api::orders::Orders;
 use crate::api::terminal::Terminal;
 use crate::objects::enums::{
     ActionCancelReason, ApplicationDetailsExternalSquareProduct,
-    BankAccountOwnershipType, BusinessAppointmentSettingsBookingLocationType,
+    BankAccountOwnershipType, BankAccountTransferType, BankAccountType,
+    BusinessAppointmentSettingsBookingLocationType,
     BusinessAppointmentSettingsCancellationPolicy,
     BusinessAppointmentSettingsMaxAppointmentsPerDayLimitType,
     BusinessBookingProfileBookingPolicy, BusinessBookingProfileCustomerTimezoneChoice,
     BuyNowPayLaterBrand, CatalogCustomAttributeDefinitionType, CatalogItemProductType,
     CatalogObjectType, CatalogPricingType, CCVStatus, CheckoutOptionsPaymentType, Currency,
-    CustomerCreationSource, DigitalWalletBrand, DigitalWalletStatus, InventoryAlertType,
-    InventoryChangeType, InventoryState, LocationStatus, LocationType,
-    OrderFulfillmentFulfillmentLineItemApplication, OrderFulfillmentPickupDetailsScheduleType,
+    CustomAttributeVisibility, CustomerCreationSource, CustomerSortField, DigitalWalletBrand,
+    DigitalWalletStatus,
+    EventTypeReleaseStatus, GiftCardActivityType, GiftCardGanSource, GiftCardRedeemStatus,
+    GiftCardState, GiftCardType, InvoiceAutomaticPaymentSource, InvoiceDeliveryMethod,
+    InvoiceRequestType, InvoiceSortField, InvoiceStatus,
+    LoyaltyProgramAccrualRuleType, LoyaltyProgramStatus,
+    InventoryAlertType, InventoryChangeType, InventoryState, LocationStatus, LocationType,
+    OrderFulfillmentDeliveryDetailsScheduleType, OrderFulfillmentFulfillmentLineItemApplication,
+    OrderFulfillmentPickupDetailsScheduleType,
     OrderFulfillmentState, OrderFulfillmentType, OrderLineItemDiscountScope,
     OrderLineItemDiscountType, OrderLineItemItemType, OrderLineItemTaxScope,
-    OrderLineItemTaxType, OrderServiceChargeCalculationPhase, OrderServiceChargeType,
+    OrderLineItemTaxType, OrderServiceChargeCalculationPhase, OrderServiceChargeScope,
+    OrderServiceChargeType,
     OrderState, PaymentSourceType, PaymentStatus, PaymentType, PaymentVerificationMethod,
-    PaymentVerificationResults, ProcessingFeeType, RefundStatus, RiskEvaluationRiskLevel,
-    SearchOrdersSortField, SortOrder, TenderCardDetailsEntryMethod, TenderCardDetailsStatus,
-    TenderType, TerminalCheckoutStatus
+    PaymentVerificationResults, PayoutEntryType, PayoutStatus, PayoutType, ProcessingFeeType,
+    RefundStatus, RiskEvaluationRiskLevel,
+    SearchEventsSortField, SearchOrdersSortField, SortOrder, TenderCardDetailsEntryMethod,
+    TenderCardDetailsStatus, TenderType, TerminalActionActionType, TerminalCheckoutStatus,
+    CheckoutLocationSettingsBrandingButtonShape, CheckoutLocationSettingsBrandingHeaderType,
+    DeviceCodeProductType, DeviceCodeStatus, SubscriptionCadence, SubscriptionPricingType,
+    CatalogDiscountType, CatalogDiscountModifyTaxBasis, ExcludeStrategy,
 };
 use crate::response::ResponseError;
 
@@ -38,6 +50,7 @@ use crate::response::ResponseError;
 pub enum Response {
     // Payments Endpoint Responses
     Payment(Payment),
+    Payments(Vec<Payment>),
 
     // Orders Endpoint Responses
     Order(Order),
@@ -45,11 +58,14 @@ pub enum Response {
     OrderEntries(Vec<OrderEntry>),
 
     // Locations Endpoint Responses
+    Location(Location),
     Locations(Vec<Location>),
 
     // Customer Endpoint Responses
     Customer(Customer),
     Customers(Vec<Customer>),
+    CustomAttribute(CustomAttribute),
+    CustomAttributeDefinition(CustomAttributeDefinition),
 
     // Catalog Endpoint Responses
     Objects(Vec<CatalogObject>),
@@ -59,14 +75,17 @@ pub enum Response {
     Limits(CatalogInfoResponseLimits),
     StandardUnitDescriptionGroup(StandardUnitDescriptionGroup),
     RelatedObjects(Vec<CatalogObject>),
+    Image(CatalogObject),
 
     // Bookings Endpoint Responses
     Booking(Booking),
     Bookings(Vec<Booking>),
     Availabilities(Vec<Availability>),
     BusinessBookingProfile(BusinessBookingProfile),
-    TeamMemberBookingProfiles(Vec<TeamMemberBookingProfile>),
+    TeamMemberBookingProfiles(TeamMemberBookingProfilesEnum),
     TeamMemberBookingProfile(TeamMemberBookingProfile),
+    LocationBookingProfiles(Vec<LocationBookingProfile>),
+    LocationBookingProfile(LocationBookingProfile),
 
     // Cards Endpoint Responses
     Cards(Vec<Card>),
@@ -76,15 +95,47 @@ pub enum Response {
     Checkout(CheckoutEnum), // Also a possible Terminals Endpoint Response
     PaymentLinks(Vec<PaymentLink>),
     PaymentLink(PaymentLink),
+    LocationSettings(CheckoutLocationSettings),
 
     // Inventory Endpoint Responses
     Counts(Vec<InventoryCount>),
+    Changes(Vec<InventoryChange>),
+    Adjustment(InventoryAdjustment),
+    Count(InventoryPhysicalCount),
+    Transfer(InventoryTransfer),
 
     // Sites Endpoint Responses
     Sites(Vec<Site>),
 
     // Terminal Endpoint Responses
     Checkouts(Vec<TerminalCheckout>),
+    Action(TerminalAction),
+
+    // Devices Endpoint Responses
+    DeviceCode(DeviceCode),
+
+    // Events Endpoint Responses
+    Events(Vec<Event>),
+    EventTypes(Vec<String>),
+    Metadata(EventMetadataEnum),
+
+    // Refunds Endpoint Responses
+    Refund(PaymentRefund),
+
+    // Payouts Endpoint Responses
+    Payout(Payout),
+    Payouts(Vec<Payout>),
+    PayoutEntries(Vec<PayoutEntry>),
+
+    // Gift Cards Endpoint Responses
+    GiftCard(GiftCard),
+    GiftCards(Vec<GiftCard>),
+    GiftCardActivity(GiftCardActivity),
+    GiftCardActivities(Vec<GiftCardActivity>),
+
+    // Invoices Endpoint Responses
+    Invoice(Invoice),
+    Invoices(Vec<Invoice>),
 }
 
 // Since both the Checkout and Terminal endpoint can return a field tagged with checkout it is
@@ -96,6 +147,15 @@ pub enum CheckoutEnum {
     TerminalCheckout(TerminalCheckout),
 }
 
+// Listing team member booking profiles returns them in a list, while retrieving them in bulk
+// returns them by team member id, both in a field tagged with team_member_booking_profiles
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TeamMemberBookingProfilesEnum {
+    List(Vec<TeamMemberBookingProfile>),
+    Bulk(HashMap<String, RetrieveTeamMemberBookingProfileResult>),
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct Location {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -169,11 +229,28 @@ pub struct Address {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sublocality: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sublocality_2: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sublocality_3: Option<String>,
+    #[serde(
+        rename = "administrative_district_level_1",
+        alias = "administrative_district_level",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub administrative_district_level: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub administrative_district_level_2: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub administrative_district_level_3: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub postal_code: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -261,6 +338,44 @@ pub struct Customer {
     pub idempotency_key: Option<String>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CustomAttribute {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<CustomAttributeVisibility>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<CustomAttributeDefinition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct CustomAttributeDefinition {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<CustomAttributeVisibility>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct Preferences {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -318,7 +433,7 @@ pub struct Card {
     pub version: Option<i64>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct FilterValue {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub  all: Option<Vec<String>>,
@@ -375,6 +490,8 @@ pub struct CatalogObject {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub subscription_plan_data: Option<CatalogSubscriptionPlan>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscription_plan_variation_data: Option<CatalogSubscriptionPlanVariation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tax_data: Option<CatalogTax>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub time_period_data: Option<CatalogTimePeriod>,
@@ -386,7 +503,20 @@ pub struct CatalogObject {
     pub version: Option<i64>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct CatalogObjectBatch {
+    pub objects: Vec<CatalogObject>,
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+pub struct CatalogIdMapping {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_object_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogObjectVariation {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -514,42 +644,41 @@ pub struct CatalogCategory {
     pub name: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogCustomAttributeDefinition {
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    allowed_object_types: Option<String>,
+    pub allowed_object_types: Option<Vec<CatalogObjectType>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    name: Option<String>,
+    pub name: Option<String>,
     #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
-    pub type_name: Option<CatalogObjectType>,
+    pub type_name: Option<CatalogCustomAttributeDefinitionType>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    app_visibility: Option<String>,
+    pub app_visibility: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    custom_attribute_usage_count: Option<i32>,
+    pub custom_attribute_usage_count: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    description: Option<String>,
+    pub description: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    key: Option<String>,
+    pub key: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    number_config: Option<CatalogCustomAttributeDefinitionNumberConfig>,
+    pub number_config: Option<CatalogCustomAttributeDefinitionNumberConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    selection_config: Option<CatalogCustomAttributeDefinitionSelectionConfig>,
+    pub selection_config: Option<CatalogCustomAttributeDefinitionSelectionConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    seller_visibility: Option<String>,
+    pub seller_visibility: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    source_application: Option<SourceApplication>,
+    pub source_application: Option<SourceApplication>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    string_config: Option<CatalogCustomAttributeDefinitionStringConfig>,
-
+    pub string_config: Option<CatalogCustomAttributeDefinitionStringConfig>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogCustomAttributeDefinitionNumberConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub precision: Option<i32>
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogCustomAttributeDefinitionSelectionConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allowed_selections: Option<Vec<CatalogCustomAttributeDefinitionSelectionConfigCustomAttributeSelection>>,
@@ -557,7 +686,7 @@ pub struct CatalogCustomAttributeDefinitionSelectionConfig {
     pub max_allowed_selections: Option<i32>
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogCustomAttributeDefinitionSelectionConfigCustomAttributeSelection {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -575,13 +704,13 @@ pub struct SourceApplication {
     pub product: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogCustomAttributeDefinitionStringConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enforce_uniqueness: Option<bool>
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogCustomAttributeValue {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub boolean_value: Option<bool>,
@@ -601,24 +730,24 @@ pub struct CatalogCustomAttributeValue {
     pub type_name: Option<CatalogCustomAttributeDefinitionType>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogDiscount {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub amount_money: Option<Money>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub discount_type: Option<String>,
+    pub discount_type: Option<CatalogDiscountType>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label_color: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub maximum_amount_money: Option<Money>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub modify_tax_basis: Option<String>,
+    pub modify_tax_basis: Option<CatalogDiscountModifyTaxBasis>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub percentage: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub pin_required: Option<String>,
+    pub pin_required: Option<bool>,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
@@ -633,7 +762,7 @@ pub struct CatalogImage {
     pub url: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogItem {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub abbreviation: Option<String>,
@@ -725,7 +854,7 @@ pub struct CatalogItemOptionValue {
     pub ordinal: Option<i32>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogItemVariation {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub available_for_booking: Option<bool>,
@@ -873,7 +1002,7 @@ pub struct CatalogModifierList {
     pub selection_type: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogPricingRule {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub customer_group_ids_any: Option<Vec<String>>,
@@ -882,7 +1011,7 @@ pub struct CatalogPricingRule {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub exclude_products_id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub exclude_strategy: Option<String>,
+    pub exclude_strategy: Option<ExcludeStrategy>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub match_products_id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -890,11 +1019,11 @@ pub struct CatalogPricingRule {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub time_period_ids: Option<String>,
+    pub time_period_ids: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub valid_from_date: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub valid_from_local_time: Option<Vec<String>>,
+    pub valid_from_local_time: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub valid_until_date: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -903,7 +1032,7 @@ pub struct CatalogPricingRule {
     pub apply_products_id: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogProductSet {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub all_products: Option<bool>,
@@ -943,28 +1072,64 @@ pub struct CatalogQuickAmount {
     pub score: Option<i64>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogSubscriptionPlan {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub phases: Option<SubscriptionPhase>
+    pub phases: Option<Vec<SubscriptionPhase>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscription_plan_variations: Option<Vec<CatalogObject>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eligible_item_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eligible_category_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all_items: Option<bool>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct CatalogSubscriptionPlanVariation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phases: Option<Vec<SubscriptionPhase>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscription_plan_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_billing_anchor_date: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub can_prorate: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successor_plan_variation_id: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct SubscriptionPhase {
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub cadence: Option<String>,
+    pub cadence: Option<SubscriptionCadence>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ordinal: Option<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub periods: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<SubscriptionPricing>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub recurring_price_money: Option<Money>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub uid: Option<String>,
 }
 
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct SubscriptionPricing {
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<SubscriptionPricingType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discount_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_money: Option<Money>,
+}
+
 #[derive(Clone, Serialize, Debug, Deserialize)]
 pub struct CatalogTax {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -981,7 +1146,7 @@ pub struct CatalogTax {
     pub percentage: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogTimePeriod {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub event: Option<String>,
@@ -1036,7 +1201,7 @@ pub struct BookingCreatorDetails {
 /// Representation of Money for the crate.
 /// The amount is given in the lowest possible denomination.
 /// So for GBP the amount is in pence.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Money {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub amount: Option<i64>,
@@ -1093,6 +1258,27 @@ pub struct BusinessAppointmentSettings {
     pub skip_booking_flow_staff_selection: Option<bool>,
 }
 
+/// The online booking settings of a single location.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LocationBookingProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub booking_site_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub online_booking_enabled: Option<bool>,
+}
+
+/// The booking profile of a single team member retrieved in bulk, or the errors that kept it from
+/// being retrieved.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RetrieveTeamMemberBookingProfileResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_member_booking_profile: Option<TeamMemberBookingProfile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<ResponseError>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TeamMemberBookingProfile {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1128,7 +1314,7 @@ pub struct Order {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub discounts: Option<Vec<OrderLineItemDiscount>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub fulfillments: Option<OrderFulfillment>,
+    pub fulfillments: Option<Vec<OrderFulfillment>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub line_items: Option<Vec<OrderLineItem>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1182,7 +1368,7 @@ pub struct ChargeRequestAdditionalRecipient {
 
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderLineItemDiscount {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub amount_money: Option<Money>,
@@ -1193,7 +1379,7 @@ pub struct OrderLineItemDiscount {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub catalog_version: Option<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<HashMap<String, String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1210,24 +1396,26 @@ pub struct OrderLineItemDiscount {
     pub uid: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderFulfillment {
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    entries: Option<Vec<OrderFulfillmentFulfillmentEntry>>,
+    pub entries: Option<Vec<OrderFulfillmentFulfillmentEntry>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    line_item_application: Option<OrderFulfillmentFulfillmentLineItemApplication>,
+    pub line_item_application: Option<OrderFulfillmentFulfillmentLineItemApplication>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pickup_details: Option<OrderFulfillmentPickupDetails>,
+    pub pickup_details: Option<OrderFulfillmentPickupDetails>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    shipment_details: Option<OrderFulfillmentShipmentDetails>,
+    pub shipment_details: Option<OrderFulfillmentShipmentDetails>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    state: Option<String>,
+    pub delivery_details: Option<OrderFulfillmentDeliveryDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<OrderFulfillmentState>,
     #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
-    fulfillment_type: Option<String>,
+    pub fulfillment_type: Option<OrderFulfillmentType>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    uid: Option<String>,
+    pub uid: Option<String>,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
@@ -1240,7 +1428,7 @@ pub struct OrderFulfillmentFulfillmentEntry {
     pub uid: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderFulfillmentPickupDetails {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub accepted_at: Option<String>,
@@ -1288,7 +1476,7 @@ pub struct OrderFulfillmentPickupDetailsCurbsidePickupDetails {
     pub curbside_details: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderFulfillmentRecipient {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub address: Option<Address>,
@@ -1302,39 +1490,81 @@ pub struct OrderFulfillmentRecipient {
     pub phone_number: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderFulfillmentShipmentDetails {
-    cancel_reason: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    canceled_at: Option<String>,
+    pub cancel_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canceled_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrier: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    carrier: Option<String>,
+    pub expected_shipped_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    expected_shipped_at: Option<String>,
+    pub failed_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    failed_at: Option<String>,
+    pub failure_reason: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    failure_reason: Option<String>,
+    pub in_progress_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    in_progress_at: Option<String>,
+    pub packaged_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    packaged_at: Option<String>,
+    pub placed_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    placed_at: Option<String>,
+    pub recipient: Option<OrderFulfillmentRecipient>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    recipient: Option<OrderFulfillmentRecipient>,
-    shipped_at: Option<String>,
+    pub shipped_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    shipping_note: Option<String>,
+    pub shipping_note: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    shipping_type: Option<String>,
+    pub shipping_type: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    tracking_number: Option<String>,
+    pub tracking_number: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    tracking_url: Option<String>,
+    pub tracking_url: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct OrderFulfillmentDeliveryDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancel_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canceled_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub courier_pickup_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub courier_provider_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub courier_support_phone_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deliver_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivery_window_duration: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dropoff_notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_delivery_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_no_contact_delivery: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub managed_delivery: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prep_time_duration: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<OrderFulfillmentRecipient>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_type: Option<OrderFulfillmentDeliveryDetailsScheduleType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub square_delivery_id: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderLineItem {
     pub quantity: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1457,7 +1687,7 @@ pub struct OrderQuantityUnit {
     pub precision: Option<i32>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderMoneyAmounts {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub discount_money: Option<Money>,
@@ -1495,7 +1725,7 @@ pub struct Refund {
     pub transaction_id: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderReturn {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub return_amounts: Option<OrderMoneyAmounts>,
@@ -1515,7 +1745,7 @@ pub struct OrderReturn {
     pub uid: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderReturnDiscount {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub amount_money: Option<Money>,
@@ -1530,7 +1760,7 @@ pub struct OrderReturnDiscount {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub percentage: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub scope: Option<String>,
+    pub scope: Option<OrderLineItemDiscountScope>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source_discount_uid: Option<String>,
     #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
@@ -1539,7 +1769,7 @@ pub struct OrderReturnDiscount {
     pub uid: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderReturnLineItem {
     pub quantity: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1580,7 +1810,7 @@ pub struct OrderReturnLineItem {
     pub variation_total_price_money: Option<Money>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderReturnLineItemModifier {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub base_price_money: Option<Money>,
@@ -1598,7 +1828,7 @@ pub struct OrderReturnLineItemModifier {
     pub uid: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderReturnServiceCharge {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub amount_money: Option<Money>,
@@ -1607,7 +1837,7 @@ pub struct OrderReturnServiceCharge {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub applied_taxes: Option<Vec<OrderLineItemAppliedTax>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub calculation_phase: Option<String>,
+    pub calculation_phase: Option<OrderServiceChargeCalculationPhase>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub catalog_object_id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1628,7 +1858,7 @@ pub struct OrderReturnServiceCharge {
     pub uid: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderReturnTax {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub applied_money: Option<Money>,
@@ -1650,7 +1880,7 @@ pub struct OrderReturnTax {
     pub uid: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default, PartialEq)]
 pub struct OrderRoundingAdjustment {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub amount_money: Option<Money>,
@@ -1687,6 +1917,8 @@ pub struct OrderServiceCharge {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub percentage: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<OrderServiceChargeScope>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub taxable: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub total_money: Option<Money>,
@@ -1704,7 +1936,7 @@ pub struct OrderSource {
     pub name: Option<String>
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct OrderLineItemTax {
     pub applied_money: Option<Money>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1819,7 +2051,7 @@ pub struct PaymentLink {
     pub url: Option<String>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CheckoutOptions {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub accepted_payment_methods: Option<AcceptedPaymentMethods>,
@@ -1834,10 +2066,19 @@ pub struct CheckoutOptions {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub redirect_url: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipping_fee: Option<ShippingFee>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub subscription_plan_id: Option<String>,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
+pub struct ShippingFee {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub charge: Money,
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct AcceptedPaymentMethods {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub afterpay_clearpay: Option<bool>,
@@ -1854,7 +2095,7 @@ pub struct CustomField {
     pub title: String,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct PrePopulatedData {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub buyer_address: Option<Address>,
@@ -1864,6 +2105,65 @@ pub struct PrePopulatedData {
     pub buyer_phone_number: Option<String>,
 }
 
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct CheckoutLocationSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer_notes_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policies: Option<Vec<CheckoutLocationSettingsPolicy>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branding: Option<CheckoutLocationSettingsBranding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tipping: Option<CheckoutLocationSettingsTipping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coupons: Option<CheckoutLocationSettingsCoupons>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct CheckoutLocationSettingsPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct CheckoutLocationSettingsBranding {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header_type: Option<CheckoutLocationSettingsBrandingHeaderType>,
+    /// The color of the buttons, as a hex code such as `#006aff`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub button_color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub button_shape: Option<CheckoutLocationSettingsBrandingButtonShape>,
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct CheckoutLocationSettingsTipping {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percentages: Option<Vec<i32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_tipping_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_percent: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_tips: Option<Vec<Money>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_smart_tip: Option<Money>,
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
+pub struct CheckoutLocationSettingsCoupons {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
 #[derive(Clone, Serialize, Debug, Deserialize)]
 pub struct QuickPay {
     pub location_id: String,
@@ -1871,7 +2171,7 @@ pub struct QuickPay {
     pub price_money: Money,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CatalogQuery {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub exact_query: Option<CatalogQueryExact>,
@@ -2002,20 +2302,20 @@ pub struct StandardUnitDescription {
     pub unit: Option<MeasurementUnit>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct CustomAttributeFilter {
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    bool_filter: Option<bool>,
+    pub bool_filter: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    custom_attribute_definition_id: Option<String>,
+    pub custom_attribute_definition_id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    key: Option<String>,
+    pub key: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    number_filter: Option<Range>,
+    pub number_filter: Option<Range>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    selection_uids_filter: Option<Vec<String>>,
+    pub selection_uids_filter: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    string_filter: Option<String>,
+    pub string_filter: Option<String>,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
@@ -2060,7 +2360,7 @@ pub struct InventoryChange {
     pub inventory_change_type: InventoryChangeType,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize)]
+#[derive(Clone, Serialize, Debug, Deserialize, Default)]
 pub struct InventoryAdjustment {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -2282,7 +2582,7 @@ pub struct BankAccountPaymentDetails {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub statement_description: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub transfer_type: Option<String>,
+    pub transfer_type: Option<BankAccountTransferType>,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
@@ -2290,7 +2590,7 @@ pub struct ACHDetails {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub account_number_suffix: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub account_type: Option<BankAccountOwnershipType>,
+    pub account_type: Option<BankAccountType>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub routing_number: Option<String>,
 }
@@ -2305,7 +2605,8 @@ pub struct BuyNowPayLaterDetails {
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
 pub struct AfterpayDetails {
-    pub email_address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_address: Option<String>,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
@@ -2382,11 +2683,11 @@ pub struct RiskEvaluation {
 #[derive(Clone, Serialize, Debug, Deserialize)]
 pub struct DigitalWalletDetails {
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    brand: Option<DigitalWalletBrand>,
+    pub brand: Option<DigitalWalletBrand>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    cash_app_details: Option<CashPaymentDetails>,
+    pub cash_app_details: Option<CashAppDetails>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    status: Option<DigitalWalletStatus>
+    pub status: Option<DigitalWalletStatus>
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
@@ -2528,8 +2829,74 @@ pub struct TerminalCheckoutQuerySort {
     pub sort_order: Option<SortOrder>,
 }
 
+/// A code to sign a Square Terminal in to the
+/// [Terminal API](https://developer.squareup.com/docs/terminal-api/overview) with.
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
-pub struct TerminalRefund {
+pub struct DeviceCode {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The code to enter on the device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product_type: Option<DeviceCodeProductType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<DeviceCodeStatus>,
+    /// When the code expires unless a device is signed in with it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pair_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_changed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paired_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TerminalAction {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_duration: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TerminalCheckoutStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancel_reason: Option<ActionCancelReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub action_type: Option<TerminalActionActionType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt_options: Option<ReceiptOptions>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReceiptOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_id: Option<String>,
+    /// Skips the buyer's choice of a digital receipt and prints a paper receipt straight away.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub print_only: Option<bool>,
+    /// Marks the receipt as a reprint of one the buyer already received.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_duplicate: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct TerminalRefund {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -2587,7 +2954,7 @@ pub struct SearchOrdersQuery {
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct SearchOrdersFilter {
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub custom_filter: Option<SearchOrdersCustomerFilter>,
+    pub customer_filter: Option<SearchOrdersCustomerFilter>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub date_time_filter: Option<SearchOrdersDateTimeFilter>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -2601,7 +2968,7 @@ pub struct SearchOrdersFilter {
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct SearchOrdersCustomerFilter {
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub customer_ids: Option<String>,
+    pub customer_ids: Option<Vec<String>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -2653,7 +3020,7 @@ pub struct OrderEntry {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
-pub struct SearchQueryAttribute {
+pub struct CustomerQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filter: Option<CustomerFilter>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2681,9 +3048,9 @@ pub struct CustomerFilter {
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct CustomerSort {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub field: Option<String>,
+    pub field: Option<CustomerSortField>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub order: Option<String>,
+    pub order: Option<SortOrder>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -2733,4 +3100,514 @@ pub struct AvailabilityQueryFilter {
     pub location_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub segment_filters: Option<Vec<SegmentFilter>>
-}
\ No newline at end of file
+}
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct Event {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merchant_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<EventData>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EventData {
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub object_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<serde_json::Value>,
+}
+
+// Both the SearchEvents and ListEventTypes endpoints return a field tagged with metadata, so it is
+// necessary to define this return field as an untagged enum
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EventMetadataEnum {
+    EventTypes(Vec<EventTypeMetadata>),
+    Events(Vec<EventMetadata>),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EventMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventTypeMetadata {
+    pub event_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version_introduced: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_status: Option<EventTypeReleaseStatus>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SearchEventsQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<SearchEventsFilter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<SearchEventsSort>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SearchEventsFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_types: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merchant_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<TimeRange>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SearchEventsSort {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<SearchEventsSortField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PaymentRefund {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_fee_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processing_fee: Option<Vec<ProcessingFee>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<RefundStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_member_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlinked: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct Payout {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arrival_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination: Option<PayoutDestination>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_to_end_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<PayoutStatus>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<PayoutType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayoutDestination {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PayoutEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_amount_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gross_amount_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub net_amount_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payout_id: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<PayoutEntryType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_charge_details: Option<PaymentBalanceActivityDetail>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_refund_details: Option<PaymentBalanceActivityDetail>,
+}
+
+/// The payment and refund a [PayoutEntry](PayoutEntry) relates to. Depending on the type of the
+/// entry, only one of them might be set.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct PaymentBalanceActivityDetail {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct GiftCard {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gan: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gan_source: Option<GiftCardGanSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<GiftCardState>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<GiftCardType>,
+}
+
+/// A change to a [GiftCard](GiftCard). Only the details matching the type of the activity are set.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct GiftCardActivity {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<GiftCardActivityType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gift_card_balance_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gift_card_gan: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gift_card_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activate_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adjust_decrement_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adjust_increment_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clear_balance_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deactivate_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_reversal_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redeem_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transfer_balance_from_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transfer_balance_to_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unblock_activity_details: Option<GiftCardActivityDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlinked_activity_refund_activity_details: Option<GiftCardActivityDetails>,
+}
+
+impl GiftCardActivity {
+    /// The details of the activity, whichever its type.
+    pub fn details(&self) -> Option<&GiftCardActivityDetails> {
+        [
+            &self.activate_activity_details,
+            &self.adjust_decrement_activity_details,
+            &self.adjust_increment_activity_details,
+            &self.block_activity_details,
+            &self.clear_balance_activity_details,
+            &self.deactivate_activity_details,
+            &self.import_activity_details,
+            &self.import_reversal_activity_details,
+            &self.load_activity_details,
+            &self.redeem_activity_details,
+            &self.refund_activity_details,
+            &self.transfer_balance_from_activity_details,
+            &self.transfer_balance_to_activity_details,
+            &self.unblock_activity_details,
+            &self.unlinked_activity_refund_activity_details,
+        ].iter().find_map(|details| details.as_ref())
+    }
+}
+
+/// The details of a [GiftCardActivity](GiftCardActivity). Depending on the type of the activity,
+/// only some of them might be set.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct GiftCardActivityDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buyer_payment_instrument_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_item_uid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redeem_activity_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<GiftCardRedeemStatus>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct LoyaltyAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enrolled_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifetime_points: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mapping: Option<LoyaltyAccountMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct LoyaltyAccountMapping {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct LoyaltyProgram {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accrual_rules: Option<Vec<LoyaltyProgramAccrualRule>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reward_tiers: Option<Vec<LoyaltyProgramRewardTier>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<LoyaltyProgramStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminology: Option<LoyaltyProgramTerminology>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct LoyaltyProgramAccrualRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accrual_type: Option<LoyaltyProgramAccrualRuleType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spend_data: Option<LoyaltyProgramAccrualRuleSpendData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visit_data: Option<LoyaltyProgramAccrualRuleVisitData>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct LoyaltyProgramAccrualRuleSpendData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excluded_category_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excluded_item_variation_ids: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct LoyaltyProgramAccrualRuleVisitData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_amount_money: Option<Money>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct LoyaltyProgramRewardTier {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<i32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct LoyaltyProgramTerminology {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub one: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct Invoice {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<InvoiceAttachment>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivery_method: Option<InvoiceDeliveryMethod>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invoice_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_requests: Option<Vec<InvoicePaymentRequest>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_recipient: Option<InvoiceRecipient>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sale_or_service_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<InvoiceStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscription_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct InvoiceAttachment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filesize: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uploaded_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct InvoicePaymentRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub automatic_payment_source: Option<InvoiceAutomaticPaymentSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub computed_amount_money: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_type: Option<InvoiceRequestType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_completed_amount_money: Option<Money>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct InvoiceQuery {
+    pub filter: InvoiceFilter,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<InvoiceSort>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct InvoiceFilter {
+    pub location_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer_ids: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct InvoiceSort {
+    pub field: InvoiceSortField,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct InvoiceRecipient {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+}
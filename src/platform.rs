@@ -0,0 +1,64 @@
+/*!
+The parts of the client that differ between native targets, where requests run on
+[tokio](https://docs.rs/tokio), and `wasm32`, where they run on the event loop and `fetch` of the
+JavaScript host, such as a browser or Cloudflare Workers.
+ */
+
+use std::future::Future;
+use std::task::Poll;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::Instant;
+// the clock of std panics on wasm32-unknown-unknown
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::Instant;
+
+// waits for the given duration without blocking the thread
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+// runs the future to completion, or gives up on it once it took the given duration
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    let mut future = Box::pin(future);
+    let mut timer = Box::pin(sleep(duration));
+
+    std::future::poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+
+        timer.as_mut().poll(cx).map(|_| None)
+    }).await
+}
+
+// whether the request failed to connect, i.e. never reached Square
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn is_connect(err: &reqwest::Error) -> bool {
+    err.is_connect()
+}
+
+// fetch does not tell a failure to connect apart from others, so it is never assumed
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn is_connect(_: &reqwest::Error) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod test_platform {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timeout() {
+        assert_eq!(timeout(Duration::from_secs(1), async { 1 }).await, Some(1));
+        assert_eq!(
+            timeout(Duration::from_millis(10), sleep(Duration::from_secs(1))).await,
+            None,
+        );
+    }
+}
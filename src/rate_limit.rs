@@ -0,0 +1,308 @@
+/*!
+Rate limiting of requests to the [Square API](https://developer.squareup.com).
+
+Square answers too many requests with `429 Too Many Requests`, usually along with a `Retry-After`
+header telling how long to back off. Every [SquareClient](crate::client::SquareClient) honors that
+header: after such a response, its requests wait until the given time has passed before they are
+sent, whether or not the rejected request is retried.
+
+A [RateLimiter](RateLimiter) with a token bucket additionally keeps the client below a given rate
+on its own, so bursts of requests are spread out instead of being rejected. Requests wait for a
+token before they are sent, and retries take a token like any other request.
+
+The limiter is shared by all clones of a client, so they queue behind the same limit. Requests
+of the [Batch](Priority::Batch) priority, such as background syncs, queue behind the
+[Interactive](Priority::Interactive) ones, such as the calls of a checkout, so the latter are
+sent first once the limit allows.
+
+# Example: Send at most 10 requests per second, in bursts of up to 20
+```
+use square_ox::{client::SquareClient, rate_limit::RateLimiter};
+
+let client = SquareClient::new("your_square_access_token")
+    .rate_limiter(RateLimiter::token_bucket(10.0, 20));
+```
+
+# Example: Send the requests of a background sync after those of the checkout
+```
+use square_ox::{client::SquareClient, rate_limit::{Priority, RateLimiter}};
+
+let client = SquareClient::new("your_square_access_token")
+    .rate_limiter(RateLimiter::token_bucket(10.0, 20));
+
+// shares the limit of the client, yielding to its requests
+let sync = client.clone().priority(Priority::Batch);
+```
+ */
+
+use crate::platform::{self, Instant};
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// the longest a Retry-After header can hold the client back, so a bogus value cannot block it
+// for good
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+// how long a batch request waits before it checks again whether interactive requests are still
+// queued ahead of it
+const BATCH_YIELD: Duration = Duration::from_millis(10);
+
+/// The class of a request, which decides the order queued requests are sent in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Priority {
+    /// Requests someone is waiting on, such as the calls of a checkout or a Terminal, which is
+    /// what a [SquareClient](crate::client::SquareClient) sends unless told otherwise.
+    #[default]
+    Interactive,
+    /// Background traffic, such as a sync, only sent while no interactive request is queued.
+    Batch,
+}
+
+/// Holds requests back while Square asked the client to back off and, with a token bucket, while
+/// the client is above its own rate. Clones share their state.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimiter {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    blocked_until: Option<Instant>,
+    bucket: Option<TokenBucket>,
+    // the interactive requests waiting in acquire
+    interactive_queued: u32,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    per_second: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    // takes a token if there is one, otherwise returns how long until there is
+    fn take(&mut self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.per_second))
+        }
+    }
+}
+
+impl RateLimiter {
+    /// A limiter that only honors the `Retry-After` header of Square's responses, which is what a
+    /// [SquareClient](crate::client::SquareClient) uses unless told otherwise.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// A limiter that also keeps the client below the given number of requests per second,
+    /// allowing bursts of up to `burst` requests after a quiet period.
+    /// # Arguments:
+    /// * `requests_per_second` - The sustained rate, which has to be positive.
+    /// * `burst` - The number of requests that can be sent at once, at least 1.
+    pub fn token_bucket(requests_per_second: f64, burst: u32) -> Self {
+        assert!(requests_per_second > 0.0, "the rate of a token bucket has to be positive");
+        let capacity = burst.max(1) as f64;
+
+        RateLimiter {
+            state: Arc::new(Mutex::new(State {
+                blocked_until: None,
+                interactive_queued: 0,
+                bucket: Some(TokenBucket {
+                    capacity,
+                    per_second: requests_per_second,
+                    tokens: capacity,
+                    refilled_at: Instant::now(),
+                }),
+            })),
+        }
+    }
+
+    /// Waits until an [interactive](Priority::Interactive) request may be sent.
+    pub async fn acquire(&self) {
+        self.acquire_with_priority(Priority::Interactive).await
+    }
+
+    /// Waits until a request of the given priority may be sent.
+    pub async fn acquire_with_priority(&self, priority: Priority) {
+        // counted until it is let through or abandoned by its caller
+        let _queued = match priority {
+            Priority::Interactive => Some(Queued::new(self)),
+            Priority::Batch => None,
+        };
+
+        while let Some(wait) = self.try_acquire(Instant::now(), priority) {
+            platform::sleep(wait).await;
+        }
+    }
+
+    /// Holds all requests back for the given duration, e.g. after Square rejected one. A shorter
+    /// hold than one already in place is ignored.
+    pub fn block_for(&self, duration: Duration) {
+        let until = Instant::now() + duration.min(MAX_RETRY_AFTER);
+        let mut state = self.state.lock().unwrap();
+
+        if state.blocked_until.is_none_or(|blocked_until| blocked_until < until) {
+            state.blocked_until = Some(until);
+        }
+    }
+
+    // lets a request through at the given time, or returns how long it has to wait
+    fn try_acquire(&self, now: Instant, priority: Priority) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.blocked_until {
+            Some(blocked_until) if blocked_until > now => return Some(blocked_until - now),
+            Some(_) => state.blocked_until = None,
+            None => {},
+        }
+
+        if priority == Priority::Batch && state.interactive_queued > 0 {
+            return Some(BATCH_YIELD);
+        }
+
+        state.bucket.as_mut().and_then(|bucket| bucket.take(now))
+    }
+}
+
+// an interactive request queued in a limiter, for as long as it is alive
+struct Queued<'a>(&'a RateLimiter);
+
+impl<'a> Queued<'a> {
+    fn new(limiter: &'a RateLimiter) -> Self {
+        limiter.state.lock().unwrap().interactive_queued += 1;
+
+        Queued(limiter)
+    }
+}
+
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().interactive_queued -= 1;
+    }
+}
+
+/// How long the `Retry-After` header of a response asks to wait, given either as a number of
+/// seconds or as an HTTP date. `None` without a valid header.
+/// # Arguments:
+/// * `headers` - The headers of the response.
+/// * `now` - The time the response was received, to compare an HTTP date to.
+pub fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    // an HTTP date is a RFC 2822 date in GMT, and one in the past means no wait at all
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+
+    Some((date.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test_rate_limit {
+    use super::*;
+    use chrono::TimeZone;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_retry_after() {
+        let now = Utc.with_ymd_and_hms(2015, 10, 21, 7, 27, 0).unwrap();
+        let headers = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(RETRY_AFTER, HeaderValue::from_static(value));
+
+            headers
+        };
+
+        assert_eq!(retry_after(&headers("120"), now), Some(Duration::from_secs(120)));
+        assert_eq!(
+            retry_after(&headers("Wed, 21 Oct 2015 07:28:00 GMT"), now),
+            Some(Duration::from_secs(60)),
+        );
+        assert_eq!(retry_after(&headers("Wed, 21 Oct 2015 07:00:00 GMT"), now), Some(Duration::ZERO));
+        assert_eq!(retry_after(&headers("soon"), now), None);
+        assert_eq!(retry_after(&HeaderMap::new(), now), None);
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let start = Instant::now();
+        let sut = RateLimiter::token_bucket(2.0, 2);
+
+        assert_eq!(sut.try_acquire(start, Priority::Interactive), None);
+        assert_eq!(sut.try_acquire(start, Priority::Interactive), None);
+        assert_eq!(sut.try_acquire(start, Priority::Interactive), Some(Duration::from_millis(500)));
+
+        let later = start + Duration::from_millis(500);
+        assert_eq!(sut.try_acquire(later, Priority::Interactive), None);
+        assert!(sut.try_acquire(later, Priority::Interactive).is_some());
+
+        // the bucket does not fill up beyond its capacity
+        let much_later = later + Duration::from_secs(60);
+        assert_eq!(sut.try_acquire(much_later, Priority::Interactive), None);
+        assert_eq!(sut.try_acquire(much_later, Priority::Interactive), None);
+        assert!(sut.try_acquire(much_later, Priority::Interactive).is_some());
+    }
+
+    #[test]
+    fn test_block_for() {
+        let sut = RateLimiter::new();
+        assert_eq!(sut.try_acquire(Instant::now(), Priority::Interactive), None);
+
+        sut.block_for(Duration::from_secs(10));
+        sut.block_for(Duration::from_secs(1));
+
+        let wait = sut.try_acquire(Instant::now(), Priority::Interactive).unwrap();
+        assert!(wait > Duration::from_secs(9) && wait <= Duration::from_secs(10));
+        let later = Instant::now() + Duration::from_secs(11);
+        assert_eq!(sut.try_acquire(later, Priority::Interactive), None);
+
+        // clones share the hold
+        sut.block_for(Duration::from_secs(3600));
+        let wait = sut.clone().try_acquire(Instant::now(), Priority::Interactive).unwrap();
+        assert!(wait <= MAX_RETRY_AFTER);
+    }
+
+    #[test]
+    fn test_priority() {
+        let start = Instant::now();
+        let sut = RateLimiter::token_bucket(1.0, 2);
+
+        {
+            let _queued = Queued::new(&sut);
+
+            // batch requests leave the tokens to the queued interactive request
+            assert_eq!(sut.try_acquire(start, Priority::Batch), Some(BATCH_YIELD));
+            assert_eq!(sut.try_acquire(start, Priority::Interactive), None);
+        }
+
+        assert_eq!(sut.try_acquire(start, Priority::Batch), None);
+        assert!(sut.try_acquire(start, Priority::Batch).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_interactive_request() {
+        let sut = RateLimiter::token_bucket(1.0, 1);
+        sut.acquire().await;
+
+        // the caller gives up on an interactive request queued behind the limit
+        let abandoned = sut.acquire();
+        assert!(tokio::time::timeout(Duration::from_millis(10), abandoned).await.is_err());
+
+        assert_eq!(sut.state.lock().unwrap().interactive_queued, 0);
+    }
+}
@@ -6,14 +6,24 @@ The most of the structs have almost all of their fields set as optional as this
 with the [Square API](https://developer.squareup.com)'s response pattern more manageable.
  */
 
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// The header Square identifies each request with, which is asked for when contacting Square's
+/// support about the request.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+#[derive(Clone, Serialize, Deserialize)]
 #[non_exhaustive]
 /// The [SquareResponse](SquareResponse) response defines the generic response type that encompasses
 /// almost all possible [Square API](https://developer.squareup.com) responses. All fields are
 /// optional to allow for handling of possible errors returned by the
 /// [Square API](https://developer.squareup.com).
+///
+/// The access, refresh and id tokens of an OAuth response are left out of its `Debug` output, so
+/// they do not end up in logs.
 pub struct SquareResponse {
     #[serde(flatten)]
     pub response: Option<crate::objects::Response>,
@@ -30,6 +40,8 @@ pub struct SquareResponse {
     #[serde(default)]
     pub id_mapping: Option<Vec<(String, String)>>,
     #[serde(default)]
+    pub id_mappings: Option<Vec<crate::objects::CatalogIdMapping>>,
+    #[serde(default)]
     pub id: Option<String>,
     #[serde(default)]
     pub cancelled_order_id: Option<String>,
@@ -39,6 +51,139 @@ pub struct SquareResponse {
     pub deleted_at: Option<String>,
     #[serde(default)]
     pub latest_time: Option<String>,
+    #[serde(default)]
+    pub access_token: Option<String>,
+    #[serde(default)]
+    pub token_type: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    #[serde(default)]
+    pub merchant_id: Option<String>,
+    #[serde(default)]
+    pub subscription_id: Option<String>,
+    #[serde(default)]
+    pub plan_id: Option<String>,
+    #[serde(default)]
+    pub id_token: Option<String>,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    #[serde(default)]
+    pub short_lived: Option<bool>,
+    #[serde(default)]
+    pub refresh_token_expires_at: Option<String>,
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
+    #[serde(default)]
+    pub client_id: Option<String>,
+    #[serde(default)]
+    pub success: Option<bool>,
+    /// The status and headers of the response, `None` unless it was received by a
+    /// [SquareClient](crate::client::SquareClient).
+    #[serde(skip)]
+    pub meta: Option<ResponseMeta>,
+}
+
+impl SquareResponse {
+    /// Consumes the [SquareResponse](SquareResponse) and returns all of the
+    /// [Response](crate::objects::Response)s it holds, in the order they were returned by the
+    /// [Square API](https://developer.squareup.com).
+    pub fn into_responses(self) -> Vec<crate::objects::Response> {
+        vec![self.response, self.opt_response01, self.opt_response02, self.opt_response03]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// The id Square assigned to the request, see [ResponseMeta::request_id](ResponseMeta::request_id).
+    pub fn request_id(&self) -> Option<&str> {
+        self.meta.as_ref().and_then(ResponseMeta::request_id)
+    }
+}
+
+impl fmt::Debug for SquareResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SquareResponse")
+            .field("response", &self.response)
+            .field("opt_response01", &self.opt_response01)
+            .field("opt_response02", &self.opt_response02)
+            .field("opt_response03", &self.opt_response03)
+            .field("errors", &self.errors)
+            .field("cursor", &self.cursor)
+            .field("id_mapping", &self.id_mapping)
+            .field("id_mappings", &self.id_mappings)
+            .field("id", &self.id)
+            .field("cancelled_order_id", &self.cancelled_order_id)
+            .field("deleted_object_ids", &self.deleted_object_ids)
+            .field("deleted_at", &self.deleted_at)
+            .field("latest_time", &self.latest_time)
+            .field("access_token", &redacted(&self.access_token))
+            .field("token_type", &self.token_type)
+            .field("expires_at", &self.expires_at)
+            .field("merchant_id", &self.merchant_id)
+            .field("subscription_id", &self.subscription_id)
+            .field("plan_id", &self.plan_id)
+            .field("id_token", &redacted(&self.id_token))
+            .field("refresh_token", &redacted(&self.refresh_token))
+            .field("short_lived", &self.short_lived)
+            .field("refresh_token_expires_at", &self.refresh_token_expires_at)
+            .field("scopes", &self.scopes)
+            .field("client_id", &self.client_id)
+            .field("success", &self.success)
+            .field("meta", &self.meta)
+            .finish()
+    }
+}
+
+// shows whether a token was returned without showing the token itself
+fn redacted(token: &Option<String>) -> Option<&'static str> {
+    token.as_ref().map(|_| "[redacted]")
+}
+
+/// What the [Square API](https://developer.squareup.com) sent along with the body of a response,
+/// kept on the [SquareResponse](SquareResponse) and on the
+/// [SquareError](crate::errors::SquareError) read from it.
+#[derive(Clone, Debug)]
+pub struct ResponseMeta {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+}
+
+impl ResponseMeta {
+    pub fn new(status: StatusCode, headers: HeaderMap) -> Self {
+        ResponseMeta { status, headers }
+    }
+
+    /// The id Square assigned to the request, sent as the
+    /// [REQUEST_ID_HEADER](REQUEST_ID_HEADER), which Square's support asks for.
+    pub fn request_id(&self) -> Option<&str> {
+        self.header(REQUEST_ID_HEADER)
+    }
+
+    /// The value of the given header, `None` if it was not sent or is not readable as text.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+/// The [BinaryResponse](BinaryResponse) holds a document, such as a PDF or a hosted page, fetched
+/// as is instead of being deserialized from JSON.
+#[derive(Clone, Debug)]
+pub struct BinaryResponse {
+    /// The URL the document was fetched from, after following any redirects.
+    pub url: String,
+    /// The media type of the document as reported in its `Content-Type` header.
+    pub content_type: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+impl BinaryResponse {
+    /// Whether the document is of the given media type, ignoring any parameters such as the
+    /// charset.
+    pub fn is_content_type(&self, media_type: &str) -> bool {
+        self.content_type.as_deref()
+            .and_then(|content_type| content_type.split(';').next())
+            .is_some_and(|content_type| content_type.trim().eq_ignore_ascii_case(media_type))
+    }
 }
 
 
@@ -53,4 +198,4 @@ pub struct ResponseError {
     pub detail: Option<String>,
     #[serde(default)]
     pub field: Option<String>,
-}
\ No newline at end of file
+}
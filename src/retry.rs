@@ -0,0 +1,215 @@
+/*!
+Automatic retries of requests to the [Square API](https://developer.squareup.com).
+
+Retrying a request whose outcome is unknown is only safe if sending it twice has the same effect
+as sending it once. Reads and Square's `PUT` and `DELETE` endpoints are idempotent, as are the
+`POST` endpoints that only search or cancel. Every other `POST`, such as creating a payment, is
+only retried if its body carries an `idempotency_key`, so Square recognizes the repeated request
+instead of charging the buyer twice. [retry_safety](retry_safety) tells which endpoints are which.
+
+A request that failed to connect never reached Square and is retried regardless.
+
+# Example: Retry failed requests up to three times
+```
+use square_ox::{client::SquareClient, retry::RetryPolicy};
+use std::time::Duration;
+
+let client = SquareClient::new("your_square_access_token")
+    .retry_policy(RetryPolicy::new(3).initial_backoff(Duration::from_millis(200)));
+```
+ */
+
+use crate::api::{SquareAPI, Verb};
+
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// Whether a request to an endpoint can be retried as is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RetrySafety {
+    /// Sending the request more than once has the same effect as sending it once.
+    Safe,
+    /// The request may only be retried if its body carries an `idempotency_key`.
+    RequiresIdempotencyKey,
+}
+
+/// Whether a request with the given verb to the given endpoint can be retried as is.
+/// # Arguments:
+/// * `verb` - The HTTP verb of the request.
+/// * `endpoint` - The endpoint the request is sent to.
+pub fn retry_safety(verb: &Verb, endpoint: &SquareAPI) -> RetrySafety {
+    match verb {
+        Verb::GET | Verb::PUT | Verb::DELETE => RetrySafety::Safe,
+        Verb::PATCH => RetrySafety::RequiresIdempotencyKey,
+        Verb::POST if idempotent_post(endpoint) => RetrySafety::Safe,
+        Verb::POST => RetrySafety::RequiresIdempotencyKey,
+    }
+}
+
+// the POST endpoints that only read data or move an object into a final state
+fn idempotent_post(endpoint: &SquareAPI) -> bool {
+    const IDEMPOTENT_SUFFIXES: [&str; 9] = [
+        "/search", "/search-catalog-items", "/batch-retrieve", "/calculate", "/token/status",
+        "/cancel", "/complete", "/disable", "/revoke",
+    ];
+
+    let path = match endpoint {
+        // searching events is a POST to the collection itself
+        SquareAPI::Events(path) if path.is_empty() => return true,
+        SquareAPI::Payments(path) | SquareAPI::Bookings(path) | SquareAPI::Locations(path) |
+        SquareAPI::Catalog(path) | SquareAPI::Customers(path) | SquareAPI::Cards(path) |
+        SquareAPI::Checkout(path) | SquareAPI::Inventory(path) | SquareAPI::Sites(path) |
+        SquareAPI::Terminals(path) | SquareAPI::Orders(path) | SquareAPI::Events(path) |
+        SquareAPI::OAuth(path) | SquareAPI::Payouts(path) | SquareAPI::Refunds(path) |
+        SquareAPI::GiftCards(path) | SquareAPI::Invoices(path) | SquareAPI::Devices(path) => path,
+    };
+
+    IDEMPOTENT_SUFFIXES.iter().any(|suffix| path.ends_with(suffix))
+}
+
+// whether a request can be retried as is, given its endpoint and body
+pub(crate) fn may_retry(verb: &Verb, endpoint: &SquareAPI, body: Option<&serde_json::Value>)
+                        -> bool {
+    match retry_safety(verb, endpoint) {
+        RetrySafety::Safe => true,
+        RetrySafety::RequiresIdempotencyKey => body
+            .and_then(|body| body.get("idempotency_key"))
+            .and_then(|key| key.as_str())
+            .is_some_and(|key| !key.is_empty()),
+    }
+}
+
+// whether a response with the given status may succeed when the request is sent again
+pub(crate) fn retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// How often and how quickly failed requests are retried. A request is retried when it failed to
+/// connect, timed out, or Square responded with `429 Too Many Requests` or a server error, and
+/// then only if [retry_safety](retry_safety) allows it.
+///
+/// The wait before a retry doubles with every attempt, starting at the initial backoff and capped
+/// at the maximum backoff.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub(crate) max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// A policy retrying a request up to the given number of times, waiting 500ms before the
+    /// first retry and at most 8s before any retry.
+    pub fn new(max_retries: u32) -> Self {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+
+    /// A policy that never retries, which is what a [SquareClient](crate::client::SquareClient)
+    /// uses unless told otherwise.
+    pub fn none() -> Self {
+        RetryPolicy::new(0)
+    }
+
+    /// Sets the wait before the first retry.
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+
+        self
+    }
+
+    /// Sets the longest wait before any retry.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+
+        self
+    }
+
+    // the wait before the retry following the given, zero based, attempt
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .checked_mul(2u32.saturating_pow(attempt))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(3)
+    }
+}
+
+#[cfg(test)]
+mod test_retry {
+    use super::*;
+
+    #[test]
+    fn test_retry_safety() {
+        assert_eq!(
+            retry_safety(&Verb::GET, &SquareAPI::Payments("/some_payment_id".to_string())),
+            RetrySafety::Safe,
+        );
+        assert_eq!(
+            retry_safety(&Verb::PUT, &SquareAPI::Orders("/some_order_id".to_string())),
+            RetrySafety::Safe,
+        );
+        assert_eq!(
+            retry_safety(&Verb::POST, &SquareAPI::Orders("/search".to_string())),
+            RetrySafety::Safe,
+        );
+        assert_eq!(
+            retry_safety(&Verb::POST, &SquareAPI::Terminals("/checkouts/some_id/cancel".to_string())),
+            RetrySafety::Safe,
+        );
+        assert_eq!(
+            retry_safety(&Verb::POST, &SquareAPI::Events("".to_string())),
+            RetrySafety::Safe,
+        );
+        assert_eq!(
+            retry_safety(&Verb::POST, &SquareAPI::Payments("".to_string())),
+            RetrySafety::RequiresIdempotencyKey,
+        );
+        assert_eq!(
+            retry_safety(&Verb::POST, &SquareAPI::Orders("/some_order_id/pay".to_string())),
+            RetrySafety::RequiresIdempotencyKey,
+        );
+    }
+
+    #[test]
+    fn test_may_retry() {
+        let create_payment = SquareAPI::Payments("".to_string());
+
+        assert!(may_retry(
+            &Verb::POST,
+            &create_payment,
+            Some(&serde_json::json!({ "idempotency_key": "some_key", "source_id": "cnon:card-nonce-ok" })),
+        ));
+        assert!(!may_retry(
+            &Verb::POST,
+            &create_payment,
+            Some(&serde_json::json!({ "source_id": "cnon:card-nonce-ok" })),
+        ));
+        assert!(!may_retry(&Verb::POST, &create_payment, Some(&serde_json::json!({ "idempotency_key": "" }))));
+        assert!(!may_retry(&Verb::POST, &create_payment, None));
+        assert!(may_retry(&Verb::GET, &create_payment, None));
+    }
+
+    #[test]
+    fn test_backoff() {
+        let sut = RetryPolicy::new(10)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1));
+
+        assert_eq!(sut.backoff(0), Duration::from_millis(100));
+        assert_eq!(sut.backoff(2), Duration::from_millis(400));
+        assert_eq!(sut.backoff(4), Duration::from_secs(1));
+        assert_eq!(sut.backoff(40), Duration::from_secs(1));
+        assert!(retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!retryable_status(StatusCode::BAD_REQUEST));
+    }
+}
@@ -0,0 +1,322 @@
+/*!
+Access tokens authenticating the requests of a [SquareClient](crate::client::SquareClient).
+
+A client asks its [TokenProvider](TokenProvider) for the access token before every request, so
+the token can change over the lifetime of the client, e.g. when it expires, without the client
+being recreated. The crate ships with providers for
+* a fixed token, [StaticToken](StaticToken), which
+  [SquareClient::new](crate::client::SquareClient::new) uses;
+* a token read from an environment variable, [EnvToken](EnvToken);
+* a token obtained, and refreshed before it expires, through a callback,
+  [RefreshingToken](RefreshingToken), e.g. through the OAuth refresh token of a seller.
+
+When Square rejects a token as unauthorized, the provider is told to
+[invalidate](TokenProvider::invalidate) it, so the next request does not reuse it.
+
+# Example: Refresh the OAuth access token of a seller
+```no_run
+use square_ox::{
+    api::oauth::ObtainTokenBody,
+    builder::Builder,
+    client::SquareClient,
+    token::{AccessToken, RefreshingToken},
+};
+
+let provider = RefreshingToken::new(|| async {
+    let body = Builder::from(ObtainTokenBody::default())
+        .client_id("some_client_id".to_string())
+        .client_secret("some_client_secret".to_string())
+        .refresh_token("some_refresh_token".to_string())
+        .build()
+        .await
+        .unwrap();
+
+    let res = SquareClient::new("").oauth().obtain_token(body).await?;
+
+    AccessToken::from_response(&res)
+});
+
+let client = SquareClient::with_token_provider(provider).production();
+```
+ */
+
+use crate::errors::SquareError;
+use crate::response::SquareResponse;
+
+use chrono::{DateTime, Duration, Utc};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+/// The future returned by a [TokenProvider](TokenProvider), which has to be `Send` on all targets
+/// but `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type TokenFuture<'a> = Pin<Box<dyn Future<Output = Result<String, SquareError>> + Send + 'a>>;
+
+/// The future returned by a [TokenProvider](TokenProvider), which has to be `Send` on all targets
+/// but `wasm32`.
+#[cfg(target_arch = "wasm32")]
+pub type TokenFuture<'a> = Pin<Box<dyn Future<Output = Result<String, SquareError>> + 'a>>;
+
+/// Hands out the access token the requests of a client are authenticated with.
+pub trait TokenProvider: Send + Sync {
+    /// The access token to authenticate the next request with.
+    fn access_token(&self) -> TokenFuture<'_>;
+
+    /// Called when Square rejected the given token as unauthorized. Does nothing unless
+    /// implemented.
+    fn invalidate(&self, access_token: &str) {
+        let _ = access_token;
+    }
+}
+
+/// A fixed access token.
+#[derive(Clone, Debug)]
+pub struct StaticToken(String);
+
+impl StaticToken {
+    pub fn new(access_token: &str) -> Self {
+        StaticToken(access_token.to_string())
+    }
+}
+
+impl TokenProvider for StaticToken {
+    fn access_token(&self) -> TokenFuture<'_> {
+        Box::pin(async move { Ok(self.0.clone()) })
+    }
+}
+
+/// An access token read from an environment variable before every request, so it can be rotated
+/// by changing the variable.
+#[derive(Clone, Debug)]
+pub struct EnvToken {
+    variable: String,
+}
+
+impl EnvToken {
+    /// # Arguments:
+    /// * `variable` - The name of the environment variable holding the token, e.g.
+    ///   `SQUARE_ACCESS_TOKEN`.
+    pub fn new(variable: &str) -> Self {
+        EnvToken {
+            variable: variable.to_string(),
+        }
+    }
+}
+
+impl TokenProvider for EnvToken {
+    fn access_token(&self) -> TokenFuture<'_> {
+        let access_token = std::env::var(&self.variable).map_err(|_| SquareError::unauthorized(
+            format!("the environment variable {} does not hold an access token", self.variable),
+        ));
+
+        Box::pin(async move { access_token })
+    }
+}
+
+/// An access token along with when it expires, as returned by the callback of a
+/// [RefreshingToken](RefreshingToken).
+#[derive(Clone, Debug)]
+pub struct AccessToken {
+    pub access_token: String,
+    /// When the token expires, `None` if it does not.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AccessToken {
+    /// The access token obtained through [obtain_token](crate::api::oauth::OAuth::obtain_token).
+    pub fn from_response(response: &SquareResponse) -> Result<Self, SquareError> {
+        let access_token = response.access_token.clone().ok_or_else(|| SquareError::unauthorized(
+            "the response does not hold an access token".to_string(),
+        ))?;
+        let expires_at = response.expires_at.as_deref()
+            .and_then(|expires_at| DateTime::parse_from_rfc3339(expires_at).ok())
+            .map(|expires_at| expires_at.with_timezone(&Utc));
+
+        Ok(AccessToken { access_token, expires_at })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type RefreshFuture = Pin<Box<dyn Future<Output = Result<AccessToken, SquareError>> + Send>>;
+#[cfg(target_arch = "wasm32")]
+type RefreshFuture = Pin<Box<dyn Future<Output = Result<AccessToken, SquareError>>>>;
+
+type Refresh = Box<dyn Fn() -> RefreshFuture + Send + Sync>;
+
+/// An access token obtained through a callback, which is called again once the token is about to
+/// expire or was rejected by Square.
+///
+/// Requests finding the token expired at the same time may each call the callback.
+pub struct RefreshingToken {
+    refresh: Refresh,
+    margin: Duration,
+    current: Mutex<Option<AccessToken>>,
+}
+
+impl RefreshingToken {
+    /// A token refreshed 5 minutes before it expires.
+    /// # Arguments:
+    /// * `refresh` - Obtains a new token, e.g. through
+    ///   [obtain_token](crate::api::oauth::OAuth::obtain_token).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new<F, Fut>(refresh: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<AccessToken, SquareError>> + Send + 'static,
+    {
+        Self::from_refresh(Box::new(move || Box::pin(refresh())))
+    }
+
+    /// A token refreshed 5 minutes before it expires.
+    /// # Arguments:
+    /// * `refresh` - Obtains a new token, e.g. through
+    ///   [obtain_token](crate::api::oauth::OAuth::obtain_token).
+    #[cfg(target_arch = "wasm32")]
+    pub fn new<F, Fut>(refresh: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<AccessToken, SquareError>> + 'static,
+    {
+        Self::from_refresh(Box::new(move || Box::pin(refresh())))
+    }
+
+    fn from_refresh(refresh: Refresh) -> Self {
+        RefreshingToken {
+            refresh,
+            margin: Duration::minutes(5),
+            current: Mutex::new(None),
+        }
+    }
+
+    /// Refresh the token the given duration before it expires instead.
+    pub fn margin(mut self, margin: std::time::Duration) -> Self {
+        self.margin = Duration::from_std(margin).expect("the margin to be a sensible duration");
+
+        self
+    }
+
+    // the current token, unless it is about to expire at the given time
+    fn current(&self, now: DateTime<Utc>) -> Option<String> {
+        self.current.lock().unwrap()
+            .as_ref()
+            .filter(|token| token.expires_at.is_none_or(|expires_at| now + self.margin < expires_at))
+            .map(|token| token.access_token.clone())
+    }
+}
+
+impl TokenProvider for RefreshingToken {
+    fn access_token(&self) -> TokenFuture<'_> {
+        Box::pin(async move {
+            if let Some(access_token) = self.current(Utc::now()) {
+                return Ok(access_token);
+            }
+
+            let token = (self.refresh)().await?;
+            let access_token = token.access_token.clone();
+            *self.current.lock().unwrap() = Some(token);
+
+            Ok(access_token)
+        })
+    }
+
+    fn invalidate(&self, access_token: &str) {
+        let mut current = self.current.lock().unwrap();
+
+        // a token refreshed in the meantime is kept
+        if current.as_ref().is_some_and(|token| token.access_token == access_token) {
+            *current = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use crate::client::SquareClient;
+    use crate::interceptor::{InterceptedRequest, InterceptedResponse, RequestInterceptor};
+    use reqwest::StatusCode;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_env_token() {
+        let sut = EnvToken::new("SQUARE_OX_TEST_ACCESS_TOKEN");
+        assert!(sut.access_token().await.is_err());
+
+        std::env::set_var("SQUARE_OX_TEST_ACCESS_TOKEN", "some_token");
+        assert_eq!(sut.access_token().await.unwrap(), "some_token");
+        std::env::remove_var("SQUARE_OX_TEST_ACCESS_TOKEN");
+    }
+
+    #[tokio::test]
+    async fn test_refreshing_token() {
+        let refreshes = Arc::new(AtomicU32::new(0));
+        let counter = refreshes.clone();
+        let sut = RefreshingToken::new(move || {
+            let refresh = counter.fetch_add(1, Ordering::SeqCst);
+
+            async move {
+                Ok(AccessToken {
+                    access_token: format!("token_{}", refresh),
+                    // the first token is about to expire, the second one lasts
+                    expires_at: Some(Utc::now() + Duration::minutes(if refresh == 0 { 1 } else { 60 })),
+                })
+            }
+        });
+
+        assert_eq!(sut.access_token().await.unwrap(), "token_0");
+        assert_eq!(sut.access_token().await.unwrap(), "token_1");
+        assert_eq!(sut.access_token().await.unwrap(), "token_1");
+        assert_eq!(refreshes.load(Ordering::SeqCst), 2);
+
+        sut.invalidate("token_0");
+        assert_eq!(sut.access_token().await.unwrap(), "token_1");
+
+        sut.invalidate("token_1");
+        assert_eq!(sut.access_token().await.unwrap(), "token_2");
+    }
+
+    // rejects every request as unauthorized without sending it
+    struct Unauthorized;
+
+    impl RequestInterceptor for Unauthorized {
+        fn before_request(&self, _: &mut InterceptedRequest) -> Option<InterceptedResponse> {
+            Some(InterceptedResponse::new(
+                StatusCode::UNAUTHORIZED,
+                r#"{ "errors": [{ "category": "AUTHENTICATION_ERROR", "code": "UNAUTHORIZED" }] }"#
+                    .to_string(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_unauthorized() {
+        let refreshes = Arc::new(AtomicU32::new(0));
+        let counter = refreshes.clone();
+        let sut = SquareClient::with_token_provider(RefreshingToken::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            async { Ok(AccessToken { access_token: "some_token".to_string(), expires_at: None }) }
+        })).interceptor(Unauthorized);
+
+        assert!(sut.locations().list().await.is_err());
+        assert!(sut.locations().list().await.is_err());
+        assert_eq!(refreshes.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_access_token_from_response() {
+        let res: SquareResponse = serde_json::from_value(serde_json::json!({
+            "access_token": "some_token",
+            "expires_at": "2022-11-17T18:43:49Z",
+        })).unwrap();
+
+        let sut = AccessToken::from_response(&res).unwrap();
+        assert_eq!(sut.access_token, "some_token");
+        assert_eq!(sut.expires_at.unwrap().to_rfc3339(), "2022-11-17T18:43:49+00:00");
+
+        let empty: SquareResponse = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(AccessToken::from_response(&empty).is_err());
+    }
+}
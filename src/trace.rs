@@ -0,0 +1,157 @@
+/*!
+Instrumentation of the requests a [SquareClient](crate::client::SquareClient) sends, available
+with the `tracing` feature.
+
+Every request runs inside a `square_request` span of the [tracing](https://docs.rs/tracing) crate,
+carrying the verb and endpoint of the request and, once a response arrived, its status, the
+request id Square assigned to it, the number of attempts it took and its latency in milliseconds.
+Every attempt additionally emits a `DEBUG` event with its URL, headers and body.
+
+The value of the `Authorization` header and any card data in the body, such as card nonces,
+verification tokens or card details, are always redacted from what is emitted, whatever level the
+subscriber records.
+ */
+
+use crate::api::Verb;
+use crate::interceptor::{InterceptedRequest, InterceptedResponse};
+use crate::response::REQUEST_ID_HEADER;
+use crate::transport::TransportError;
+
+use reqwest::header::{HeaderMap, AUTHORIZATION};
+use serde_json::Value;
+use std::time::Duration;
+use tracing::field;
+
+const REDACTED: &str = "[REDACTED]";
+
+// the keys of a body holding card data or other secrets, redacted wherever they are nested
+const SENSITIVE_KEYS: [&str; 9] = [
+    "source_id",
+    "card_nonce",
+    "verification_token",
+    "card",
+    "card_details",
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "password",
+];
+
+// the span a request runs in, with the fields of its response left to be recorded
+pub(crate) fn request_span(verb: Verb, endpoint: &str) -> tracing::Span {
+    tracing::info_span!(
+        "square_request",
+        verb = ?verb,
+        endpoint = endpoint,
+        status = field::Empty,
+        request_id = field::Empty,
+        attempts = field::Empty,
+        latency_ms = field::Empty,
+    )
+}
+
+// emits the attempt about to be sent, redacted
+pub(crate) fn attempt(request: &InterceptedRequest) {
+    let body = request.body.as_ref().map(redact_body).unwrap_or(Value::Null);
+
+    tracing::debug!(
+        attempt = request.attempt,
+        url = %request.url,
+        headers = ?redact_headers(&request.headers),
+        body = %body,
+        "sending request",
+    );
+}
+
+// emits the failure of the transport to send an attempt, which carries no headers or body
+pub(crate) fn transport_error(err: &TransportError) {
+    tracing::debug!(kind = ?err.kind(), error = %err, "transport failed");
+}
+
+// records the final response of the request on the current span
+pub(crate) fn response(response: &InterceptedResponse, attempts: u32, latency: Duration) {
+    let span = tracing::Span::current();
+
+    span.record("status", response.status.as_u16());
+    if let Some(request_id) = response.headers.get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok()) {
+        span.record("request_id", request_id);
+    }
+    span.record("attempts", attempts);
+    span.record("latency_ms", latency.as_millis() as u64);
+}
+
+// the headers with the value of the Authorization header replaced
+fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers.iter()
+        .map(|(name, value)| {
+            let value = if name == AUTHORIZATION {
+                REDACTED.to_string()
+            } else {
+                value.to_str().unwrap_or(REDACTED).to_string()
+            };
+
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+// the body with the values of all sensitive keys replaced
+fn redact_body(body: &Value) -> Value {
+    match body {
+        Value::Object(fields) => Value::Object(fields.iter()
+            .map(|(key, value)| {
+                let value = if SENSITIVE_KEYS.contains(&key.as_str()) {
+                    Value::String(REDACTED.to_string())
+                } else {
+                    redact_body(value)
+                };
+
+                (key.clone(), value)
+            })
+            .collect()),
+        Value::Array(values) => Value::Array(values.iter().map(redact_body).collect()),
+        value => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test_trace {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer some_token"));
+        headers.insert("Square-Version", HeaderValue::from_static("2022-10-19"));
+
+        let redacted = redact_headers(&headers);
+
+        assert!(redacted.contains(&("authorization".to_string(), REDACTED.to_string())));
+        assert!(redacted.contains(&("square-version".to_string(), "2022-10-19".to_string())));
+        assert!(!format!("{:?}", redacted).contains("some_token"));
+    }
+
+    #[test]
+    fn test_redact_body() {
+        let body = json!({
+            "idempotency_key": "some_key",
+            "source_id": "cnon:card-nonce-ok",
+            "verification_token": "verf:some_token",
+            "amount_money": { "amount": 100, "currency": "USD" },
+            "card": { "cardholder_name": "John Doe", "exp_month": 12 },
+            "payments": [{ "card_details": { "card": { "last_4": "1111" } } }],
+        });
+
+        assert_eq!(redact_body(&body), json!({
+            "idempotency_key": "some_key",
+            "source_id": REDACTED,
+            "verification_token": REDACTED,
+            "amount_money": { "amount": 100, "currency": "USD" },
+            "card": REDACTED,
+            "payments": [{ "card_details": REDACTED }],
+        }));
+    }
+}
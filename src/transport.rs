@@ -0,0 +1,388 @@
+/*!
+The HTTP transport a [SquareClient](crate::client::SquareClient) sends its requests to the
+[Square API](https://developer.squareup.com) through.
+
+A client sends its requests through a [ReqwestTransport](ReqwestTransport) over its own
+connection pool unless it is given another [HttpTransport](HttpTransport), e.g. one built on hyper
+or isahc, or one answering from memory so code using the client can be tested without network.
+
+A transport is handed every attempt of every request as it is to be sent, with the query already
+part of the URL and the body already encoded, and returns the response as it arrived. Retries,
+rate limiting, interceptors and the errors returned by Square are all handled by the client.
+
+A [BudgetTransport](budget::BudgetTransport) wraps another transport to assert how often, and how
+quickly, every endpoint is called in the tests of code using the client.
+
+The connection settings of the client, such as
+[connect_timeout](crate::client::SquareClient::connect_timeout), only apply to the
+[ReqwestTransport](ReqwestTransport) it uses by default, while the
+[timeout](HttpRequest::timeout) and [read timeout](HttpRequest::read_timeout) of the client are
+handed to every transport along with the request.
+
+# Example: Answer every request from memory
+```
+use square_ox::{
+    client::SquareClient,
+    transport::{HttpRequest, HttpResponse, HttpTransport, TransportFuture},
+};
+use reqwest::StatusCode;
+
+struct NoLocations;
+
+impl HttpTransport for NoLocations {
+    fn send(&self, _: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async {
+            Ok(HttpResponse::new(StatusCode::OK, r#"{ "locations": [] }"#.to_string()))
+        })
+    }
+}
+
+let client = SquareClient::new("some_token").transport(NoLocations);
+```
+ */
+
+pub mod budget;
+
+use crate::api::Verb;
+use crate::platform;
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// An attempt of a request, as it is handed to an [HttpTransport](HttpTransport).
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    pub verb: Verb,
+    /// The URL the request is sent to, including its query.
+    pub url: String,
+    /// The headers, including the `Authorization` and `Content-Type` headers.
+    pub headers: HeaderMap,
+    /// The encoded body, a JSON document or a multipart form.
+    pub body: Option<Vec<u8>>,
+    /// How long the attempt may take as a whole, from connecting until the response body has been
+    /// read.
+    pub timeout: Option<Duration>,
+    /// How long reading the response body may take once its headers have arrived.
+    pub read_timeout: Option<Duration>,
+}
+
+/// A response, as it was received by an [HttpTransport](HttpTransport) or made up by an
+/// [interceptor](crate::interceptor::RequestInterceptor).
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// A response with the given status and body and no headers.
+    pub fn new(status: StatusCode, body: String) -> Self {
+        HttpResponse {
+            status,
+            headers: HeaderMap::new(),
+            body,
+        }
+    }
+}
+
+/// What kept an [HttpTransport](HttpTransport) from receiving a response, which decides whether
+/// the request is retried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransportErrorKind {
+    /// The request failed to connect, so it never reached Square and can always be retried.
+    Connect,
+    /// The request, or reading its response, took longer than it was allowed to.
+    Timeout,
+    Other,
+}
+
+/// The failure of an [HttpTransport](HttpTransport) to send a request or to receive its response.
+#[derive(Debug)]
+pub struct TransportError {
+    kind: TransportErrorKind,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl TransportError {
+    pub fn new<E: Into<Box<dyn Error + Send + Sync>>>(kind: TransportErrorKind, error: E) -> Self {
+        TransportError {
+            kind,
+            source: error.into(),
+        }
+    }
+
+    pub fn kind(&self) -> TransportErrorKind {
+        self.kind
+    }
+
+    pub fn is_connect(&self) -> bool {
+        self.kind == TransportErrorKind::Connect
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        self.kind == TransportErrorKind::Timeout
+    }
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl Error for TransportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(err: reqwest::Error) -> Self {
+        let kind = if err.is_timeout() {
+            TransportErrorKind::Timeout
+        } else if platform::is_connect(&err) {
+            TransportErrorKind::Connect
+        } else {
+            TransportErrorKind::Other
+        };
+
+        TransportError::new(kind, err)
+    }
+}
+
+/// The future returned by an [HttpTransport](HttpTransport), which has to be `Send` on all
+/// targets but `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<HttpResponse, TransportError>> + Send + 'a>>;
+
+/// The future returned by an [HttpTransport](HttpTransport), which has to be `Send` on all
+/// targets but `wasm32`.
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<HttpResponse, TransportError>> + 'a>>;
+
+/// Sends the requests of the client it is given to and receives their responses.
+pub trait HttpTransport: Send + Sync {
+    /// Sends a single attempt of a request and receives its response, whatever its status.
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
+
+/// The [HttpTransport](HttpTransport) a client uses unless told otherwise, sending the requests
+/// through a [reqwest](https://docs.rs/reqwest) client.
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// A transport sending the requests through the given client, and with it its connection
+    /// pool and settings.
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let method = match request.verb {
+                Verb::GET => Method::GET,
+                Verb::POST => Method::POST,
+                Verb::PUT => Method::PUT,
+                Verb::PATCH => Method::PATCH,
+                Verb::DELETE => Method::DELETE,
+            };
+
+            let mut builder = self.client.request(method, &request.url).headers(request.headers);
+
+            // fetch cannot be given a timeout on wasm32
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+
+            let response = builder.send().await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = match request.read_timeout {
+                Some(timeout) => platform::timeout(timeout, response.text())
+                    .await
+                    .ok_or_else(|| TransportError::new(
+                        TransportErrorKind::Timeout,
+                        format!("reading the response took longer than {:?}", timeout),
+                    ))??,
+                None => response.text().await?,
+            };
+
+            Ok(HttpResponse { status, headers, body })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_transport {
+    use super::*;
+    use crate::api::SquareAPI;
+    use crate::api::catalog::images::ImageFile;
+    use crate::client::SquareClient;
+    use crate::retry::RetryPolicy;
+    use std::sync::{Arc, Mutex};
+
+    // fails to connect as often as told, then answers with the canned body
+    struct Fake {
+        requests: Arc<Mutex<Vec<HttpRequest>>>,
+        failures: Mutex<u32>,
+        body: &'static str,
+    }
+
+    impl HttpTransport for Fake {
+        fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+            self.requests.lock().unwrap().push(request);
+
+            let mut failures = self.failures.lock().unwrap();
+            let sent = if *failures > 0 {
+                *failures -= 1;
+                Err(TransportError::new(TransportErrorKind::Connect, "connection refused"))
+            } else {
+                Ok(HttpResponse::new(StatusCode::OK, self.body.to_string()))
+            };
+
+            Box::pin(async move { sent })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transport() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let sut = SquareClient::new("some_token")
+            .base_url("http://square.test")
+            .timeout(Duration::from_secs(5))
+            .retry_policy(RetryPolicy::new(1).initial_backoff(Duration::from_millis(1)))
+            .transport(Fake {
+                requests: requests.clone(),
+                failures: Mutex::new(1),
+                body: r#"{ "customers": [] }"#,
+            });
+
+        sut.request(
+            Verb::POST,
+            SquareAPI::Customers("/search".to_string()),
+            Some(&serde_json::json!({ "limit": 1 })),
+            Some(vec![("cursor".to_string(), "some cursor".to_string())]),
+        ).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+
+        let request = &requests[1];
+        assert_eq!(request.verb, Verb::POST);
+        assert_eq!(request.url, "http://square.test/v2/customers/search?cursor=some+cursor");
+        assert_eq!(request.headers["authorization"], "Bearer some_token");
+        assert_eq!(request.headers["content-type"], "application/json");
+        assert_eq!(request.body.as_deref(), Some(br#"{"limit":1}"#.as_ref()));
+        assert_eq!(request.timeout, Some(Duration::from_secs(5)));
+    }
+
+    #[tokio::test]
+    async fn test_request_options() {
+        use crate::client::{HeaderValue, RequestOptions};
+
+        let requests = Arc::new(Mutex::new(vec![]));
+        let sut = SquareClient::new("some_token")
+            .base_url("http://square.test")
+            .timeout(Duration::from_secs(5))
+            .transport(Fake {
+                requests: requests.clone(),
+                failures: Mutex::new(1),
+                body: r#"{ "locations": [] }"#,
+            });
+
+        sut.request_with_options(
+            Verb::GET,
+            SquareAPI::Locations("".to_string()),
+            None::<&()>,
+            None,
+            RequestOptions::new()
+                .timeout(Duration::from_secs(120))
+                .retry_policy(RetryPolicy::new(1).initial_backoff(Duration::from_millis(1)))
+                .square_version("2022-09-21")
+                .header("x-correlation-id", HeaderValue::from_static("some_correlation_id")),
+        ).await.unwrap();
+
+        // the client itself is left as it was
+        sut.locations().list().await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1].timeout, Some(Duration::from_secs(120)));
+        assert_eq!(requests[1].headers["square-version"], "2022-09-21");
+        assert_eq!(requests[1].headers["x-correlation-id"], "some_correlation_id");
+        assert_eq!(requests[2].timeout, Some(Duration::from_secs(5)));
+        assert_eq!(requests[2].headers["square-version"], crate::client::SQUARE_VERSION);
+        assert!(!requests[2].headers.contains_key("x-correlation-id"));
+    }
+
+    #[tokio::test]
+    async fn test_multipart() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let sut = SquareClient::new("some_token")
+            .base_url("http://square.test")
+            .transport(Fake {
+                requests: requests.clone(),
+                failures: Mutex::new(0),
+                body: "{}",
+            });
+        let file = ImageFile {
+            file_name: "logo.png".to_string(),
+            content_type: "image/png".to_string(),
+            bytes: vec![1, 2, 3],
+        };
+
+        sut.request_multipart(
+            Verb::POST,
+            SquareAPI::Catalog("/images".to_string()),
+            &serde_json::json!({ "idempotency_key": "some_key" }),
+            Some(("image_file", &file)),
+        ).await.unwrap();
+
+        let request = requests.lock().unwrap().pop().unwrap();
+        let content_type = request.headers["content-type"].to_str().unwrap();
+        let boundary = content_type.strip_prefix("multipart/form-data; boundary=").unwrap();
+
+        let mut expected = format!(
+            "--{0}\r\nContent-Disposition: form-data; name=\"request\"\r\n\
+            Content-Type: application/json\r\n\r\n{{\"idempotency_key\":\"some_key\"}}\r\n\
+            --{0}\r\nContent-Disposition: form-data; name=\"image_file\"; filename=\"logo.png\"\r\n\
+            Content-Type: image/png\r\n\r\n",
+            boundary,
+        ).into_bytes();
+        expected.extend([1, 2, 3]);
+        expected.extend(format!("\r\n--{}--\r\n", boundary).into_bytes());
+
+        assert_eq!(request.body, Some(expected));
+    }
+
+    #[tokio::test]
+    async fn test_transport_error() {
+        let sut = SquareClient::new("some_token")
+            .base_url("http://square.test")
+            .transport(Fake {
+                requests: Arc::new(Mutex::new(vec![])),
+                failures: Mutex::new(1),
+                body: "",
+            });
+
+        assert!(sut.locations().list().await.is_err());
+    }
+}
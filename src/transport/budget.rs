@@ -0,0 +1,316 @@
+/*!
+Call count and latency budgets per endpoint, asserted in the integration tests of code using a
+[SquareClient](crate::client::SquareClient).
+
+A [BudgetTransport](BudgetTransport) wraps the transport the requests are sent through, a
+mock or the [ReqwestTransport](super::ReqwestTransport) talking to the sandbox, and records
+how often every endpoint was called and how long each call took. Once the code under test ran,
+the recorded calls are checked against the [Budget](Budget)s set per endpoint, e.g. to catch
+code that retrieves the customers of a list one at a time instead of searching for them at
+once.
+
+Endpoints are given by their verb and path as in the API reference of Square, whose placeholders
+match any id, e.g. `GET /v2/customers/{customer_id}`. Every attempt of a retried request counts as
+a call.
+
+# Example: Catch an N+1 pattern in a test
+```no_run
+use square_ox::{
+    api::Verb,
+    client::SquareClient,
+    transport::budget::{Budget, BudgetTransport},
+};
+use std::time::Duration;
+
+async {
+    let budgets = BudgetTransport::over_reqwest(reqwest::Client::new())
+        .budget(Verb::GET, "/v2/customers/{customer_id}", Budget::new().max_calls(1))
+        .budget(
+            Verb::POST,
+            "/v2/customers/search",
+            Budget::new().max_latency(Duration::from_secs(2)),
+        );
+    let client = SquareClient::new("some_token").transport(budgets.clone());
+
+    // run the code under test with the client
+
+    budgets.assert_within_budgets();
+};
+```
+ */
+
+use super::{HttpRequest, HttpTransport, ReqwestTransport, TransportFuture};
+use crate::api::Verb;
+use crate::platform::Instant;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The calls an endpoint is allowed, all of them unlimited unless told otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct Budget {
+    max_calls: Option<usize>,
+    max_latency: Option<Duration>,
+}
+
+impl Budget {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Allow at most the given number of calls.
+    pub fn max_calls(self, max_calls: usize) -> Self {
+        Self {
+            max_calls: Some(max_calls),
+            ..self
+        }
+    }
+
+    /// Allow every call to take at most the given duration, until its response was received.
+    pub fn max_latency(self, max_latency: Duration) -> Self {
+        Self {
+            max_latency: Some(max_latency),
+            ..self
+        }
+    }
+}
+
+/// An endpoint whose calls exceeded its [Budget](Budget).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BudgetViolation {
+    /// The endpoint as its budget was set, e.g. `GET /v2/customers/*`.
+    pub endpoint: String,
+    /// The number of calls to the endpoint.
+    pub calls: usize,
+    /// The longest of the calls to the endpoint.
+    pub max_latency: Duration,
+}
+
+impl fmt::Display for BudgetViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} was called {} times, taking up to {:?}",
+            self.endpoint, self.calls, self.max_latency,
+        )
+    }
+}
+
+// a budget along with the endpoint it applies to
+#[derive(Debug)]
+struct EndpointBudget {
+    verb: Verb,
+    segments: Vec<String>,
+    budget: Budget,
+}
+
+impl EndpointBudget {
+    fn matches(&self, verb: Verb, path: &str) -> bool {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        self.verb == verb
+            && self.segments.len() == segments.len()
+            && self.segments.iter()
+                .zip(segments)
+                .all(|(pattern, segment)| is_placeholder(pattern) || pattern == segment)
+    }
+
+    fn endpoint(&self) -> String {
+        format!("{:?} /{}", self.verb, self.segments.join("/"))
+    }
+}
+
+// whether the segment of a path matches any id, either `*` or named like `{customer_id}`
+fn is_placeholder(segment: &str) -> bool {
+    segment == "*" || (segment.starts_with('{') && segment.ends_with('}'))
+}
+
+/// An [HttpTransport](HttpTransport) recording the calls sent through it, to check them against
+/// the [Budget](Budget)s set per endpoint. Clones share the recorded calls and the budgets.
+#[derive(Clone)]
+pub struct BudgetTransport {
+    inner: Arc<dyn HttpTransport>,
+    budgets: Arc<Mutex<Vec<EndpointBudget>>>,
+    // the verb and path of every call along with how long it took
+    calls: Arc<Mutex<Vec<(Verb, String, Duration)>>>,
+}
+
+impl BudgetTransport {
+    /// Records the calls sent through the given transport.
+    pub fn new<T: HttpTransport + 'static>(inner: T) -> Self {
+        BudgetTransport {
+            inner: Arc::new(inner),
+            budgets: Arc::new(Mutex::new(vec![])),
+            calls: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    /// Records the calls sent to Square, or whichever base URL the client is given, through a
+    /// [ReqwestTransport](super::ReqwestTransport) of the given client.
+    pub fn over_reqwest(client: reqwest::Client) -> Self {
+        Self::new(ReqwestTransport::new(client))
+    }
+
+    /// Sets the budget of the endpoint of the given verb and path, such as
+    /// `/v2/customers/{customer_id}`, whose placeholders, or `*` segments, match any id. A call
+    /// counts against every budget it matches. The budget applies to the clones of the transport
+    /// too, including those already given to a client.
+    pub fn budget(self, verb: Verb, path: &str, budget: Budget) -> Self {
+        self.budgets.lock().unwrap().push(EndpointBudget {
+            verb,
+            segments: path.trim_matches('/').split('/').map(String::from).collect(),
+            budget,
+        });
+
+        self
+    }
+
+    /// The number of calls recorded to the given verb and path, which may contain placeholders.
+    pub fn calls(&self, verb: Verb, path: &str) -> usize {
+        let endpoint = EndpointBudget {
+            verb,
+            segments: path.trim_matches('/').split('/').map(String::from).collect(),
+            budget: Budget::new(),
+        };
+
+        self.calls.lock().unwrap()
+            .iter()
+            .filter(|(verb, path, _)| endpoint.matches(*verb, path))
+            .count()
+    }
+
+    /// The endpoints whose calls exceeded their budget.
+    pub fn violations(&self) -> Vec<BudgetViolation> {
+        let calls = self.calls.lock().unwrap();
+
+        self.budgets.lock().unwrap().iter()
+            .filter_map(|endpoint| {
+                let latencies: Vec<Duration> = calls.iter()
+                    .filter(|(verb, path, _)| endpoint.matches(*verb, path))
+                    .map(|(_, _, latency)| *latency)
+                    .collect();
+                let max_latency = latencies.iter().max().copied().unwrap_or_default();
+
+                let exceeded = endpoint.budget.max_calls.is_some_and(|max| latencies.len() > max)
+                    || endpoint.budget.max_latency.is_some_and(|max| max_latency > max);
+
+                exceeded.then(|| BudgetViolation {
+                    endpoint: endpoint.endpoint(),
+                    calls: latencies.len(),
+                    max_latency,
+                })
+            })
+            .collect()
+    }
+
+    /// Panics, listing the endpoints, unless all calls were within their budgets.
+    pub fn assert_within_budgets(&self) {
+        let violations = self.violations();
+
+        assert!(
+            violations.is_empty(),
+            "the calls exceeded their budgets:\n{}",
+            violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"),
+        );
+    }
+
+    /// Forgets the calls recorded so far, e.g. between two tests.
+    pub fn reset(&self) {
+        self.calls.lock().unwrap().clear();
+    }
+
+    /// The calls recorded so far per endpoint, e.g. `GET /v2/locations`, to set the budgets from.
+    pub fn summary(&self) -> BTreeMap<String, usize> {
+        let mut summary = BTreeMap::new();
+        for (verb, path, _) in self.calls.lock().unwrap().iter() {
+            *summary.entry(format!("{:?} {}", verb, path)).or_default() += 1;
+        }
+
+        summary
+    }
+}
+
+impl HttpTransport for BudgetTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let verb = request.verb;
+        let path = reqwest::Url::parse(&request.url)
+            .map(|url| url.path().to_string())
+            .unwrap_or_else(|_| request.url.clone());
+
+        Box::pin(async move {
+            let started = Instant::now();
+            let sent = self.inner.send(request).await;
+            self.calls.lock().unwrap().push((verb, path, started.elapsed()));
+
+            sent
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_budget {
+    use super::*;
+    use crate::client::SquareClient;
+    use crate::transport::HttpResponse;
+    use reqwest::StatusCode;
+
+    // answers every request with an empty object
+    struct Empty;
+
+    impl HttpTransport for Empty {
+        fn send(&self, _: HttpRequest) -> TransportFuture<'_> {
+            Box::pin(async { Ok(HttpResponse::new(StatusCode::OK, "{}".to_string())) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_budgets() {
+        let sut = BudgetTransport::new(Empty)
+            .budget(Verb::GET, "/v2/customers/{customer_id}", Budget::new().max_calls(1))
+            .budget(
+                Verb::GET,
+                "/v2/locations",
+                Budget::new().max_calls(1).max_latency(Duration::from_secs(5)),
+            );
+        let client = SquareClient::new("some_token")
+            .base_url("http://square.test")
+            .transport(sut.clone());
+
+        client.locations().list().await.unwrap();
+        for id in ["some_id", "other_id"] {
+            client.customers().retrieve(id.to_string()).await.unwrap();
+        }
+
+        assert_eq!(sut.calls(Verb::GET, "/v2/customers/*"), 2);
+        assert_eq!(sut.calls(Verb::GET, "/v2/customers/some_id"), 1);
+        assert_eq!(sut.summary()["GET /v2/locations"], 1);
+
+        let violations = sut.violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].endpoint, "GET /v2/customers/{customer_id}");
+        assert_eq!(violations[0].calls, 2);
+
+        sut.reset();
+        sut.assert_within_budgets();
+
+        // budgets set once the transport was given to the client still apply
+        let sut = sut.budget(Verb::GET, "/v2/locations", Budget::new().max_calls(0));
+        client.locations().list().await.unwrap();
+        assert_eq!(sut.violations().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "GET /v2/locations was called 2 times")]
+    fn test_assert_within_budgets() {
+        let sut = BudgetTransport::new(Empty)
+            .budget(Verb::GET, "/v2/locations", Budget::new().max_calls(1));
+        for _ in 0..2 {
+            let call = (Verb::GET, "/v2/locations".to_string(), Duration::ZERO);
+            sut.calls.lock().unwrap().push(call);
+        }
+
+        sut.assert_within_budgets();
+    }
+}
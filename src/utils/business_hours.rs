@@ -0,0 +1,256 @@
+/*!
+Evaluation of the [BusinessHours](crate::objects::BusinessHours) of a
+[Location](crate::objects::Location).
+
+Business hours are given in the local time of the location, so they are evaluated in its IANA
+`timezone`. A period whose end lies before its start runs past midnight into the next day. An
+opening time skipped by a daylight saving time change is moved to the moment the clocks jump,
+and one that occurs twice resolves to its first occurrence.
+
+Useful for checkout cutoffs and pickup scheduling.
+
+# Example: Check whether a location is open
+```
+use chrono::{TimeZone, Utc};
+use square_ox::objects::{BusinessHours, BusinessHoursPeriod, Location};
+
+let location = Location {
+    timezone: Some("America/New_York".to_string()),
+    business_hours: Some(BusinessHours {
+        periods: vec![BusinessHoursPeriod {
+            day_of_week: "MON".to_string(),
+            start_local_time: "09:00:00".to_string(),
+            end_local_time: "17:00:00".to_string(),
+        }],
+    }),
+    ..Default::default()
+};
+
+// Monday, 10:00 in New York
+assert!(location.is_open_at(&Utc.with_ymd_and_hms(2022, 6, 6, 14, 0, 0).unwrap()).unwrap());
+```
+ */
+
+use crate::errors::BusinessHoursError;
+use crate::objects::{BusinessHoursPeriod, Location};
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Weekday,
+};
+use chrono_tz::Tz;
+
+impl Location {
+    /// Whether the location is open at the given time according to its business hours.
+    /// Fails if the location has no valid timezone or business hours.
+    /// # Arguments:
+    /// * `datetime` - The time to check, in any timezone.
+    pub fn is_open_at<T: TimeZone>(&self, datetime: &DateTime<T>)
+                                   -> Result<bool, BusinessHoursError> {
+        let (timezone, periods) = self.schedule()?;
+        let local = datetime.with_timezone(&timezone).naive_local();
+
+        Ok(periods.iter().any(|period| period.contains(local)))
+    }
+
+    /// The first time strictly after the given time at which one of the business hours periods of
+    /// the location starts, in the timezone of the location. Yields `None` for a location without
+    /// any periods. Fails if the location has no valid timezone or business hours.
+    /// # Arguments:
+    /// * `datetime` - The time to search from, in any timezone.
+    pub fn next_opening_after<T: TimeZone>(&self, datetime: &DateTime<T>)
+                                           -> Result<Option<DateTime<Tz>>, BusinessHoursError> {
+        let (timezone, periods) = self.schedule()?;
+        let after = datetime.with_timezone(&timezone);
+        let today = after.date_naive();
+
+        // a week and a day ahead covers every weekday, including today's after the given time
+        let opening = (0..=7)
+            .filter_map(|days| today.checked_add_signed(Duration::days(days)))
+            .flat_map(|date| {
+                periods.iter()
+                    .filter(move |period| period.weekday == date.weekday())
+                    .map(move |period| resolve(&timezone, date.and_time(period.start)))
+            })
+            .filter(|opening| opening > &after)
+            .min();
+
+        Ok(opening)
+    }
+
+    // the timezone of the location along with its parsed business hours periods
+    pub(super) fn schedule(&self) -> Result<(Tz, Vec<Period>), BusinessHoursError> {
+        let timezone = self.timezone.as_deref()
+            .ok_or(BusinessHoursError)?
+            .parse::<Tz>()
+            .map_err(|_| BusinessHoursError)?;
+
+        let periods = self.business_hours.as_ref()
+            .ok_or(BusinessHoursError)?
+            .periods.iter()
+            .map(Period::parse)
+            .collect::<Result<Vec<Period>, BusinessHoursError>>()?;
+
+        Ok((timezone, periods))
+    }
+}
+
+// a business hours period with its day and local times parsed
+pub(super) struct Period {
+    pub(super) weekday: Weekday,
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl Period {
+    fn parse(period: &BusinessHoursPeriod) -> Result<Self, BusinessHoursError> {
+        let weekday = match period.day_of_week.as_str() {
+            "MON" => Weekday::Mon,
+            "TUE" => Weekday::Tue,
+            "WED" => Weekday::Wed,
+            "THU" => Weekday::Thu,
+            "FRI" => Weekday::Fri,
+            "SAT" => Weekday::Sat,
+            "SUN" => Weekday::Sun,
+            _ => return Err(BusinessHoursError),
+        };
+
+        Ok(Period {
+            weekday,
+            start: parse_local_time(&period.start_local_time)?,
+            end: parse_local_time(&period.end_local_time)?,
+        })
+    }
+
+    // whether the local time falls within the period starting on its own day or the day before
+    fn contains(&self, local: NaiveDateTime) -> bool {
+        let today = local.date();
+
+        [today.pred_opt(), Some(today)].iter()
+            .flatten()
+            .filter(|date| date.weekday() == self.weekday)
+            .any(|date| {
+                let (start, end) = self.span(*date);
+
+                start <= local && local < end
+            })
+    }
+
+    // the local start and end of the period on the given date
+    pub(super) fn span(&self, date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+        let end_date = if self.end > self.start {
+            date
+        } else {
+            date.succ_opt().unwrap_or(date)
+        };
+
+        (date.and_time(self.start), end_date.and_time(self.end))
+    }
+}
+
+fn parse_local_time(time: &str) -> Result<NaiveTime, BusinessHoursError> {
+    NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+        .map_err(|_| BusinessHoursError)
+}
+
+// the instant of a local time, moved forward to the end of a daylight saving time gap if the clocks
+// skip it
+pub(super) fn resolve(timezone: &Tz, local: NaiveDateTime) -> DateTime<Tz> {
+    let mut candidate = local;
+
+    loop {
+        match timezone.from_local_datetime(&candidate) {
+            LocalResult::Single(datetime) => return datetime,
+            LocalResult::Ambiguous(earliest, _) => return earliest,
+            LocalResult::None => candidate += Duration::minutes(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_business_hours {
+    use super::*;
+    use crate::objects::BusinessHours;
+    use chrono::Utc;
+
+    fn period(day_of_week: &str, start: &str, end: &str) -> BusinessHoursPeriod {
+        BusinessHoursPeriod {
+            day_of_week: day_of_week.to_string(),
+            start_local_time: start.to_string(),
+            end_local_time: end.to_string(),
+        }
+    }
+
+    fn location() -> Location {
+        Location {
+            timezone: Some("America/New_York".to_string()),
+            business_hours: Some(BusinessHours {
+                periods: vec![
+                    period("MON", "09:00:00", "17:00:00"),
+                    period("FRI", "22:00:00", "02:00:00"),
+                    period("SUN", "02:30", "04:00"),
+                ],
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn utc(month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2022, month, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_is_open_at() {
+        let sut = location();
+
+        // 09:00 on a Monday after the switch to daylight saving time
+        assert!(sut.is_open_at(&utc(3, 14, 13, 0)).unwrap());
+        // 08:30 on a Monday before the switch
+        assert!(!sut.is_open_at(&utc(3, 7, 13, 30)).unwrap());
+        // 01:30 on a Saturday, within the period starting on Friday
+        assert!(sut.is_open_at(&utc(3, 12, 6, 30)).unwrap());
+        // 02:30 on a Saturday, after the period starting on Friday
+        assert!(!sut.is_open_at(&utc(3, 12, 7, 30)).unwrap());
+        // 17:00 on a Monday, as the period closes
+        assert!(!sut.is_open_at(&utc(3, 14, 21, 0)).unwrap());
+    }
+
+    #[test]
+    fn test_next_opening_after() {
+        let sut = location();
+
+        // 02:30 on the Sunday the clocks jump from 02:00 to 03:00, so the location opens at 03:00
+        assert_eq!(
+            sut.next_opening_after(&utc(3, 12, 12, 0)).unwrap().unwrap().with_timezone(&Utc),
+            utc(3, 13, 7, 0),
+        );
+        assert_eq!(
+            sut.next_opening_after(&utc(3, 13, 8, 0)).unwrap().unwrap().with_timezone(&Utc),
+            utc(3, 14, 13, 0),
+        );
+        // an opening exactly at the given time is not after it
+        assert_eq!(
+            sut.next_opening_after(&utc(3, 14, 13, 0)).unwrap().unwrap().with_timezone(&Utc),
+            utc(3, 19, 2, 0),
+        );
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        let mut sut = location();
+        sut.timezone = Some("Mars/Olympus_Mons".to_string());
+
+        assert!(sut.is_open_at(&utc(3, 14, 13, 0)).is_err());
+
+        let mut sut = location();
+        sut.business_hours = Some(BusinessHours { periods: vec![period("MONDAY", "09:00", "17:00")] });
+
+        assert!(sut.next_opening_after(&utc(3, 14, 13, 0)).is_err());
+
+        let mut sut = location();
+        sut.business_hours = Some(BusinessHours { periods: vec![] });
+
+        assert!(sut.next_opening_after(&utc(3, 14, 13, 0)).unwrap().is_none());
+    }
+}
@@ -0,0 +1,220 @@
+/*!
+Progress of a [LoyaltyAccount](crate::objects::LoyaltyAccount) through the reward tiers of its
+[LoyaltyProgram](crate::objects::LoyaltyProgram).
+
+The current tier is the most valuable reward the points balance of the account can be redeemed
+for, the next tier the cheapest reward it cannot afford yet. How far the account is from the next
+tier is estimated from the accrual rules of the program: the number of purchases under a `VISIT`
+rule, and the amount to spend under the most generous `SPEND` rule.
+
+Useful for customer-facing displays such as "3 more visits until your free coffee".
+
+# Example: Show the progress toward the next reward
+```
+use square_ox::objects::{
+    LoyaltyAccount, LoyaltyProgram, LoyaltyProgramAccrualRule, LoyaltyProgramRewardTier,
+    enums::LoyaltyProgramAccrualRuleType,
+};
+
+let program = LoyaltyProgram {
+    reward_tiers: Some(vec![LoyaltyProgramRewardTier {
+        name: Some("Free coffee".to_string()),
+        points: Some(10),
+        ..Default::default()
+    }]),
+    accrual_rules: Some(vec![LoyaltyProgramAccrualRule {
+        accrual_type: Some(LoyaltyProgramAccrualRuleType::Visit),
+        points: Some(2),
+        ..Default::default()
+    }]),
+    ..Default::default()
+};
+let account = LoyaltyAccount { balance: Some(5), ..Default::default() };
+
+let progress = account.progress(&program).unwrap();
+
+assert_eq!(progress.points_needed, 5);
+assert_eq!(progress.estimated_purchases, Some(3));
+```
+ */
+
+use crate::objects::{
+    LoyaltyAccount, LoyaltyProgram, LoyaltyProgramAccrualRule, LoyaltyProgramRewardTier, Money,
+    enums::LoyaltyProgramAccrualRuleType,
+};
+
+/// How far a loyalty account has come in the reward tiers of its program.
+#[derive(Clone, Debug)]
+pub struct LoyaltyProgress {
+    /// The points balance of the account.
+    pub balance: i32,
+    /// The most valuable tier the balance can be redeemed for, if any.
+    pub current_tier: Option<LoyaltyProgramRewardTier>,
+    /// The cheapest tier the balance cannot be redeemed for yet, if any.
+    pub next_tier: Option<LoyaltyProgramRewardTier>,
+    /// The points still missing for the next tier, 0 if there is none.
+    pub points_needed: i32,
+    /// The share of the points of the next tier the balance covers, from 0.0 to 1.0.
+    pub progress: f64,
+    /// The number of purchases earning the missing points under the `VISIT` rule of the program.
+    pub estimated_purchases: Option<i32>,
+    /// The amount to spend to earn the missing points under the most generous `SPEND` rule of the
+    /// program.
+    pub estimated_spend: Option<Money>,
+}
+
+impl LoyaltyAccount {
+    /// The progress of the account through the reward tiers of the given program. Tiers and rules
+    /// without points are ignored. Returns `None` if the account belongs to another program.
+    /// # Arguments:
+    /// * `program` - The loyalty program of the account.
+    pub fn progress(&self, program: &LoyaltyProgram) -> Option<LoyaltyProgress> {
+        if let (Some(program_id), Some(id)) = (&self.program_id, &program.id) {
+            if program_id != id {
+                return None;
+            }
+        }
+
+        let balance = self.balance.unwrap_or(0);
+        let mut tiers: Vec<(i32, &LoyaltyProgramRewardTier)> = program.reward_tiers.iter()
+            .flatten()
+            .filter_map(|tier| tier.points.filter(|points| *points > 0).map(|points| (points, tier)))
+            .collect();
+        tiers.sort_by_key(|(points, _)| *points);
+
+        let current_tier = tiers.iter()
+            .rev()
+            .find(|(points, _)| *points <= balance)
+            .map(|(_, tier)| (*tier).clone());
+        let next = tiers.iter().find(|(points, _)| *points > balance);
+
+        let (points_needed, progress) = match next {
+            Some((points, _)) => (points - balance, balance.max(0) as f64 / *points as f64),
+            None => (0, 1.0),
+        };
+
+        let rules = program.accrual_rules.as_deref().unwrap_or_default();
+
+        Some(LoyaltyProgress {
+            balance,
+            current_tier,
+            next_tier: next.map(|(_, tier)| (*tier).clone()),
+            points_needed,
+            progress,
+            estimated_purchases: estimated_purchases(rules, points_needed),
+            estimated_spend: estimated_spend(rules, points_needed),
+        })
+    }
+}
+
+// the number of visits earning the points under the first VISIT rule
+fn estimated_purchases(rules: &[LoyaltyProgramAccrualRule], points: i32) -> Option<i32> {
+    let per_visit = rules.iter()
+        .filter(|rule| rule.accrual_type == Some(LoyaltyProgramAccrualRuleType::Visit))
+        .find_map(|rule| rule.points.filter(|points| *points > 0))?;
+
+    Some(div_ceil(points.max(0) as i64, per_visit as i64) as i32)
+}
+
+// the amount to spend to earn the points under the SPEND rule earning the most points per amount,
+// given that spend is only rewarded in whole multiples of the amount of the rule
+fn estimated_spend(rules: &[LoyaltyProgramAccrualRule], points: i32) -> Option<Money> {
+    let (per_spend, amount) = rules.iter()
+        .filter(|rule| rule.accrual_type == Some(LoyaltyProgramAccrualRuleType::Spend))
+        .filter_map(|rule| {
+            let per_spend = rule.points.filter(|points| *points > 0)?;
+            let amount = rule.spend_data.as_ref()?.amount_money.as_ref()?;
+
+            amount.amount.filter(|value| *value > 0).map(|_| (per_spend as i64, amount))
+        })
+        .max_by(|(a_points, a), (b_points, b)| {
+            (a_points * b.amount.unwrap_or(1)).cmp(&(b_points * a.amount.unwrap_or(1)))
+        })?;
+
+    Some(Money {
+        amount: Some(div_ceil(points.max(0) as i64, per_spend) * amount.amount.unwrap_or(0)),
+        currency: amount.currency.clone(),
+    })
+}
+
+fn div_ceil(value: i64, divisor: i64) -> i64 {
+    (value + divisor - 1) / divisor
+}
+
+#[cfg(test)]
+mod test_loyalty {
+    use super::*;
+
+    fn program() -> LoyaltyProgram {
+        serde_json::from_value(serde_json::json!({
+            "id": "some_program_id",
+            "reward_tiers": [
+                { "id": "T2", "name": "Free pastry", "points": 25 },
+                { "id": "T1", "name": "Free coffee", "points": 10 },
+                { "id": "T0", "name": "Broken tier" },
+            ],
+            "accrual_rules": [
+                { "accrual_type": "SPEND", "points": 1, "spend_data": {
+                    "amount_money": { "amount": 200, "currency": "USD" }
+                } },
+                { "accrual_type": "SPEND", "points": 2, "spend_data": {
+                    "amount_money": { "amount": 300, "currency": "USD" }
+                } },
+                { "accrual_type": "VISIT", "points": 4, "visit_data": {
+                    "minimum_amount_money": { "amount": 500, "currency": "USD" }
+                } },
+            ],
+        })).unwrap()
+    }
+
+    fn account(balance: i32) -> LoyaltyAccount {
+        LoyaltyAccount {
+            balance: Some(balance),
+            program_id: Some("some_program_id".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let actual = account(12).progress(&program()).unwrap();
+
+        assert_eq!(actual.current_tier.unwrap().id, Some("T1".to_string()));
+        assert_eq!(actual.next_tier.unwrap().id, Some("T2".to_string()));
+        assert_eq!(actual.points_needed, 13);
+        assert!((actual.progress - 0.48).abs() < f64::EPSILON);
+        assert_eq!(actual.estimated_purchases, Some(4));
+        // 2 points per 3.00 beats 1 point per 2.00, and 7 multiples of 3.00 earn 14 points
+        assert_eq!(actual.estimated_spend.unwrap().amount, Some(2100));
+
+        let actual = account(3).progress(&program()).unwrap();
+
+        assert!(actual.current_tier.is_none());
+        assert_eq!(actual.next_tier.unwrap().id, Some("T1".to_string()));
+        assert_eq!(actual.points_needed, 7);
+        assert_eq!(actual.estimated_purchases, Some(2));
+    }
+
+    #[test]
+    fn test_progress_past_last_tier() {
+        let actual = account(30).progress(&program()).unwrap();
+
+        assert_eq!(actual.current_tier.unwrap().id, Some("T2".to_string()));
+        assert!(actual.next_tier.is_none());
+        assert_eq!(actual.points_needed, 0);
+        assert_eq!(actual.progress, 1.0);
+        assert_eq!(actual.estimated_purchases, Some(0));
+        assert_eq!(actual.estimated_spend.unwrap().amount, Some(0));
+
+        let mut other = account(30);
+        other.program_id = Some("other_program_id".to_string());
+
+        assert!(other.progress(&program()).is_none());
+
+        let actual = account(5).progress(&LoyaltyProgram::default()).unwrap();
+
+        assert!(actual.next_tier.is_none());
+        assert!(actual.estimated_purchases.is_none());
+        assert!(actual.estimated_spend.is_none());
+    }
+}
@@ -0,0 +1,12 @@
+/*!
+Helpers built on top of the objects of the [Square API](https://developer.squareup.com).
+
+These do not call the [Square API](https://developer.squareup.com) themselves, they work with the
+data returned from or sent to it.
+ */
+
+pub mod business_hours;
+pub mod loyalty;
+pub mod pickup;
+pub mod rounding;
+pub mod tips;
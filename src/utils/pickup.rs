@@ -0,0 +1,293 @@
+/*!
+Pickup time slots for order-ahead flows, built on the [BusinessHours](crate::objects::BusinessHours)
+of a [Location](crate::objects::Location).
+
+The business hours of the location are divided into slots of a fixed length. A slot is offered
+once the kitchen has had the preparation time to get an order ready for it, and as long as fewer
+orders than the capacity of a slot are already due to be picked up in it. Those are counted from
+the pickup fulfillments of the open orders of the location, leaving out fulfillments that were
+completed, canceled or failed.
+
+[pickup_at](PickupSlot::pickup_at) and [pickup_window_duration](PickupSlot::pickup_window_duration)
+of a slot fit the builder of
+[OrderFulfillmentPickupDetails](crate::objects::OrderFulfillmentPickupDetails).
+
+# Example: Offer the pickup slots of a location
+```
+use chrono::{Duration, TimeZone, Utc};
+use square_ox::objects::{BusinessHours, BusinessHoursPeriod, Location};
+use square_ox::utils::pickup::PickupSlotOptions;
+
+let location = Location {
+    timezone: Some("America/New_York".to_string()),
+    business_hours: Some(BusinessHours {
+        periods: vec![BusinessHoursPeriod {
+            day_of_week: "MON".to_string(),
+            start_local_time: "11:00:00".to_string(),
+            end_local_time: "14:00:00".to_string(),
+        }],
+    }),
+    ..Default::default()
+};
+
+let options = PickupSlotOptions::new().slot_length(Duration::minutes(30)).capacity(4);
+// Monday, 12:00 in New York, without any open orders
+let slots = location
+    .pickup_slots(&Utc.with_ymd_and_hms(2022, 6, 6, 16, 0, 0).unwrap(), &options, &[])
+    .unwrap();
+
+// with the default preparation time of 20 minutes, the first slot starts at 12:30
+assert_eq!(slots[0].pickup_at(), "2022-06-06T12:30:00-04:00");
+assert_eq!(slots[0].pickup_window_duration(), "PT30M");
+```
+ */
+
+use crate::errors::BusinessHoursError;
+use crate::objects::{Location, Order};
+use crate::objects::enums::{OrderFulfillmentState, OrderFulfillmentType};
+use crate::utils::business_hours::resolve;
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, TimeZone};
+use chrono_tz::Tz;
+
+/// How to divide business hours into pickup slots.
+#[derive(Clone, Debug)]
+pub struct PickupSlotOptions {
+    slot_length: Duration,
+    prep_time: Duration,
+    capacity: usize,
+    days_ahead: i64,
+}
+
+impl Default for PickupSlotOptions {
+    fn default() -> Self {
+        PickupSlotOptions {
+            slot_length: Duration::minutes(15),
+            prep_time: Duration::minutes(20),
+            capacity: 5,
+            days_ahead: 2,
+        }
+    }
+}
+
+impl PickupSlotOptions {
+    /// Slots of 15 minutes, each taking up to 5 orders, offered from 20 minutes and up to 2 days
+    /// ahead.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The length of a slot.
+    pub fn slot_length(mut self, slot_length: Duration) -> Self {
+        self.slot_length = slot_length;
+
+        self
+    }
+
+    /// The time it takes to prepare an order, before which no slot is offered.
+    pub fn prep_time(mut self, prep_time: Duration) -> Self {
+        self.prep_time = prep_time;
+
+        self
+    }
+
+    /// The number of orders that can be picked up in a single slot.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+
+        self
+    }
+
+    /// The number of days after today to offer slots on.
+    pub fn days_ahead(mut self, days_ahead: u32) -> Self {
+        self.days_ahead = days_ahead as i64;
+
+        self
+    }
+}
+
+/// A time slot an order can be picked up in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PickupSlot {
+    pub start: DateTime<Tz>,
+    pub end: DateTime<Tz>,
+    /// The number of open orders already due to be picked up in the slot.
+    pub booked: usize,
+    /// The number of orders the slot can still take.
+    pub remaining: usize,
+}
+
+impl PickupSlot {
+    /// The start of the slot as an RFC 3339 timestamp, as taken by `pickup_at`.
+    pub fn pickup_at(&self) -> String {
+        self.start.to_rfc3339()
+    }
+
+    /// The length of the slot as an ISO 8601 duration, as taken by `pickup_window_duration`.
+    pub fn pickup_window_duration(&self) -> String {
+        let seconds = (self.end - self.start).num_seconds();
+
+        if seconds % 60 == 0 {
+            format!("PT{}M", seconds / 60)
+        } else {
+            format!("PT{}S", seconds)
+        }
+    }
+}
+
+impl Location {
+    /// The pickup slots within the business hours of the location that can still take an order,
+    /// in chronological order. Fails if the location has no valid timezone or business hours.
+    /// # Arguments:
+    /// * `now` - The current time, in any timezone.
+    /// * `options` - How to divide the business hours into slots.
+    /// * `open_orders` - The open orders of the location, whose pickup fulfillments take up the
+    ///   slots.
+    pub fn pickup_slots<T: TimeZone>(&self, now: &DateTime<T>, options: &PickupSlotOptions,
+                                     open_orders: &[Order])
+                                     -> Result<Vec<PickupSlot>, BusinessHoursError> {
+        let (timezone, periods) = self.schedule()?;
+        let now = now.with_timezone(&timezone);
+        let earliest = now + options.prep_time;
+        let today = now.date_naive();
+
+        if options.slot_length <= Duration::zero() {
+            return Ok(vec![]);
+        }
+
+        let pickups = pickup_times(open_orders);
+
+        // a period starting yesterday may still run today
+        let mut starts = (-1..=options.days_ahead)
+            .filter_map(|days| today.checked_add_signed(Duration::days(days)))
+            .flat_map(|date| {
+                periods.iter()
+                    .filter(move |period| period.weekday == date.weekday())
+                    .map(move |period| period.span(date))
+            })
+            .flat_map(|(start, end)| {
+                let (start, end) = (resolve(&timezone, start), resolve(&timezone, end));
+
+                (0..)
+                    .map(move |slot| start + options.slot_length * slot)
+                    .take_while(move |slot_start| *slot_start + options.slot_length <= end)
+            })
+            .filter(|start| *start >= earliest)
+            .collect::<Vec<DateTime<Tz>>>();
+        starts.sort();
+        starts.dedup();
+
+        Ok(starts.into_iter()
+            .map(|start| {
+                let end = start + options.slot_length;
+                let booked = pickups.iter()
+                    .filter(|pickup| start <= **pickup && **pickup < end)
+                    .count();
+
+                PickupSlot {
+                    start,
+                    end,
+                    booked,
+                    remaining: options.capacity.saturating_sub(booked),
+                }
+            })
+            .filter(|slot| slot.remaining > 0)
+            .collect())
+    }
+}
+
+// the pickup times of the pickup fulfillments of the orders that are still to be picked up
+fn pickup_times(orders: &[Order]) -> Vec<DateTime<FixedOffset>> {
+    orders.iter()
+        .flat_map(|order| order.fulfillments.iter().flatten())
+        .filter(|fulfillment| fulfillment.fulfillment_type == Some(OrderFulfillmentType::Pickup))
+        .filter(|fulfillment| !matches!(
+            fulfillment.state,
+            Some(OrderFulfillmentState::Completed) | Some(OrderFulfillmentState::Canceled) |
+            Some(OrderFulfillmentState::Failed)
+        ))
+        .filter_map(|fulfillment| fulfillment.pickup_details.as_ref()?.pickup_at.as_deref())
+        .filter_map(|pickup_at| DateTime::parse_from_rfc3339(pickup_at).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod test_pickup {
+    use super::*;
+    use crate::objects::{BusinessHours, BusinessHoursPeriod};
+    use chrono::Utc;
+
+    fn location() -> Location {
+        Location {
+            timezone: Some("America/New_York".to_string()),
+            business_hours: Some(BusinessHours {
+                periods: vec![
+                    BusinessHoursPeriod {
+                        day_of_week: "MON".to_string(),
+                        start_local_time: "11:00:00".to_string(),
+                        end_local_time: "12:00:00".to_string(),
+                    },
+                    BusinessHoursPeriod {
+                        day_of_week: "SUN".to_string(),
+                        start_local_time: "23:30:00".to_string(),
+                        end_local_time: "00:30:00".to_string(),
+                    },
+                ],
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn order(pickup_at: &str, state: &str) -> Order {
+        serde_json::from_value(serde_json::json!({
+            "fulfillments": [{
+                "type": "PICKUP",
+                "state": state,
+                "pickup_details": { "pickup_at": pickup_at },
+            }],
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_pickup_slots() {
+        let options = PickupSlotOptions::new()
+            .slot_length(Duration::minutes(30))
+            .capacity(2)
+            .days_ahead(1);
+        // Sunday, 23:00 in New York
+        let now = Utc.with_ymd_and_hms(2022, 6, 6, 3, 0, 0).unwrap();
+        let orders = vec![
+            order("2022-06-06T11:00:00-04:00", "PROPOSED"),
+            order("2022-06-06T15:10:00Z", "RESERVED"),
+            order("2022-06-06T11:40:00-04:00", "RESERVED"),
+            order("2022-06-06T11:45:00-04:00", "CANCELED"),
+        ];
+
+        let actual = location().pickup_slots(&now, &options, &orders).unwrap();
+        let starts = actual.iter().map(|slot| slot.pickup_at()).collect::<Vec<String>>();
+
+        // the period from 23:30 on Sunday runs past midnight, and the slot from 11:00 on Monday is
+        // fully booked
+        assert_eq!(starts, vec![
+            "2022-06-05T23:30:00-04:00",
+            "2022-06-06T00:00:00-04:00",
+            "2022-06-06T11:30:00-04:00",
+        ]);
+        assert_eq!(actual[2].booked, 1);
+        assert_eq!(actual[2].remaining, 1);
+        assert_eq!(actual[2].pickup_window_duration(), "PT30M");
+    }
+
+    #[test]
+    fn test_pickup_slots_prep_time() {
+        let options = PickupSlotOptions::new().prep_time(Duration::minutes(45)).days_ahead(0);
+        // Monday, 10:30 in New York
+        let now = Utc.with_ymd_and_hms(2022, 6, 6, 14, 30, 0).unwrap();
+
+        let actual = location().pickup_slots(&now, &options, &[]).unwrap();
+
+        assert_eq!(actual.len(), 3);
+        assert_eq!(actual[0].pickup_at(), "2022-06-06T11:15:00-04:00");
+        assert_eq!(actual[0].remaining, 5);
+    }
+}
@@ -0,0 +1,199 @@
+/*!
+Cash rounding for jurisdictions without the smallest coins of their currency, such as Canada,
+which no longer has a one cent coin and rounds cash totals to the nearest five cents.
+
+Card and other non-cash tenders are charged the exact amount, only cash tenders are rounded. The
+difference is recorded on an [Order](crate::objects::Order) as its
+[OrderRoundingAdjustment](crate::objects::OrderRoundingAdjustment), which is included in its
+`total_money`.
+
+# Example: Give change for a cash tender in Canada
+```
+use square_ox::{
+    objects::{Money, enums::Currency},
+    utils::rounding::CashRounding,
+};
+
+let rounding = CashRounding::for_currency(&Currency::CAD).unwrap();
+let due = Money { amount: Some(1037), currency: Currency::CAD };
+let tendered = Money { amount: Some(2000), currency: Currency::CAD };
+
+assert_eq!(rounding.round(&due).amount, Some(1035));
+assert_eq!(rounding.change_back(&due, &tendered).unwrap().amount, Some(965));
+```
+ */
+
+use crate::objects::{Money, Order, OrderRoundingAdjustment, enums::Currency};
+
+/// The name given to the [OrderRoundingAdjustment](OrderRoundingAdjustment)s this module creates.
+const ROUNDING_ADJUSTMENT_NAME: &str = "Cash rounding";
+
+/// Rounds cash amounts to the nearest multiple of the smallest coin in circulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CashRounding {
+    increment: i64,
+}
+
+impl CashRounding {
+    /// Rounds to the nearest multiple of the given increment, in the lowest denomination of the
+    /// currency, e.g. `5` for the nearest five cents. Increments below 1 are treated as 1.
+    pub fn new(increment: i64) -> Self {
+        CashRounding {
+            increment: increment.max(1),
+        }
+    }
+
+    /// The legally required cash rounding of the currency, or `None` if cash amounts in it are
+    /// paid exactly. <br/>
+    /// CAD, AUD and CHF are rounded to the nearest 5 cents, NZD to the nearest 10 cents.
+    pub fn for_currency(currency: &Currency) -> Option<Self> {
+        match currency {
+            Currency::CAD | Currency::AUD | Currency::CHF => Some(CashRounding::new(5)),
+            Currency::NZD => Some(CashRounding::new(10)),
+            _ => None,
+        }
+    }
+
+    /// The increment amounts are rounded to, in the lowest denomination of the currency.
+    pub fn increment(&self) -> i64 {
+        self.increment
+    }
+
+    /// Rounds the amount to the nearest increment, rounding halves away from zero. An amount
+    /// without a value is treated as 0.
+    pub fn round(&self, amount: &Money) -> Money {
+        let value = amount.amount.unwrap_or(0);
+        let half = self.increment / 2;
+        let rounded = if value >= 0 {
+            (value + half) / self.increment * self.increment
+        } else {
+            (value - half) / self.increment * self.increment
+        };
+
+        Money { amount: Some(rounded), currency: amount.currency.clone() }
+    }
+
+    /// The [OrderRoundingAdjustment](OrderRoundingAdjustment) that rounds the amount, or `None`
+    /// if it needs no rounding.
+    pub fn adjustment(&self, amount: &Money) -> Option<OrderRoundingAdjustment> {
+        let difference = self.round(amount).amount.unwrap_or(0) - amount.amount.unwrap_or(0);
+        if difference == 0 {
+            return None
+        }
+
+        Some(OrderRoundingAdjustment {
+            amount_money: Some(Money { amount: Some(difference), currency: amount.currency.clone() }),
+            name: Some(ROUNDING_ADJUSTMENT_NAME.to_string()),
+            uid: None,
+        })
+    }
+
+    /// The change to give back for the cash tendered against the amount due, after rounding the
+    /// amount due. Returns `None` if the currencies differ or the cash tendered does not cover
+    /// the rounded amount.
+    pub fn change_back(&self, amount_due: &Money, tendered: &Money) -> Option<Money> {
+        if amount_due.currency != tendered.currency {
+            return None
+        }
+
+        let change = tendered.amount.unwrap_or(0) - self.round(amount_due).amount.unwrap_or(0);
+        if change < 0 {
+            return None
+        }
+
+        Some(Money { amount: Some(change), currency: tendered.currency.clone() })
+    }
+}
+
+impl Order {
+    /// The amount of the rounding adjustment included in the `total_money` of the order, if any.
+    pub fn rounding_adjustment_money(&self) -> Option<&Money> {
+        self.rounding_adjustment.as_ref()?.amount_money.as_ref()
+    }
+
+    /// The [OrderRoundingAdjustment](OrderRoundingAdjustment) to pay the order in cash, replacing
+    /// any adjustment it already holds. Returns `None` if the order holds no `total_money` or its
+    /// total before rounding needs no rounding.
+    pub fn cash_rounding_adjustment(&self, rounding: &CashRounding)
+        -> Option<OrderRoundingAdjustment> {
+        let total = self.total_money.as_ref()?;
+        let adjustment = self.rounding_adjustment_money()
+            .and_then(|adjustment| adjustment.amount)
+            .unwrap_or(0);
+
+        rounding.adjustment(&Money {
+            amount: Some(total.amount.unwrap_or(0) - adjustment),
+            currency: total.currency.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_rounding {
+    use super::*;
+
+    fn cad(amount: i64) -> Money {
+        Money { amount: Some(amount), currency: Currency::CAD }
+    }
+
+    #[test]
+    fn test_round_to_nickel() {
+        let rounding = CashRounding::for_currency(&Currency::CAD).unwrap();
+
+        let actual: Vec<Option<i64>> = vec![1001, 1002, 1003, 1007, 1008, -1002, -1003].iter()
+            .map(|amount| rounding.round(&cad(*amount)).amount)
+            .collect();
+
+        assert_eq!(
+            vec![Some(1000), Some(1000), Some(1005), Some(1005), Some(1010), Some(-1000), Some(-1005)],
+            actual
+        );
+        assert!(CashRounding::for_currency(&Currency::USD).is_none());
+    }
+
+    #[test]
+    fn test_adjustment() {
+        let rounding = CashRounding::new(10);
+
+        let expected = OrderRoundingAdjustment {
+            amount_money: Some(cad(-4)),
+            name: Some(ROUNDING_ADJUSTMENT_NAME.to_string()),
+            uid: None,
+        };
+
+        assert_eq!(Some(expected), rounding.adjustment(&cad(1234)));
+        assert!(rounding.adjustment(&cad(1230)).is_none());
+    }
+
+    #[test]
+    fn test_change_back() {
+        let rounding = CashRounding::new(5);
+
+        assert_eq!(Some(cad(1265)), rounding.change_back(&cad(3733), &cad(5000)));
+        assert!(rounding.change_back(&cad(3733), &cad(3700)).is_none());
+        assert!(rounding.change_back(
+            &cad(100),
+            &Money { amount: Some(500), currency: Currency::USD },
+        ).is_none());
+    }
+
+    #[test]
+    fn test_order_cash_rounding_adjustment() {
+        let rounding = CashRounding::new(5);
+        let order = Order {
+            total_money: Some(cad(1035)),
+            rounding_adjustment: Some(OrderRoundingAdjustment {
+                amount_money: Some(cad(-2)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(Some(&cad(-2)), order.rounding_adjustment_money());
+        assert_eq!(
+            Some(cad(-2)),
+            order.cash_rounding_adjustment(&rounding).and_then(|adjustment| adjustment.amount_money)
+        );
+        assert!(Order::default().cash_rounding_adjustment(&rounding).is_none());
+    }
+}
@@ -0,0 +1,224 @@
+/*!
+Tip suggestions matching the tipping options presented by a Square Terminal.
+
+Useful for custom checkout UIs that want to offer the same tip amounts a buyer would see on a
+Square Terminal configured with the same [TipSettings](crate::objects::TipSettings).
+
+# Example: Suggest tips for an amount
+```
+use square_ox::{
+    objects::{Money, enums::Currency},
+    utils::tips::TipCalculator,
+};
+
+let suggestions = TipCalculator::new()
+    .tip_percentages(vec![18, 20, 22])
+    .suggest(&Money { amount: Some(4250), currency: Currency::USD });
+
+assert_eq!(suggestions[0].tip_money.amount, Some(765));
+```
+ */
+
+use crate::objects::{Money, Order, TipSettings};
+
+/// The percentages a Square Terminal uses when no `tip_percentages` are configured.
+const DEFAULT_TIP_PERCENTAGES: [i32; 3] = [15, 20, 25];
+
+/// The percentages presented by "Smart Tip Amounts" for amounts of 10.00 or greater.
+const SMART_TIP_PERCENTAGES: [i32; 4] = [0, 5, 10, 15];
+
+/// Amounts below this many whole units are presented fixed tip amounts under "Smart Tip Amounts".
+const SMART_TIP_THRESHOLD_UNITS: i64 = 10;
+
+/// A single tip option, holding the percentage it was derived from if it is not a fixed amount.
+#[derive(Clone, Debug)]
+pub struct TipSuggestion {
+    pub percentage: Option<i32>,
+    pub tip_money: Money,
+}
+
+/// Computes the tip options for an amount or an [Order](Order).
+#[derive(Clone, Debug)]
+pub struct TipCalculator {
+    tip_percentages: Vec<i32>,
+    smart_tipping: bool,
+    round_to_whole_units: bool,
+}
+
+impl Default for TipCalculator {
+    fn default() -> Self {
+        TipCalculator {
+            tip_percentages: DEFAULT_TIP_PERCENTAGES.to_vec(),
+            smart_tipping: false,
+            round_to_whole_units: false,
+        }
+    }
+}
+
+impl From<&TipSettings> for TipCalculator {
+    fn from(settings: &TipSettings) -> Self {
+        TipCalculator {
+            tip_percentages: settings.tip_percentages.clone()
+                .unwrap_or_else(|| DEFAULT_TIP_PERCENTAGES.to_vec()),
+            smart_tipping: settings.smart_tipping.unwrap_or(false),
+            round_to_whole_units: false,
+        }
+    }
+}
+
+impl TipCalculator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The tip percentages to suggest, specified as integers from 0 to 100 (inclusive).
+    /// Values outside of that range are ignored.
+    pub fn tip_percentages(mut self, tip_percentages: Vec<i32>) -> Self {
+        self.tip_percentages = tip_percentages;
+
+        self
+    }
+
+    /// Use the "Smart Tip Amounts" behavior of a Square Terminal. <br/>
+    /// For amounts under 10.00 the suggestions are no tip, .50, 1.00 and 2.00, for amounts of
+    /// 10.00 or greater they are 0%, 5%, 10% and 15%. The `tip_percentages` are ignored.
+    pub fn smart_tipping(mut self) -> Self {
+        self.smart_tipping = true;
+
+        self
+    }
+
+    /// Round percentage based suggestions to the nearest whole unit of the currency, e.g. whole
+    /// dollars for USD.
+    pub fn round_to_whole_units(mut self) -> Self {
+        self.round_to_whole_units = true;
+
+        self
+    }
+
+    /// Suggests tips for the given amount. An amount without a value is treated as 0.
+    pub fn suggest(&self, amount: &Money) -> Vec<TipSuggestion> {
+        let base = amount.amount.unwrap_or(0);
+        let unit = amount.currency.unit();
+
+        if self.smart_tipping && base < SMART_TIP_THRESHOLD_UNITS * unit {
+            return vec![0, unit / 2, unit, 2 * unit].into_iter()
+                .map(|tip| TipSuggestion {
+                    percentage: None,
+                    tip_money: Money { amount: Some(tip), currency: amount.currency.clone() },
+                })
+                .collect()
+        }
+
+        let percentages = if self.smart_tipping {
+            SMART_TIP_PERCENTAGES.to_vec()
+        } else {
+            self.tip_percentages.clone()
+        };
+
+        percentages.into_iter()
+            .filter(|percentage| (0..=100).contains(percentage))
+            .map(|percentage| {
+                let mut tip = round_half_up(base * percentage as i64, 100);
+                if self.round_to_whole_units {
+                    tip = round_half_up(tip, unit) * unit;
+                }
+
+                TipSuggestion {
+                    percentage: Some(percentage),
+                    tip_money: Money { amount: Some(tip), currency: amount.currency.clone() },
+                }
+            })
+            .collect()
+    }
+
+    /// Suggests tips for an [Order](Order), based on its `total_money` excluding any tip already
+    /// included in it. Returns `None` if the order holds no `total_money`.
+    pub fn suggest_for_order(&self, order: &Order) -> Option<Vec<TipSuggestion>> {
+        let total = order.total_money.as_ref()?;
+        let tip = order.total_tip_money.as_ref()
+            .and_then(|tip| tip.amount)
+            .unwrap_or(0);
+
+        Some(self.suggest(&Money {
+            amount: Some(total.amount.unwrap_or(0) - tip),
+            currency: total.currency.clone(),
+        }))
+    }
+}
+
+// divides and rounds halves away from zero, the way amounts are rounded at the point of sale
+pub(crate) fn round_half_up(value: i64, divisor: i64) -> i64 {
+    if value >= 0 {
+        (value + divisor / 2) / divisor
+    } else {
+        (value - divisor / 2) / divisor
+    }
+}
+
+#[cfg(test)]
+mod test_tips {
+    use crate::objects::enums::Currency;
+    use super::*;
+
+    fn amounts(suggestions: Vec<TipSuggestion>) -> Vec<Option<i64>> {
+        suggestions.into_iter().map(|suggestion| suggestion.tip_money.amount).collect()
+    }
+
+    #[test]
+    fn test_suggest_default_percentages() {
+        let actual = TipCalculator::new()
+            .suggest(&Money { amount: Some(1999), currency: Currency::USD });
+
+        assert_eq!(vec![Some(300), Some(400), Some(500)], amounts(actual));
+    }
+
+    #[test]
+    fn test_suggest_round_to_whole_units() {
+        let actual = TipCalculator::new()
+            .round_to_whole_units()
+            .suggest(&Money { amount: Some(4250), currency: Currency::USD });
+
+        assert_eq!(vec![Some(600), Some(900), Some(1100)], amounts(actual));
+    }
+
+    #[test]
+    fn test_suggest_zero_decimal_currency() {
+        let actual = TipCalculator::new()
+            .tip_percentages(vec![15, 101])
+            .suggest(&Money { amount: Some(1250), currency: Currency::JPY });
+
+        assert_eq!(vec![Some(188)], amounts(actual));
+    }
+
+    #[test]
+    fn test_suggest_smart_tipping() {
+        let calculator = TipCalculator::from(&TipSettings {
+            allow_tipping: Some(true),
+            custom_tip_field: None,
+            separate_tip_screen: None,
+            smart_tipping: Some(true),
+            tip_percentages: None
+        });
+
+        let below = calculator.suggest(&Money { amount: Some(850), currency: Currency::USD });
+        let above = calculator.suggest(&Money { amount: Some(2000), currency: Currency::USD });
+
+        assert_eq!(vec![Some(0), Some(50), Some(100), Some(200)], amounts(below));
+        assert_eq!(vec![Some(0), Some(100), Some(200), Some(300)], amounts(above));
+    }
+
+    #[test]
+    fn test_suggest_for_order() {
+        let order = Order {
+            total_money: Some(Money { amount: Some(2300), currency: Currency::USD }),
+            total_tip_money: Some(Money { amount: Some(300), currency: Currency::USD }),
+            ..Default::default()
+        };
+
+        let actual = TipCalculator::new().suggest_for_order(&order).unwrap();
+
+        assert_eq!(vec![Some(300), Some(400), Some(500)], amounts(actual));
+        assert!(TipCalculator::new().suggest_for_order(&Order::default()).is_none());
+    }
+}
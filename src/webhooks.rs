@@ -0,0 +1,357 @@
+/*!
+Processing of the webhook events of the [Square API](https://developer.squareup.com).
+
+A [Processor](Processor) runs the handlers registered for a type of event, such as
+`payment.updated`, with the object of the event deserialized into the type the handler asks for.
+Events are processed once: an event whose `event_id` was processed before, or is being processed
+on another thread, is skipped, so Square delivering an event twice does not run its handlers
+twice. The ids are kept for 28 days by default, the longest an event can be searched for by
+[backfill](Processor::backfill), after which they are forgotten so memory use stays bounded.
+
+Square does not deliver the events of the time a webhook endpoint was down again.
+[backfill](Processor::backfill) searches those events through the
+[Events API](crate::api::events) instead and runs them through the same handlers, skipping the
+ones that did arrive. Events can only be searched for the past 28 days, and only once they were
+enabled through [enable_events](crate::api::events::Events::enable_events).
+
+# Example: Handle payments and recover after downtime
+```no_run
+use square_ox::{
+    client::SquareClient,
+    objects::{Event, Payment, TimeRange},
+    webhooks::Processor,
+};
+
+async {
+    let processor = Processor::new()
+        .on("payment.updated", |event: &Event, payment: Payment| {
+            println!("{:?} is now {:?}", payment.id, payment.status);
+        });
+
+    // in the handler of the webhook endpoint
+    let handled = processor.process_notification(r#"{ "type": "payment.updated" }"#);
+
+    // once the endpoint is back up
+    let handled = processor
+        .backfill(&SquareClient::new("some_token"), TimeRange {
+            start_at: Some("2022-10-12T07:20:50.52Z".to_string()),
+            end_at: Some("2022-10-13T07:20:50.52Z".to_string()),
+        })
+        .await;
+};
+```
+ */
+
+use crate::api::events::SearchEventsBody;
+use crate::builder::Builder;
+use crate::client::SquareClient;
+use crate::errors::SquareError;
+use crate::objects::{Event, Response, TimeRange};
+
+use serde::de::DeserializeOwned;
+use crate::platform::Instant;
+
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How long the ids of processed events are kept by default, see
+/// [retention](Processor::retention).
+pub const DEFAULT_RETENTION: Duration = Duration::from_secs(28 * 24 * 60 * 60);
+
+type Handler = Box<dyn Fn(&Event) -> Result<(), serde_json::Error> + Send + Sync>;
+
+/// Runs the handlers registered for the types of webhook events, once per event.
+pub struct Processor {
+    handlers: HashMap<String, Vec<Handler>>,
+    retention: Duration,
+    processed: Mutex<Processed>,
+}
+
+// the ids of the events being processed (false) or processed (true), along with the processed
+// ones in the order they finished so the expired ones can be dropped from the front
+#[derive(Default)]
+struct Processed {
+    ids: HashMap<String, bool>,
+    finished: VecDeque<(Instant, String)>,
+}
+
+impl Processed {
+    fn expire(&mut self, retention: Duration) {
+        while let Some((finished_at, _)) = self.finished.front() {
+            if finished_at.elapsed() < retention {
+                break;
+            }
+            if let Some((_, event_id)) = self.finished.pop_front() {
+                self.ids.remove(&event_id);
+            }
+        }
+    }
+}
+
+impl Default for Processor {
+    fn default() -> Self {
+        Processor {
+            handlers: HashMap::new(),
+            retention: DEFAULT_RETENTION,
+            processed: Default::default(),
+        }
+    }
+}
+
+impl Processor {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets how long the id of a processed event is kept to skip a second delivery of it,
+    /// [DEFAULT_RETENTION](DEFAULT_RETENTION) by default.
+    /// # Arguments:
+    /// * `retention` - How long to keep the ids, counted from when the event was processed.
+    pub fn retention(self, retention: Duration) -> Self {
+        Self {
+            retention,
+            ..self
+        }
+    }
+
+    /// Registers a handler for the events of a type. Handlers of the same type run in the order
+    /// they were registered.
+    /// # Arguments:
+    /// * `event_type` - The type of the events to handle, such as `payment.updated`.
+    /// * `handler` - Called with the event and its object, such as the
+    ///   [Payment](crate::objects::Payment) of a `payment.updated` event. Ask for an `Option` to
+    ///   handle events without an object, such as those of deleted objects.
+    pub fn on<T, F>(mut self, event_type: &str, handler: F) -> Self
+        where T: DeserializeOwned,
+              F: Fn(&Event, T) + Send + Sync + 'static {
+        self.handlers.entry(event_type.to_string()).or_default().push(Box::new(move |event| {
+            handler(event, event_object(event)?);
+
+            Ok(())
+        }));
+
+        self
+    }
+
+    /// Runs the handlers of the event and returns whether any ran. Handlers do not run for an
+    /// event that was processed before, or is being processed on another thread. Fails if the
+    /// object of the event can not be deserialized for a handler, in which case the event is not
+    /// marked as processed.
+    /// # Arguments:
+    /// * `event` - The event, as delivered to the webhook endpoint or found by a search.
+    pub fn process(&self, event: &Event) -> Result<bool, SquareError> {
+        let handlers = match event.event_type.as_ref()
+            .and_then(|event_type| self.handlers.get(event_type)) {
+            Some(handlers) => handlers,
+            None => return Ok(false),
+        };
+
+        // reserve the id before running the handlers, so a concurrent delivery skips the event
+        if let Some(event_id) = event.event_id.as_ref() {
+            let mut processed = self.processed.lock().map_err(|_| SquareError::from(None))?;
+            processed.expire(self.retention);
+            if processed.ids.contains_key(event_id) {
+                return Ok(false);
+            }
+            processed.ids.insert(event_id.clone(), false);
+        }
+
+        let result = handlers.iter().try_for_each(|handler| handler(event));
+
+        if let Some(event_id) = event.event_id.clone() {
+            let mut processed = self.processed.lock().map_err(|_| SquareError::from(None))?;
+            match result {
+                Ok(_) => {
+                    processed.ids.insert(event_id.clone(), true);
+                    processed.finished.push_back((Instant::now(), event_id));
+                },
+                Err(_) => {
+                    processed.ids.remove(&event_id);
+                },
+            }
+        }
+
+        result?;
+
+        Ok(true)
+    }
+
+    /// Runs the handlers of the event in the body of a webhook notification, see
+    /// [process](Processor::process).
+    /// # Arguments:
+    /// * `body` - The body of the notification as received.
+    pub fn process_notification(&self, body: &str) -> Result<bool, SquareError> {
+        self.process(&serde_json::from_str(body)?)
+    }
+
+    /// Searches the events of the registered types that were created within the range and runs
+    /// them through their handlers, oldest first, returning the number of events handled. Events
+    /// that were processed before are skipped.
+    /// # Arguments:
+    /// * `client` - The client to search the events with.
+    /// * `range` - The range the events were created in, with both ends in RFC 3339 format.
+    pub async fn backfill(&self, client: &SquareClient, range: TimeRange)
+                          -> Result<usize, SquareError> {
+        if self.handlers.is_empty() {
+            return Ok(0);
+        }
+
+        let mut event_types = self.handlers.keys().cloned().collect::<Vec<String>>();
+        event_types.sort();
+
+        let mut handled = 0;
+        let mut cursor = None;
+        loop {
+            let mut body = Builder::from(SearchEventsBody::default())
+                .event_types(event_types.clone())
+                .created_at(range.clone())
+                .limit(100)
+                .sort_ascending();
+            if let Some(cursor) = cursor {
+                body = body.cursor(cursor);
+            }
+            let body = body.build().await.map_err(|_| SquareError::from(None))?;
+
+            let res = client.events().search_events(body).await?;
+            cursor = res.cursor.clone();
+
+            for response in res.into_responses() {
+                if let Response::Events(events) = response {
+                    for event in events.iter() {
+                        if self.process(event)? {
+                            handled += 1;
+                        }
+                    }
+                }
+            }
+
+            if cursor.is_none() {
+                return Ok(handled);
+            }
+        }
+    }
+}
+
+// the object of the event, which Square keys by its type, such as `{ "payment": { .. } }`
+fn event_object<T: DeserializeOwned>(event: &Event) -> Result<T, serde_json::Error> {
+    let object = event.data.as_ref()
+        .and_then(|data| {
+            let object = data.object.as_ref()?;
+
+            data.object_type.as_ref()
+                .and_then(|object_type| object.get(object_type))
+                .or(Some(object))
+        })
+        .cloned()
+        .unwrap_or(Value::Null);
+
+    serde_json::from_value(object)
+}
+
+#[cfg(test)]
+mod test_webhooks {
+    use super::*;
+    use crate::objects::Payment;
+    use std::sync::Arc;
+
+    fn payment_updated(event_id: &str) -> String {
+        serde_json::json!({
+            "merchant_id": "merchant_id",
+            "type": "payment.updated",
+            "event_id": event_id,
+            "created_at": "2022-10-12T07:20:50.52Z",
+            "data": {
+                "type": "payment",
+                "id": "payment_id",
+                "object": { "payment": { "id": "payment_id" } },
+            },
+        }).to_string()
+    }
+
+    #[test]
+    fn test_process() {
+        let handled = Arc::new(Mutex::new(vec![]));
+        let shared = handled.clone();
+        let sut = Processor::new()
+            .on("payment.updated", move |event: &Event, payment: Payment| {
+                shared.lock().unwrap().push((event.event_id.clone().unwrap(), payment.id));
+            });
+
+        assert!(sut.process_notification(&payment_updated("first")).unwrap());
+        // a second delivery of the same event
+        assert!(!sut.process_notification(&payment_updated("first")).unwrap());
+        assert!(sut.process_notification(&payment_updated("second")).unwrap());
+        assert!(!sut.process_notification(r#"{ "type": "refund.updated" }"#).unwrap());
+        assert!(sut.process_notification("not json").is_err());
+
+        assert_eq!(*handled.lock().unwrap(), vec![
+            ("first".to_string(), Some("payment_id".to_string())),
+            ("second".to_string(), Some("payment_id".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_process_failed() {
+        let sut = Processor::new().on("payment.updated", |_: &Event, _: Payment| {});
+        let malformed = payment_updated("first")
+            .replace(r#"{"id":"payment_id"}"#, r#"{"id":1}"#);
+
+        assert!(sut.process_notification(&malformed).is_err());
+        // the failed event is not marked as processed
+        assert!(sut.process_notification(&payment_updated("first")).unwrap());
+    }
+
+    #[test]
+    fn test_process_concurrently() {
+        let handled = Arc::new(Mutex::new(0));
+        let shared = handled.clone();
+        let sut = Arc::new(Processor::new()
+            .on("payment.updated", move |_: &Event, _: Payment| {
+                std::thread::sleep(Duration::from_millis(50));
+                *shared.lock().unwrap() += 1;
+            }));
+
+        let deliveries = (0..4)
+            .map(|_| {
+                let sut = sut.clone();
+                std::thread::spawn(move || {
+                    sut.process_notification(&payment_updated("first")).unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+        let ran = deliveries.into_iter()
+            .map(|delivery| delivery.join().unwrap())
+            .filter(|ran| *ran)
+            .count();
+
+        assert_eq!(ran, 1);
+        assert_eq!(*handled.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_retention() {
+        let sut = Processor::new()
+            .retention(Duration::ZERO)
+            .on("payment.updated", |_: &Event, _: Payment| {});
+
+        assert!(sut.process_notification(&payment_updated("first")).unwrap());
+        // forgotten straight away
+        assert!(sut.process_notification(&payment_updated("first")).unwrap());
+        assert!(sut.processed.lock().unwrap().finished.len() <= 1);
+    }
+
+    #[test]
+    fn test_event_object() {
+        let event: Event = serde_json::from_str(&payment_updated("first")).unwrap();
+        let payment: Payment = event_object(&event).unwrap();
+        assert_eq!(payment.id, Some("payment_id".to_string()));
+
+        let deleted: Event = serde_json::from_value(serde_json::json!({
+            "type": "customer.deleted",
+            "data": { "type": "customer", "id": "customer_id", "deleted": true },
+        })).unwrap();
+        assert!(event_object::<Option<Payment>>(&deleted).unwrap().is_none());
+    }
+}
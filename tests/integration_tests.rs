@@ -41,11 +41,15 @@ async fn test_list_catalog() {
 
     let sut = square_ox::client::SquareClient::new(&access_token);
 
-    let input = catalog::CatalogListParameterBuilder::new().build().await;
+    let input = catalog::CatalogListParameterBuilder::new();
 
     let res = sut.catalog()
-        .list(Some(input))
+        .list(input)
+        .next_page()
         .await;
 
+    assert!(res.is_some());
+    let res = res.unwrap();
+
     assert!(res.is_ok());
 }
\ No newline at end of file